@@ -0,0 +1,121 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Attribute, Data, DeriveInput, Error, Fields};
+
+use crate::util;
+
+struct Attrs {
+    class: Option<String>,
+    message: Option<String>,
+}
+
+fn attrs(attrs: &[Attribute], known: &[&str]) -> Result<Attrs, Error> {
+    let mut args = util::Args::from_attrs(attrs, known)?;
+    Ok(Attrs {
+        class: args.extract("class")?,
+        message: args.extract("message")?,
+    })
+}
+
+// Looks up the class on first use and caches it. Conversion to an `Error`
+// can only happen on a Ruby thread, so access to the static is serialised by
+// the GVL.
+fn class_lookup(class: &str) -> TokenStream {
+    quote! {
+        {
+            static mut CLASS: Option<magnus::ExceptionClass> = None;
+            unsafe {
+                match CLASS {
+                    Some(v) => Ok(v),
+                    None => magnus::RClass::default()
+                        .funcall::<_, _, magnus::ExceptionClass>("const_get", (#class,))
+                        .map(|v| {
+                            CLASS = Some(v);
+                            v
+                        }),
+                }
+            }
+        }
+    }
+}
+
+fn message(message: Option<String>) -> TokenStream {
+    match message {
+        Some(v) => quote! { String::from(#v) },
+        None => quote! { e.to_string() },
+    }
+}
+
+pub fn expand_derive_into_error(input: DeriveInput) -> TokenStream {
+    let known: &[&str] = match input.data {
+        Data::Struct(_) => &["class", "message"],
+        _ => &["class"],
+    };
+    let type_attrs = match attrs(&input.attrs, known) {
+        Ok(v) => v,
+        Err(e) => return e.into_compile_error(),
+    };
+
+    let ident = &input.ident;
+    let arms = match &input.data {
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let variant_attrs = match attrs(&variant.attrs, &["class", "message"]) {
+                    Ok(v) => v,
+                    Err(e) => return e.into_compile_error(),
+                };
+                let class = match variant_attrs.class.or_else(|| type_attrs.class.clone()) {
+                    Some(v) => class_lookup(&v),
+                    None => {
+                        return Error::new(
+                            variant.span(),
+                            "missing #[magnus(class = \"...\")] attribute",
+                        )
+                        .into_compile_error()
+                    }
+                };
+                let message = message(variant_attrs.message);
+                let variant_ident = &variant.ident;
+                let pattern = match variant.fields {
+                    Fields::Named(_) => quote! { #ident::#variant_ident { .. } },
+                    Fields::Unnamed(_) => quote! { #ident::#variant_ident(..) },
+                    Fields::Unit => quote! { #ident::#variant_ident },
+                };
+                arms.push(quote! { #pattern => (#class, #message), });
+            }
+            arms
+        }
+        Data::Struct(_) => match type_attrs.class {
+            Some(v) => {
+                let class = class_lookup(&v);
+                let message = message(type_attrs.message);
+                vec![quote! { _ => (#class, #message), }]
+            }
+            None => {
+                return Error::new(input.span(), "missing #[magnus(class = \"...\")] attribute")
+                    .into_compile_error()
+            }
+        },
+        Data::Union(_) => {
+            return Error::new(input.span(), "IntoError can't be derived for unions")
+                .into_compile_error()
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics From<#ident #ty_generics> for magnus::Error #where_clause {
+            fn from(e: #ident #ty_generics) -> Self {
+                let (class, message): (Result<magnus::ExceptionClass, magnus::Error>, String) =
+                    match &e {
+                        #(#arms)*
+                    };
+                match class {
+                    Ok(class) => magnus::Error::new(class, message),
+                    Err(err) => err,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Fields, FieldsNamed, Type};
+
+use crate::util;
+
+fn named_fields<'a>(input: &'a DeriveInput, derive: &str) -> Result<&'a FieldsNamed, Error> {
+    if !input.generics.to_token_stream().is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            format!("{} can't be derived for generic types", derive),
+        ));
+    }
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields),
+            _ => Err(Error::new(
+                input.span(),
+                format!(
+                    "{} can only be derived for structs with named fields",
+                    derive
+                ),
+            )),
+        },
+        _ => Err(Error::new(
+            input.span(),
+            format!("{} can only be derived for structs", derive),
+        )),
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+struct Field<'a> {
+    ident: &'a syn::Ident,
+    name: String,
+    default: bool,
+    optional: bool,
+}
+
+fn fields<'a>(fields: &'a FieldsNamed) -> Result<Vec<Field<'a>>, Error> {
+    let mut res: Vec<Field> = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        let mut args = util::Args::from_attrs(&field.attrs, &["rename", "default"])?;
+        let name = args
+            .extract::<Option<String>>("rename")?
+            .unwrap_or_else(|| ident.to_string().trim_start_matches("r#").to_owned());
+        if res.iter().any(|f| f.name == name) {
+            return Err(Error::new(
+                field.span(),
+                format!("duplicate key `{}`", name),
+            ));
+        }
+        res.push(Field {
+            ident,
+            name,
+            default: args.extract::<Option<()>>("default")?.is_some(),
+            optional: is_option(&field.ty),
+        });
+    }
+    Ok(res)
+}
+
+pub fn expand_derive_into_hash(input: DeriveInput) -> TokenStream {
+    let fields = match named_fields(&input, "IntoHash").and_then(fields) {
+        Ok(v) => v,
+        Err(e) => return e.into_compile_error(),
+    };
+
+    let ident = &input.ident;
+    let idents = fields.iter().map(|f| f.ident);
+    let names = fields.iter().map(|f| &f.name);
+    quote! {
+        impl From<#ident> for magnus::RHash {
+            fn from(val: #ident) -> Self {
+                let hash = magnus::RHash::new();
+                #(hash.aset(magnus::StaticSymbol::new(#names), val.#idents).unwrap();)*
+                hash
+            }
+        }
+
+        impl From<#ident> for magnus::Value {
+            fn from(val: #ident) -> Self {
+                magnus::RHash::from(val).into()
+            }
+        }
+    }
+}
+
+pub fn expand_derive_try_convert(input: DeriveInput) -> TokenStream {
+    let deny_unknown_fields = match util::Args::from_attrs(&input.attrs, &["deny_unknown_fields"])
+        .and_then(|mut args| args.extract::<Option<()>>("deny_unknown_fields"))
+    {
+        Ok(v) => v.is_some(),
+        Err(e) => return e.into_compile_error(),
+    };
+    let fields = match named_fields(&input, "TryConvert").and_then(fields) {
+        Ok(v) => v,
+        Err(e) => return e.into_compile_error(),
+    };
+
+    let ident = &input.ident;
+    let type_name = ident.to_string();
+
+    let deny = if deny_unknown_fields {
+        let names = fields.iter().map(|f| &f.name);
+        quote! {
+            let known: &[&str] = &[#(#names),*];
+            for key in hash.funcall::<_, _, magnus::RArray>("keys", ())?.each() {
+                let key = key?;
+                let name: String = key.funcall("to_s", ())?;
+                if !known.contains(&name.as_str()) {
+                    return Err(magnus::Error::new(
+                        magnus::exception::arg_error(),
+                        format!("unknown key {} for {}", key.inspect(), #type_name),
+                    ));
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let values = fields.iter().map(|f| {
+        let name = &f.name;
+        let missing = if f.default {
+            quote! { Default::default() }
+        } else if f.optional {
+            quote! { None }
+        } else {
+            quote! {
+                return Err(magnus::Error::new(
+                    magnus::exception::arg_error(),
+                    format!("missing key :{} for {}", #name, #type_name),
+                ))
+            }
+        };
+        quote! {
+            match lookup(#name) {
+                Some(v) => v.try_convert()?,
+                None => #missing,
+            }
+        }
+    });
+    let idents = fields.iter().map(|f| f.ident);
+
+    quote! {
+        impl magnus::TryConvert for #ident {
+            fn try_convert(val: magnus::Value) -> Result<Self, magnus::Error> {
+                let hash = <magnus::RHash as magnus::TryConvert>::try_convert(val)?;
+                let lookup = |name: &str| {
+                    hash.get(magnus::StaticSymbol::new(name))
+                        .or_else(|| hash.get(name))
+                };
+                #deny
+                Ok(Self {
+                    #(#idents: #values,)*
+                })
+            }
+        }
+    }
+}
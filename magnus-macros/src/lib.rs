@@ -19,7 +19,10 @@
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
 
+mod error;
+mod hash;
 mod init;
+mod symbol_enum;
 mod typed_data;
 mod util;
 
@@ -111,6 +114,8 @@ pub fn init(attrs: TokenStream, item: TokenStream) -> TokenStream {
 ///   implmentation does not call Ruby.
 /// * `size` - Report the [`std::mem::size_of_val`] of the type to Ruby, used
 ///   to aid in deciding when to run the garbage collector.
+/// * `frozen_shareable` - Enable the `frozen_shareable` flag, allowing frozen
+///   instances to be shared between Ractors. Requires the type to be [`Sync`].
 ///
 /// # Examples
 ///
@@ -175,7 +180,8 @@ pub fn derive_data_type_functions(input: TokenStream) -> TokenStream {
 /// * `size` - Enable Ruby calling the `DataTypeFunctions::size` function.
 /// * `compact` - Enable Ruby calling the `DataTypeFunctions::compact` function.
 /// * `wb_protected` - Enable the `wb_protected` flag.
-/// * `frozen_shareable` - Enable the `frozen_shareable` flag.
+/// * `frozen_shareable` - Enable the `frozen_shareable` flag, allowing frozen
+///   instances to be shared between Ractors. Requires the type to be [`Sync`].
 ///
 /// # Examples
 ///
@@ -229,3 +235,207 @@ pub fn derive_data_type_functions(input: TokenStream) -> TokenStream {
 pub fn derive_typed_data(input: TokenStream) -> TokenStream {
     typed_data::expand_derive_typed_data(parse_macro_input!(input)).into()
 }
+
+/// Derives `From<T> for magnus::Error`, converting a Rust error type to a
+/// Ruby exception.
+///
+/// Unless a message is set with the `message` attribute the type must
+/// implement [`Display`](std::fmt::Display), which is used as the exception's
+/// message.
+///
+/// # Attributes
+///
+/// The `#[magnus(...)]` attribute can be set on the type, and on enum
+/// variants, with the following values.
+///
+/// * `class = "..."` - sets the Ruby exception class. Supports module paths,
+///   e.g. `Foo::Bar::Error`. When set on the enum it is the default for
+///   variants without their own attribute. Required for every variant.
+/// * `message = "..."` - sets a fixed exception message, rather than using
+///   `Display`. Can be set on a struct, or on enum variants.
+///
+/// The class is looked up by name the first time an error is converted, so
+/// must be defined before then, and is cached after that.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt;
+///
+/// use magnus::{Error, IntoError};
+///
+/// #[derive(Debug, IntoError)]
+/// #[magnus(class = "RuntimeError")]
+/// enum ConfigError {
+///     #[magnus(class = "ArgumentError")]
+///     InvalidKey(String),
+///     #[magnus(class = "Config::MissingError")]
+///     Missing { key: String },
+///     #[magnus(message = "config file corrupt")]
+///     Corrupt,
+/// }
+///
+/// impl fmt::Display for ConfigError {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         match self {
+///             Self::InvalidKey(key) => write!(f, "invalid key: {}", key),
+///             Self::Missing { key } => write!(f, "missing key: {}", key),
+///             Self::Corrupt => write!(f, "corrupt"),
+///         }
+///     }
+/// }
+///
+/// fn lookup(key: String) -> Result<String, Error> {
+///     Err(ConfigError::Missing { key }.into())
+/// }
+/// ```
+#[proc_macro_derive(IntoError, attributes(magnus))]
+pub fn derive_into_error(input: TokenStream) -> TokenStream {
+    error::expand_derive_into_error(parse_macro_input!(input)).into()
+}
+
+/// Derives `From<T> for magnus::Value` and `TryConvert` for a fieldless
+/// enum, converting variants to and from Ruby Symbols.
+///
+/// Conversion to Rust accepts either a Symbol or a String, raising
+/// `ArgumentError` for a name that doesn't match any variant.
+///
+/// # Attributes
+///
+/// The `#[magnus(...)]` attribute can be set on the enum and its variants.
+///
+/// On the enum:
+///
+/// * `rename_all = "..."` - sets the case convention used to convert variant
+///   names to Symbols. One of `"snake_case"` (the default), `"lowercase"`,
+///   `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`, `"SCREAMING_SNAKE_CASE"`,
+///   `"kebab-case"`, or `"SCREAMING-KEBAB-CASE"`.
+///
+/// On variants:
+///
+/// * `rename = "..."` - sets the Symbol name for the variant, overriding
+///   `rename_all`.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_global_function, function, Error, SymbolEnum};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, SymbolEnum)]
+/// enum Mode {
+///     Strict,
+///     Lenient,
+///     #[magnus(rename = "none")]
+///     Off,
+/// }
+///
+/// // called from Ruby as `relax(:strict) #=> :lenient`
+/// fn relax(mode: Mode) -> Mode {
+///     match mode {
+///         Mode::Strict => Mode::Lenient,
+///         _ => Mode::Off,
+///     }
+/// }
+///
+/// #[magnus::init]
+/// fn init() -> Result<(), Error> {
+///     define_global_function("relax", function!(relax, 1));
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_derive(SymbolEnum, attributes(magnus))]
+pub fn derive_symbol_enum(input: TokenStream) -> TokenStream {
+    symbol_enum::expand_derive_symbol_enum(parse_macro_input!(input)).into()
+}
+
+/// Derives `From<T> for magnus::RHash` and `From<T> for magnus::Value`,
+/// converting a struct with named fields to a Ruby Hash with Symbol keys.
+///
+/// This does not implement magnus' internal `IntoValue` trait, but as
+/// `Into<Value>` is implemented the type can be returned from functions bound
+/// as Ruby methods and passed as arguments anywhere Magnus accepts
+/// `Into<Value>`.
+///
+/// Every field must implement `Into<magnus::Value>`. This is a lighter
+/// weight alternative to the `serde` feature for simple option and result
+/// objects.
+///
+/// # Attributes
+///
+/// The `#[magnus(...)]` attribute can be set on fields with the following
+/// values.
+///
+/// * `rename = "..."` - sets the Hash key for the field.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::IntoHash;
+///
+/// #[derive(IntoHash)]
+/// struct Stats {
+///     count: usize,
+///     #[magnus(rename = "avg")]
+///     average: f64,
+/// }
+///
+/// // returned to Ruby as `{count: 3, avg: 2.5}`
+/// fn stats() -> Stats {
+///     Stats {
+///         count: 3,
+///         average: 2.5,
+///     }
+/// }
+/// ```
+#[proc_macro_derive(IntoHash, attributes(magnus))]
+pub fn derive_into_hash(input: TokenStream) -> TokenStream {
+    hash::expand_derive_into_hash(parse_macro_input!(input)).into()
+}
+
+/// Derives `TryConvert`, converting a Ruby Hash to a struct with named
+/// fields.
+///
+/// Every field must implement `TryConvert`. Fields are looked up with a
+/// Symbol key, falling back to a String key. A missing key for a field of
+/// type `Option<T>` is converted to `None`, and for other fields raises an
+/// `ArgumentError`. Unknown keys are ignored.
+///
+/// # Attributes
+///
+/// The `#[magnus(...)]` attribute can be set on the struct, and on fields.
+///
+/// On the struct:
+///
+/// * `deny_unknown_fields` - raise an `ArgumentError` if the Hash contains
+///   any keys that don't match a field.
+///
+/// On fields:
+///
+/// * `rename = "..."` - sets the Hash key for the field.
+/// * `default` - use the field type's [`Default`] value when the key is
+///   missing.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::TryConvert;
+///
+/// #[derive(TryConvert)]
+/// #[magnus(deny_unknown_fields)]
+/// struct Options {
+///     #[magnus(rename = "timeout_secs")]
+///     timeout: Option<f64>,
+///     #[magnus(default)]
+///     retries: u32,
+///     verbose: bool,
+/// }
+///
+/// // called from Ruby as `connect({timeout_secs: 1.5, verbose: true})`
+/// fn connect(opts: Options) -> bool {
+///     opts.verbose && opts.retries == 0 && opts.timeout.is_some()
+/// }
+/// ```
+#[proc_macro_derive(TryConvert, attributes(magnus))]
+pub fn derive_try_convert(input: TokenStream) -> TokenStream {
+    hash::expand_derive_try_convert(parse_macro_input!(input)).into()
+}
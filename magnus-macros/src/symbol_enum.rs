@@ -0,0 +1,150 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{spanned::Spanned, Attribute, Data, DeriveInput, Error, Fields};
+
+use crate::util;
+
+fn string_attr(attrs: &[Attribute], name: &str) -> Result<Option<String>, Error> {
+    util::Args::from_attrs(attrs, &[name])?.extract::<Option<String>>(name)
+}
+
+fn snake_case(ident: &str) -> String {
+    let mut s = String::with_capacity(ident.len() + 4);
+    for (i, c) in ident.char_indices() {
+        if c.is_uppercase() && i > 0 {
+            s.push('_');
+        }
+        s.extend(c.to_lowercase());
+    }
+    s
+}
+
+fn rename(ident: &str, case: &str) -> Option<String> {
+    let s = match case {
+        "lowercase" => ident.to_lowercase(),
+        "UPPERCASE" => ident.to_uppercase(),
+        "PascalCase" => ident.to_owned(),
+        "camelCase" => {
+            let mut chars = ident.chars();
+            match chars.next() {
+                Some(c) => c.to_lowercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+        "snake_case" => snake_case(ident),
+        "SCREAMING_SNAKE_CASE" => snake_case(ident).to_uppercase(),
+        "kebab-case" => snake_case(ident).replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake_case(ident).replace('_', "-").to_uppercase(),
+        _ => return None,
+    };
+    Some(s)
+}
+
+pub fn expand_derive_symbol_enum(input: DeriveInput) -> TokenStream {
+    if !input.generics.to_token_stream().is_empty() {
+        return Error::new(
+            input.generics.span(),
+            "SymbolEnum can't be derived for generic types",
+        )
+        .into_compile_error();
+    }
+    let case = match string_attr(&input.attrs, "rename_all") {
+        Ok(v) => v.unwrap_or_else(|| String::from("snake_case")),
+        Err(e) => return e.into_compile_error(),
+    };
+
+    let ident = &input.ident;
+    let data = match &input.data {
+        Data::Enum(v) => v,
+        _ => {
+            return Error::new(input.span(), "SymbolEnum can only be derived for enums")
+                .into_compile_error()
+        }
+    };
+
+    if data.variants.is_empty() {
+        return Error::new(input.span(), "SymbolEnum can't be derived for empty enums")
+            .into_compile_error();
+    }
+
+    let mut names = Vec::new();
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Error::new(
+                variant.span(),
+                "SymbolEnum can only be derived for enums without fields",
+            )
+            .into_compile_error();
+        }
+        let name = match string_attr(&variant.attrs, "rename") {
+            Ok(Some(v)) => v,
+            Ok(None) => match rename(&variant.ident.to_string(), &case) {
+                Some(v) => v,
+                None => {
+                    return Error::new(
+                        input.span(),
+                        format!("unknown rename_all case convention `{}`", case),
+                    )
+                    .into_compile_error()
+                }
+            },
+            Err(e) => return e.into_compile_error(),
+        };
+        if names.contains(&name) {
+            return Error::new(variant.span(), format!("duplicate name `{}`", name))
+                .into_compile_error();
+        }
+        names.push(name);
+        variants.push(&variant.ident);
+    }
+
+    let expected = names
+        .iter()
+        .map(|name| format!(":{}", name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let type_name = ident.to_string();
+
+    quote! {
+        impl From<#ident> for magnus::Value {
+            fn from(val: #ident) -> Self {
+                let name = match val {
+                    #(#ident::#variants => #names,)*
+                };
+                magnus::StaticSymbol::new(name).into()
+            }
+        }
+
+        impl magnus::TryConvert for #ident {
+            fn try_convert(val: magnus::Value) -> Result<Self, magnus::Error> {
+                let name = if let Some(sym) = magnus::Symbol::from_value(val) {
+                    sym.name()?
+                } else if let Some(s) = magnus::RString::from_value(val) {
+                    std::borrow::Cow::Owned(s.to_string()?)
+                } else {
+                    return Err(magnus::Error::new(
+                        magnus::exception::type_error(),
+                        format!(
+                            "no implicit conversion of {} into {}",
+                            unsafe { val.classname() },
+                            #type_name,
+                        ),
+                    ));
+                };
+                match &*name {
+                    #(#names => Ok(#ident::#variants),)*
+                    _ => Err(magnus::Error::new(
+                        magnus::exception::arg_error(),
+                        format!(
+                            "invalid value for {}: {}, expected one of {}",
+                            #type_name,
+                            val.inspect(),
+                            #expected,
+                        ),
+                    )),
+                }
+            }
+        }
+    }
+}
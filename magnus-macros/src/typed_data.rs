@@ -1,6 +1,6 @@
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{spanned::Spanned, DeriveInput, Error, Meta};
+use syn::{spanned::Spanned, DeriveInput, Error};
 
 use crate::util;
 
@@ -28,29 +28,11 @@ pub fn expand_derive_typed_data(input: DeriveInput) -> TokenStream {
         )
         .into_compile_error();
     }
-    let mut attrs = input
-        .attrs
-        .clone()
-        .into_iter()
-        .filter(|attr| attr.path.is_ident("magnus"))
-        .collect::<Vec<_>>();
-    if attrs.len() > 1 {
-        return attrs
-            .into_iter()
-            .map(|a| Error::new(a.span(), "duplicate attribute"))
-            .reduce(|mut a, b| {
-                a.combine(b);
-                a
-            })
-            .unwrap()
-            .into_compile_error();
-    }
-    if attrs.is_empty() {
-        return Error::new(input.span(), "missing #[magnus] attribute").into_compile_error();
-    }
-    let attrs = match attrs.remove(0).parse_meta() {
-        Ok(Meta::List(v)) => v.nested.into_iter().collect(),
-        Ok(v) => return Error::new_spanned(v, "Expected meta list").into_compile_error(),
+    let attrs = match util::magnus_attr(&input.attrs) {
+        Ok(Some(v)) => v,
+        Ok(None) => {
+            return Error::new(input.span(), "missing #[magnus] attribute").into_compile_error()
+        }
         Err(e) => return e.into_compile_error(),
     };
     let mut args = match util::Args::new_with_aliases(
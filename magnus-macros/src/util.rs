@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use proc_macro2::Span;
-use syn::{AttributeArgs, Error, Lit, Meta, MetaNameValue, NestedMeta, Path};
+use syn::{
+    spanned::Spanned, Attribute, AttributeArgs, Error, Lit, Meta, MetaNameValue, NestedMeta, Path,
+};
 
 pub struct Value {
     path: Path,
@@ -59,6 +61,32 @@ impl Extract for Option<()> {
     }
 }
 
+/// Returns the arguments of the `#[magnus(...)]` attribute in `attrs`, or
+/// `None` if there is no such attribute.
+pub fn magnus_attr(attrs: &[Attribute]) -> Result<Option<AttributeArgs>, Error> {
+    let mut attrs = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("magnus"))
+        .collect::<Vec<_>>();
+    if attrs.len() > 1 {
+        return Err(attrs
+            .into_iter()
+            .map(|a| Error::new(a.span(), "duplicate attribute"))
+            .reduce(|mut a, b| {
+                a.combine(b);
+                a
+            })
+            .unwrap());
+    }
+    if attrs.is_empty() {
+        return Ok(None);
+    }
+    match attrs.remove(0).parse_meta()? {
+        Meta::List(v) => Ok(Some(v.nested.into_iter().collect())),
+        v => Err(Error::new_spanned(v, "Expected meta list")),
+    }
+}
+
 pub struct Args(HashMap<String, Value>);
 
 impl Args {
@@ -66,6 +94,12 @@ impl Args {
         Self::new_with_aliases(args, known, &HashMap::new())
     }
 
+    /// Parses the `#[magnus(...)]` attribute in `attrs`, a missing attribute
+    /// is treated as an empty argument list.
+    pub fn from_attrs(attrs: &[Attribute], known: &[&str]) -> Result<Self, Error> {
+        Self::new(magnus_attr(attrs)?.unwrap_or_default(), known)
+    }
+
     pub fn new_with_aliases(
         args: AttributeArgs,
         known: &[&str],
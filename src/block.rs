@@ -3,13 +3,14 @@
 use std::{fmt, mem::forget, ops::Deref, os::raw::c_int};
 
 use rb_sys::{
-    rb_block_given_p, rb_block_proc, rb_data_typed_object_wrap, rb_obj_is_proc, rb_proc_arity,
-    rb_proc_call, rb_proc_lambda_p, rb_proc_new, rb_yield, rb_yield_splat, rb_yield_values2, VALUE,
+    rb_block_given_p, rb_block_proc, rb_current_receiver, rb_data_typed_object_wrap,
+    rb_obj_is_proc, rb_proc_arity, rb_proc_call, rb_proc_lambda_p, rb_proc_new, rb_yield,
+    rb_yield_splat, rb_yield_values2, VALUE,
 };
 
 use crate::{
     enumerator::Enumerator,
-    error::{ensure, protect, Error},
+    error::{ensure_raw, protect, Error},
     exception,
     into_value::IntoValue,
     memoize,
@@ -185,6 +186,23 @@ impl Proc {
         get_ruby!().proc_from_fn(block)
     }
 
+    /// Create a new `Proc` from a closure that is passed the block's `self`
+    /// as its first argument.
+    ///
+    /// For use with methods such as `instance_exec` that evaluate a block with
+    /// a different `self`.
+    pub(crate) fn from_fn_with_self<F, R>(mut block: F) -> Self
+    where
+        F: 'static + Send + FnMut(Value, &[Value]) -> R,
+        R: BlockReturn,
+    {
+        Self::from_fn(move |args, _block| {
+            // self of the currently executing block
+            let rb_self = unsafe { Value::new(rb_current_receiver()) };
+            block(rb_self, args)
+        })
+    }
+
     /// Call the proc with `args`.
     ///
     /// Returns `Ok(T)` if the proc runs without error and the return value
@@ -254,6 +272,42 @@ impl Proc {
     pub fn is_lambda(self) -> bool {
         unsafe { Value::new(rb_proc_lambda_p(self.as_rb_value())).to_bool() }
     }
+
+    /// Returns a curried version of `self`.
+    ///
+    /// A curried proc receives some arguments. If a sufficient number of
+    /// arguments are supplied, it passes the supplied arguments to `self` and
+    /// returns the result. Otherwise, returns another curried proc that takes
+    /// the rest of the arguments.
+    ///
+    /// `arity` may be used to specify the number of arguments required when
+    /// `self` takes optional or a variable number of arguments. Returns `Err`
+    /// if `self` is a lambda and `arity` does not match the arguments `self`
+    /// accepts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{block::Proc, eval};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let proc = eval::<Proc>("lambda {|a, b, c| a + b + c}").unwrap();
+    /// let curried = proc.curry(None).unwrap();
+    /// let partial: Proc = curried.call((1,)).unwrap();
+    /// let partial: Proc = partial.call((2,)).unwrap();
+    /// assert_eq!(partial.call::<_, i64>((3,)).unwrap(), 6);
+    ///
+    /// let proc = eval::<Proc>("proc {|*args| args.sum}").unwrap();
+    /// let curried = proc.curry(Some(2)).unwrap();
+    /// let partial: Proc = curried.call((1,)).unwrap();
+    /// assert_eq!(partial.call::<_, i64>((2,)).unwrap(), 3);
+    /// ```
+    pub fn curry(self, arity: Option<usize>) -> Result<Proc, Error> {
+        match arity {
+            Some(n) => self.funcall("curry", (n,)),
+            None => self.funcall("curry", ()),
+        }
+    }
 }
 
 impl Deref for Proc {
@@ -495,7 +549,7 @@ where
                   // closure might never reach the end, so wouldn't drop. The second
                   // closure is always run, and always after the first, so we do the
                   // drop there
-    ensure(
+    ensure_raw(
         || {
             for val in &mut *ptr {
                 rb_yield(val.into().as_rb_value());
@@ -516,7 +570,7 @@ where
 {
     let ptr = &mut iter as *mut I;
     forget(iter);
-    ensure(
+    ensure_raw(
         || {
             for val in &mut *ptr {
                 let vals = val.into_arg_list();
@@ -538,7 +592,7 @@ where
 {
     let ptr = &mut iter as *mut I;
     forget(iter);
-    ensure(
+    ensure_raw(
         || {
             for val in &mut *ptr {
                 rb_yield_splat(val.as_rb_value());
@@ -18,11 +18,12 @@ use crate::{
     error::{protect, Error},
     exception,
     into_value::IntoValue,
-    module::Module,
+    method::Method,
+    module::{Attr, Module, RModule},
     object::Object,
     ruby_handle::RubyHandle,
     try_convert::{ArgList, TryConvert},
-    value::{private, NonZeroValue, ReprValue, Value},
+    value::{private, BoxValue, NonZeroValue, ReprValue, Value},
 };
 
 /// A Value pointer to a RClass struct, Ruby's internal representation of
@@ -348,6 +349,255 @@ impl Class for RClass {
     }
 }
 
+type BuilderStep = Box<dyn FnOnce(RClass) -> Result<(), Error>>;
+
+/// A builder to declaratively define a class along with its constants and
+/// methods.
+///
+/// Errors from defining the individual constants and methods are deferred
+/// until [`build`](ClassBuilder::build) is called, so that a large API can be
+/// described with a single `?`.
+///
+/// See [`builder`] to create a `ClassBuilder`.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{class, eval, function, method};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn greet(name: String) -> String {
+///     format!("Hello, {}!", name)
+/// }
+///
+/// fn shout(rb_self: String) -> String {
+///     rb_self.to_uppercase()
+/// }
+///
+/// class::builder("Greeter")
+///     .constant("VERSION", "1.0.0")
+///     .singleton_method("greet", function!(greet, 1))
+///     .build()
+///     .unwrap();
+///
+/// class::builder("LoudString")
+///     .superclass(class::string())
+///     .method("shout", method!(shout, 0))
+///     .build()
+///     .unwrap();
+///
+/// assert!(eval::<bool>(r#"Greeter::VERSION == "1.0.0""#).unwrap());
+/// assert!(eval::<bool>(r#"Greeter.greet("world") == "Hello, world!""#).unwrap());
+/// assert!(eval::<bool>(r#"LoudString.new("hi").shout == "HI""#).unwrap());
+/// ```
+pub struct ClassBuilder {
+    name: String,
+    namespace: Option<RModule>,
+    superclass: RClass,
+    steps: Vec<BuilderStep>,
+}
+
+impl ClassBuilder {
+    /// Create a new `ClassBuilder` for a class named `name`.
+    ///
+    /// See also [`builder`].
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            namespace: None,
+            superclass: Default::default(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Define the class in `namespace`'s scope, rather than the root scope.
+    pub fn under(mut self, namespace: RModule) -> Self {
+        self.namespace = Some(namespace);
+        self
+    }
+
+    /// Set the parent class of the class. Defaults to `Object`.
+    pub fn superclass(mut self, superclass: RClass) -> Self {
+        self.superclass = superclass;
+        self
+    }
+
+    /// Define a constant named `name` with the value `value` in the class.
+    ///
+    /// See [`Module::const_set`].
+    pub fn constant<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: Into<Value>,
+    {
+        let name = name.to_owned();
+        // value is held on the heap until `build` is called, so must be
+        // protected from the GC.
+        let value = BoxValue::new(value.into());
+        self.steps
+            .push(Box::new(move |class| class.const_set(name, *value)));
+        self
+    }
+
+    /// Include `module` in the class.
+    ///
+    /// See [`Module::include_module`].
+    pub fn include_module(mut self, module: RModule) -> Self {
+        let module = BoxValue::new(module);
+        self.steps
+            .push(Box::new(move |class| class.include_module(*module)));
+        self
+    }
+
+    /// Define an instance method in the class.
+    ///
+    /// See [`Module::define_method`].
+    pub fn method<M>(mut self, name: &str, func: M) -> Self
+    where
+        M: Method + 'static,
+    {
+        let name = name.to_owned();
+        self.steps
+            .push(Box::new(move |class| class.define_method(name, func)));
+        self
+    }
+
+    /// Define a private instance method in the class.
+    ///
+    /// See [`Module::define_private_method`].
+    pub fn private_method<M>(mut self, name: &str, func: M) -> Self
+    where
+        M: Method + 'static,
+    {
+        let name = name.to_owned();
+        self.steps.push(Box::new(move |class| {
+            class.define_private_method(&name, func)
+        }));
+        self
+    }
+
+    /// Define a protected instance method in the class.
+    ///
+    /// See [`Module::define_protected_method`].
+    pub fn protected_method<M>(mut self, name: &str, func: M) -> Self
+    where
+        M: Method + 'static,
+    {
+        let name = name.to_owned();
+        self.steps.push(Box::new(move |class| {
+            class.define_protected_method(&name, func)
+        }));
+        self
+    }
+
+    /// Define a singleton method (aka class method) on the class.
+    ///
+    /// See [`Object::define_singleton_method`].
+    pub fn singleton_method<M>(mut self, name: &str, func: M) -> Self
+    where
+        M: Method + 'static,
+    {
+        let name = name.to_owned();
+        self.steps.push(Box::new(move |class| {
+            class.define_singleton_method(&name, func)
+        }));
+        self
+    }
+
+    /// Define attribute accessor methods in the class.
+    ///
+    /// See [`Module::define_attr`].
+    pub fn attr(mut self, name: &str, rw: Attr) -> Self {
+        let name = name.to_owned();
+        self.steps
+            .push(Box::new(move |class| class.define_attr(name, rw)));
+        self
+    }
+
+    /// Alias the method `src` as `dst` in the class.
+    ///
+    /// See [`Module::define_alias`].
+    pub fn alias(mut self, dst: &str, src: &str) -> Self {
+        let dst = dst.to_owned();
+        let src = src.to_owned();
+        self.steps
+            .push(Box::new(move |class| class.define_alias(dst, src)));
+        self
+    }
+
+    /// Define the class, then its constants and methods in the order they
+    /// were added to the builder, returning the class.
+    ///
+    /// A failing step does not prevent later steps from running. If a single
+    /// step fails its error is returned, if multiple steps fail a combined
+    /// error is returned, of the first failure's class, with a message
+    /// listing every failure. Returns `Err` without running any steps if the
+    /// class can not be defined.
+    pub fn build(self) -> Result<RClass, Error> {
+        let class = match self.namespace {
+            Some(namespace) => namespace.define_class(self.name.as_str(), self.superclass)?,
+            None => crate::define_class(&self.name, self.superclass)?,
+        };
+        let mut errors = Vec::new();
+        for step in self.steps {
+            match step(class) {
+                Ok(()) => (),
+                // break/throw must not be swallowed
+                Err(e @ Error::Jump(_)) => return Err(e),
+                Err(e) => errors.push(e),
+            }
+        }
+        if errors.len() > 1 {
+            let exception_class = match &errors[0] {
+                Error::Error(class, _) => *class,
+                Error::Exception(e) => e.exception_class(),
+                Error::Jump(_) => unreachable!(),
+            };
+            let messages = errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(Error::new(
+                exception_class,
+                format!(
+                    "{} errors building {}: {}",
+                    errors.len(),
+                    self.name,
+                    messages
+                ),
+            ));
+        }
+        match errors.pop() {
+            Some(e) => Err(e),
+            None => Ok(class),
+        }
+    }
+}
+
+/// Create a [`ClassBuilder`] for a class named `name`.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{class, Module};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let class = class::builder("Example")
+///     .constant("ANSWER", 42)
+///     .build()
+///     .unwrap();
+/// assert_eq!(class.const_get::<_, i64>("ANSWER").unwrap(), 42);
+/// ```
+pub fn builder<T>(name: T) -> ClassBuilder
+where
+    T: Into<String>,
+{
+    ClassBuilder::new(name)
+}
+
 impl RubyHandle {
     #[inline]
     pub fn class_array(&self) -> RClass {
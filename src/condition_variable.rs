@@ -0,0 +1,183 @@
+use std::{fmt, ops::Deref, time::Duration};
+
+use crate::{
+    class::RClass,
+    error::Error,
+    exception,
+    into_value::IntoValue,
+    memoize,
+    module::Module,
+    mutex::Mutex,
+    object::Object,
+    ruby_handle::RubyHandle,
+    try_convert::TryConvert,
+    value::{private, NonZeroValue, ReprValue, Value},
+};
+
+impl RubyHandle {
+    fn class_condition_variable(&self) -> RClass {
+        *memoize!(RClass: self.class_thread().const_get("ConditionVariable").unwrap())
+    }
+
+    pub fn condition_variable_new(&self) -> ConditionVariable {
+        let val = self.class_condition_variable().new_instance(()).unwrap();
+        unsafe { ConditionVariable(NonZeroValue::new_unchecked(val)) }
+    }
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's
+/// ConditionVariable class.
+///
+/// This is Ruby's `Thread::ConditionVariable`, used with a [`Mutex`] to wait
+/// for a condition to be signalled by another thread.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{
+///     eval,
+///     thread::{ConditionVariable, Mutex},
+///     Thread,
+/// };
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let mutex = Mutex::new();
+/// let cond = ConditionVariable::new();
+/// let _: magnus::Value = eval("$ready = false").unwrap();
+///
+/// let waiter = Thread::create(move || -> Result<bool, magnus::Error> {
+///     mutex.synchronize(|| {
+///         while !eval::<bool>("$ready")? {
+///             cond.wait(mutex, None)?;
+///         }
+///         Ok(true)
+///     })?
+/// })
+/// .unwrap();
+///
+/// mutex
+///     .synchronize(|| {
+///         let _: magnus::Value = eval("$ready = true").unwrap();
+///         cond.signal().unwrap();
+///     })
+///     .unwrap();
+/// assert!(waiter.value::<bool>().unwrap());
+/// ```
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct ConditionVariable(NonZeroValue);
+
+impl ConditionVariable {
+    /// Return `Some(ConditionVariable)` if `val` is a `ConditionVariable`,
+    /// `None` otherwise.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(get_ruby!().class_condition_variable())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    /// Create a new `ConditionVariable`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn new() -> Self {
+        get_ruby!().condition_variable_new()
+    }
+
+    /// Unlock `mutex` and wait to be signalled, or for `timeout` to pass,
+    /// then relock `mutex`.
+    ///
+    /// `mutex` must be locked by the current thread. As wakeups may be
+    /// spurious the condition being waited for should be checked in a loop.
+    pub fn wait(self, mutex: Mutex, timeout: Option<Duration>) -> Result<(), Error> {
+        match timeout {
+            Some(t) => self.funcall::<_, _, Value>("wait", (mutex, t.as_secs_f64()))?,
+            None => self.funcall::<_, _, Value>("wait", (mutex,))?,
+        };
+        Ok(())
+    }
+
+    /// Wake one thread waiting on `self`.
+    pub fn signal(self) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("signal", ())?;
+        Ok(())
+    }
+
+    /// Wake all threads waiting on `self`.
+    pub fn broadcast(self) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("broadcast", ())?;
+        Ok(())
+    }
+}
+
+impl Default for ConditionVariable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for ConditionVariable {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for ConditionVariable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for ConditionVariable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for ConditionVariable {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<ConditionVariable> for Value {
+    fn from(val: ConditionVariable) -> Self {
+        *val
+    }
+}
+
+impl Object for ConditionVariable {}
+
+unsafe impl private::ReprValue for ConditionVariable {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for ConditionVariable {}
+
+impl TryConvert for ConditionVariable {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!(
+                    "no implicit conversion of {} into ConditionVariable",
+                    unsafe { val.classname() },
+                ),
+            )
+        })
+    }
+}
@@ -1,31 +1,242 @@
 //! Helpers for use when embedding Ruby in a Rust project.
 
 use std::{
+    error::Error as StdError,
     ffi::CString,
-    sync::atomic::{AtomicBool, Ordering},
+    fmt,
+    io::Write,
+    os::raw::c_int,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        mpsc, Arc, Condvar, Mutex,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
 };
 
+#[cfg(unix)]
+use rb_sys::rb_thread_atfork;
 #[cfg(windows)]
 use rb_sys::rb_w32_sysinit;
 use rb_sys::{
-    ruby_cleanup, ruby_exec_node, ruby_executable_node, ruby_options, ruby_set_script_name,
-    ruby_setup,
+    rb_gv_get, rb_gv_set, rb_load, rb_set_end_proc, ruby_cleanup, ruby_exec_node,
+    ruby_executable_node, ruby_options, ruby_set_argv, ruby_set_script_name, ruby_setup, VALUE,
 };
 
-use crate::{r_string::RString, ruby_handle::RubyHandle};
+#[cfg(any(all(unix, ruby_gte_3_1), docsrs))]
+use crate::block::Proc;
+use crate::{
+    class::RClass,
+    error::{bug_from_panic, protect, Error, OwnedError},
+    exception,
+    executor::{ExecutorStoppedError, RubyExecutor},
+    gc, memoize,
+    module::RModule,
+    r_array::RArray,
+    r_string::RString,
+    ruby_handle::RubyHandle,
+    thread::without_gvl,
+    try_convert::{ArgList, TryConvert, TryConvertOwned},
+    value::{Value, QNIL},
+};
 
 /// A guard value that will run the cleanup function for the Ruby VM when
 /// dropped.
 pub struct Cleanup();
 
+impl Cleanup {
+    /// Require the feature `feature`, as with Ruby's `require`.
+    ///
+    /// Returns `Ok(true)` if the feature was loaded, `Ok(false)` if it had
+    /// already been loaded, and `Err` if it could not be loaded or raised an
+    /// exception while loading. See also [`require`](crate::require()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::eval;
+    /// let cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(cleanup.require("json").unwrap());
+    /// assert!(!cleanup.require("json").unwrap());
+    /// assert_eq!(eval::<String>("[1, 2].to_json").unwrap(), "[1,2]");
+    /// ```
+    #[cfg(ruby_gte_2_7)]
+    pub fn require<T>(&self, feature: T) -> Result<bool, Error>
+    where
+        T: Into<RString>,
+    {
+        get_ruby!().require(feature)
+    }
+
+    /// Require the feature `feature`, as with Ruby's `require`.
+    ///
+    /// Returns `Ok(true)` if the feature was loaded, `Ok(false)` if it had
+    /// already been loaded, and `Err` if it could not be loaded or raised an
+    /// exception while loading. See also [`require`](crate::require()).
+    #[cfg(ruby_lt_2_7)]
+    pub fn require(&self, feature: &str) -> Result<bool, Error> {
+        get_ruby!().require(feature)
+    }
+
+    /// Require the file at `path`, relative to the current working directory
+    /// rather than searching `$LOAD_PATH`.
+    ///
+    /// Returns `Ok(true)` if the file was loaded, `Ok(false)` if it had
+    /// already been loaded, and `Err` if it could not be loaded or raised an
+    /// exception while loading.
+    pub fn require_relative<T>(&self, path: T) -> Result<bool, Error>
+    where
+        T: Into<RString>,
+    {
+        get_ruby!().require_relative(path)
+    }
+
+    /// Load and run the file at `path`, as with Ruby's `load`.
+    ///
+    /// Unlike [`require`](Cleanup::require) the file is loaded every time
+    /// this is called. If `wrap` is `true` the file is run under an anonymous
+    /// module, so it can not pollute the global namespace.
+    ///
+    /// Returns `Ok(true)` if the file was loaded, and `Err` if it could not
+    /// be found or raised an exception.
+    pub fn load_file<T>(&self, path: T, wrap: bool) -> Result<bool, Error>
+    where
+        T: Into<RString>,
+    {
+        get_ruby!().load_file(path, wrap)
+    }
+}
+
+impl Cleanup {
+    /// Clean up the Ruby VM now, returning its exit status.
+    ///
+    /// This runs any `at_exit` handlers (and end procs registered with
+    /// [`set_end_proc`]), and so may not return `0` if one of them calls
+    /// `exit`. The returned status is suitable to pass to
+    /// [`std::process::exit`].
+    ///
+    /// The same clean up is run when `Cleanup` is dropped, but the exit
+    /// status is discarded.
+    ///
+    /// # Safety
+    ///
+    /// Must be called at the very end of the process, after all Ruby
+    /// execution has finished. See [`init`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// let cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let _: Value = eval("at_exit { exit 3 }").unwrap();
+    ///
+    /// let status = unsafe { cleanup.finish() };
+    /// assert_eq!(status, 3);
+    /// ```
+    pub unsafe fn finish(self) -> i32 {
+        let status = cleanup_vm(0);
+        std::mem::forget(self);
+        status
+    }
+}
+
 impl Drop for Cleanup {
     fn drop(&mut self) {
         unsafe {
-            ruby_cleanup(0);
+            cleanup_vm(0);
         }
     }
 }
 
+/// Clean up the Ruby VM, returning its exit status.
+unsafe fn cleanup_vm(status: c_int) -> c_int {
+    // in a process forked without Ruby's knowledge cleanup would attempt to
+    // stop threads that don't exist, crashing the process
+    if is_forked() {
+        return status;
+    }
+    ruby_cleanup(status)
+}
+
+impl RubyHandle {
+    pub fn set_end_proc<F>(&self, func: F)
+    where
+        F: 'static + Send + FnOnce(),
+    {
+        unsafe extern "C" fn call<F>(arg: VALUE)
+        where
+            F: FnOnce(),
+        {
+            let func = Box::from_raw(arg as *mut F);
+            if let Err(e) = catch_unwind(AssertUnwindSafe(func)) {
+                bug_from_panic(e, "panic in end proc")
+            }
+        }
+
+        let data = Box::into_raw(Box::new(func)) as VALUE;
+        unsafe { rb_set_end_proc(Some(call::<F>), data) };
+    }
+
+    pub unsafe fn exit(&self, status: i32) -> ! {
+        std::process::exit(cleanup_vm(status))
+    }
+}
+
+/// Register `func` to be run when the Ruby VM is cleaned up.
+///
+/// End procs are run along with `at_exit` handlers, in reverse order of
+/// registration, when the VM is cleaned up, such as when [`Cleanup`] is
+/// dropped, or when Ruby is exiting when embedding Rust in Ruby.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::embed;
+/// # let _cleanup = unsafe { embed::init() };
+///
+/// embed::set_end_proc(|| println!("shutting down"));
+/// ```
+pub fn set_end_proc<F>(func: F)
+where
+    F: 'static + Send + FnOnce(),
+{
+    get_ruby!().set_end_proc(func)
+}
+
+/// Clean up the Ruby VM and exit the process with `status`.
+///
+/// This shuts down the VM in the same way as the `ruby` executable, running
+/// any `at_exit` handlers (and end procs registered with [`set_end_proc`])
+/// before exiting. If one of these handlers calls `exit` the process will
+/// exit with that status instead.
+///
+/// Unlike Ruby's `Kernel#exit` this does not raise `SystemExit`, and so can
+/// not be rescued. It does not return.
+///
+/// # Safety
+///
+/// Must not be called while Ruby code is running, e.g. from a Rust function
+/// called from Ruby. Ruby threads other than the current one are killed
+/// without unwinding their Rust stack.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub unsafe fn exit(status: i32) -> ! {
+    get_ruby!().exit(status)
+}
+
+/// The id of the process the Ruby VM is running in, or 0 if not yet
+/// initialised.
+static VM_PID: AtomicU32 = AtomicU32::new(0);
+
 /// Initialises the Ruby VM.
 ///
 /// Calling this function is only required when embedding Ruby in Rust. It is
@@ -37,6 +248,10 @@ impl Drop for Cleanup {
 /// any code calling Ruby. Must not drop Cleanup until the very end of the
 /// process, after all Ruby execution has finished.
 ///
+/// This records the process id, for [`is_forked`], but does not hook Ruby's
+/// `fork`. To have [`is_forked`] distinguish forks made by Ruby call
+/// [`track_forks`].
+///
 /// # Panics
 ///
 /// Panics if called more than once.
@@ -51,6 +266,317 @@ pub unsafe fn init() -> Cleanup {
     init_options(&["-e", ""])
 }
 
+/// Initialises the Ruby VM, with the given script name and arguments.
+///
+/// Like [`init`], but `$0` (`$PROGRAM_NAME`) will be set to `script_name` and
+/// `ARGV` to `args`, rather than `"-e"` and an empty array. This allows Ruby
+/// code that inspects these, such as `OptionParser`, to behave as it would
+/// when run by the `ruby` executable.
+///
+/// `args` are not interpreted as options to the Ruby VM.
+///
+/// # Safety
+///
+/// Must be called in `main()`, or at least a function higher up the stack than
+/// any code calling Ruby. Must not drop Cleanup until the very end of the
+/// process, after all Ruby execution has finished.
+///
+/// # Panics
+///
+/// Panics if called more than once, or if any of `args` contain a nul byte.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::eval;
+///
+/// let _cleanup =
+///     unsafe { magnus::embed::init_with_args("my_tool", &["--verbose", "input.txt"]) };
+///
+/// assert_eq!(eval::<String>("$0").unwrap(), "my_tool");
+/// assert_eq!(
+///     eval::<Vec<String>>("ARGV").unwrap(),
+///     vec!["--verbose", "input.txt"]
+/// );
+/// ```
+#[inline(always)]
+pub unsafe fn init_with_args<T>(script_name: &str, args: &[T]) -> Cleanup
+where
+    T: AsRef<str>,
+{
+    builder()
+        .script_name(script_name)
+        .args(args.iter().map(|s| s.as_ref()))
+        .init()
+}
+
+/// Create a new [`Builder`], to configure the Ruby VM before
+/// initialising it.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::eval;
+///
+/// let _cleanup = unsafe {
+///     magnus::embed::builder()
+///         .load_path("lib")
+///         .did_you_mean(false)
+///         .init()
+/// };
+///
+/// assert!(eval::<bool>("$LOAD_PATH.include?(File.expand_path('lib'))").unwrap());
+/// ```
+pub fn builder() -> Builder {
+    Builder::new()
+}
+
+/// A builder to configure the Ruby VM before initialising it.
+///
+/// See [`builder`].
+pub struct Builder {
+    script_name: Option<String>,
+    args: Option<Vec<CString>>,
+    load_path: Vec<String>,
+    gems: bool,
+    did_you_mean: bool,
+    gem_home: Option<String>,
+    gem_path: Vec<String>,
+    env: Vec<(String, String)>,
+    opts: Vec<String>,
+}
+
+impl Builder {
+    /// Create a new `Builder`.
+    ///
+    /// See also [`builder`].
+    pub fn new() -> Self {
+        Self {
+            script_name: None,
+            args: None,
+            load_path: Vec::new(),
+            gems: true,
+            did_you_mean: true,
+            gem_home: None,
+            gem_path: Vec::new(),
+            env: Vec::new(),
+            opts: Vec::new(),
+        }
+    }
+
+    /// Set `$0` (`$PROGRAM_NAME`). Defaults to `"-e"`.
+    ///
+    /// See [`init_with_args`].
+    pub fn script_name<T>(mut self, name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.script_name = Some(name.into());
+        self
+    }
+
+    /// Set `ARGV`. Defaults to an empty array.
+    ///
+    /// `args` are not interpreted as options to the Ruby VM.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of `args` contain a nul byte.
+    pub fn args<I, T>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Vec<u8>>,
+    {
+        self.args = Some(args.into_iter().map(|s| CString::new(s).unwrap()).collect());
+        self
+    }
+
+    /// Add `path` to `$LOAD_PATH`, as with the `-I` option to `ruby`.
+    ///
+    /// Paths added this way are searched before the standard library and
+    /// any gems. Relative paths are expanded relative to the current working
+    /// directory.
+    pub fn load_path<T>(mut self, path: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.load_path.push(path.into());
+        self
+    }
+
+    /// Enable or disable loading RubyGems. Defaults to enabled.
+    ///
+    /// Disabling RubyGems also disables did_you_mean, and any other default
+    /// gems loaded at startup. Equivalent to the `--disable-gems` option to
+    /// `ruby`.
+    pub fn gems(mut self, enabled: bool) -> Self {
+        self.gems = enabled;
+        self
+    }
+
+    /// Enable or disable loading did_you_mean. Defaults to enabled.
+    ///
+    /// Equivalent to the `--disable-did_you_mean` option to `ruby`.
+    pub fn did_you_mean(mut self, enabled: bool) -> Self {
+        self.did_you_mean = enabled;
+        self
+    }
+
+    /// Set the `GEM_HOME` environment variable, the directory gems are
+    /// installed to.
+    ///
+    /// The environment variable is set for the whole process when
+    /// [`init`](Builder::init) is called, overriding any existing value.
+    pub fn gem_home<T>(mut self, path: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.gem_home = Some(path.into());
+        self
+    }
+
+    /// Add `path` to the `GEM_PATH` environment variable, the directories
+    /// searched for installed gems.
+    ///
+    /// The environment variable is set for the whole process when
+    /// [`init`](Builder::init) is called, overriding any existing value.
+    pub fn gem_path<T>(mut self, path: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.gem_path.push(path.into());
+        self
+    }
+
+    /// Enable or disable frozen string literals, as with the
+    /// `--enable-frozen-string-literal` and `--disable-frozen-string-literal`
+    /// options to `ruby`.
+    pub fn frozen_string_literal(self, enabled: bool) -> Self {
+        if enabled {
+            self.opt("--enable-frozen-string-literal")
+        } else {
+            self.opt("--disable-frozen-string-literal")
+        }
+    }
+
+    /// Set the warning level, as with the `-W` option to `ruby`.
+    ///
+    /// `0` silences all warnings, `1` shows important warnings (the default),
+    /// and `2` (or higher) shows all warnings, equivalent to setting
+    /// `$VERBOSE` to `nil`, `false`, and `true` respectively.
+    pub fn warning_level(self, level: u8) -> Self {
+        self.opt(format!("-W{}", level.min(2)))
+    }
+
+    /// Set the environment variable `key` to `value` before initialising
+    /// Ruby.
+    ///
+    /// This is useful for settings that Ruby only reads from the
+    /// environment at startup, such as the garbage collector tuning
+    /// variables (e.g. `RUBY_GC_HEAP_GROWTH_FACTOR`).
+    ///
+    /// The environment variable is set for the whole process when
+    /// [`init`](Builder::init) is called, overriding any existing value.
+    pub fn env<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Pass `opt` as a command line option to the Ruby VM, as if passed to
+    /// the `ruby` executable, e.g. `"--disable-gems"`, `"-W0"`, or
+    /// `"--jit"`.
+    ///
+    /// Options are passed in the order they were added, after those set by
+    /// other methods of this builder. Options that run code, or take a script
+    /// file, should not be used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{embed::Builder, eval};
+    ///
+    /// let _cleanup = unsafe {
+    ///     Builder::new()
+    ///         .opt("--disable-gems")
+    ///         .opt("--enable-frozen-string-literal")
+    ///         .init()
+    /// };
+    ///
+    /// assert!(eval::<bool>(r#""literal".frozen?"#).unwrap());
+    /// ```
+    pub fn opt<T>(mut self, opt: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.opts.push(opt.into());
+        self
+    }
+
+    /// Initialises the Ruby VM.
+    ///
+    /// See [`init`].
+    ///
+    /// # Safety
+    ///
+    /// Must be called in `main()`, or at least a function higher up the stack
+    /// than any code calling Ruby. Must not drop Cleanup until the very end of
+    /// the process, after all Ruby execution has finished.
+    ///
+    /// # Panics
+    ///
+    /// Panics if Ruby has already been initialised, if any of the `gem_path`
+    /// entries contain the platform's path separator, or if any of the
+    /// options contain a nul byte.
+    #[inline(always)]
+    pub unsafe fn init(self) -> Cleanup {
+        for (key, value) in &self.env {
+            std::env::set_var(key, value);
+        }
+        if let Some(gem_home) = &self.gem_home {
+            std::env::set_var("GEM_HOME", gem_home);
+        }
+        if !self.gem_path.is_empty() {
+            std::env::set_var("GEM_PATH", std::env::join_paths(&self.gem_path).unwrap());
+        }
+        let mut opts = self
+            .load_path
+            .iter()
+            .map(|path| format!("-I{}", path))
+            .collect::<Vec<_>>();
+        if !self.gems {
+            opts.push("--disable-gems".to_owned());
+        }
+        if !self.did_you_mean {
+            opts.push("--disable-did_you_mean".to_owned());
+        }
+        opts.extend(self.opts);
+        opts.push("-e".to_owned());
+        opts.push("".to_owned());
+        let cleanup = init_options(&opts.iter().map(|s| s.as_str()).collect::<Vec<_>>());
+        if let Some(script_name) = self.script_name {
+            ruby_script(script_name.as_str());
+        }
+        if let Some(args) = self.args {
+            let mut argv = args
+                .iter()
+                .map(|cs| cs.as_ptr() as *mut _)
+                .collect::<Vec<_>>();
+            ruby_set_argv(argv.len() as c_int, argv.as_mut_ptr());
+        }
+        cleanup
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[inline(always)]
 unsafe fn init_options(opts: &[&str]) -> Cleanup {
     static INIT: AtomicBool = AtomicBool::new(false);
@@ -82,6 +608,7 @@ unsafe fn init_options(opts: &[&str]) -> Cleanup {
             if ruby_exec_node(node) != 0 {
                 panic!("Ruby init code failed");
             };
+            VM_PID.store(std::process::id(), Ordering::SeqCst);
             cleanup
         }
         Err(true) => panic!("Ruby already initialized"),
@@ -89,6 +616,364 @@ unsafe fn init_options(opts: &[&str]) -> Cleanup {
     }
 }
 
+impl RubyHandle {
+    pub fn require_relative<T>(&self, path: T) -> Result<bool, Error>
+    where
+        T: Into<RString>,
+    {
+        let path: RString = self.class_file().funcall("expand_path", (path.into(),))?;
+        #[cfg(ruby_gte_2_7)]
+        let res = self.require(path);
+        #[cfg(ruby_lt_2_7)]
+        let res = self.require(&path.to_string()?);
+        res
+    }
+
+    pub fn load_file<T>(&self, path: T, wrap: bool) -> Result<bool, Error>
+    where
+        T: Into<RString>,
+    {
+        let path = path.into();
+        protect(|| unsafe {
+            rb_load(path.as_rb_value(), wrap as c_int);
+            *QNIL
+        })?;
+        Ok(true)
+    }
+}
+
+impl RubyHandle {
+    #[cfg(any(all(unix, ruby_gte_3_1), docsrs))]
+    pub fn embed_track_forks(&self) -> Result<(), Error> {
+        static TRACKING: AtomicBool = AtomicBool::new(false);
+        if TRACKING.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        let hook: Proc = self.eval(
+            r#"
+            lambda do |callback|
+              Process.singleton_class.prepend(Module.new do
+                define_method(:_fork) do
+                  pid = super()
+                  callback.call if pid == 0
+                  pid
+                end
+              end)
+            end
+            "#,
+        )?;
+        let callback = self.proc_from_fn(|_, _| VM_PID.store(std::process::id(), Ordering::SeqCst));
+        hook.call::<_, Value>((callback,))?;
+        Ok(())
+    }
+}
+
+/// Track forks made by Ruby (e.g. with `Process.fork`), so that
+/// [`is_forked`] can distinguish them from forks made without Ruby's
+/// knowledge.
+///
+/// This prepends a module to `Process`'s singleton class overriding
+/// `Process._fork`, which will be visible to all Ruby code in the process,
+/// including other libraries that hook `_fork`. Calling this more than once
+/// has no further effect.
+///
+/// Only available on Unix with Ruby 3.1+, where `Process._fork` exists.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::embed;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// embed::track_forks().unwrap();
+/// assert!(!embed::is_forked());
+/// ```
+#[cfg(any(all(unix, ruby_gte_3_1), docsrs))]
+#[cfg_attr(docsrs, doc(cfg(all(unix, ruby_gte_3_1))))]
+pub fn track_forks() -> Result<(), Error> {
+    get_ruby!().embed_track_forks()
+}
+
+/// Returns whether the current process is a fork of the process the Ruby VM
+/// was initialised in, made without Ruby's knowledge.
+///
+/// When a process forks only the thread that called `fork` is copied to the
+/// child process. Ruby's own `fork` (e.g. `Process.fork`, or `Kernel#fork`)
+/// accounts for this, but a fork made by other means, such as a Rust
+/// library that daemonises the process, leaves the Ruby VM in an
+/// inconsistent state. Using Ruby in the child process after such a fork is
+/// undefined behaviour, unless [`after_fork`] is called.
+///
+/// If the current process is such a fork the Ruby VM will not be cleaned up
+/// when [`Cleanup`] is dropped.
+///
+/// Forks made by Ruby are only distinguished from other forks after
+/// [`track_forks`] has been called. Without it (and always on Ruby versions
+/// before 3.1) this will also return `true` in the child process after a
+/// fork made by Ruby's own `fork`.
+///
+/// Always returns `false` if the Ruby VM was not initialised with
+/// [`init`] (e.g. when embedding Rust in Ruby).
+pub fn is_forked() -> bool {
+    let pid = VM_PID.load(Ordering::SeqCst);
+    pid != 0 && pid != std::process::id()
+}
+
+/// Restore the Ruby VM to a useable state in a process forked without
+/// Ruby's knowledge.
+///
+/// This performs the same clean up Ruby does in the child process after its
+/// own `fork`, marking all threads other than the current one as dead.
+/// After this [`is_forked`] will return `false`.
+///
+/// # Safety
+///
+/// Must only be called in the child process after a fork, on the thread
+/// that called `fork`, and that thread must have been a Ruby thread holding
+/// the GVL at the time of the fork.
+#[cfg(unix)]
+pub unsafe fn after_fork() {
+    rb_thread_atfork();
+    VM_PID.store(std::process::id(), Ordering::SeqCst);
+}
+
+impl RubyHandle {
+    pub fn push_load_path<T>(&self, path: T) -> Result<(), Error>
+    where
+        T: Into<RString>,
+    {
+        let path: RString = self.class_file().funcall("expand_path", (path.into(),))?;
+        let load_path = unsafe {
+            let name = CString::new("$LOAD_PATH").unwrap();
+            RArray::from_rb_value_unchecked(rb_gv_get(name.as_ptr()))
+        };
+        load_path.push(path)
+    }
+}
+
+/// Add `path` to the end of `$LOAD_PATH`.
+///
+/// Relative paths are expanded relative to the current working directory.
+/// To add paths that are searched before the standard library and any gems
+/// see [`Builder::load_path`].
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{embed, eval};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// embed::push_load_path("vendor/ruby").unwrap();
+/// assert!(eval::<bool>("$LOAD_PATH.last.end_with?('vendor/ruby')").unwrap());
+/// ```
+pub fn push_load_path<T>(path: T) -> Result<(), Error>
+where
+    T: Into<RString>,
+{
+    get_ruby!().push_load_path(path)
+}
+
+/// An IO-like class, passing writes on to a Rust `Write`.
+///
+/// `<<`, `print`, `printf`, and `puts` are implemented in terms of `write`
+/// (rather than with `IO::generic_writable`, which is only available once
+/// `stringio` has been required).
+const OUTPUT_CLASS: &str = r#"
+Class.new do
+  def initialize(write, flush)
+    @write = write
+    @flush = flush
+  end
+
+  def write(*args)
+    args.sum { |arg| @write.call(arg.to_s) }
+  end
+
+  def <<(obj)
+    write(obj)
+    self
+  end
+
+  def print(*args)
+    write(*args)
+    write($\) if $\
+    nil
+  end
+
+  def printf(format_string, *args)
+    write(format(format_string, *args))
+    nil
+  end
+
+  def puts(*args)
+    return puts("") if args.empty?
+    args.flatten.each do |arg|
+      line = arg.to_s
+      write(line.end_with?("\n") ? line : line + "\n")
+    end
+    nil
+  end
+
+  def flush
+    @flush.call
+    self
+  end
+
+  def fsync
+    flush
+    0
+  end
+
+  def sync
+    true
+  end
+
+  def sync=(value)
+  end
+
+  def tty?
+    false
+  end
+  alias_method :isatty, :tty?
+
+  def fileno
+    nil
+  end
+end
+"#;
+
+impl RubyHandle {
+    fn class_output(&self) -> RClass {
+        *memoize!(RClass: {
+            let class = self.eval::<RClass>(OUTPUT_CLASS).unwrap();
+            gc::register_mark_object(class);
+            class
+        })
+    }
+
+    fn redirect_output<W>(&self, name: &str, writer: W) -> Result<(), Error>
+    where
+        W: 'static + Send + Write,
+    {
+        let writer = Arc::new(Mutex::new(writer));
+        let flush_writer = writer.clone();
+        let write = self.proc_from_fn(move |args, _| -> Result<usize, Error> {
+            let s = RString::try_convert(args[0])?;
+            let bytes = unsafe { s.as_slice() };
+            writer
+                .lock()
+                .unwrap()
+                .write_all(bytes)
+                .map_err(|e| Error::new(exception::io_error(), e.to_string()))?;
+            Ok(bytes.len())
+        });
+        let flush = self.proc_from_fn(move |_, _| -> Result<(), Error> {
+            flush_writer
+                .lock()
+                .unwrap()
+                .flush()
+                .map_err(|e| Error::new(exception::io_error(), e.to_string()))
+        });
+        let output = self.class_output().new_instance((write, flush))?;
+        let name = CString::new(name).unwrap();
+        protect(|| unsafe { Value::new(rb_gv_set(name.as_ptr(), output.as_rb_value())) })?;
+        Ok(())
+    }
+
+    pub fn redirect_stdout<W>(&self, writer: W) -> Result<(), Error>
+    where
+        W: 'static + Send + Write,
+    {
+        self.redirect_output("$stdout", writer)
+    }
+
+    pub fn redirect_stderr<W>(&self, writer: W) -> Result<(), Error>
+    where
+        W: 'static + Send + Write,
+    {
+        self.redirect_output("$stderr", writer)
+    }
+}
+
+/// Replace Ruby's `$stdout` with an IO-like object that writes to `writer`.
+///
+/// Output from Ruby methods that write to `$stdout`, such as `puts` and
+/// `print`, will be written to `writer`. Output written directly to the
+/// process's standard output file descriptor (e.g. with the `STDOUT`
+/// constant, by C extensions, or by child processes) is not redirected.
+///
+/// The original `$stdout` can be restored by setting `$stdout = STDOUT`.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use std::{
+///     io::{self, Write},
+///     sync::{Arc, Mutex},
+/// };
+///
+/// use magnus::{embed, eval, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// #[derive(Clone, Default)]
+/// struct Buffer(Arc<Mutex<Vec<u8>>>);
+///
+/// impl Write for Buffer {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         self.0.lock().unwrap().write(buf)
+///     }
+///
+///     fn flush(&mut self) -> io::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let buffer = Buffer::default();
+/// embed::redirect_stdout(buffer.clone()).unwrap();
+///
+/// let _: Value = eval(r#"puts "hello, world""#).unwrap();
+/// assert_eq!(*buffer.0.lock().unwrap(), b"hello, world\n");
+/// ```
+pub fn redirect_stdout<W>(writer: W) -> Result<(), Error>
+where
+    W: 'static + Send + Write,
+{
+    get_ruby!().redirect_stdout(writer)
+}
+
+/// Replace Ruby's `$stderr` with an IO-like object that writes to `writer`.
+///
+/// Output from Ruby methods that write to `$stderr`, such as `warn`, will be
+/// written to `writer`. Output written directly to the process's standard
+/// error file descriptor (e.g. with the `STDERR` constant, by C extensions,
+/// or by child processes) is not redirected.
+///
+/// The original `$stderr` can be restored by setting `$stderr = STDERR`.
+///
+/// See [`redirect_stdout`] for an example.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn redirect_stderr<W>(writer: W) -> Result<(), Error>
+where
+    W: 'static + Send + Write,
+{
+    get_ruby!().redirect_stderr(writer)
+}
+
 impl RubyHandle {
     pub fn script<T>(&self, name: T)
     where
@@ -110,3 +995,532 @@ where
 {
     get_ruby!().script(name)
 }
+
+impl RubyHandle {
+    pub fn using(&self, module: RModule) -> Result<(), Error> {
+        let main = self.eval::<Value>("self")?;
+        main.funcall::<_, _, Value>("using", (module,))?;
+        Ok(())
+    }
+}
+
+/// Activate the refinements in `module` for the top-level scope.
+///
+/// This is the equivalent of calling `using` at the top level of a Ruby
+/// script. Refinements will only apply to Ruby code subsequently evaluated at
+/// the top level, e.g. with [`eval`](crate::eval()).
+///
+/// As with Ruby's `using`, this is only permitted at the top level, and will
+/// return `Err` if called while a Ruby method is executing, such as from a
+/// Rust function bound as a Ruby method.
+///
+/// See [`RModule::define_refinement`].
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{class, define_module, embed, eval, method, Module};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn shout(rb_self: String) -> String {
+///     rb_self.to_uppercase()
+/// }
+///
+/// let module = define_module("Shouting").unwrap();
+/// let refinement = module.define_refinement(class::string()).unwrap();
+/// refinement.define_method("shout", method!(shout, 0)).unwrap();
+///
+/// embed::using(module).unwrap();
+/// let res: String = eval(r#""hello".shout"#).unwrap();
+/// assert_eq!(res, "HELLO");
+/// ```
+pub fn using(module: RModule) -> Result<(), Error> {
+    get_ruby!().using(module)
+}
+
+impl RubyHandle {
+    pub fn eval_script<T>(&self, source: &str, path: &str) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.iseq_compile(source, path, 1)?.eval()
+    }
+}
+
+/// Evaluate `source` as if it were the contents of the Ruby file at `path`,
+/// converting the result to a `T`.
+///
+/// Unlike [`eval`](crate::eval()), where code is reported as being from
+/// `(eval)`, `__FILE__`, `__dir__`, and backtraces will refer to `path`.
+/// `path` does not need to exist. This is intended for running Ruby code
+/// embedded in a Rust binary with [`include_str!`], see also [`prelude!`].
+///
+/// The code is run at the top level, as if it were a file being `require`d.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{embed, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let source = "def where_am_i; __FILE__; end";
+/// let _: Value = embed::eval_script(source, "lib/app/setup.rb").unwrap();
+///
+/// let res: String = embed::eval_script("where_am_i", "main.rb").unwrap();
+/// assert_eq!(res, "lib/app/setup.rb");
+/// ```
+///
+/// [`prelude!`]: crate::prelude!
+pub fn eval_script<T>(source: &str, path: &str) -> Result<T, Error>
+where
+    T: TryConvert,
+{
+    get_ruby!().eval_script(source, path)
+}
+
+/// Evaluate a Ruby file embedded in the binary at compile time.
+///
+/// `prelude!("path/to/file.rb")` includes the file with [`include_str!`]
+/// (so `path` is relative to the current Rust source file), and evaluates it
+/// with [`embed::eval_script`](crate::embed::eval_script), using `path` as
+/// the file name for `__FILE__` and backtraces.
+///
+/// Returns `Result<T, Error>`, where `T` is inferred from the context.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```ignore
+/// let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let _: magnus::Value = magnus::prelude!("ruby/helpers.rb").unwrap();
+/// let _: magnus::Value = magnus::prelude!("ruby/app.rb").unwrap();
+/// ```
+#[macro_export]
+macro_rules! prelude {
+    ($path:expr) => {
+        $crate::embed::eval_script(include_str!($path), $path)
+    };
+}
+
+/// Boot the Ruby VM on a new, dedicated, thread.
+///
+/// Returns a [`Vm`] handle that can be used from any thread to run code on
+/// the VM's thread. This allows a multi-threaded Rust program to embed Ruby
+/// without each thread having to account for Ruby's threading rules.
+///
+/// The VM's thread exits, and the Ruby VM is cleaned up, once
+/// [`Vm::shutdown`] is called, or all handles are dropped.
+///
+/// # Panics
+///
+/// Panics if Ruby has already been initialised.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::embed;
+///
+/// let vm = embed::spawn();
+///
+/// let workers = (1..=4)
+///     .map(|i| {
+///         let vm = vm.clone();
+///         std::thread::spawn(move || vm.eval::<i64>(&format!("{} * 10", i)).unwrap())
+///     })
+///     .collect::<Vec<_>>();
+/// let total: i64 = workers.into_iter().map(|w| w.join().unwrap()).sum();
+/// assert_eq!(total, 100);
+///
+/// let res: String = vm.call("format", ("%05.1f", 3.14159)).unwrap();
+/// assert_eq!(res, "003.1");
+///
+/// vm.shutdown();
+/// ```
+pub fn spawn() -> Vm {
+    let (tx, rx) = mpsc::channel();
+    let thread = std::thread::spawn(move || {
+        let cleanup = unsafe { init() };
+        let (executor, executor_loop) = RubyExecutor::pair();
+        let _ = tx.send(executor);
+        let _ = executor_loop.run();
+        drop(cleanup);
+    });
+    match rx.recv() {
+        Ok(executor) => Vm {
+            executor,
+            thread: Arc::new(Mutex::new(Some(thread))),
+        },
+        Err(_) => match thread.join() {
+            Err(e) => resume_unwind(e),
+            Ok(()) => unreachable!(),
+        },
+    }
+}
+
+/// A handle to a Ruby VM running on a dedicated thread.
+///
+/// The handle is `Send` and `Clone`, so can be shared between threads.
+///
+/// See [`spawn`].
+#[derive(Clone)]
+pub struct Vm {
+    executor: RubyExecutor,
+    thread: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl Vm {
+    /// Run `func` on the VM's thread, waiting for it to complete.
+    ///
+    /// See [`RubyExecutor::run`].
+    pub fn run<F, R>(&self, func: F) -> Result<R, VmError>
+    where
+        F: 'static + Send + FnOnce() -> R,
+        R: 'static + Send,
+    {
+        Ok(self.executor.run(func)?)
+    }
+
+    /// Evaluate a string of Ruby code on the VM's thread, converting the
+    /// result to a `T`.
+    ///
+    /// `T` must be a Rust type, as Ruby values can't be returned from the
+    /// VM's thread.
+    ///
+    /// See [`eval`](crate::eval()).
+    pub fn eval<T>(&self, s: &str) -> Result<T, VmError>
+    where
+        T: 'static + Send + TryConvertOwned,
+    {
+        let s = s.to_owned();
+        self.run(move || get_ruby!().eval(&s).map_err(OwnedError::from))?
+            .map_err(VmError::from)
+    }
+
+    /// Call the top-level method `method` on the VM's thread, with the
+    /// arguments `args`, converting the result to a `T`.
+    ///
+    /// `T` must be a Rust type, as Ruby values can't be returned from the
+    /// VM's thread.
+    pub fn call<A, T>(&self, method: &str, args: A) -> Result<T, VmError>
+    where
+        A: 'static + Send + ArgList,
+        T: 'static + Send + TryConvertOwned,
+    {
+        let method = method.to_owned();
+        self.run(move || {
+            get_ruby!()
+                .eval::<Value>("self")
+                .and_then(|main| main.funcall(method.as_str(), args))
+                .map_err(OwnedError::from)
+        })?
+        .map_err(VmError::from)
+    }
+
+    /// Stop the VM, once it has run any closures already sent, waiting for
+    /// the Ruby VM to be cleaned up.
+    ///
+    /// Any further attempts to use the VM will return
+    /// `Err(VmError::Stopped)`. If called from the VM's thread the VM will
+    /// stop once the current closure returns.
+    pub fn shutdown(&self) {
+        self.executor.shutdown();
+        if RubyHandle::get().is_ok() {
+            return;
+        }
+        let thread = self.thread.lock().unwrap().take();
+        if let Some(thread) = thread {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl fmt::Debug for Vm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Vm").finish()
+    }
+}
+
+/// An error returned by [`Vm`].
+#[derive(Debug)]
+pub enum VmError {
+    /// The VM has stopped.
+    Stopped,
+    /// Ruby raised an exception.
+    Ruby(OwnedError),
+}
+
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stopped => write!(f, "Ruby VM has stopped."),
+            Self::Ruby(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl StdError for VmError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Stopped => None,
+            Self::Ruby(e) => Some(e),
+        }
+    }
+}
+
+impl From<ExecutorStoppedError> for VmError {
+    fn from(_: ExecutorStoppedError) -> Self {
+        Self::Stopped
+    }
+}
+
+impl From<OwnedError> for VmError {
+    fn from(e: OwnedError) -> Self {
+        Self::Ruby(e)
+    }
+}
+
+/// A token that can be used to cancel Ruby code run with
+/// [`eval_with_cancel`].
+///
+/// The token is `Send` and `Clone`, so can be cancelled from any thread.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<Watch>);
+
+#[derive(Default)]
+struct Watch {
+    state: Mutex<WatchState>,
+    cond: Condvar,
+}
+
+#[derive(Default)]
+struct WatchState {
+    cancelled: bool,
+    finished: bool,
+    interrupted: bool,
+}
+
+enum Wake {
+    Cancelled,
+    Expired,
+    Finished,
+    Interrupted,
+}
+
+impl CancelToken {
+    /// Create a new `CancelToken`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Cancel the Ruby code being run with this token.
+    ///
+    /// If no code is currently being run, the next call to
+    /// [`eval_with_cancel`] with this token will be cancelled immediately.
+    pub fn cancel(&self) {
+        self.0.state.lock().unwrap().cancelled = true;
+        self.0.cond.notify_all();
+    }
+
+    /// Returns whether [`cancel`](CancelToken::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.state.lock().unwrap().cancelled
+    }
+
+    fn set_finished(&self, finished: bool) {
+        self.0.state.lock().unwrap().finished = finished;
+        self.0.cond.notify_all();
+    }
+
+    fn is_finished(&self) -> bool {
+        self.0.state.lock().unwrap().finished
+    }
+
+    fn interrupt(&self) {
+        self.0.state.lock().unwrap().interrupted = true;
+        self.0.cond.notify_all();
+    }
+
+    /// Wait until cancelled, finished, interrupted, or `deadline` passes.
+    fn wait(&self, deadline: Option<Instant>) -> Wake {
+        let mut state = self.0.state.lock().unwrap();
+        loop {
+            if state.finished {
+                return Wake::Finished;
+            }
+            if state.cancelled {
+                return Wake::Cancelled;
+            }
+            if state.interrupted {
+                state.interrupted = false;
+                return Wake::Interrupted;
+            }
+            state = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Wake::Expired;
+                    }
+                    self.0.cond.wait_timeout(state, deadline - now).unwrap().0
+                }
+                None => self.0.cond.wait(state).unwrap(),
+            };
+        }
+    }
+}
+
+impl fmt::Debug for CancelToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancelToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+impl RubyHandle {
+    fn eval_watched<T>(
+        &self,
+        s: &str,
+        deadline: Option<Instant>,
+        token: &CancelToken,
+    ) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        token.set_finished(false);
+        let target = self.thread_current();
+        let watch_token = token.clone();
+        let watcher = self.thread_create(move || -> Result<(), Error> {
+            let message = loop {
+                let wake = unsafe {
+                    without_gvl(
+                        || watch_token.wait(deadline),
+                        Some(|| watch_token.interrupt()),
+                    )
+                }?;
+                match wake {
+                    Wake::Cancelled => break "execution cancelled",
+                    Wake::Expired => break "execution expired",
+                    Wake::Finished => return Ok(()),
+                    Wake::Interrupted => get_ruby!().check_interrupts()?,
+                }
+            };
+            // the evaluating thread sets finished while holding the GVL, so
+            // can't finish between this check and the raise
+            if !watch_token.is_finished() {
+                let _: Value = target.funcall("raise", (exception::interrupt(), message))?;
+            }
+            Ok(())
+        })?;
+        let res = self.eval(s);
+        token.set_finished(true);
+        // an interrupt raised just as evaluation finished may still be
+        // pending, so handle it here rather than leaving it for later code
+        let watched = watcher.join(None).and_then(|_| self.check_interrupts());
+        let val = res?;
+        watched?;
+        Ok(val)
+    }
+
+    pub fn eval_with_timeout<T>(&self, s: &str, timeout: Duration) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.eval_watched(s, Some(Instant::now() + timeout), &CancelToken::new())
+    }
+
+    pub fn eval_with_cancel<T>(&self, s: &str, token: &CancelToken) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.eval_watched(s, None, token)
+    }
+}
+
+/// Evaluate a string of Ruby code, raising an `Interrupt` exception in the
+/// code if it has not completed after `timeout`.
+///
+/// This allows bounding the execution time of untrusted or user-provided
+/// code. `Interrupt` is not a `StandardError`, so will not be caught by a
+/// bare `rescue`. Other Ruby threads continue to run while the code is being
+/// evaluated, so are able to perform the timeout.
+///
+/// Returns `Err` containing the `Interrupt` exception if the timeout passed.
+///
+/// See also [`eval`](crate::eval()).
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use magnus::{embed, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let res = embed::eval_with_timeout::<i64>("1 + 2", Duration::from_secs(1));
+/// assert_eq!(res.unwrap(), 3);
+///
+/// let res = embed::eval_with_timeout::<Value>("loop {}", Duration::from_millis(10));
+/// assert!(res.unwrap_err().is_kind_of(magnus::exception::interrupt()));
+/// ```
+pub fn eval_with_timeout<T>(s: &str, timeout: Duration) -> Result<T, Error>
+where
+    T: TryConvert,
+{
+    get_ruby!().eval_with_timeout(s, timeout)
+}
+
+/// Evaluate a string of Ruby code, raising an `Interrupt` exception in the
+/// code if `token` is cancelled.
+///
+/// `token` can be cancelled from any thread. See [`eval_with_timeout`].
+///
+/// Returns `Err` containing the `Interrupt` exception if the token was
+/// cancelled.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use magnus::{
+///     embed::{self, CancelToken},
+///     Value,
+/// };
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let token = CancelToken::new();
+/// let canceller = token.clone();
+/// std::thread::spawn(move || {
+///     std::thread::sleep(Duration::from_millis(10));
+///     canceller.cancel();
+/// });
+///
+/// let res = embed::eval_with_cancel::<Value>("sleep", &token);
+/// assert!(res.unwrap_err().is_kind_of(magnus::exception::interrupt()));
+/// ```
+pub fn eval_with_cancel<T>(s: &str, token: &CancelToken) -> Result<T, Error>
+where
+    T: TryConvert,
+{
+    get_ruby!().eval_with_cancel(s, token)
+}
@@ -3,20 +3,53 @@ use std::{fmt, ops::Deref};
 use rb_sys::VALUE;
 
 use crate::{
+    block::Proc,
     class,
     error::Error,
     exception,
     into_value::IntoValue,
     object::Object,
     ruby_handle::RubyHandle,
-    try_convert::TryConvert,
+    try_convert::{ArgList, TryConvert},
     value::{private, NonZeroValue, ReprValue, Value},
 };
 
+impl RubyHandle {
+    pub fn enumerator_from_fn<F>(&self, mut func: F) -> Enumerator
+    where
+        F: 'static + Send + FnMut(Yielder) -> Result<(), Error>,
+    {
+        let block = self.proc_from_fn(move |args, _block| func(Yielder(args[0])));
+        self.class_enumerator()
+            .funcall_with_block("new", (), block)
+            .unwrap()
+    }
+}
+
 /// Wrapper type for a Value known to be an instance of Ruby's Enumerator class.
 ///
+/// `Enumerator` implements [`Iterator`] using Ruby's external iteration
+/// (`Enumerator#next`), so any Ruby enumerable can be consumed from Rust with
+/// the usual iterator adapters. Ruby's `StopIteration` exception ends
+/// iteration, any other exception is returned as an `Err` item.
+///
 /// All [`Value`] methods should be available on this type through [`Deref`],
 /// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, Enumerator};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let enumerator: Enumerator = eval("[1, 2, 3, 4].each").unwrap();
+/// let res = enumerator
+///     .take(3)
+///     .map(|v| v.and_then(|v| v.try_convert::<i64>()).map(|i| i * 2))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(res, vec![2, 4, 6]);
+/// ```
 #[derive(Clone, Copy)]
 #[repr(transparent)]
 pub struct Enumerator(NonZeroValue);
@@ -35,6 +68,84 @@ impl Enumerator {
     pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
         Self(NonZeroValue::new_unchecked(Value::new(val)))
     }
+
+    /// Create a new `Enumerator` that lazily produces values from `func`.
+    ///
+    /// `func` is called each time the enumerator is iterated, and should
+    /// produce values by calling [`Yielder::yield_value`]. This is equivalent
+    /// to Ruby's `Enumerator.new { |yielder| ... }`, so values are only
+    /// produced as they are consumed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Enumerator};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let fib = Enumerator::from_fn(|yielder| {
+    ///     let (mut a, mut b) = (0_u64, 1_u64);
+    ///     loop {
+    ///         yielder.yield_value(a)?;
+    ///         let next = a + b;
+    ///         a = b;
+    ///         b = next;
+    ///     }
+    /// });
+    ///
+    /// let res: bool = eval!("fib.take(6) == [0, 1, 1, 2, 3, 5]", fib).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn from_fn<F>(func: F) -> Self
+    where
+        F: 'static + Send + FnMut(Yielder) -> Result<(), Error>,
+    {
+        get_ruby!().enumerator_from_fn(func)
+    }
+
+    /// Return the next value without advancing the iteration.
+    ///
+    /// Returns `None` when the iteration is finished.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Enumerator};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let mut enumerator: Enumerator = eval("[1, 2].each").unwrap();
+    /// let peeked = enumerator.peek().unwrap().unwrap();
+    /// let next = enumerator.next().unwrap().unwrap();
+    /// assert!(peeked.eql(&next).unwrap());
+    /// ```
+    pub fn peek(self) -> Option<Result<Value, Error>> {
+        match self.funcall("peek", ()) {
+            Ok(v) => Some(Ok(v)),
+            Err(e) if e.is_kind_of(exception::stop_iteration()) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    /// Reset the iteration back to the start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Enumerator};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let mut enumerator: Enumerator = eval("[1, 2].each").unwrap();
+    /// assert_eq!(enumerator.by_ref().count(), 2);
+    /// enumerator.rewind().unwrap();
+    /// assert_eq!(enumerator.count(), 2);
+    /// ```
+    pub fn rewind(self) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("rewind", ())?;
+        Ok(())
+    }
 }
 
 impl Iterator for Enumerator {
@@ -107,3 +218,115 @@ impl TryConvert for Enumerator {
         })
     }
 }
+
+/// Adapter to convert a Rust [`Iterator`] to a lazy Ruby `Enumerator`.
+///
+/// Returning `Lazy<I>` from a method bound with
+/// [`method!`](crate::method!)/[`function!`](crate::function!) produces an
+/// `Enumerator` without first collecting the iterator in to an `Array`. Items
+/// are taken from the iterator and converted to Ruby objects only as the
+/// enumerator is iterated, so infinite or expensive iterators can be returned.
+///
+/// As a Rust iterator can only be consumed once, iterating the enumerator a
+/// second time (or after stopping early with `break` or `first`) continues
+/// from where the previous iteration stopped, rather than restarting.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_global_function, eval, function, Lazy};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn squares() -> Lazy<impl Iterator<Item = u64>> {
+///     Lazy((1..).map(|i: u64| i * i))
+/// }
+///
+/// define_global_function("squares", function!(squares, 0));
+///
+/// let res: bool = eval("squares.first(4) == [1, 4, 9, 16]").unwrap();
+/// assert!(res);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Lazy<I>(pub I);
+
+impl<I> Lazy<I> {
+    /// Return the wrapped iterator.
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
+impl<I> IntoValue for Lazy<I>
+where
+    I: 'static + Send + Iterator,
+    I::Item: Into<Value>,
+{
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let mut iter = self.0;
+        handle
+            .enumerator_from_fn(move |yielder| {
+                for item in iter.by_ref() {
+                    yielder.yield_value(item)?;
+                }
+                Ok(())
+            })
+            .into()
+    }
+}
+
+impl<I> From<Lazy<I>> for Value
+where
+    I: 'static + Send + Iterator,
+    I::Item: Into<Value>,
+{
+    fn from(val: Lazy<I>) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+/// The object passed to the function given to [`Enumerator::from_fn`], used
+/// to produce values.
+///
+/// Wraps Ruby's `Enumerator::Yielder`.
+#[derive(Clone, Copy)]
+pub struct Yielder(Value);
+
+impl Yielder {
+    /// Produce `val` from the enumerator.
+    ///
+    /// Returns the result of the block when the enumerator is iterated with a
+    /// block, or `nil` when it is iterated externally (e.g. with `next`).
+    ///
+    /// Returns `Err` if iteration was interrupted (e.g. with `break`), which
+    /// should be propagated with `?`.
+    pub fn yield_value<T>(self, val: T) -> Result<Value, Error>
+    where
+        T: Into<Value>,
+    {
+        self.0.funcall("yield", (val.into(),))
+    }
+
+    /// Produce multiple values from the enumerator as a single iteration step.
+    ///
+    /// See [`yield_value`](Yielder::yield_value).
+    pub fn yield_values<T>(self, vals: T) -> Result<Value, Error>
+    where
+        T: ArgList,
+    {
+        self.0.funcall("yield", vals)
+    }
+}
+
+impl Deref for Yielder {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Yielder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.inspect())
+    }
+}
@@ -1,10 +1,28 @@
 //! Rust types for working with Ruby Exceptions and other interrupts.
 
-use std::{any::Any, borrow::Cow, ffi::CString, fmt, mem::transmute, ops::Deref, os::raw::c_int};
+use std::{
+    any::Any,
+    borrow::Cow,
+    cell::RefCell,
+    ffi::CString,
+    fmt,
+    mem::transmute,
+    ops::Deref,
+    os::raw::{c_char, c_int},
+    panic::{catch_unwind, resume_unwind, set_hook, take_hook, AssertUnwindSafe},
+    ptr,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Once,
+    },
+};
 
+#[cfg(windows)]
+use rb_sys::rb_w32_map_errno;
 use rb_sys::{
-    rb_bug, rb_ensure, rb_errinfo, rb_exc_raise, rb_iter_break, rb_iter_break_value, rb_jump_tag,
-    rb_protect, rb_set_errinfo, rb_warning, ruby_special_consts, VALUE,
+    rb_bug, rb_ensure, rb_errinfo, rb_exc_raise, rb_iter_break, rb_iter_break_value, rb_ivar_set,
+    rb_jump_tag, rb_protect, rb_set_errinfo, rb_syserr_new, rb_warn, rb_warning,
+    ruby_special_consts, VALUE,
 };
 
 use crate::{
@@ -13,9 +31,11 @@ use crate::{
     module::Module,
     r_string::RString,
     ruby_handle::RubyHandle,
-    value::{ReprValue, Value, QNIL},
+    value::{Id, ReprValue, Value, QNIL},
 };
 
+pub use crate::ruby_handle::RubyUnavailableError;
+
 /// A Rust representation of a Ruby `Exception` or other interrupt.
 #[derive(Debug)]
 pub enum Error {
@@ -37,6 +57,154 @@ impl Error {
         Self::Error(class, msg.into())
     }
 
+    /// Create a new `Error` with `msg`, that has `cause` as its cause.
+    ///
+    /// Ruby sets the cause of an exception automatically when raising from
+    /// within a `rescue` block, but this is lost when an error is caught in
+    /// Rust and a new error returned. This allows the chain of errors to be
+    /// kept.
+    ///
+    /// If `cause` is an [`Error::Jump`] it is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, exception, Error, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// fn load_config() -> Result<Value, Error> {
+    ///     eval("Integer('not a number')").map_err(|e| {
+    ///         Error::with_cause(exception::runtime_error(), "failed to load config", e)
+    ///     })
+    /// }
+    ///
+    /// let err = load_config().unwrap_err();
+    /// let cause = err.value().unwrap().cause().unwrap().unwrap();
+    /// assert!(cause.is_kind_of(exception::arg_error()));
+    /// ```
+    pub fn with_cause<T>(class: ExceptionClass, msg: T, cause: Error) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let ex = match Self::new(class, msg).exception_result() {
+            Ok(ex) => ex,
+            Err(e) => return e,
+        };
+        if let Error::Jump(_) = cause {
+            return Self::Exception(ex);
+        }
+        let cause = cause.exception();
+        // Ruby stores the cause in an internal ivar named `cause` (no `@`),
+        // which `raise` will not overwrite once set.
+        unsafe {
+            rb_ivar_set(
+                ex.as_rb_value(),
+                Id::from("cause").as_rb_id(),
+                cause.as_rb_value(),
+            )
+        };
+        Self::Exception(ex)
+    }
+
+    /// Create a new `Error` with `msg`, capturing a Rust backtrace.
+    ///
+    /// The Rust backtrace is prepended to the Ruby backtrace of the
+    /// exception, so that when the error is raised and reported by Ruby the
+    /// location in Rust code where it originated is included.
+    ///
+    /// The backtrace is only captured when enabled by the `RUST_BACKTRACE`
+    /// or `RUST_LIB_BACKTRACE` environment variables (see
+    /// [`std::backtrace`]), otherwise this is the same as [`Error::new`].
+    ///
+    /// Requires Rust 1.65 or later.
+    #[cfg(feature = "backtrace")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "backtrace")))]
+    pub fn with_backtrace<T>(class: ExceptionClass, msg: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        use std::backtrace::{Backtrace, BacktraceStatus};
+
+        use rb_sys::rb_make_backtrace;
+
+        use crate::r_array::RArray;
+
+        let backtrace = Backtrace::capture();
+        if backtrace.status() != BacktraceStatus::Captured {
+            return Self::new(class, msg);
+        }
+        let ex = match Self::new(class, msg).exception_result() {
+            Ok(ex) => ex,
+            Err(e) => return e,
+        };
+        let lines = RArray::new();
+        for line in rust_backtrace_lines(&backtrace) {
+            if let Err(e) = lines.push(RString::from(line)) {
+                return e;
+            }
+        }
+        // followed by the Ruby backtrace of the current method
+        let ruby_lines = unsafe { RArray::from_rb_value_unchecked(rb_make_backtrace()) };
+        if let Err(e) = lines.concat(ruby_lines) {
+            return e;
+        }
+        // `raise` will not overwrite a backtrace that has already been set
+        if let Err(e) = ex.funcall::<_, _, Value>("set_backtrace", (lines,)) {
+            return e;
+        }
+        Self::Exception(ex)
+    }
+
+    /// Create a new `Error` for the C `errno` value `errno`.
+    ///
+    /// The error will be an instance of the matching `Errno::*` exception
+    /// class, e.g. `Errno::ENOENT`, or `SystemCallError` for an unknown code.
+    ///
+    /// On Windows `errno` values differ from the Win32 error codes returned
+    /// by [`std::io::Error::raw_os_error`], use [`Error::last_os_error`] or
+    /// convert the [`std::io::Error`] with [`From`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Error, ExceptionClass};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// // ENOENT is 2 on all supported platforms
+    /// let err = Error::from_errno(2);
+    /// let enoent: ExceptionClass = eval("Errno::ENOENT").unwrap();
+    /// assert!(err.is_kind_of(enoent));
+    /// ```
+    pub fn from_errno(errno: i32) -> Self {
+        protect(|| unsafe { Exception::from_rb_value_unchecked(rb_syserr_new(errno, ptr::null())) })
+            .map(Self::Exception)
+            .unwrap_or_else(|e| e)
+    }
+
+    /// Create a new `Error` for the last OS error that occurred on the
+    /// current thread (`errno` on Unix, `GetLastError()` on Windows).
+    ///
+    /// See [`Error::from_errno`].
+    pub fn last_os_error() -> Self {
+        // last_os_error always has a raw_os_error
+        Self::from_os_error(std::io::Error::last_os_error().raw_os_error().unwrap_or(0))
+    }
+
+    /// Create a new `Error` from an OS error code, as returned by
+    /// [`std::io::Error::raw_os_error`].
+    #[cfg(not(windows))]
+    fn from_os_error(code: i32) -> Self {
+        Self::from_errno(code)
+    }
+
+    /// Create a new `Error` from an OS error code, as returned by
+    /// [`std::io::Error::raw_os_error`].
+    #[cfg(windows)]
+    fn from_os_error(code: i32) -> Self {
+        // Win32 error codes need mapping to C errno values
+        Self::from_errno(unsafe { rb_w32_map_errno(code as _) })
+    }
+
     /// Create a new `RuntimeError` with `msg`.
     #[deprecated(
         since = "0.5.0",
@@ -50,6 +218,28 @@ impl Error {
     }
 
     /// Create a new error that will break from a loop when returned to Ruby.
+    ///
+    /// `val` will be the return value of the method the block was passed to,
+    /// or `nil` if `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Error, RArray, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let values: RArray = eval("[1, 3, 4, 5]").unwrap();
+    /// let res: i64 = values
+    ///     .block_call("each", (), |args, _| {
+    ///         let i = args[0].try_convert::<i64>()?;
+    ///         if i % 2 == 0 {
+    ///             return Err(Error::iter_break(Some(i)));
+    ///         }
+    ///         Ok(Value::default())
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(res, 4);
+    /// ```
     pub fn iter_break<T>(val: Option<T>) -> Self
     where
         T: Into<Value>,
@@ -71,8 +261,66 @@ impl Error {
         }
     }
 
+    /// Returns the error formatted as Ruby would display it for an uncaught
+    /// exception, including the backtrace if the error was raised in Ruby.
+    ///
+    /// The output will not be highlighted with terminal escape sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, exception, Error, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let err = Error::new(exception::arg_error(), "bad value");
+    /// assert_eq!(err.full_message(), "bad value (ArgumentError)");
+    ///
+    /// let err = eval::<Value>(r#"raise ArgumentError, "bad value""#).unwrap_err();
+    /// assert!(err.full_message().starts_with("eval:1:in "));
+    /// ```
+    pub fn full_message(&self) -> String {
+        match self {
+            Error::Jump(_) => self.to_string(),
+            Error::Error(class, msg) => format!("{} ({})", msg, class),
+            Error::Exception(e) => e.full_message().unwrap_or_else(|_| self.to_string()),
+        }
+    }
+
+    /// Returns the [`Tag`] if `self` is an [`Error::Jump`], such as `break`,
+    /// `next`, or `throw`, rather than an exception.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let err = eval::<Value>("raise 'oops'").unwrap_err();
+    /// assert_eq!(err.tag(), None);
+    /// ```
+    pub fn tag(&self) -> Option<Tag> {
+        match self {
+            Error::Jump(tag) => Some(*tag),
+            _ => None,
+        }
+    }
+
     /// Matches the internal `Exception` against `class` with same semantics as
     /// Ruby's `rescue`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{define_error, eval, exception, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let my_error = define_error("MyError", exception::standard_error()).unwrap();
+    ///
+    /// let err = eval::<Value>(r#"raise MyError, "oops""#).unwrap_err();
+    /// assert!(err.is_kind_of(my_error));
+    /// assert!(err.is_kind_of(exception::standard_error()));
+    /// assert!(!err.is_kind_of(exception::arg_error()));
+    /// ```
     pub fn is_kind_of<T>(&self, class: T) -> bool
     where
         T: Deref<Target = Value> + Module,
@@ -84,38 +332,166 @@ impl Error {
         }
     }
 
+    /// Returns whether `self` is an `ArgumentError` (or a subclass).
+    pub fn is_arg_error(&self) -> bool {
+        self.is_kind_of(exception::arg_error())
+    }
+
+    /// Returns whether `self` is a `TypeError` (or a subclass).
+    pub fn is_type_error(&self) -> bool {
+        self.is_kind_of(exception::type_error())
+    }
+
+    /// Returns whether `self` is a `RuntimeError` (or a subclass).
+    pub fn is_runtime_error(&self) -> bool {
+        self.is_kind_of(exception::runtime_error())
+    }
+
+    /// Returns whether `self` is a `NameError` (or a subclass).
+    pub fn is_name_error(&self) -> bool {
+        self.is_kind_of(exception::name_error())
+    }
+
+    /// Returns whether `self` is a `NoMethodError` (or a subclass).
+    pub fn is_no_method_error(&self) -> bool {
+        self.is_kind_of(exception::no_method_error())
+    }
+
+    /// Returns whether `self` is a `KeyError` (or a subclass).
+    pub fn is_key_error(&self) -> bool {
+        self.is_kind_of(exception::key_error())
+    }
+
+    /// Returns whether `self` is an `IndexError` (or a subclass).
+    pub fn is_index_error(&self) -> bool {
+        self.is_kind_of(exception::index_error())
+    }
+
+    /// Returns whether `self` is a `RangeError` (or a subclass).
+    pub fn is_range_error(&self) -> bool {
+        self.is_kind_of(exception::range_error())
+    }
+
+    /// Returns whether `self` is a `FrozenError` (or a subclass).
+    pub fn is_frozen_error(&self) -> bool {
+        self.is_kind_of(exception::frozen_error())
+    }
+
+    /// Returns whether `self` is a `ZeroDivisionError` (or a subclass).
+    pub fn is_zero_div_error(&self) -> bool {
+        self.is_kind_of(exception::zero_div_error())
+    }
+
+    /// Returns whether `self` is a `StopIteration` (or a subclass).
+    pub fn is_stop_iteration(&self) -> bool {
+        self.is_kind_of(exception::stop_iteration())
+    }
+
+    /// Returns the Ruby `Exception` wrapped by this error, if any.
+    ///
+    /// This will return `None` for an `Error` created in Rust with
+    /// [`Error::new`] that has not yet been raised, and for `Error::Jump`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let err = eval::<Value>(r#"raise ArgumentError, "bad value""#).unwrap_err();
+    /// let exception = err.value().unwrap();
+    /// assert_eq!(exception.message().unwrap(), "bad value");
+    /// assert!(exception.backtrace().unwrap().is_some());
+    /// ```
+    pub fn value(&self) -> Option<Exception> {
+        match self {
+            Error::Exception(e) => Some(*e),
+            _ => None,
+        }
+    }
+
     /// Consumes `self`, returning an `Exception`.
     ///
     /// # Panics
     ///
     /// Panics if called on an `Error::Jump`.
     fn exception(self) -> Exception {
+        match self.exception_result() {
+            Ok(e) | Err(Error::Exception(e)) => e,
+            Err(err) => unreachable!("*very* unexpected error: {}", err),
+        }
+    }
+
+    /// Consumes `self`, returning an `Exception`, or the error from creating
+    /// the exception.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on an `Error::Jump`.
+    fn exception_result(self) -> Result<Exception, Error> {
         match self {
             Error::Jump(_) => panic!("Error::exception() called on {}", self),
-            Error::Error(class, msg) => match class.new_instance((RString::new(msg.as_ref()),)) {
-                Ok(e) | Err(Error::Exception(e)) => e,
-                Err(err) => unreachable!("*very* unexpected error: {}", err),
-            },
-            Error::Exception(e) => e,
+            Error::Error(class, msg) => class.new_instance((RString::new(msg.as_ref()),)),
+            Error::Exception(e) => Ok(e),
         }
     }
 
     /// Create an `Error` from the error value of [`std::panic::catch_unwind`].
     ///
-    /// The Ruby Exception will be `fatal`, terminating the Ruby process, but
-    /// allowing cleanup code to run.
+    /// By default the Ruby Exception will be `fatal`, terminating the Ruby
+    /// process, but allowing cleanup code to run. See [`set_panic_policy`].
     pub(crate) fn from_panic(e: Box<dyn Any + Send + 'static>) -> Self {
-        let msg = if let Some(&m) = e.downcast_ref::<&'static str>() {
+        let msg: Cow<'static, str> = if let Some(&m) = e.downcast_ref::<&'static str>() {
             m.into()
         } else if let Some(m) = e.downcast_ref::<String>() {
             m.clone().into()
         } else {
             "panic".into()
         };
-        Self::Error(exception::fatal(), msg)
+        match panic_policy() {
+            PanicPolicy::Fatal => Self::Error(exception::fatal(), msg),
+            PanicPolicy::RuntimeError => {
+                let msg = match PANIC_LOCATION.with(|l| l.borrow_mut().take()) {
+                    Some(location) => format!("{} at {}", msg, location).into(),
+                    None => msg,
+                };
+                Self::Error(exception::runtime_error(), msg)
+            }
+        }
     }
 }
 
+/// Return early from a function with an [`Error`] of `class` and a formatted
+/// message.
+///
+/// This is shorthand for
+/// `return Err(Error::new(class, format!(...)).into())`. The message is only
+/// formatted if this is reached.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{exception, raise, Error};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn check_range(i: i64, max: i64) -> Result<i64, Error> {
+///     if i > max {
+///         raise!(exception::range_error(), "{} out of range (max {})", i, max);
+///     }
+///     Ok(i)
+/// }
+///
+/// assert_eq!(check_range(5, 10).unwrap(), 5);
+/// let err = check_range(15, 10).unwrap_err();
+/// assert_eq!(err.to_string(), "RangeError: 15 out of range (max 10)");
+/// ```
+#[macro_export]
+macro_rules! raise {
+    ($class:expr, $($arg:tt)+) => {
+        return Err($crate::Error::new($class, format!($($arg)+)).into())
+    };
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -126,6 +502,130 @@ impl fmt::Display for Error {
     }
 }
 
+impl std::error::Error for Error {}
+
+/// A Ruby error, detached from the Ruby VM.
+///
+/// [`Error`] may contain references to Ruby objects, so must not be sent to
+/// another thread, or stored anywhere not visible to Ruby's garbage
+/// collector. `OwnedError` copies the exception's class name, message, and
+/// backtrace to Rust types, so is [`Send`], [`Sync`], and can be stored
+/// indefinitely. It implements [`std::error::Error`], so can be used with
+/// crates such as `anyhow`.
+///
+/// An `OwnedError` can be converted back to an [`Error`] to be raised.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{error::OwnedError, eval, Error, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn run() -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+///     eval::<Value>(r#"raise ArgumentError, "bad value""#)
+///         .map_err(|e| OwnedError::from(e).into())
+/// }
+///
+/// let err = run().unwrap_err().downcast::<OwnedError>().unwrap();
+/// assert_eq!(err.class_name(), "ArgumentError");
+/// assert_eq!(err.message(), "bad value");
+/// assert_eq!(err.to_string(), "ArgumentError: bad value");
+///
+/// let err = Error::from(*err);
+/// assert!(err.is_arg_error());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedError {
+    class_name: String,
+    message: String,
+    backtrace: Option<Vec<String>>,
+}
+
+impl OwnedError {
+    /// The name of the exception's class.
+    pub fn class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    /// The exception's message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The exception's backtrace, if it was raised in Ruby.
+    pub fn backtrace(&self) -> Option<&[String]> {
+        self.backtrace.as_deref()
+    }
+}
+
+impl fmt::Display for OwnedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.class_name, self.message)
+    }
+}
+
+impl std::error::Error for OwnedError {}
+
+/// Converts an `Error` to an `OwnedError`.
+///
+/// [`Error::Jump`] is converted to a `LocalJumpError`.
+impl From<Error> for OwnedError {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Jump(tag) => Self {
+                class_name: String::from("LocalJumpError"),
+                message: tag.to_string(),
+                backtrace: None,
+            },
+            Error::Error(class, msg) => Self {
+                class_name: class.to_string(),
+                message: msg.into_owned(),
+                backtrace: None,
+            },
+            Error::Exception(e) => Self {
+                class_name: unsafe { e.classname() }.into_owned(),
+                message: e.message().unwrap_or_else(|_| e.to_string()),
+                backtrace: e
+                    .backtrace()
+                    .ok()
+                    .flatten()
+                    .and_then(|b| b.to_vec::<String>().ok()),
+            },
+        }
+    }
+}
+
+/// Converts an `OwnedError` back to an `Error`.
+///
+/// The exception class is looked up by name, falling back to `RuntimeError`
+/// if it is not found.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+impl From<OwnedError> for Error {
+    fn from(e: OwnedError) -> Self {
+        // `Object.const_get` resolves namespaced names, e.g. `Foo::Bar`
+        let class = crate::class::object()
+            .funcall::<_, _, ExceptionClass>("const_get", (e.class_name.as_str(),))
+            .unwrap_or_else(|_| exception::runtime_error());
+        let err = Self::new(class, e.message);
+        let backtrace = match e.backtrace {
+            Some(v) => v,
+            None => return err,
+        };
+        let ex = match err.exception_result() {
+            Ok(ex) => ex,
+            Err(e) => return e,
+        };
+        let lines = crate::r_array::RArray::from_vec(backtrace);
+        if let Err(e) = ex.funcall::<_, _, Value>("set_backtrace", (lines,)) {
+            return e;
+        }
+        Self::Exception(ex)
+    }
+}
+
 impl From<Tag> for Error {
     fn from(val: Tag) -> Self {
         Self::Jump(val)
@@ -138,9 +638,126 @@ impl From<Exception> for Error {
     }
 }
 
+/// Converts an [`std::io::Error`] to an `Error`.
+///
+/// Errors with a raw OS error code are converted to the matching `Errno::*`
+/// exception (e.g. `Errno::ENOENT`), unexpected end of file errors to
+/// `EOFError`, and all other errors to `IOError`.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, Error};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn read(path: &str) -> Result<String, Error> {
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+///
+/// let err = read("/path/does/not/exist").unwrap_err();
+/// let errno: magnus::ExceptionClass = eval("Errno::ENOENT").unwrap();
+/// assert!(err.is_kind_of(errno));
+/// ```
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        if let Some(code) = e.raw_os_error() {
+            return Self::from_os_error(code);
+        }
+        match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => Self::new(exception::eof_error(), e.to_string()),
+            _ => Self::new(exception::io_error(), e.to_string()),
+        }
+    }
+}
+
+/// Converts to an `EncodingError`.
+impl From<std::str::Utf8Error> for Error {
+    fn from(e: std::str::Utf8Error) -> Self {
+        Self::new(exception::encoding_error(), e.to_string())
+    }
+}
+
+/// Converts to an `EncodingError`.
+impl From<std::string::FromUtf8Error> for Error {
+    fn from(e: std::string::FromUtf8Error) -> Self {
+        Self::new(exception::encoding_error(), e.to_string())
+    }
+}
+
+/// Converts to an `ArgumentError`.
+impl From<std::ffi::NulError> for Error {
+    fn from(e: std::ffi::NulError) -> Self {
+        Self::new(exception::arg_error(), e.to_string())
+    }
+}
+
+/// Converts to an `ArgumentError`.
+impl From<std::num::ParseIntError> for Error {
+    fn from(e: std::num::ParseIntError) -> Self {
+        Self::new(exception::arg_error(), e.to_string())
+    }
+}
+
+/// Converts to an `ArgumentError`.
+impl From<std::num::ParseFloatError> for Error {
+    fn from(e: std::num::ParseFloatError) -> Self {
+        Self::new(exception::arg_error(), e.to_string())
+    }
+}
+
+/// Converts to an `ArgumentError`.
+impl From<std::str::ParseBoolError> for Error {
+    fn from(e: std::str::ParseBoolError) -> Self {
+        Self::new(exception::arg_error(), e.to_string())
+    }
+}
+
+/// Converts to a `RangeError`.
+impl From<std::num::TryFromIntError> for Error {
+    fn from(e: std::num::TryFromIntError) -> Self {
+        Self::new(exception::range_error(), e.to_string())
+    }
+}
+
+/// Converts to a `RangeError`.
+impl From<std::char::CharTryFromError> for Error {
+    fn from(e: std::char::CharTryFromError) -> Self {
+        Self::new(exception::range_error(), e.to_string())
+    }
+}
+
+/// Formats a Rust backtrace as Ruby-style backtrace lines, e.g.
+/// "src/lib.rs:10:5:in `my_crate::my_function'".
+#[cfg(feature = "backtrace")]
+fn rust_backtrace_lines(backtrace: &std::backtrace::Backtrace) -> Vec<String> {
+    let s = backtrace.to_string();
+    let mut iter = s.lines().peekable();
+    let mut lines = Vec::new();
+    while let Some(line) = iter.next() {
+        let symbol = match line.trim_start().split_once(": ") {
+            Some((_, symbol)) => symbol,
+            None => continue,
+        };
+        let location = iter.peek().and_then(|l| l.trim_start().strip_prefix("at "));
+        if location.is_some() {
+            iter.next();
+        }
+        // skip the frames for capturing the backtrace
+        if symbol.starts_with("std::backtrace") || symbol.contains("Error::with_backtrace") {
+            continue;
+        }
+        lines.push(format!("{}:in `{}'", location.unwrap_or("<rust>"), symbol));
+    }
+    lines
+}
+
 /// The state of a call to Ruby exiting early, interrupting the normal flow
 /// of code.
-#[derive(Debug)]
+///
+/// Calls to Ruby that exit with a `Tag` must have the resulting
+/// [`Error::Jump`] returned to Ruby to allow the control flow (e.g. `break`
+/// returning a value from the method a block was passed to) to complete.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(i32)]
 pub enum Tag {
     // None = 0,
@@ -221,6 +838,10 @@ where
     // of the api and safe to do.
     // In this case we use arg2 to pass a pointer the Rust closure we actually
     // want to call, and arg1 is just a simple adapter to call arg2.
+    // `$!`, the exception currently being handled (if any), is restored
+    // after catching an exception so that an error raised afterwards still
+    // gets it as its implicit cause.
+    let errinfo = unsafe { rb_errinfo() };
     let result = unsafe {
         let mut some_func = Some(func);
         let closure = &mut some_func as *mut Option<F> as VALUE;
@@ -233,14 +854,55 @@ where
         // Tag::Raise
         6 => unsafe {
             let ex = Exception::from_rb_value_unchecked(rb_errinfo());
-            rb_set_errinfo(QNIL.as_rb_value());
+            rb_set_errinfo(errinfo);
             Err(Error::Exception(ex))
         },
         other => Err(Error::Jump(unsafe { transmute(other) })),
     }
 }
 
-pub(crate) fn ensure<F1, F2>(func: F1, ensure: F2) -> Value
+/// Calls `func`, then `cleanup`, with `cleanup` run even if `func` returns an
+/// error or panics.
+///
+/// As all functions exposed by magnus that call Ruby catch exceptions and
+/// other interrupts (such as `break` or `throw`) and return them as an
+/// [`Error`], `cleanup` will always be run before the error is returned. If
+/// `func` panics then `cleanup` is run and the panic is resumed.
+///
+/// This is useful for releasing resources such as locks or handles to
+/// foreign libraries, when using a guard type implementing [`Drop`] isn't
+/// convenient.
+///
+/// # Examples
+///
+/// ```
+/// use std::cell::Cell;
+///
+/// use magnus::{error::ensure, eval, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let locked = Cell::new(true);
+/// let res = ensure(
+///     || eval::<Value>("raise 'oops'"),
+///     || locked.set(false),
+/// );
+/// assert!(res.is_err());
+/// assert!(!locked.get());
+/// ```
+pub fn ensure<F, C, T>(func: F, cleanup: C) -> Result<T, Error>
+where
+    F: FnOnce() -> Result<T, Error>,
+    C: FnOnce(),
+{
+    let res = catch_unwind(AssertUnwindSafe(func));
+    cleanup();
+    match res {
+        Ok(v) => v,
+        Err(e) => resume_unwind(e),
+    }
+}
+
+pub(crate) fn ensure_raw<F1, F2>(func: F1, ensure: F2) -> Value
 where
     F1: FnOnce() -> Value,
     F2: FnOnce(),
@@ -308,6 +970,140 @@ pub(crate) fn bug_from_panic(e: Box<dyn Any + Send + 'static>, or: &str) -> ! {
     bug(&msg)
 }
 
+/// How panics in Rust code called from Ruby are handled.
+///
+/// See [`set_panic_policy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PanicPolicy {
+    /// Convert panics to Ruby's `fatal` exception, terminating the Ruby
+    /// process, but allowing cleanup code to run. This is the default.
+    Fatal,
+    /// Convert panics to a `RuntimeError`, with the panic message and the
+    /// location of the panic as the exception's message.
+    RuntimeError,
+}
+
+static PANIC_POLICY: AtomicU8 = AtomicU8::new(PanicPolicy::Fatal as u8);
+
+thread_local! {
+    static PANIC_LOCATION: RefCell<Option<String>> = RefCell::new(None);
+}
+
+fn panic_policy() -> PanicPolicy {
+    match PANIC_POLICY.load(Ordering::Relaxed) {
+        1 => PanicPolicy::RuntimeError,
+        _ => PanicPolicy::Fatal,
+    }
+}
+
+/// Set how panics in Rust functions bound as Ruby methods, and Rust closures
+/// called from Ruby, are handled.
+///
+/// With [`PanicPolicy::RuntimeError`] a panic will be raised as a
+/// `RuntimeError` that can be rescued by Ruby code, rather than terminating
+/// the process. This may be preferable for long running processes such as
+/// application servers. Setting this policy installs a panic hook (calling
+/// any previously set hook) to record the location of the panic.
+///
+/// This should only be used where Rust code is known to be
+/// [unwind safe](std::panic::UnwindSafe), as state may be left inconsistent
+/// after a panic.
+///
+/// Panics in the [`DataTypeFunctions`](crate::typed_data::DataTypeFunctions)
+/// callbacks run during garbage collection can not be recovered from, and
+/// will always terminate the process.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{
+///     define_global_function, error::{set_panic_policy, PanicPolicy}, eval, function,
+/// };
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// set_panic_policy(PanicPolicy::RuntimeError);
+///
+/// fn oops() {
+///     panic!("oops")
+/// }
+///
+/// define_global_function("oops", function!(oops, 0));
+/// let res: bool = eval(
+///     r#"
+///     begin
+///       oops
+///     rescue RuntimeError => e
+///       e.message.start_with?("oops at ")
+///     end
+///     "#,
+/// )
+/// .unwrap();
+/// assert!(res);
+/// # set_panic_policy(PanicPolicy::Fatal);
+/// ```
+pub fn set_panic_policy(policy: PanicPolicy) {
+    static HOOK: Once = Once::new();
+
+    if policy == PanicPolicy::RuntimeError {
+        HOOK.call_once(|| {
+            let prev = take_hook();
+            set_hook(Box::new(move |info| {
+                if let Some(location) = info.location() {
+                    let location = location.to_string();
+                    PANIC_LOCATION.with(|l| *l.borrow_mut() = Some(location));
+                }
+                prev(info);
+            }));
+        });
+    }
+    PANIC_POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+/// Calls `func`, passing any error that is an instance of one of `classes` to
+/// `handler`.
+///
+/// Errors are matched against `classes` with the same semantics as Ruby's
+/// `rescue`. Errors that don't match, along with non-exception interrupts
+/// such as `break` or `throw` ([`Error::Jump`]), are returned unchanged.
+///
+/// This allows handling specific exceptions while letting anything else, such
+/// as `Interrupt` or `SystemExit`, continue to propagate.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{error::rescue, eval, exception, Error};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn parse(s: &str) -> Result<i64, Error> {
+///     rescue(
+///         || eval(&format!("Integer({:?})", s)),
+///         &[exception::arg_error(), exception::type_error()],
+///         |_| Ok(0),
+///     )
+/// }
+///
+/// assert_eq!(parse("42").unwrap(), 42);
+/// assert_eq!(parse("forty two").unwrap(), 0);
+///
+/// let res: Result<i64, Error> = rescue(
+///     || eval("raise Interrupt"),
+///     &[exception::standard_error()],
+///     |_| Ok(0),
+/// );
+/// assert!(res.unwrap_err().is_kind_of(exception::interrupt()));
+/// ```
+pub fn rescue<F, H, T>(func: F, classes: &[ExceptionClass], handler: H) -> Result<T, Error>
+where
+    F: FnOnce() -> Result<T, Error>,
+    H: FnOnce(Error) -> Result<T, Error>,
+{
+    match func() {
+        Err(e) if classes.iter().any(|c| e.is_kind_of(*c)) => handler(e),
+        res => res,
+    }
+}
+
 /// Immediately terminate the process, printing Ruby internal state for
 /// debugging.
 pub fn bug(s: &str) -> ! {
@@ -323,6 +1119,21 @@ impl RubyHandle {
         let s = CString::new(s).unwrap();
         unsafe { rb_warning(s.as_ptr()) };
     }
+
+    pub fn warn(&self, s: &str) {
+        let s = CString::new(s).unwrap();
+        unsafe { rb_warn("%s\0".as_ptr() as *const c_char, s.as_ptr()) };
+    }
+
+    #[cfg(any(ruby_gte_3_0, docsrs))]
+    pub fn category_warn(&self, category: WarningCategory, s: &str) -> Result<(), Error> {
+        use crate::{module, r_hash::RHash, symbol::Symbol};
+
+        let kwargs = RHash::new();
+        kwargs.aset(Symbol::new("category"), Symbol::new(category.name()))?;
+        module::kernel().funcall_kw::<_, _, Value>("warn", (s, kwargs))?;
+        Ok(())
+    }
 }
 
 /// Outputs `s` to Ruby's stderr if Ruby is configured to output warnings.
@@ -335,3 +1146,74 @@ impl RubyHandle {
 pub fn warning(s: &str) {
     get_ruby!().warning(s)
 }
+
+/// Outputs `s` to Ruby's stderr, unless warnings have been disabled (e.g.
+/// with the `-W0` command line flag).
+///
+/// See also [`warning`], which only outputs in verbose mode.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn warn(s: &str) {
+    get_ruby!().warn(s)
+}
+
+/// Categories of warnings that can be enabled or disabled with `Warning[]=`.
+///
+/// See [`category_warn`].
+#[cfg(any(ruby_gte_3_0, docsrs))]
+#[cfg_attr(docsrs, doc(cfg(ruby_gte_3_0)))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WarningCategory {
+    /// Warnings for deprecated features.
+    Deprecated,
+    /// Warnings for experimental features.
+    Experimental,
+    /// Warnings for features that may impact performance. Requires Ruby 3.3
+    /// or later.
+    Performance,
+}
+
+#[cfg(any(ruby_gte_3_0, docsrs))]
+impl WarningCategory {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Deprecated => "deprecated",
+            Self::Experimental => "experimental",
+            Self::Performance => "performance",
+        }
+    }
+}
+
+/// Outputs `s` to Ruby's stderr as a warning in `category`, if warnings for
+/// that category are enabled.
+///
+/// This is equivalent to calling `Kernel#warn` with the `category` keyword,
+/// so will call `Warning.warn`, allowing warnings to be captured or
+/// redirected by Ruby code.
+///
+/// Returns an error if the category is not supported by the current Ruby
+/// version.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{
+///     error::{category_warn, WarningCategory},
+///     eval, Value,
+/// };
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// eval::<Value>("Warning[:deprecated] = true").unwrap();
+/// category_warn(WarningCategory::Deprecated, "`old_method` is deprecated").unwrap();
+/// ```
+#[cfg(any(ruby_gte_3_0, docsrs))]
+#[cfg_attr(docsrs, doc(cfg(ruby_gte_3_0)))]
+pub fn category_warn(category: WarningCategory, s: &str) -> Result<(), Error> {
+    get_ruby!().category_warn(category, s)
+}
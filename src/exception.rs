@@ -25,7 +25,9 @@ use crate::{
     module::Module,
     object::Object,
     r_array::RArray,
+    r_hash::RHash,
     ruby_handle::RubyHandle,
+    symbol::Symbol,
     try_convert::{ArgList, TryConvert},
     value::{
         private::{self, ReprValue as _},
@@ -63,6 +65,144 @@ impl Exception {
     pub fn backtrace(&self) -> Result<Option<RArray>, Error> {
         self.funcall("backtrace", ())
     }
+
+    /// Return the Ruby backtrace for the exception as a list of [`Frame`]s.
+    ///
+    /// Returns `None` if the exception has not been raised, or had its
+    /// backtrace set to a list of strings with `Exception#set_backtrace`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let err = eval::<Value>(
+    ///     r#"
+    ///     def fail_here
+    ///       raise "oops"
+    ///     end
+    ///     fail_here
+    ///     "#,
+    /// )
+    /// .unwrap_err();
+    /// let frames = err.value().unwrap().backtrace_frames().unwrap().unwrap();
+    /// assert_eq!(frames[0].label.as_deref(), Some("fail_here"));
+    /// assert_eq!(frames[0].lineno, Some(3));
+    /// ```
+    pub fn backtrace_frames(&self) -> Result<Option<Vec<Frame>>, Error> {
+        let locations = match self.funcall::<_, _, Option<RArray>>("backtrace_locations", ())? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        locations
+            .to_vec::<Value>()?
+            .into_iter()
+            .map(|loc| {
+                Ok(Frame {
+                    path: loc.funcall("path", ())?,
+                    lineno: loc.funcall("lineno", ())?,
+                    label: loc.funcall("label", ())?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()
+            .map(Some)
+    }
+
+    /// Return the exception's message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Exception};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let e: Exception = eval(r#"ArgumentError.new("bad value")"#).unwrap();
+    /// assert_eq!(e.message().unwrap(), "bad value");
+    /// ```
+    pub fn message(&self) -> Result<String, Error> {
+        self.funcall("message", ())
+    }
+
+    /// Return the message, class name, and backtrace of the exception
+    /// formatted as Ruby would display them for an uncaught exception.
+    ///
+    /// The output will not be highlighted with terminal escape sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Exception};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let e: Exception = eval(r#"ArgumentError.new("bad value")"#).unwrap();
+    /// assert!(e.full_message().unwrap().contains("bad value (ArgumentError)"));
+    /// ```
+    pub fn full_message(&self) -> Result<String, Error> {
+        let kwargs = RHash::new();
+        kwargs.aset(Symbol::new("highlight"), false)?;
+        self.funcall_kw("full_message", (kwargs,))
+    }
+
+    /// Return the exception that was being handled when this exception was
+    /// raised, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Exception};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let e: Exception = eval(
+    ///     r#"
+    ///     begin
+    ///       begin
+    ///         raise "inner"
+    ///       rescue
+    ///         raise ArgumentError, "outer"
+    ///       end
+    ///     rescue => e
+    ///       e
+    ///     end
+    ///     "#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(e.cause().unwrap().unwrap().message().unwrap(), "inner");
+    /// ```
+    pub fn cause(&self) -> Result<Option<Exception>, Error> {
+        self.funcall("cause", ())
+    }
+
+    /// Return the class of the exception.
+    pub fn exception_class(&self) -> ExceptionClass {
+        unsafe { ExceptionClass::from_value_unchecked(*self.class()) }
+    }
+}
+
+/// A single frame of a Ruby backtrace.
+///
+/// See [`Exception::backtrace_frames`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// The path of the source file.
+    pub path: Option<String>,
+    /// The line number in the source file.
+    pub lineno: Option<u32>,
+    /// The label of the frame, usually the method name.
+    pub label: Option<String>,
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.as_deref().unwrap_or("<unknown>"))?;
+        if let Some(lineno) = self.lineno {
+            write!(f, ":{}", lineno)?;
+        }
+        if let Some(label) = &self.label {
+            write!(f, ":in `{}'", label)?;
+        }
+        Ok(())
+    }
 }
 
 impl Deref for Exception {
@@ -0,0 +1,354 @@
+//! Run closures on a Ruby thread from any thread.
+//!
+//! Threads not created by Ruby can never call Ruby, and Ruby threads that
+//! have released the GVL must reacquire it first. [`RubyExecutor`] runs a
+//! dedicated Ruby thread that closures can be sent to from any thread, with
+//! the result returned to the sender.
+
+use std::{
+    collections::VecDeque,
+    error::Error as StdError,
+    fmt,
+    future::Future,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use crate::{
+    error::Error,
+    ruby_handle::RubyHandle,
+    thread::{check_interrupts, without_gvl, Thread},
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct Queue {
+    jobs: VecDeque<Job>,
+    shutdown: bool,
+    interrupted: bool,
+    stopped: bool,
+}
+
+enum Next {
+    Job(Job),
+    Interrupted,
+    Shutdown,
+}
+
+#[derive(Default)]
+struct Shared {
+    queue: Mutex<Queue>,
+    cond: Condvar,
+}
+
+impl Shared {
+    fn push(&self, job: Job) -> Result<(), ExecutorStoppedError> {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.stopped || queue.shutdown {
+            return Err(ExecutorStoppedError);
+        }
+        queue.jobs.push_back(job);
+        self.cond.notify_one();
+        Ok(())
+    }
+
+    fn next(&self) -> Next {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            if let Some(job) = queue.jobs.pop_front() {
+                return Next::Job(job);
+            }
+            if queue.interrupted {
+                queue.interrupted = false;
+                return Next::Interrupted;
+            }
+            if queue.shutdown {
+                return Next::Shutdown;
+            }
+            queue = self.cond.wait(queue).unwrap();
+        }
+    }
+
+    fn interrupt(&self) {
+        self.queue.lock().unwrap().interrupted = true;
+        self.cond.notify_one();
+    }
+
+    fn shutdown(&self) {
+        self.queue.lock().unwrap().shutdown = true;
+        self.cond.notify_one();
+    }
+}
+
+/// Marks the executor as stopped when the executor thread exits, for any
+/// reason, dropping any queued jobs so their callers are notified.
+struct StopGuard(Arc<Shared>);
+
+impl Drop for StopGuard {
+    fn drop(&mut self) {
+        let jobs = {
+            let mut queue = self.0.queue.lock().unwrap();
+            queue.stopped = true;
+            std::mem::take(&mut queue.jobs)
+        };
+        drop(jobs);
+    }
+}
+
+/// Shuts down the executor when the last handle is dropped.
+struct ShutdownOnDrop(Arc<Shared>);
+
+impl Drop for ShutdownOnDrop {
+    fn drop(&mut self) {
+        self.0.shutdown();
+    }
+}
+
+/// A handle to a Ruby thread that runs closures sent from any thread.
+///
+/// The handle is `Send` and `Clone`, so can be shared with threads not
+/// created by Ruby, such as those of a thread pool or async runtime. Closures
+/// sent with [`run`](RubyExecutor::run) or
+/// [`run_async`](RubyExecutor::run_async) are run, in order, on the
+/// executor's Ruby thread with the GVL held, and so may call Ruby. While
+/// waiting for closures to run the executor's thread releases the GVL.
+///
+/// The executor's thread exits once [`shutdown`](RubyExecutor::shutdown) is
+/// called, or all handles are dropped, after running any closures already
+/// sent.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, executor::RubyExecutor, without_gvl, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let executor = RubyExecutor::spawn().unwrap();
+///
+/// let handle = executor.clone();
+/// let worker = std::thread::spawn(move || {
+///     let sum: u64 = (1..=10).sum();
+///     // this thread can't call Ruby, so sends the work to the executor
+///     handle
+///         .run(move || {
+///             let _: Value = eval(&format!("$sum = {}", sum)).unwrap();
+///         })
+///         .unwrap();
+/// });
+/// // release the GVL while waiting, so the executor can run
+/// unsafe { without_gvl(|| worker.join().unwrap(), None::<fn()>) }.unwrap();
+///
+/// let res: u64 = eval("$sum").unwrap();
+/// assert_eq!(res, 55);
+/// ```
+#[derive(Clone)]
+pub struct RubyExecutor {
+    shared: Arc<Shared>,
+    _shutdown: Arc<ShutdownOnDrop>,
+}
+
+impl RubyExecutor {
+    /// Start a new executor thread, returning a handle to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn spawn() -> Result<Self, Error> {
+        get_ruby!().executor_spawn()
+    }
+
+    /// Run `func` on the executor's Ruby thread, waiting for it to complete.
+    ///
+    /// Returns the result of `func`, or `Err` if the executor has stopped
+    /// before `func` could be run. A panic in `func` will be propagated to
+    /// the caller of this function.
+    ///
+    /// When called from a non-Ruby thread the result of `func` **must not**
+    /// contain any Ruby values, these should be converted to Rust types
+    /// within `func`.
+    ///
+    /// If called from a Ruby thread holding the GVL `func` is called
+    /// immediately on the current thread, rather than waiting for the
+    /// executor (which would otherwise deadlock, as the executor's thread
+    /// can't run without the GVL).
+    pub fn run<F, R>(&self, func: F) -> Result<R, ExecutorStoppedError>
+    where
+        F: 'static + Send + FnOnce() -> R,
+        R: 'static + Send,
+    {
+        if RubyHandle::get().is_ok() {
+            return Ok(func());
+        }
+        let task = self.run_async(func);
+        let mut state = task.0.state.lock().unwrap();
+        while !state.done {
+            state = task.0.cond.wait(state).unwrap();
+        }
+        match state.result.take() {
+            Some(Ok(v)) => Ok(v),
+            Some(Err(e)) => resume_unwind(e),
+            None => Err(ExecutorStoppedError),
+        }
+    }
+
+    /// Send `func` to be run on the executor's Ruby thread, returning a
+    /// [`Task`] future that resolves to the result of `func`.
+    ///
+    /// The future resolves to `Err` if the executor has stopped before `func`
+    /// could be run. A panic in `func` will be propagated to the task that
+    /// polls the future.
+    pub fn run_async<F, R>(&self, func: F) -> Task<R>
+    where
+        F: 'static + Send + FnOnce() -> R,
+        R: 'static + Send,
+    {
+        let shared = Arc::new(TaskShared {
+            state: Mutex::new(TaskState {
+                result: None,
+                done: false,
+                waker: None,
+            }),
+            cond: Condvar::new(),
+        });
+        let completer = Completer(shared.clone());
+        // if the executor has stopped the job is dropped, so the completer
+        // marks the task as done without a result.
+        let _ = self.shared.push(Box::new(move || {
+            completer.complete(catch_unwind(AssertUnwindSafe(func)));
+        }));
+        Task(shared)
+    }
+
+    /// Stop the executor thread, once it has run any closures already sent.
+    pub fn shutdown(&self) {
+        self.shared.shutdown();
+    }
+}
+
+impl fmt::Debug for RubyExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RubyExecutor").finish()
+    }
+}
+
+impl RubyExecutor {
+    /// Create a new executor, returning a handle to it, and the loop to be
+    /// run on the executor's Ruby thread.
+    pub(crate) fn pair() -> (Self, ExecutorLoop) {
+        let shared = Arc::new(Shared::default());
+        let executor = RubyExecutor {
+            _shutdown: Arc::new(ShutdownOnDrop(shared.clone())),
+            shared: shared.clone(),
+        };
+        (executor, ExecutorLoop(shared))
+    }
+}
+
+/// The loop run by an executor's Ruby thread, running closures as they are
+/// sent, until the executor is shut down.
+pub(crate) struct ExecutorLoop(Arc<Shared>);
+
+impl ExecutorLoop {
+    /// Run the loop on the current thread.
+    ///
+    /// Must be called from a Ruby thread holding the GVL.
+    pub(crate) fn run(self) -> Result<(), Error> {
+        let _guard = StopGuard(self.0.clone());
+        loop {
+            let next = unsafe { without_gvl(|| self.0.next(), Some(|| self.0.interrupt())) }?;
+            match next {
+                Next::Job(job) => job(),
+                Next::Interrupted => check_interrupts()?,
+                Next::Shutdown => return Ok(()),
+            }
+        }
+    }
+}
+
+impl RubyHandle {
+    pub fn executor_spawn(&self) -> Result<RubyExecutor, Error> {
+        let (executor, executor_loop) = RubyExecutor::pair();
+        Thread::create(move || executor_loop.run())?;
+        Ok(executor)
+    }
+}
+
+struct TaskState<R> {
+    result: Option<thread::Result<R>>,
+    // the job has run, or was dropped without running
+    done: bool,
+    waker: Option<Waker>,
+}
+
+struct TaskShared<R> {
+    state: Mutex<TaskState<R>>,
+    cond: Condvar,
+}
+
+/// Delivers the result of a job to its [`Task`], marking the task as done
+/// when dropped, with or without a result.
+struct Completer<R>(Arc<TaskShared<R>>);
+
+impl<R> Completer<R> {
+    fn complete(&self, result: thread::Result<R>) {
+        self.0.state.lock().unwrap().result = Some(result);
+    }
+}
+
+impl<R> Drop for Completer<R> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.0.state.lock().unwrap();
+            state.done = true;
+            state.waker.take()
+        };
+        self.0.cond.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// A future resolving to the result of a closure sent to a
+/// [`RubyExecutor`] with [`run_async`](RubyExecutor::run_async).
+pub struct Task<R>(Arc<TaskShared<R>>);
+
+impl<R> Future for Task<R> {
+    type Output = Result<R, ExecutorStoppedError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.0.state.lock().unwrap();
+        match state.result.take() {
+            Some(Ok(v)) => Poll::Ready(Ok(v)),
+            Some(Err(e)) => resume_unwind(e),
+            None if state.done => Poll::Ready(Err(ExecutorStoppedError)),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<R> fmt::Debug for Task<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Task").finish()
+    }
+}
+
+/// An error returned when a closure can not be run because the
+/// [`RubyExecutor`] has stopped.
+#[derive(Debug)]
+pub struct ExecutorStoppedError;
+
+impl fmt::Display for ExecutorStoppedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ruby executor has stopped.")
+    }
+}
+
+impl StdError for ExecutorStoppedError {}
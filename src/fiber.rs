@@ -0,0 +1,413 @@
+use std::{fmt, ops::Deref, os::raw::c_int};
+
+#[cfg(ruby_gte_3_0)]
+use rb_sys::rb_fiber_transfer;
+use rb_sys::{
+    rb_fiber_alive_p, rb_fiber_current, rb_fiber_resume, rb_fiber_yield, rb_obj_is_fiber,
+    rb_thread_current, rb_thread_local_aref, rb_thread_local_aset, VALUE,
+};
+
+use crate::{
+    class::RClass,
+    error::{protect, Error},
+    exception,
+    into_value::IntoValue,
+    memoize,
+    method::BlockReturn,
+    module::Module,
+    object::Object,
+    ruby_handle::RubyHandle,
+    try_convert::{ArgList, TryConvert},
+    value::{private, Id, NonZeroValue, ReprValue, Value},
+};
+
+impl RubyHandle {
+    pub fn fiber_new<F, R>(&self, mut func: F) -> Result<Fiber, Error>
+    where
+        F: 'static + Send + FnMut(&[Value]) -> R,
+        R: BlockReturn,
+    {
+        let class = memoize!(RClass: self.class_object().const_get("Fiber").unwrap());
+        let block = self.proc_from_fn(move |args, _block| func(args));
+        class.funcall_with_block("new", (), block)
+    }
+
+    pub fn fiber_current(&self) -> Fiber {
+        unsafe { Fiber::from_rb_value_unchecked(rb_fiber_current()) }
+    }
+
+    pub fn fiber_yield<A, T>(&self, args: A) -> Result<T, Error>
+    where
+        A: ArgList,
+        T: TryConvert,
+    {
+        let args = args.into_arg_list();
+        let slice = args.as_ref();
+        unsafe {
+            protect(|| {
+                Value::new(rb_fiber_yield(
+                    slice.len() as c_int,
+                    slice.as_ptr() as *const VALUE,
+                ))
+            })
+            .and_then(|v| v.try_convert())
+        }
+    }
+
+    #[cfg(ruby_gte_3_0)]
+    pub fn fiber_transfer<A, T>(&self, fiber: Fiber, args: A) -> Result<T, Error>
+    where
+        A: ArgList,
+        T: TryConvert,
+    {
+        let args = args.into_arg_list();
+        let slice = args.as_ref();
+        unsafe {
+            protect(|| {
+                Value::new(rb_fiber_transfer(
+                    fiber.as_rb_value(),
+                    slice.len() as c_int,
+                    slice.as_ptr() as *const VALUE,
+                ))
+            })
+            .and_then(|v| v.try_convert())
+        }
+    }
+
+    #[cfg(ruby_lt_3_0)]
+    pub fn fiber_transfer<A, T>(&self, fiber: Fiber, args: A) -> Result<T, Error>
+    where
+        A: ArgList,
+        T: TryConvert,
+    {
+        // before Ruby 3.0 `Fiber#transfer` is defined by the fiber extension
+        self.require("fiber")?;
+        fiber.funcall("transfer", args)
+    }
+
+    pub fn fiber_local_get<K, T>(&self, key: K) -> Result<T, Error>
+    where
+        K: Into<Id>,
+        T: TryConvert,
+    {
+        let id = key.into();
+        unsafe { Value::new(rb_thread_local_aref(rb_thread_current(), id.as_rb_id())) }
+            .try_convert()
+    }
+
+    pub fn fiber_local_set<K, T>(&self, key: K, val: T) -> Result<(), Error>
+    where
+        K: Into<Id>,
+        T: Into<Value>,
+    {
+        let id = key.into();
+        let val = val.into();
+        unsafe {
+            protect(|| {
+                Value::new(rb_thread_local_aset(
+                    rb_thread_current(),
+                    id.as_rb_id(),
+                    val.as_rb_value(),
+                ))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's Fiber class.
+///
+/// Fibers are coroutines, a block of code that can be paused (by calling
+/// [`Fiber::yield_values`] from within the fiber) and later resumed (with
+/// [`Fiber::resume`]).
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{Error, Fiber};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let fiber = Fiber::new(|args| -> Result<i64, Error> {
+///     let mut total = args[0].try_convert::<i64>()?;
+///     loop {
+///         total += Fiber::yield_values::<_, i64>((total,))?;
+///     }
+/// })
+/// .unwrap();
+///
+/// assert_eq!(fiber.resume::<_, i64>((1,)).unwrap(), 1);
+/// assert_eq!(fiber.resume::<_, i64>((2,)).unwrap(), 3);
+/// assert_eq!(fiber.resume::<_, i64>((3,)).unwrap(), 6);
+/// ```
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Fiber(NonZeroValue);
+
+impl Fiber {
+    /// Return `Some(Fiber)` if `val` is a `Fiber`, `None` otherwise.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            Value::new(rb_obj_is_fiber(val.as_rb_value()))
+                .to_bool()
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Create a new `Fiber` that will run `func`.
+    ///
+    /// `func` does not run until the fiber is first [resumed](Fiber::resume),
+    /// at which point it will receive the arguments passed to `resume`. The
+    /// value returned by `func` will be returned from the final call to
+    /// `resume`, after which the fiber is no longer [alive](Fiber::is_alive).
+    ///
+    /// `func` may return any `R` or `Result<R, Error>` where `R` implements
+    /// `Into<Value>`. Returning `Err(Error)` will raise the error in the
+    /// resuming fiber.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::Fiber;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let fiber = Fiber::new(|args| args.len()).unwrap();
+    /// assert_eq!(fiber.resume::<_, usize>((1, 2, 3)).unwrap(), 3);
+    /// assert!(!fiber.is_alive());
+    /// ```
+    pub fn new<F, R>(func: F) -> Result<Self, Error>
+    where
+        F: 'static + Send + FnMut(&[Value]) -> R,
+        R: BlockReturn,
+    {
+        get_ruby!().fiber_new(func)
+    }
+
+    /// Return the currently running fiber.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn current() -> Self {
+        get_ruby!().fiber_current()
+    }
+
+    /// Pause the currently running fiber, returning control to the fiber that
+    /// resumed it.
+    ///
+    /// `args` are returned from the call to [`Fiber::resume`] that resumed
+    /// the current fiber. A single argument will be returned as is, multiple
+    /// arguments will be returned as an array. When the current fiber is next
+    /// resumed this function returns the arguments passed to that `resume`
+    /// call, in the same manner.
+    ///
+    /// Returns `Err` if called from the root fiber.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn yield_values<A, T>(args: A) -> Result<T, Error>
+    where
+        A: ArgList,
+        T: TryConvert,
+    {
+        get_ruby!().fiber_yield(args)
+    }
+
+    /// Pause the currently running fiber, returning `val` to the fiber that
+    /// resumed it.
+    ///
+    /// See [`Fiber::yield_values`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn yield_value<T, U>(val: T) -> Result<U, Error>
+    where
+        T: Into<Value>,
+        U: TryConvert,
+    {
+        get_ruby!().fiber_yield((val.into(),))
+    }
+
+    /// Get the value of the fiber-local variable `key` for the currently
+    /// running fiber.
+    ///
+    /// This is the equivalent of Ruby's `Thread.current[key]`, which despite
+    /// the name is local to the current fiber. Returns `nil` (e.g. `Ok(None)`
+    /// for `T = Option<_>`) if the variable is not set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::Fiber;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// Fiber::local_set("request_id", 42).unwrap();
+    /// assert_eq!(Fiber::local_get::<_, Option<i64>>("request_id").unwrap(), Some(42));
+    ///
+    /// let fiber = Fiber::new(|_| Fiber::local_get::<_, Option<i64>>("request_id")).unwrap();
+    /// assert_eq!(fiber.resume::<_, Option<i64>>(()).unwrap(), None);
+    /// ```
+    pub fn local_get<K, T>(key: K) -> Result<T, Error>
+    where
+        K: Into<Id>,
+        T: TryConvert,
+    {
+        get_ruby!().fiber_local_get(key)
+    }
+
+    /// Set the value of the fiber-local variable `key` for the currently
+    /// running fiber.
+    ///
+    /// See [`Fiber::local_get`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn local_set<K, T>(key: K, val: T) -> Result<(), Error>
+    where
+        K: Into<Id>,
+        T: Into<Value>,
+    {
+        get_ruby!().fiber_local_set(key, val)
+    }
+
+    /// Resume `self`, passing `args`.
+    ///
+    /// If this is the first time `self` has been resumed `args` are passed as
+    /// the arguments to the fiber's function, otherwise they are returned
+    /// from the [`Fiber::yield_values`] call that paused `self`.
+    ///
+    /// Returns the values passed to [`Fiber::yield_values`], or the value
+    /// returned by the fiber's function when it finishes. Returns `Err` if
+    /// `self` is no longer alive, or raises an exception.
+    pub fn resume<A, T>(self, args: A) -> Result<T, Error>
+    where
+        A: ArgList,
+        T: TryConvert,
+    {
+        let args = args.into_arg_list();
+        let slice = args.as_ref();
+        unsafe {
+            protect(|| {
+                Value::new(rb_fiber_resume(
+                    self.as_rb_value(),
+                    slice.len() as c_int,
+                    slice.as_ptr() as *const VALUE,
+                ))
+            })
+            .and_then(|v| v.try_convert())
+        }
+    }
+
+    /// Transfer control to `self`, passing `args`.
+    ///
+    /// Unlike [`resume`](Fiber::resume), control is not returned to the
+    /// current fiber when `self` yields, rather `self` must explicitly
+    /// transfer control back.
+    ///
+    /// Returns the values passed when control is transferred back to the
+    /// current fiber.
+    pub fn transfer<A, T>(self, args: A) -> Result<T, Error>
+    where
+        A: ArgList,
+        T: TryConvert,
+    {
+        get_ruby!().fiber_transfer(self, args)
+    }
+
+    /// Returns whether `self` can still be resumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Fiber, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let fiber = Fiber::new(|_| Fiber::yield_values::<_, Value>(())).unwrap();
+    /// assert!(fiber.is_alive());
+    /// let _: Value = fiber.resume(()).unwrap();
+    /// assert!(fiber.is_alive());
+    /// let _: Value = fiber.resume(()).unwrap();
+    /// assert!(!fiber.is_alive());
+    /// ```
+    pub fn is_alive(self) -> bool {
+        unsafe { Value::new(rb_fiber_alive_p(self.as_rb_value())).to_bool() }
+    }
+}
+
+impl Deref for Fiber {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for Fiber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Fiber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for Fiber {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<Fiber> for Value {
+    fn from(val: Fiber) -> Self {
+        *val
+    }
+}
+
+impl Object for Fiber {}
+
+unsafe impl private::ReprValue for Fiber {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for Fiber {}
+
+impl TryConvert for Fiber {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Fiber", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
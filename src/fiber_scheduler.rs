@@ -0,0 +1,265 @@
+use std::{
+    fmt,
+    future::Future,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+    time::Duration,
+};
+
+use rb_sys::{
+    rb_fiber_scheduler_block, rb_fiber_scheduler_current, rb_fiber_scheduler_io_wait,
+    rb_fiber_scheduler_kernel_sleep, rb_fiber_scheduler_unblock,
+};
+
+use crate::{
+    error::{protect, Error},
+    fiber::Fiber,
+    into_value::IntoValue,
+    object::Object,
+    ruby_handle::RubyHandle,
+    value::{private, NonZeroValue, ReprValue, Value, QNIL},
+};
+
+/// How long to wait for a future to be woken before polling it again, when
+/// its waker couldn't notify the scheduler directly.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+impl RubyHandle {
+    pub fn fiber_scheduler_current(&self) -> Option<FiberScheduler> {
+        let val = unsafe { Value::new(rb_fiber_scheduler_current()) };
+        (!val.is_nil()).then(|| unsafe { FiberScheduler(NonZeroValue::new_unchecked(val)) })
+    }
+}
+
+fn timeout_value(timeout: Option<Duration>) -> Value {
+    timeout
+        .map(|t| Value::from(t.as_secs_f64()))
+        .unwrap_or(QNIL)
+}
+
+/// The Fiber scheduler for the current thread.
+///
+/// Ruby 3.0 allows a scheduler object to be set for a thread with
+/// `Fiber.set_scheduler`. Blocking operations in non-blocking fibers are then
+/// delegated to the scheduler, so that other fibers can run in the meantime.
+/// This type allows Rust code to make use of the scheduler's hooks, and to
+/// wait on Rust futures without blocking other fibers with
+/// [`block_on`](FiberScheduler::block_on).
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct FiberScheduler(NonZeroValue);
+
+impl FiberScheduler {
+    /// Return the Fiber scheduler for the current thread, if the current
+    /// fiber is non-blocking and a scheduler has been set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::FiberScheduler;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// // the main fiber is blocking
+    /// assert!(FiberScheduler::current().is_none());
+    /// ```
+    pub fn current() -> Option<Self> {
+        get_ruby!().fiber_scheduler_current()
+    }
+
+    /// Sleep the current fiber for `duration`, or until woken if `duration`
+    /// is `None`, allowing other fibers to run.
+    pub fn kernel_sleep(self, duration: Option<Duration>) -> Result<(), Error> {
+        let duration = timeout_value(duration);
+        protect(|| unsafe {
+            Value::new(rb_fiber_scheduler_kernel_sleep(
+                self.as_rb_value(),
+                duration.as_rb_value(),
+            ))
+        })?;
+        Ok(())
+    }
+
+    /// Block the current fiber until it is [unblocked](Self::unblock), or
+    /// `timeout` has passed.
+    ///
+    /// `blocker` is an object describing the reason for blocking, it is
+    /// passed to the scheduler for informational purposes.
+    pub fn block<T>(self, blocker: T, timeout: Option<Duration>) -> Result<(), Error>
+    where
+        T: Into<Value>,
+    {
+        let blocker = blocker.into();
+        let timeout = timeout_value(timeout);
+        protect(|| unsafe {
+            Value::new(rb_fiber_scheduler_block(
+                self.as_rb_value(),
+                blocker.as_rb_value(),
+                timeout.as_rb_value(),
+            ))
+        })?;
+        Ok(())
+    }
+
+    /// Wake `fiber`, previously blocked with [`block`](Self::block).
+    ///
+    /// `blocker` should be the same object passed to `block`.
+    pub fn unblock<T>(self, blocker: T, fiber: Fiber) -> Result<(), Error>
+    where
+        T: Into<Value>,
+    {
+        let blocker = blocker.into();
+        protect(|| unsafe {
+            Value::new(rb_fiber_scheduler_unblock(
+                self.as_rb_value(),
+                blocker.as_rb_value(),
+                fiber.as_rb_value(),
+            ))
+        })?;
+        Ok(())
+    }
+
+    /// Wait for `io` to become ready for the `events` specified (a bitmask
+    /// of Ruby's `IO::READABLE`, `IO::PRIORITY`, and `IO::WRITABLE`), or for
+    /// `timeout` to pass.
+    ///
+    /// Returns the events that are ready, or `false` if the timeout expired.
+    pub fn io_wait<T>(self, io: T, events: i32, timeout: Option<Duration>) -> Result<Value, Error>
+    where
+        T: Into<Value>,
+    {
+        let io = io.into();
+        let events = Value::from(events);
+        let timeout = timeout_value(timeout);
+        protect(|| unsafe {
+            Value::new(rb_fiber_scheduler_io_wait(
+                self.as_rb_value(),
+                io.as_rb_value(),
+                events.as_rb_value(),
+                timeout.as_rb_value(),
+            ))
+        })
+    }
+
+    /// Run `future` to completion, suspending the current fiber while the
+    /// future is pending so that other fibers can run.
+    ///
+    /// This is intended for futures driven by an external reactor, such as
+    /// an async runtime running on other threads. `future` is polled on the
+    /// current thread, with the GVL held. When woken from a Ruby thread the
+    /// current fiber is unblocked immediately, otherwise it will be polled
+    /// again within a short interval.
+    ///
+    /// Returns `Err` if the fiber is interrupted while waiting.
+    ///
+    /// # Safety
+    ///
+    /// `future` is stored on the heap, where it is not visible to Ruby's
+    /// garbage collector. `future` **must not** hold any Ruby values that
+    /// aren't otherwise protected from garbage collection, e.g. with
+    /// [`BoxValue`](crate::value::BoxValue).
+    pub unsafe fn block_on<F>(self, future: F) -> Result<F::Output, Error>
+    where
+        F: Future,
+    {
+        let mut future = Box::pin(future);
+        let fiber = Fiber::current();
+        let notify = Arc::new(FiberWaker {
+            scheduler: self,
+            fiber,
+            woken: AtomicBool::new(false),
+            blocked: AtomicBool::new(false),
+        });
+        let waker = Waker::from(notify.clone());
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(v) = future.as_mut().poll(&mut cx) {
+                return Ok(v);
+            }
+            while !notify.woken.swap(false, Ordering::SeqCst) {
+                notify.blocked.store(true, Ordering::SeqCst);
+                let res = self.block(fiber, Some(POLL_INTERVAL));
+                notify.blocked.store(false, Ordering::SeqCst);
+                res?;
+            }
+        }
+    }
+}
+
+struct FiberWaker {
+    scheduler: FiberScheduler,
+    fiber: Fiber,
+    woken: AtomicBool,
+    blocked: AtomicBool,
+}
+
+impl Wake for FiberWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::SeqCst);
+        // the scheduler can only be notified from a Ruby thread, otherwise
+        // the fiber will notice it's been woken once its block times out.
+        if RubyHandle::get().is_ok() && self.blocked.swap(false, Ordering::SeqCst) {
+            let _ = self.scheduler.unblock(self.fiber, self.fiber);
+        }
+    }
+}
+
+impl Deref for FiberScheduler {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for FiberScheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for FiberScheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for FiberScheduler {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<FiberScheduler> for Value {
+    fn from(val: FiberScheduler) -> Self {
+        *val
+    }
+}
+
+impl Object for FiberScheduler {}
+
+unsafe impl private::ReprValue for FiberScheduler {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for FiberScheduler {}
@@ -1,17 +1,34 @@
 //! Functions for working with Ruby's Garbage Collector.
 
-use std::ops::{Deref, Range};
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, HashMap, VecDeque},
+    ffi::c_void,
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, Range},
+    panic::catch_unwind,
+};
 
+use rb_sys::{
+    rb_event_flag_t, rb_tracearg_event_flag, rb_tracearg_from_tracepoint, rb_tracepoint_disable,
+    rb_tracepoint_enable, rb_tracepoint_enabled_p, rb_tracepoint_new,
+    RUBY_INTERNAL_EVENT_GC_END_MARK, RUBY_INTERNAL_EVENT_GC_END_SWEEP,
+    RUBY_INTERNAL_EVENT_GC_ENTER, RUBY_INTERNAL_EVENT_GC_EXIT, RUBY_INTERNAL_EVENT_GC_START,
+};
 use rb_sys::{
     rb_gc_adjust_memory_usage, rb_gc_count, rb_gc_disable, rb_gc_enable, rb_gc_mark,
     rb_gc_mark_locations, rb_gc_register_address, rb_gc_register_mark_object, rb_gc_start,
-    rb_gc_stat, rb_gc_unregister_address, VALUE,
+    rb_gc_stat, rb_gc_unregister_address, rb_gc_writebarrier, rb_gc_writebarrier_unprotect, VALUE,
 };
 #[cfg(ruby_gte_2_7)]
 use rb_sys::{rb_gc_location, rb_gc_mark_movable};
 
 use crate::{
-    error::{protect, Error},
+    block::Proc,
+    class,
+    error::{bug_from_panic, protect, Error},
+    module::{self, Module, RModule},
     r_hash::RHash,
     ruby_handle::RubyHandle,
     symbol::Symbol,
@@ -43,6 +60,162 @@ where
     unsafe { rb_gc_mark_locations(start as *const VALUE, end as *const VALUE) }
 }
 
+/// Mark each Object produced by `values`.
+///
+/// Used to mark any stored Ruby objects when implementing
+/// [`DataTypeFunctions::mark`](`crate::typed_data::DataTypeFunctions::mark`).
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use magnus::{gc, DataTypeFunctions, TypedData, Value};
+///
+/// #[derive(TypedData)]
+/// #[magnus(class = "Registry", mark)]
+/// struct Registry {
+///     entries: HashMap<String, Value>,
+/// }
+///
+/// impl DataTypeFunctions for Registry {
+///     fn mark(&self) {
+///         gc::mark_iter(self.entries.values().copied());
+///     }
+/// }
+/// ```
+pub fn mark_iter<I>(values: I)
+where
+    I: IntoIterator,
+    I::Item: ReprValue,
+{
+    for value in values {
+        mark(&value.to_value());
+    }
+}
+
+/// Trait for types containing Ruby objects that can be marked.
+///
+/// This is implemented for Ruby types, and for standard library collections
+/// of types implementing `Mark`, allowing the bodies of
+/// [`DataTypeFunctions::mark`](`crate::typed_data::DataTypeFunctions::mark`)
+/// implementations to be simplified.
+///
+/// Maps will only mark their values.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use magnus::{
+///     gc::{self, Mark},
+///     DataTypeFunctions, RString, TypedData, Value,
+/// };
+///
+/// #[derive(TypedData)]
+/// #[magnus(class = "Example", mark)]
+/// struct Example {
+///     list: Vec<Value>,
+///     by_name: HashMap<String, RString>,
+///     maybe: Option<Value>,
+/// }
+///
+/// impl DataTypeFunctions for Example {
+///     fn mark(&self) {
+///         self.list.mark();
+///         self.by_name.mark();
+///         self.maybe.mark();
+///     }
+/// }
+/// ```
+pub trait Mark {
+    /// Mark any Ruby objects contained in `self`.
+    fn mark(&self);
+}
+
+impl<T> Mark for T
+where
+    T: ReprValue,
+{
+    fn mark(&self) {
+        mark(&self.to_value());
+    }
+}
+
+impl<T> Mark for [T]
+where
+    T: Mark,
+{
+    fn mark(&self) {
+        for value in self {
+            value.mark();
+        }
+    }
+}
+
+impl<T, const N: usize> Mark for [T; N]
+where
+    T: Mark,
+{
+    fn mark(&self) {
+        self[..].mark();
+    }
+}
+
+impl<T> Mark for Vec<T>
+where
+    T: Mark,
+{
+    fn mark(&self) {
+        self[..].mark();
+    }
+}
+
+impl<T> Mark for VecDeque<T>
+where
+    T: Mark,
+{
+    fn mark(&self) {
+        for value in self {
+            value.mark();
+        }
+    }
+}
+
+impl<T> Mark for Option<T>
+where
+    T: Mark,
+{
+    fn mark(&self) {
+        if let Some(value) = self {
+            value.mark();
+        }
+    }
+}
+
+impl<K, V, S> Mark for HashMap<K, V, S>
+where
+    V: Mark,
+{
+    fn mark(&self) {
+        for value in self.values() {
+            value.mark();
+        }
+    }
+}
+
+impl<K, V> Mark for BTreeMap<K, V>
+where
+    V: Mark,
+{
+    fn mark(&self) {
+        for value in self.values() {
+            value.mark();
+        }
+    }
+}
+
 /// Mark an Object and let Ruby know it is moveable.
 ///
 /// The [`Value`] type is effectly a pointer to a Ruby object. Ruby's garbage
@@ -120,6 +293,233 @@ where
     unsafe { rb_gc_unregister_address(valref as *const _ as *mut VALUE) }
 }
 
+/// Inform Ruby's garbage collector that `parent` now references `child`.
+///
+/// Ruby's generational garbage collector requires that when an old object
+/// that is "write barrier protected" is updated to reference another object,
+/// the garbage collector is notified with a write barrier. Otherwise the
+/// referenced object may be collected while still in use.
+///
+/// This is only required for Rust types wrapped as Ruby objects with the
+/// `wb_protected` flag (see
+/// [`DataTypeBuilder::wb_protected`](crate::typed_data::DataTypeBuilder::wb_protected)),
+/// and must be called whenever a new Ruby object is stored in the wrapped
+/// type after it has been wrapped. See [`WriteBarrier`] for a type that
+/// ensures this.
+pub fn writebarrier<P, C>(parent: P, child: C)
+where
+    P: ReprValue,
+    C: ReprValue,
+{
+    let child = child.to_value();
+    if !child.is_immediate() {
+        unsafe { rb_gc_writebarrier(parent.to_value().as_rb_value(), child.as_rb_value()) };
+    }
+}
+
+/// Remove the "write barrier protected" status of `obj`.
+///
+/// After calling this function, the garbage collector will no longer rely on
+/// write barriers for `obj`, at the cost of some garbage collection
+/// performance. This can be used when a write barrier protected object is
+/// going to be updated in a way where it's impractical to call
+/// [`writebarrier`].
+pub fn writebarrier_unprotect<T>(obj: T)
+where
+    T: ReprValue,
+{
+    unsafe { rb_gc_writebarrier_unprotect(obj.to_value().as_rb_value()) };
+}
+
+/// A Ruby value stored in a Rust type wrapped as a "write barrier protected"
+/// Ruby object.
+///
+/// The value can only be replaced by supplying the Ruby object that contains
+/// this type, allowing [`set`](WriteBarrier::set) to run the required
+/// [`writebarrier`].
+///
+/// The value will still need to be marked, see
+/// [`DataTypeFunctions::mark`](crate::typed_data::DataTypeFunctions::mark).
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{
+///     define_class, eval, gc, method, typed_data::Obj, DataTypeFunctions, Module, TypedData,
+///     Value, QNIL,
+/// };
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// #[derive(TypedData)]
+/// #[magnus(class = "Container", mark, wb_protected)]
+/// struct Container {
+///     value: gc::WriteBarrier<Value>,
+/// }
+///
+/// impl DataTypeFunctions for Container {
+///     fn mark(&self) {
+///         gc::mark(&self.value.get());
+///     }
+/// }
+///
+/// fn set(rb_self: Obj<Container>, value: Value) {
+///     rb_self.get().value.set(rb_self, value);
+/// }
+///
+/// fn get(rb_self: &Container) -> Value {
+///     rb_self.value.get()
+/// }
+///
+/// let class = define_class("Container", Default::default()).unwrap();
+/// class.define_method("value=", method!(set, 1)).unwrap();
+/// class.define_method("value", method!(get, 0)).unwrap();
+///
+/// let container = Obj::wrap(Container {
+///     value: gc::WriteBarrier::new(*QNIL),
+/// });
+/// let res: String = eval!(r#"container.value = "example"; container.value"#, container).unwrap();
+/// assert_eq!(res, "example");
+/// ```
+pub struct WriteBarrier<T> {
+    value: Cell<T>,
+}
+
+impl<T> WriteBarrier<T>
+where
+    T: ReprValue,
+{
+    /// Create a new `WriteBarrier` holding `value`.
+    ///
+    /// This should be used when constructing the Rust type before it is
+    /// wrapped as a Ruby object. Use [`set`](WriteBarrier::set) to change the
+    /// value afterwards.
+    pub fn new(value: T) -> Self {
+        Self {
+            value: Cell::new(value),
+        }
+    }
+
+    /// Return the held value.
+    pub fn get(&self) -> T {
+        self.value.get()
+    }
+
+    /// Replace the held value with `value`.
+    ///
+    /// `parent` must be the Ruby object wrapping the Rust type containing
+    /// `self`.
+    pub fn set<P>(&self, parent: P, value: T)
+    where
+        P: ReprValue,
+    {
+        self.value.set(value);
+        writebarrier(parent, value);
+    }
+}
+
+impl<T> fmt::Debug for WriteBarrier<T>
+where
+    T: ReprValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get().to_value().inspect())
+    }
+}
+
+/// A slot holding a Ruby value that is protected from garbage collection.
+///
+/// The slot is registered with the garbage collector when the `Root` is
+/// created and unregistered when it is dropped, so the value held will not be
+/// collected (or moved by compaction) while the `Root` is alive. The held
+/// value can be replaced with [`set`](Root::set).
+///
+/// Unlike [`BoxValue`](crate::value::BoxValue), `Root` is not [`Send`], as the
+/// value it contains may only be used from a Ruby thread. To store a value in
+/// a static, use a `thread_local!`.
+///
+/// # Examples
+///
+/// ```
+/// use std::cell::RefCell;
+///
+/// use magnus::{block::Proc, eval, gc, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// thread_local! {
+///     static LOGGER: RefCell<Option<gc::Root<Proc>>> = RefCell::new(None);
+/// }
+///
+/// fn set_logger(logger: Proc) {
+///     LOGGER.with(|l| *l.borrow_mut() = Some(gc::Root::new(logger)));
+/// }
+///
+/// fn log(msg: &str) {
+///     LOGGER.with(|l| {
+///         if let Some(logger) = &*l.borrow() {
+///             let _: Value = logger.call((msg,)).unwrap();
+///         }
+///     })
+/// }
+///
+/// set_logger(eval("proc { |msg| $log = msg }").unwrap());
+/// gc::start();
+/// log("hello");
+/// assert_eq!(eval::<String>("$log").unwrap(), "hello");
+/// ```
+pub struct Root<T> {
+    slot: Box<T>,
+    // Ruby values may only be used from a Ruby thread, so make Root !Send
+    phantom: PhantomData<*mut ()>,
+}
+
+impl<T> Root<T>
+where
+    T: ReprValue,
+{
+    /// Create a new `Root` holding `value`.
+    pub fn new(value: T) -> Self {
+        let slot = Box::new(value);
+        register_address(&*slot);
+        Self {
+            slot,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Return the held value.
+    pub fn get(&self) -> T {
+        *self.slot
+    }
+
+    /// Replace the held value with `value`, returning the previous value.
+    pub fn set(&mut self, value: T) -> T {
+        std::mem::replace(&mut *self.slot, value)
+    }
+}
+
+impl<T> Drop for Root<T> {
+    fn drop(&mut self) {
+        unsafe { rb_gc_unregister_address(&*self.slot as *const _ as *mut VALUE) }
+    }
+}
+
+impl<T> Deref for Root<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.slot
+    }
+}
+
+impl<T> fmt::Debug for Root<T>
+where
+    T: ReprValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.slot.to_value().inspect())
+    }
+}
+
 impl RubyHandle {
     pub fn gc_disable(&self) -> bool {
         unsafe { Value::new(rb_gc_disable()).to_bool() }
@@ -133,6 +533,14 @@ impl RubyHandle {
         unsafe { rb_gc_start() };
     }
 
+    pub fn gc_start_with(&self, full_mark: bool, immediate_sweep: bool) -> Result<(), Error> {
+        let kwargs = RHash::new();
+        kwargs.aset(Symbol::new("full_mark"), full_mark)?;
+        kwargs.aset(Symbol::new("immediate_sweep"), immediate_sweep)?;
+        module::gc().funcall_kw::<_, _, Value>("start", (kwargs,))?;
+        Ok(())
+    }
+
     pub fn gc_adjust_memory_usage(&self, diff: isize) {
         unsafe { rb_gc_adjust_memory_usage(diff as _) };
     }
@@ -171,6 +579,19 @@ impl RubyHandle {
 /// # Panics
 ///
 /// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::gc;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let was_disabled = gc::disable();
+/// // allocation-heavy code that must not be interrupted by GC
+/// if !was_disabled {
+///     gc::enable();
+/// }
+/// ```
 pub fn disable() -> bool {
     get_ruby!().gc_disable()
 }
@@ -206,6 +627,34 @@ pub fn start() {
     get_ruby!().gc_start()
 }
 
+/// Trigger a GC run, with control over the type of run.
+///
+/// This is equivalent to calling `GC.start` with the `full_mark` and
+/// `immediate_sweep` keyword arguments. Passing `false` for `full_mark` will
+/// perform a minor GC, only marking young objects. Passing `false` for
+/// `immediate_sweep` allows Ruby to defer the sweep phase, performing it
+/// lazily.
+///
+/// `gc::start_with(true, true)` is equivalent to [`start`].
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::gc;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let before = gc::stat("minor_gc_count").unwrap();
+/// gc::start_with(false, true).unwrap();
+/// assert!(gc::stat("minor_gc_count").unwrap() > before);
+/// ```
+pub fn start_with(full_mark: bool, immediate_sweep: bool) -> Result<(), Error> {
+    get_ruby!().gc_start_with(full_mark, immediate_sweep)
+}
+
 /// Inform Ruby of external memory usage.
 ///
 /// The Ruby GC is run when Ruby thinks it's running out of memory, but won't
@@ -215,9 +664,38 @@ pub fn start() {
 ///
 /// Pass negative numbers to indicate memory has been freed.
 ///
+/// This is useful for Rust types wrapped as Ruby objects that hold large
+/// buffers. Without it Ruby may see only a small Ruby heap, and so never run
+/// the GC to free those objects, while the process's memory usage grows.
+///
 /// # Panics
 ///
 /// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::gc;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// struct Buffer(Vec<u8>);
+///
+/// impl Buffer {
+///     fn new(len: usize) -> Self {
+///         gc::adjust_memory_usage(len as isize);
+///         Self(vec![0; len])
+///     }
+/// }
+///
+/// impl Drop for Buffer {
+///     fn drop(&mut self) {
+///         gc::adjust_memory_usage(-(self.0.len() as isize));
+///     }
+/// }
+///
+/// let buf = Buffer::new(1024 * 1024);
+/// drop(buf);
+/// ```
 pub fn adjust_memory_usage(diff: isize) {
     get_ruby!().gc_adjust_memory_usage(diff)
 }
@@ -228,15 +706,43 @@ pub fn adjust_memory_usage(diff: isize) {
 /// # Panics
 ///
 /// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::gc;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let before = gc::count();
+/// gc::start();
+/// assert!(gc::count() > before);
+/// ```
 pub fn count() -> usize {
     get_ruby!().gc_count()
 }
 
 /// Returns the GC profiling value for `key`.
 ///
+/// See Ruby's documentation for `GC.stat` for the available keys.
+///
+/// Returns `Err` if `key` is not a valid stat key.
+///
 /// # Panics
 ///
 /// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::gc;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let before = gc::stat("major_gc_count").unwrap();
+/// gc::start();
+/// assert!(gc::stat("major_gc_count").unwrap() > before);
+///
+/// assert!(gc::stat("not_a_real_key").is_err());
+/// ```
 pub fn stat<T>(key: T) -> Result<usize, Error>
 where
     T: Into<Symbol>,
@@ -249,6 +755,499 @@ where
 /// # Panics
 ///
 /// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{gc, Symbol};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let stats = gc::all_stats();
+/// let count: usize = stats.fetch(Symbol::new("count")).unwrap();
+/// assert!(count <= gc::count());
+/// ```
 pub fn all_stats() -> RHash {
     get_ruby!().gc_all_stats()
 }
+
+impl RubyHandle {
+    pub fn gc_define_finalizer<T, F>(&self, obj: T, func: F) -> Result<(), Error>
+    where
+        T: ReprValue,
+        F: 'static + Send + FnOnce(),
+    {
+        let mut func = Some(func);
+        let proc = self.proc_from_fn(move |_args, _block| {
+            if let Some(func) = func.take() {
+                func();
+            }
+        });
+        class::object()
+            .const_get::<_, RModule>("ObjectSpace")?
+            .funcall::<_, _, Value>("define_finalizer", (obj.to_value(), proc))?;
+        Ok(())
+    }
+
+    pub fn gc_undefine_finalizer<T>(&self, obj: T) -> Result<(), Error>
+    where
+        T: ReprValue,
+    {
+        class::object()
+            .const_get::<_, RModule>("ObjectSpace")?
+            .funcall::<_, _, Value>("undefine_finalizer", (obj.to_value(),))?;
+        Ok(())
+    }
+}
+
+/// Register `func` to be run after `obj` has been garbage collected.
+///
+/// This is equivalent to `ObjectSpace.define_finalizer` with a Rust closure.
+/// `func` will be run at most once, sometime after `obj` is collected, or
+/// when the Ruby VM exits. As `obj` no longer exists when `func` runs it is
+/// not passed to `func`, and `func` must not reference it, otherwise `obj`
+/// can never be collected.
+///
+/// Returns `Err` if `obj` can not have a finalizer, e.g. it is frozen or an
+/// immediate value.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{class, gc};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let obj = class::object().new_instance(()).unwrap();
+/// gc::define_finalizer(obj, || println!("object collected")).unwrap();
+/// ```
+pub fn define_finalizer<T, F>(obj: T, func: F) -> Result<(), Error>
+where
+    T: ReprValue,
+    F: 'static + Send + FnOnce(),
+{
+    get_ruby!().gc_define_finalizer(obj, func)
+}
+
+/// Remove all finalizers for `obj`.
+///
+/// This is equivalent to `ObjectSpace.undefine_finalizer`, and will remove
+/// finalizers defined with [`define_finalizer`] as well as those defined from
+/// Ruby.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{class, gc};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let obj = class::object().new_instance(()).unwrap();
+/// gc::define_finalizer(obj, || panic!("finalizer should not run")).unwrap();
+/// gc::undefine_finalizer(obj).unwrap();
+/// ```
+pub fn undefine_finalizer<T>(obj: T) -> Result<(), Error>
+where
+    T: ReprValue,
+{
+    get_ruby!().gc_undefine_finalizer(obj)
+}
+
+impl RubyHandle {
+    fn gc_profiler(&self) -> Result<RModule, Error> {
+        module::gc().const_get("Profiler")
+    }
+
+    pub fn gc_profiler_enable(&self) -> Result<(), Error> {
+        self.gc_profiler()?.funcall::<_, _, Value>("enable", ())?;
+        Ok(())
+    }
+
+    pub fn gc_profiler_disable(&self) -> Result<(), Error> {
+        self.gc_profiler()?.funcall::<_, _, Value>("disable", ())?;
+        Ok(())
+    }
+
+    pub fn gc_profiler_is_enabled(&self) -> Result<bool, Error> {
+        self.gc_profiler()?.funcall("enabled?", ())
+    }
+
+    pub fn gc_profiler_clear(&self) -> Result<(), Error> {
+        self.gc_profiler()?.funcall::<_, _, Value>("clear", ())?;
+        Ok(())
+    }
+
+    pub fn gc_profiler_total_time(&self) -> Result<f64, Error> {
+        self.gc_profiler()?.funcall("total_time", ())
+    }
+
+    pub fn gc_profiler_result(&self) -> Result<String, Error> {
+        self.gc_profiler()?.funcall("result", ())
+    }
+}
+
+/// Start collecting GC profiling data.
+///
+/// This is equivalent to `GC::Profiler.enable`.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::gc;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// gc::profiler_enable().unwrap();
+/// gc::start();
+/// assert!(gc::profiler_total_time().unwrap() > 0.0);
+/// println!("{}", gc::profiler_result().unwrap());
+/// gc::profiler_disable().unwrap();
+/// ```
+pub fn profiler_enable() -> Result<(), Error> {
+    get_ruby!().gc_profiler_enable()
+}
+
+/// Stop collecting GC profiling data.
+///
+/// This is equivalent to `GC::Profiler.disable`. Data collected so far is
+/// retained, see [`profiler_clear`].
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn profiler_disable() -> Result<(), Error> {
+    get_ruby!().gc_profiler_disable()
+}
+
+/// Returns whether GC profiling data is being collected.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn profiler_is_enabled() -> Result<bool, Error> {
+    get_ruby!().gc_profiler_is_enabled()
+}
+
+/// Discard collected GC profiling data.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn profiler_clear() -> Result<(), Error> {
+    get_ruby!().gc_profiler_clear()
+}
+
+/// Returns the total time, in seconds, spent in garbage collection since
+/// profiling was enabled or last cleared.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn profiler_total_time() -> Result<f64, Error> {
+    get_ruby!().gc_profiler_total_time()
+}
+
+/// Returns a report of collected GC profiling data, formatted as a table.
+///
+/// This is equivalent to `GC::Profiler.result`.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn profiler_result() -> Result<String, Error> {
+    get_ruby!().gc_profiler_result()
+}
+
+/// Garbage collection events that can be observed with [`add_event_hook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A GC run has started.
+    Start,
+    /// The marking phase has finished.
+    EndMark,
+    /// The sweeping phase has finished.
+    EndSweep,
+    /// The garbage collector has been entered, either to run a step of
+    /// (incremental) marking or (lazy) sweeping, or for a complete GC run.
+    Enter,
+    /// The garbage collector has been exited.
+    Exit,
+}
+
+impl Event {
+    fn flag(self) -> rb_event_flag_t {
+        (match self {
+            Self::Start => RUBY_INTERNAL_EVENT_GC_START,
+            Self::EndMark => RUBY_INTERNAL_EVENT_GC_END_MARK,
+            Self::EndSweep => RUBY_INTERNAL_EVENT_GC_END_SWEEP,
+            Self::Enter => RUBY_INTERNAL_EVENT_GC_ENTER,
+            Self::Exit => RUBY_INTERNAL_EVENT_GC_EXIT,
+        }) as rb_event_flag_t
+    }
+
+    fn from_flag(flag: rb_event_flag_t) -> Option<Self> {
+        [
+            Self::Start,
+            Self::EndMark,
+            Self::EndSweep,
+            Self::Enter,
+            Self::Exit,
+        ]
+        .iter()
+        .copied()
+        .find(|e| e.flag() == flag)
+    }
+}
+
+/// A hook registered with [`add_event_hook`].
+///
+/// The hook is enabled when created, and remains registered for the life of
+/// the process, but can be disabled and re-enabled.
+#[derive(Clone, Copy)]
+pub struct EventHook(Value);
+
+impl EventHook {
+    /// Enable the hook.
+    pub fn enable(self) -> Result<(), Error> {
+        protect(|| unsafe { Value::new(rb_tracepoint_enable(self.0.as_rb_value())) })?;
+        Ok(())
+    }
+
+    /// Disable the hook.
+    pub fn disable(self) -> Result<(), Error> {
+        protect(|| unsafe { Value::new(rb_tracepoint_disable(self.0.as_rb_value())) })?;
+        Ok(())
+    }
+
+    /// Returns whether the hook is enabled.
+    pub fn is_enabled(self) -> bool {
+        unsafe { Value::new(rb_tracepoint_enabled_p(self.0.as_rb_value())).to_bool() }
+    }
+}
+
+impl RubyHandle {
+    pub fn gc_add_event_hook(&self, events: &[Event], func: fn(Event)) -> Result<EventHook, Error> {
+        unsafe extern "C" fn call(tpval: VALUE, data: *mut c_void) {
+            let func = std::mem::transmute::<*mut c_void, fn(Event)>(data);
+            let flag = rb_tracearg_event_flag(rb_tracearg_from_tracepoint(tpval));
+            if let Some(event) = Event::from_flag(flag) {
+                if let Err(e) = catch_unwind(|| func(event)) {
+                    bug_from_panic(e, "panic in GC event hook")
+                }
+            }
+        }
+
+        let flags = events.iter().fold(0, |acc, e| acc | e.flag());
+        let tracepoint = protect(|| unsafe {
+            Value::new(rb_tracepoint_new(
+                QNIL.as_rb_value(),
+                flags,
+                Some(call),
+                func as *mut c_void,
+            ))
+        })?;
+        register_mark_object(tracepoint);
+        let hook = EventHook(tracepoint);
+        hook.enable()?;
+        Ok(hook)
+    }
+}
+
+/// Register `func` to be called on the garbage collection `events`.
+///
+/// This can be used to record GC pauses alongside other metrics, e.g. by
+/// recording the time of [`Event::Enter`] and [`Event::Exit`].
+///
+/// `func` is called from within the garbage collector, so **must not** call
+/// any Ruby API functions, allocate Ruby objects, or panic. The process will
+/// abort if `func` panics.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use magnus::gc;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// static RUNS: AtomicUsize = AtomicUsize::new(0);
+///
+/// fn on_gc(_event: gc::Event) {
+///     RUNS.fetch_add(1, Ordering::Relaxed);
+/// }
+///
+/// let hook = gc::add_event_hook(&[gc::Event::Start], on_gc).unwrap();
+/// gc::start();
+/// assert!(RUNS.load(Ordering::Relaxed) > 0);
+/// hook.disable().unwrap();
+/// ```
+pub fn add_event_hook(events: &[Event], func: fn(Event)) -> Result<EventHook, Error> {
+    get_ruby!().gc_add_event_hook(events, func)
+}
+
+impl RubyHandle {
+    pub fn gc_stress(&self, enable: bool) -> Result<(), Error> {
+        module::gc().funcall::<_, _, Value>("stress=", (enable,))?;
+        Ok(())
+    }
+
+    pub fn gc_is_stress(&self) -> Result<bool, Error> {
+        module::gc()
+            .funcall::<_, _, Value>("stress", ())
+            .map(|v| v.to_bool())
+    }
+
+    pub fn gc_with_stress<F, T>(&self, func: F) -> Result<T, Error>
+    where
+        F: FnOnce() -> T,
+    {
+        struct Restore(Value);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let _ = module::gc().funcall::<_, _, Value>("stress=", (self.0,));
+            }
+        }
+
+        let _restore = Restore(module::gc().funcall("stress", ())?);
+        self.gc_stress(true)?;
+        Ok(func())
+    }
+
+    #[cfg(any(ruby_gte_2_7, docsrs))]
+    pub fn gc_compact(&self) -> Result<RHash, Error> {
+        module::gc().funcall("compact", ())
+    }
+
+    #[cfg(any(ruby_gte_2_7, docsrs))]
+    pub fn gc_verify_compaction_references(&self) -> Result<(), Error> {
+        module::gc().funcall::<_, _, Value>("verify_compaction_references", ())?;
+        Ok(())
+    }
+}
+
+/// Enable or disable GC stress mode.
+///
+/// In stress mode Ruby will run the garbage collector at every opportunity.
+/// This is extremely slow, but is useful in tests to find bugs, such as
+/// objects not being correctly marked.
+///
+/// See also [`with_stress`].
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::gc;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// gc::stress(true).unwrap();
+/// assert!(gc::is_stress().unwrap());
+/// gc::stress(false).unwrap();
+/// assert!(!gc::is_stress().unwrap());
+/// ```
+pub fn stress(enable: bool) -> Result<(), Error> {
+    get_ruby!().gc_stress(enable)
+}
+
+/// Returns whether GC stress mode is enabled.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn is_stress() -> Result<bool, Error> {
+    get_ruby!().gc_is_stress()
+}
+
+/// Run `func` with GC stress mode enabled.
+///
+/// The previous stress mode is restored after `func` returns, or if it
+/// panics.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{gc, RArray};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let ary = gc::with_stress(|| {
+///     let ary = RArray::new();
+///     for i in 0..10 {
+///         ary.push(format!("value {}", i)).unwrap();
+///     }
+///     ary
+/// })
+/// .unwrap();
+/// assert_eq!(ary.len(), 10);
+/// assert!(!gc::is_stress().unwrap());
+/// ```
+pub fn with_stress<F, T>(func: F) -> Result<T, Error>
+where
+    F: FnOnce() -> T,
+{
+    get_ruby!().gc_with_stress(func)
+}
+
+/// Run a full GC, compacting the heap.
+///
+/// Returns a Hash of information about the objects moved. This is
+/// equivalent to `GC.compact`.
+///
+/// Returns `Err` if compaction is not supported on the current platform.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::gc;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// # #[cfg(ruby_gte_2_7)]
+/// let _ = gc::compact();
+/// ```
+#[cfg(any(ruby_gte_2_7, docsrs))]
+#[cfg_attr(docsrs, doc(cfg(ruby_gte_2_7)))]
+pub fn compact() -> Result<RHash, Error> {
+    get_ruby!().gc_compact()
+}
+
+/// Compact the heap, moving every movable object, then check all
+/// references are updated.
+///
+/// This is equivalent to `GC.verify_compaction_references`, and is intended
+/// for testing implementations of
+/// [`DataTypeFunctions::compact`](crate::typed_data::DataTypeFunctions::compact).
+/// Ruby will crash if an object has not been correctly updated.
+///
+/// Returns `Err` if compaction is not supported on the current platform.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+#[cfg(any(ruby_gte_2_7, docsrs))]
+#[cfg_attr(docsrs, doc(cfg(ruby_gte_2_7)))]
+pub fn verify_compaction_references() -> Result<(), Error> {
+    get_ruby!().gc_verify_compaction_references()
+}
@@ -0,0 +1,752 @@
+//! Types and functions for working with Ruby IO.
+//!
+//! [`Io`] wraps a Ruby IO object, or an IO-like object such as a `StringIO`,
+//! and implements [`std::io::Read`] and [`std::io::Write`].
+//!
+//! Blocking on a file descriptor directly (e.g. with `poll(2)` or a Rust
+//! event loop) while holding the GVL stops all other Ruby threads, and can't
+//! be interrupted by Ruby. The waiting functions in this module wait in a way
+//! that allows other threads to run, and that Ruby's interrupts (such as
+//! `Thread#raise`, `Timeout`, or `SIGINT`) understand.
+
+#[cfg(any(unix, ruby_gte_3_0, docsrs))]
+use std::time::Duration;
+use std::{
+    ffi::CString,
+    fmt, io,
+    ops::{BitOr, Deref},
+};
+#[cfg(unix)]
+use std::{mem::MaybeUninit, os::unix::io::RawFd, ptr};
+
+use rb_sys::rb_gv_get;
+#[cfg(any(ruby_gte_3_0, docsrs))]
+use rb_sys::rb_io_wait;
+#[cfg(unix)]
+use rb_sys::{
+    rb_fd_init, rb_fd_isset, rb_fd_set, rb_fd_term, rb_fdset_t, rb_thread_fd_select, timeval,
+};
+
+use crate::{
+    class,
+    error::Error,
+    exception,
+    into_value::IntoValue,
+    object::Object,
+    r_string::RString,
+    ruby_handle::RubyHandle,
+    try_convert::TryConvert,
+    value::{private, NonZeroValue, ReprValue, Value},
+};
+#[cfg(any(unix, ruby_gte_3_0, docsrs))]
+use crate::{error::protect, value::QNIL};
+#[cfg(all(unix, ruby_gte_3_0))]
+use crate::{r_hash::RHash, symbol::Symbol};
+
+/// A set of IO readiness events, such as readable or writable.
+///
+/// Events can be combined with `|`.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::io::IoEvents;
+///
+/// let events = IoEvents::READABLE | IoEvents::WRITABLE;
+/// assert!(events.contains(IoEvents::READABLE));
+/// assert!(!events.contains(IoEvents::PRIORITY));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct IoEvents(u32);
+
+impl IoEvents {
+    /// The file descriptor is readable.
+    pub const READABLE: Self = Self(1);
+    /// The file descriptor has urgent/out-of-band data to read.
+    pub const PRIORITY: Self = Self(2);
+    /// The file descriptor is writable.
+    pub const WRITABLE: Self = Self(4);
+
+    /// Create an empty set of events.
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns whether `self` contains no events.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns whether all events in `other` are in `self`.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Return the events as the bitmask used by Ruby.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+}
+
+impl BitOr for IoEvents {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl fmt::Debug for IoEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_set();
+        for (event, name) in [
+            (Self::READABLE, "READABLE"),
+            (Self::PRIORITY, "PRIORITY"),
+            (Self::WRITABLE, "WRITABLE"),
+        ]
+        .iter()
+        {
+            if self.contains(*event) {
+                list.entry(&format_args!("{}", name));
+            }
+        }
+        list.finish()
+    }
+}
+
+#[cfg(unix)]
+struct FdSet(rb_fdset_t);
+
+#[cfg(unix)]
+impl FdSet {
+    fn new() -> Self {
+        unsafe {
+            let mut set = MaybeUninit::<rb_fdset_t>::zeroed().assume_init();
+            rb_fd_init(&mut set);
+            Self(set)
+        }
+    }
+
+    fn set(&mut self, fd: RawFd) {
+        unsafe { rb_fd_set(fd, &mut self.0) };
+    }
+
+    fn is_set(&self, fd: RawFd) -> bool {
+        unsafe { rb_fd_isset(fd, &self.0) != 0 }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for FdSet {
+    fn drop(&mut self) {
+        unsafe { rb_fd_term(&mut self.0) };
+    }
+}
+
+impl RubyHandle {
+    #[cfg(unix)]
+    pub fn io_select(
+        &self,
+        fds: &[(RawFd, IoEvents)],
+        timeout: Option<Duration>,
+    ) -> Result<Vec<(RawFd, IoEvents)>, Error> {
+        let mut read = FdSet::new();
+        let mut write = FdSet::new();
+        let mut except = FdSet::new();
+        let mut max = -1;
+        for &(fd, events) in fds {
+            if events.contains(IoEvents::READABLE) {
+                read.set(fd);
+            }
+            if events.contains(IoEvents::WRITABLE) {
+                write.set(fd);
+            }
+            if events.contains(IoEvents::PRIORITY) {
+                except.set(fd);
+            }
+            max = max.max(fd);
+        }
+        let mut tv = timeout.map(|t| timeval {
+            tv_sec: t.as_secs() as _,
+            tv_usec: t.subsec_micros() as _,
+        });
+        let tv_ptr = tv.as_mut().map_or(ptr::null_mut(), |tv| tv as *mut timeval);
+
+        let mut n = 0;
+        // Ruby will raise if interrupted while waiting
+        protect(|| unsafe {
+            n = rb_thread_fd_select(max + 1, &mut read.0, &mut write.0, &mut except.0, tv_ptr);
+            QNIL
+        })?;
+        if n < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let mut ready = Vec::new();
+        for &(fd, _) in fds {
+            let mut events = IoEvents::empty();
+            if read.is_set(fd) {
+                events = events | IoEvents::READABLE;
+            }
+            if write.is_set(fd) {
+                events = events | IoEvents::WRITABLE;
+            }
+            if except.is_set(fd) {
+                events = events | IoEvents::PRIORITY;
+            }
+            if !events.is_empty() {
+                ready.push((fd, events));
+            }
+        }
+        Ok(ready)
+    }
+
+    #[cfg(any(ruby_gte_3_0, docsrs))]
+    pub fn io_wait<T>(
+        &self,
+        io: T,
+        events: IoEvents,
+        timeout: Option<Duration>,
+    ) -> Result<IoEvents, Error>
+    where
+        T: Into<Value>,
+    {
+        let io = io.into();
+        let events = Value::from(events.bits());
+        let timeout = timeout
+            .map(|t| Value::from(t.as_secs_f64()))
+            .unwrap_or(QNIL);
+        let res = protect(|| unsafe {
+            Value::new(rb_io_wait(
+                io.as_rb_value(),
+                events.as_rb_value(),
+                timeout.as_rb_value(),
+            ))
+        })?;
+        if res.is_false() {
+            return Ok(IoEvents::empty());
+        }
+        res.try_convert().map(IoEvents)
+    }
+
+    #[cfg(all(unix, ruby_gte_3_0))]
+    pub fn io_wait_fd(
+        &self,
+        fd: RawFd,
+        events: IoEvents,
+        timeout: Option<Duration>,
+    ) -> Result<IoEvents, Error> {
+        // wrap fd in an IO so the wait can be handled by a Fiber scheduler.
+        // fd is owned by the caller, so must never be closed by Ruby, even if
+        // the IO is left to the GC.
+        let kwargs = RHash::new();
+        kwargs.aset(Symbol::new("autoclose"), false)?;
+        let io: Value = self.class_io().funcall_kw("for_fd", (fd, kwargs))?;
+        let res = self.io_wait(io, events, timeout);
+        let closed = io.funcall::<_, _, Value>("close", ());
+        // an error from the wait takes precedence over one from close
+        let res = res?;
+        closed?;
+        Ok(res)
+    }
+
+    #[cfg(all(unix, not(ruby_gte_3_0)))]
+    pub fn io_wait_fd(
+        &self,
+        fd: RawFd,
+        events: IoEvents,
+        timeout: Option<Duration>,
+    ) -> Result<IoEvents, Error> {
+        self.io_select(&[(fd, events)], timeout)
+            .map(|ready| ready.first().map(|(_, e)| *e).unwrap_or_default())
+    }
+}
+
+/// Wait until one or more of the file descriptors in `fds` are ready for the
+/// paired events, or `timeout` has passed.
+///
+/// Other Ruby threads can run while waiting. Returns `Err` if Ruby
+/// interrupts the current thread while waiting (e.g. with `Thread#raise`) or
+/// the underlying `select` call fails.
+///
+/// Returns the file descriptors that are ready along with the events they
+/// are ready for, which will be empty if the timeout expired.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use std::{os::unix::io::AsRawFd, os::unix::net::UnixStream, time::Duration};
+///
+/// use magnus::io::{select, IoEvents};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let (a, b) = UnixStream::pair().unwrap();
+/// let fds = [(a.as_raw_fd(), IoEvents::READABLE), (b.as_raw_fd(), IoEvents::WRITABLE)];
+/// let ready = select(&fds, Some(Duration::from_millis(10))).unwrap();
+/// assert_eq!(ready, vec![(b.as_raw_fd(), IoEvents::WRITABLE)]);
+/// ```
+#[cfg(unix)]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+pub fn select(
+    fds: &[(RawFd, IoEvents)],
+    timeout: Option<Duration>,
+) -> Result<Vec<(RawFd, IoEvents)>, Error> {
+    get_ruby!().io_select(fds, timeout)
+}
+
+/// Wait until the file descriptor `fd` is ready for any of `events`, or
+/// `timeout` has passed.
+///
+/// Other Ruby threads can run while waiting. Returns `Err` if Ruby
+/// interrupts the current thread while waiting (e.g. with `Thread#raise`) or
+/// the underlying `select` call fails.
+///
+/// Returns the events `fd` is ready for, which will be empty if the timeout
+/// expired.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use std::{io::Write, os::unix::io::AsRawFd, os::unix::net::UnixStream, time::Duration};
+///
+/// use magnus::io::{wait_fd, IoEvents};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let (a, mut b) = UnixStream::pair().unwrap();
+/// let timeout = Some(Duration::from_millis(10));
+/// assert!(wait_fd(a.as_raw_fd(), IoEvents::READABLE, timeout).unwrap().is_empty());
+/// b.write_all(b"hello").unwrap();
+/// assert_eq!(wait_fd(a.as_raw_fd(), IoEvents::READABLE, timeout).unwrap(), IoEvents::READABLE);
+/// ```
+#[cfg(unix)]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+pub fn wait_fd(fd: RawFd, events: IoEvents, timeout: Option<Duration>) -> Result<IoEvents, Error> {
+    get_ruby!()
+        .io_select(&[(fd, events)], timeout)
+        .map(|ready| ready.first().map(|(_, e)| *e).unwrap_or_default())
+}
+
+/// Wait until the file descriptor `fd` is readable, or `timeout` has passed.
+///
+/// Returns `true` if `fd` is readable, or `false` if the timeout expired.
+///
+/// Like [`wait_io`], and unlike [`wait_fd`], when called from a non-blocking
+/// fiber this will defer to the [Fiber scheduler](crate::FiberScheduler),
+/// allowing other fibers to run while waiting (on Ruby 3.0+). Otherwise other
+/// Ruby threads can run while waiting. Returns `Err` if Ruby interrupts the
+/// current thread while waiting (e.g. with `Thread#raise`).
+///
+/// `fd` remains owned by the caller, and is not closed.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use std::{io::Write, os::unix::io::AsRawFd, os::unix::net::UnixStream, time::Duration};
+///
+/// use magnus::io::wait_readable;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let (a, mut b) = UnixStream::pair().unwrap();
+/// let timeout = Some(Duration::from_millis(10));
+/// assert!(!wait_readable(a.as_raw_fd(), timeout).unwrap());
+/// b.write_all(b"hello").unwrap();
+/// assert!(wait_readable(a.as_raw_fd(), timeout).unwrap());
+/// ```
+#[cfg(unix)]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+pub fn wait_readable(fd: RawFd, timeout: Option<Duration>) -> Result<bool, Error> {
+    get_ruby!()
+        .io_wait_fd(fd, IoEvents::READABLE, timeout)
+        .map(|e| e.contains(IoEvents::READABLE))
+}
+
+/// Wait until the file descriptor `fd` is writable, or `timeout` has passed.
+///
+/// Returns `true` if `fd` is writable, or `false` if the timeout expired.
+///
+/// See [`wait_readable`] for details.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use std::{os::unix::io::AsRawFd, os::unix::net::UnixStream, time::Duration};
+///
+/// use magnus::io::wait_writable;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let (a, _b) = UnixStream::pair().unwrap();
+/// assert!(wait_writable(a.as_raw_fd(), Some(Duration::from_millis(10))).unwrap());
+/// ```
+#[cfg(unix)]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+pub fn wait_writable(fd: RawFd, timeout: Option<Duration>) -> Result<bool, Error> {
+    get_ruby!()
+        .io_wait_fd(fd, IoEvents::WRITABLE, timeout)
+        .map(|e| e.contains(IoEvents::WRITABLE))
+}
+
+/// Wait until the Ruby IO object `io` is ready for any of `events`, or
+/// `timeout` has passed.
+///
+/// Unlike [`wait_fd`], when called from a non-blocking fiber this will defer
+/// to the [Fiber scheduler](crate::FiberScheduler), allowing other fibers to
+/// run while waiting.
+///
+/// Returns the events `io` is ready for, which will be empty if the timeout
+/// expired. Returns `Err` if Ruby interrupts the current thread while
+/// waiting, or `io` is not an IO object.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use magnus::{
+///     eval,
+///     io::{wait_io, IoEvents},
+///     RArray,
+/// };
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let pipe: RArray = eval("IO.pipe").unwrap();
+/// let (reader, writer): (magnus::Value, magnus::Value) = pipe.try_convert().unwrap();
+/// let timeout = Some(Duration::from_millis(10));
+/// assert!(wait_io(reader, IoEvents::READABLE, timeout).unwrap().is_empty());
+/// assert_eq!(wait_io(writer, IoEvents::WRITABLE, timeout).unwrap(), IoEvents::WRITABLE);
+/// ```
+#[cfg(any(ruby_gte_3_0, docsrs))]
+#[cfg_attr(docsrs, doc(cfg(ruby_gte_3_0)))]
+pub fn wait_io<T>(io: T, events: IoEvents, timeout: Option<Duration>) -> Result<IoEvents, Error>
+where
+    T: Into<Value>,
+{
+    get_ruby!().io_wait(io, events, timeout)
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's IO class, or
+/// an IO-like object.
+///
+/// As well as instances of `IO` (including `File` and sockets), this type
+/// represents IO-like objects that respond to `#read` or `#write`, such as a
+/// `StringIO`, so Rust code can stream to and from any of these uniformly.
+/// [`Io::from_value`] only checks the class, so only accepts instances of
+/// `IO`. Converting with [`TryConvert`] also accepts IO-like objects, and
+/// objects that can be implicitly converted to `IO` with `#to_io`.
+///
+/// `Io` implements [`std::io::Read`] and [`std::io::Write`] by calling the
+/// Ruby object's `#readpartial` (or `#read` for IO-like objects without
+/// `#readpartial`), `#write`, and `#flush` methods. Ruby exceptions are
+/// converted to [`std::io::Error`], with `SystemCallError`s keeping their
+/// OS error code (except on Windows).
+/// As the inherent `read`, `write`, and `flush` methods take precedence,
+/// the trait methods must be called as e.g. `Write::flush(&mut io)`, although
+/// the provided methods such as `write_all` and `read_to_end` can be called
+/// directly.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{Read, Write};
+///
+/// use magnus::{eval, io::Io, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let val: Value = eval("require 'stringio'; StringIO.new").unwrap();
+/// let mut io: Io = val.try_convert().unwrap();
+/// io.write_all(b"hello, world").unwrap();
+///
+/// io.funcall::<_, _, Value>("rewind", ()).unwrap();
+/// let mut buf = String::new();
+/// io.read_to_string(&mut buf).unwrap();
+/// assert_eq!(buf, "hello, world");
+/// ```
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Io(NonZeroValue);
+
+impl Io {
+    /// Return `Some(Io)` if `val` is an `IO`, `None` otherwise.
+    ///
+    /// This does not accept IO-like objects, as checking for them requires
+    /// calling Ruby methods, see [`TryConvert`] for that.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(class::io())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    /// Read up to `len` bytes, or until end of file if `len` is `None`.
+    ///
+    /// Returns `None` when `len` is `Some` and the end of file has been
+    /// reached, matching Ruby's `IO#read`. The returned string is binary
+    /// (ASCII-8BIT) when `len` is given.
+    pub fn read(self, len: Option<usize>) -> Result<Option<RString>, Error> {
+        match len {
+            Some(len) => self.funcall("read", (len,)),
+            None => self.funcall("read", ()),
+        }
+    }
+
+    /// Write `val` (converted to a string with `#to_s`), returning the number
+    /// of bytes written.
+    pub fn write<T>(self, val: T) -> Result<usize, Error>
+    where
+        T: Into<Value>,
+    {
+        self.funcall("write", (val.into(),))
+    }
+
+    /// Flush any buffered data to the underlying file or stream.
+    pub fn flush(self) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("flush", ()).map(|_| ())
+    }
+
+    /// Return the file descriptor backing `self`.
+    ///
+    /// Returns `None` for IO-like objects without a file descriptor, such as
+    /// a `StringIO`, and `Err` if `self` is closed.
+    pub fn fileno(self) -> Result<Option<i32>, Error> {
+        self.funcall("fileno", ())
+    }
+
+    /// Close `self`.
+    pub fn close(self) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("close", ()).map(|_| ())
+    }
+}
+
+impl RubyHandle {
+    fn global_var(&self, name: &str) -> Value {
+        let name = CString::new(name).unwrap();
+        unsafe { Value::new(rb_gv_get(name.as_ptr())) }
+    }
+
+    pub fn io_stdin(&self) -> Result<Io, Error> {
+        self.global_var("$stdin").try_convert()
+    }
+
+    pub fn io_stdout(&self) -> Io {
+        // Ruby only allows `$stdout` to be set to an object responding to
+        // `#write`, so this is always IO-like
+        unsafe { Io(NonZeroValue::new_unchecked(self.global_var("$stdout"))) }
+    }
+
+    pub fn io_stderr(&self) -> Io {
+        // as with `$stdout`, Ruby checks `$stderr` responds to `#write`
+        unsafe { Io(NonZeroValue::new_unchecked(self.global_var("$stderr"))) }
+    }
+}
+
+/// Return the current value of `$stdin`.
+///
+/// As `$stdin` can be reassigned in Ruby this may be any object, such as a
+/// `StringIO`, and not necessarily the process's standard input. Returns
+/// `Err` if it has been set to an object that isn't IO-like.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn stdin() -> Result<Io, Error> {
+    get_ruby!().io_stdin()
+}
+
+/// Return the current value of `$stdout`.
+///
+/// As `$stdout` can be reassigned in Ruby this may be any object with a
+/// `#write` method, such as a `StringIO`, and not necessarily the process's
+/// standard output. Writing to this rather than with e.g. Rust's `println!`
+/// respects this redirection.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+///
+/// use magnus::{eval, io, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let _: Value = eval("require 'stringio'; $stdout = StringIO.new").unwrap();
+/// write!(io::stdout(), "hello {}", 42).unwrap();
+/// let res: String = eval("$stdout.string").unwrap();
+/// let _: Value = eval("$stdout = STDOUT").unwrap();
+/// assert_eq!(res, "hello 42");
+/// ```
+pub fn stdout() -> Io {
+    get_ruby!().io_stdout()
+}
+
+/// Return the current value of `$stderr`.
+///
+/// As `$stderr` can be reassigned in Ruby this may be any object with a
+/// `#write` method, such as a `StringIO`, and not necessarily the process's
+/// standard error.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn stderr() -> Io {
+    get_ruby!().io_stderr()
+}
+
+fn into_io_error(e: Error) -> io::Error {
+    // on Windows OS error codes aren't errno values, so can't be converted
+    #[cfg(not(windows))]
+    {
+        if let Error::Exception(ex) = &e {
+            if e.is_kind_of(exception::system_call_error()) {
+                if let Ok(errno) = ex.funcall::<_, _, i32>("errno", ()) {
+                    return io::Error::from_raw_os_error(errno);
+                }
+            }
+        }
+    }
+    let kind = if e.is_kind_of(exception::eof_error()) {
+        io::ErrorKind::UnexpectedEof
+    } else {
+        io::ErrorKind::Other
+    };
+    io::Error::new(kind, e.to_string())
+}
+
+impl io::Read for Io {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // `readpartial` returns whatever is available rather than blocking
+        // until `buf` can be filled, and raises `EOFError` at end of file
+        let partial = self
+            .respond_to("readpartial", false)
+            .map_err(into_io_error)?;
+        let s = if partial {
+            match self.funcall::<_, _, RString>("readpartial", (buf.len(),)) {
+                Ok(s) => s,
+                Err(e) if e.is_kind_of(exception::eof_error()) => return Ok(0),
+                Err(e) => return Err(into_io_error(e)),
+            }
+        } else {
+            match Io::read(*self, Some(buf.len())).map_err(into_io_error)? {
+                Some(s) => s,
+                None => return Ok(0),
+            }
+        };
+        // safe as we don't give Ruby the chance to mess with the string while
+        // we hold a refrence to the slice.
+        let bytes = unsafe { s.as_slice() };
+        let len = bytes.len().min(buf.len());
+        buf[..len].copy_from_slice(&bytes[..len]);
+        Ok(len)
+    }
+}
+
+impl io::Write for Io {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let res: Value = self
+            .funcall("write", (RString::from_slice(buf),))
+            .map_err(into_io_error)?;
+        // IO-like objects don't always return the number of bytes written
+        Ok(res.try_convert().unwrap_or(buf.len()))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.respond_to("flush", false).map_err(into_io_error)? {
+            return Ok(());
+        }
+        Io::flush(*self).map_err(into_io_error)
+    }
+}
+
+impl Deref for Io {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for Io {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Io {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for Io {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<Io> for Value {
+    fn from(val: Io) -> Self {
+        *val
+    }
+}
+
+impl Object for Io {}
+
+unsafe impl private::ReprValue for Io {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for Io {}
+
+impl TryConvert for Io {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        if let Some(io) = Self::from_value(val) {
+            return Ok(io);
+        }
+        if let Some(io) = class::io().funcall::<_, _, Option<Value>>("try_convert", (val,))? {
+            return Ok(unsafe { Self(NonZeroValue::new_unchecked(io)) });
+        }
+        if val.respond_to("read", false)? || val.respond_to("write", false)? {
+            return Ok(unsafe { Self(NonZeroValue::new_unchecked(val)) });
+        }
+        Err(Error::new(
+            exception::type_error(),
+            format!("no implicit conversion of {} into IO", unsafe {
+                val.classname()
+            },),
+        ))
+    }
+}
@@ -0,0 +1,356 @@
+use std::{
+    fmt,
+    ops::Deref,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+};
+#[cfg(ruby_gte_3_2)]
+use std::{os::raw::c_void, ptr};
+
+#[cfg(ruby_gte_3_2)]
+use rb_sys::{rb_io_buffer_get_bytes_for_reading, rb_io_buffer_get_bytes_for_writing};
+use rb_sys::{rb_io_buffer_lock, rb_io_buffer_unlock};
+
+#[cfg(ruby_gte_3_2)]
+use crate::value::QNIL;
+use crate::{
+    class::RClass,
+    error::{protect, Error},
+    exception,
+    into_value::IntoValue,
+    memoize,
+    module::Module,
+    object::Object,
+    r_file::RFile,
+    r_string::RString,
+    ruby_handle::RubyHandle,
+    try_convert::TryConvert,
+    value::{private, NonZeroValue, ReprValue, Value},
+};
+
+impl RubyHandle {
+    fn class_io_buffer(&self) -> RClass {
+        *memoize!(RClass: self.class_io().const_get("Buffer").unwrap())
+    }
+
+    pub fn io_buffer_new(&self, size: usize) -> Result<IoBuffer, Error> {
+        self.class_io_buffer()
+            .new_instance((size,))
+            .map(|val| unsafe { IoBuffer(NonZeroValue::new_unchecked(val)) })
+    }
+
+    pub fn io_buffer_for(&self, s: RString) -> Result<IoBuffer, Error> {
+        self.class_io_buffer().funcall("for", (s,))
+    }
+
+    pub fn io_buffer_map(
+        &self,
+        file: RFile,
+        size: Option<usize>,
+        offset: usize,
+        readonly: bool,
+    ) -> Result<IoBuffer, Error> {
+        let class = self.class_io_buffer();
+        let flags: Value = if readonly {
+            class.const_get("READONLY")?
+        } else {
+            Value::from(0)
+        };
+        class.funcall("map", (file, size, offset, flags))
+    }
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's `IO::Buffer`
+/// class.
+///
+/// `IO::Buffer` is a fixed size buffer of raw memory, either allocated by
+/// Ruby, memory mapped from a file, or a view of a string. It can be passed
+/// to Ruby IO methods such as `IO#read` and `IO#write` to read or write large
+/// amounts of data without intermediate strings. Slices share memory with
+/// the buffer they were taken from.
+///
+/// Reading from and writing to an `IoBuffer` from Rust with
+/// [`get_bytes`](IoBuffer::get_bytes)/[`set_bytes`](IoBuffer::set_bytes)
+/// copies the data once, between the buffer and a Rust slice.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::IoBuffer;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let buffer = IoBuffer::new(8).unwrap();
+/// buffer.set_bytes(2, b"abc").unwrap();
+///
+/// let slice = buffer.slice(2, 3).unwrap();
+/// let mut buf = [0; 3];
+/// slice.get_bytes(0, &mut buf).unwrap();
+/// assert_eq!(&buf, b"abc");
+/// ```
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct IoBuffer(NonZeroValue);
+
+impl IoBuffer {
+    /// Return `Some(IoBuffer)` if `val` is an `IO::Buffer`, `None` otherwise.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(get_ruby!().class_io_buffer())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    /// Allocate a new zeroed `IoBuffer` of `size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn new(size: usize) -> Result<Self, Error> {
+        get_ruby!().io_buffer_new(size)
+    }
+
+    /// Create a read-only `IoBuffer` viewing the memory of the string `s`,
+    /// without copying.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn for_string(s: RString) -> Result<Self, Error> {
+        get_ruby!().io_buffer_for(s)
+    }
+
+    /// Memory map `size` bytes of `file` (or the whole file if `size` is
+    /// `None`), starting at `offset`.
+    ///
+    /// If `readonly` is `false` writes to the buffer will be written to the
+    /// file, and `file` must have been opened for writing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn map(
+        file: RFile,
+        size: Option<usize>,
+        offset: usize,
+        readonly: bool,
+    ) -> Result<Self, Error> {
+        get_ruby!().io_buffer_map(file, size, offset, readonly)
+    }
+
+    /// Return the size of the buffer in bytes.
+    pub fn size(self) -> usize {
+        self.funcall("size", ()).unwrap()
+    }
+
+    /// Return a new `IoBuffer` of `len` bytes starting at `offset`, sharing
+    /// memory with `self`.
+    ///
+    /// Returns `Err` if the slice would extend beyond the end of `self`.
+    pub fn slice(self, offset: usize, len: usize) -> Result<Self, Error> {
+        self.funcall("slice", (offset, len))
+    }
+
+    /// Copy bytes starting at `offset` in the buffer in to `buf`, filling
+    /// `buf`.
+    ///
+    /// Returns `Err` if there are not enough bytes in the buffer after
+    /// `offset` to fill `buf`.
+    #[cfg(ruby_gte_3_2)]
+    pub fn get_bytes(self, offset: usize, buf: &mut [u8]) -> Result<(), Error> {
+        let mut base = ptr::null();
+        let mut size = 0;
+        protect(|| unsafe {
+            rb_io_buffer_get_bytes_for_reading(self.as_rb_value(), &mut base, &mut size);
+            QNIL
+        })?;
+        check_bounds(offset, buf.len(), size)?;
+        // safe as the buffer can't be freed or resized while we hold the
+        // pointer, we don't give Ruby the chance to run any code.
+        unsafe {
+            ptr::copy_nonoverlapping((base as *const u8).add(offset), buf.as_mut_ptr(), buf.len())
+        };
+        Ok(())
+    }
+
+    /// Copy bytes starting at `offset` in the buffer in to `buf`, filling
+    /// `buf`.
+    ///
+    /// Returns `Err` if there are not enough bytes in the buffer after
+    /// `offset` to fill `buf`.
+    #[cfg(ruby_lt_3_2)]
+    pub fn get_bytes(self, offset: usize, buf: &mut [u8]) -> Result<(), Error> {
+        let s: RString = self.funcall("get_string", (offset, buf.len()))?;
+        // safe as we don't give Ruby the chance to mess with the string while
+        // we hold a refrence to the slice.
+        let bytes = unsafe { s.as_slice() };
+        check_bounds(0, buf.len(), bytes.len())?;
+        buf.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Copy all of `buf` in to the buffer starting at `offset`.
+    ///
+    /// Returns `Err` if the buffer is read-only or locked, or `buf` would
+    /// extend beyond the end of the buffer.
+    #[cfg(ruby_gte_3_2)]
+    pub fn set_bytes(self, offset: usize, buf: &[u8]) -> Result<(), Error> {
+        let mut base: *mut c_void = ptr::null_mut();
+        let mut size = 0;
+        protect(|| unsafe {
+            rb_io_buffer_get_bytes_for_writing(self.as_rb_value(), &mut base, &mut size);
+            QNIL
+        })?;
+        check_bounds(offset, buf.len(), size)?;
+        // safe as the buffer can't be freed or resized while we hold the
+        // pointer, we don't give Ruby the chance to run any code.
+        unsafe { ptr::copy_nonoverlapping(buf.as_ptr(), (base as *mut u8).add(offset), buf.len()) };
+        Ok(())
+    }
+
+    /// Copy all of `buf` in to the buffer starting at `offset`.
+    ///
+    /// Returns `Err` if the buffer is read-only or locked, or `buf` would
+    /// extend beyond the end of the buffer.
+    #[cfg(ruby_lt_3_2)]
+    pub fn set_bytes(self, offset: usize, buf: &[u8]) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("set_string", (RString::from_slice(buf), offset))
+            .map(|_| ())
+    }
+
+    /// Lock the buffer for the duration of `func`, preventing it from being
+    /// resized or freed by other code, such as a Ruby thread.
+    ///
+    /// Returns `Err` if the buffer is already locked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::IoBuffer;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let buffer = IoBuffer::new(4).unwrap();
+    /// let res = buffer.lock(|| {
+    ///     assert!(buffer.is_locked());
+    ///     assert!(buffer.lock(|| ()).is_err());
+    ///     42
+    /// });
+    /// assert_eq!(res.unwrap(), 42);
+    /// assert!(!buffer.is_locked());
+    /// ```
+    pub fn lock<F, R>(self, func: F) -> Result<R, Error>
+    where
+        F: FnOnce() -> R,
+    {
+        protect(|| unsafe { Value::new(rb_io_buffer_lock(self.as_rb_value())) })?;
+        let res = catch_unwind(AssertUnwindSafe(func));
+        let unlocked = protect(|| unsafe { Value::new(rb_io_buffer_unlock(self.as_rb_value())) });
+        match res {
+            Ok(v) => unlocked.map(|_| v),
+            Err(e) => resume_unwind(e),
+        }
+    }
+
+    /// Return whether the buffer is currently locked.
+    pub fn is_locked(self) -> bool {
+        self.funcall("locked?", ()).unwrap()
+    }
+
+    /// Return whether the buffer's memory was allocated by Ruby for the
+    /// buffer.
+    pub fn is_internal(self) -> bool {
+        self.funcall("internal?", ()).unwrap()
+    }
+
+    /// Return whether the buffer's memory is owned by another object, such as
+    /// a string or another buffer.
+    pub fn is_external(self) -> bool {
+        self.funcall("external?", ()).unwrap()
+    }
+
+    /// Return whether the buffer's memory is mapped from a file.
+    pub fn is_mapped(self) -> bool {
+        self.funcall("mapped?", ()).unwrap()
+    }
+
+    /// Return whether the buffer is read-only.
+    pub fn is_readonly(self) -> bool {
+        self.funcall("readonly?", ()).unwrap()
+    }
+
+    /// Return whether the buffer has no memory, e.g. after being freed.
+    pub fn is_null(self) -> bool {
+        self.funcall("null?", ()).unwrap()
+    }
+}
+
+fn check_bounds(offset: usize, len: usize, size: usize) -> Result<(), Error> {
+    match offset.checked_add(len) {
+        Some(end) if end <= size => Ok(()),
+        _ => Err(Error::new(
+            exception::arg_error(),
+            "specified offset+length exceeds buffer size",
+        )),
+    }
+}
+
+impl Deref for IoBuffer {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for IoBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for IoBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for IoBuffer {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<IoBuffer> for Value {
+    fn from(val: IoBuffer) -> Self {
+        *val
+    }
+}
+
+impl Object for IoBuffer {}
+
+unsafe impl private::ReprValue for IoBuffer {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for IoBuffer {}
+
+impl TryConvert for IoBuffer {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into IO::Buffer", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
@@ -0,0 +1,203 @@
+//! Types and functions for working with Ruby's compiled instruction
+//! sequences.
+
+use std::{fmt, ops::Deref};
+
+use crate::{
+    class::RClass,
+    error::Error,
+    exception,
+    into_value::IntoValue,
+    memoize,
+    module::Module,
+    object::Object,
+    r_string::RString,
+    ruby_handle::RubyHandle,
+    try_convert::TryConvert,
+    value::{private, NonZeroValue, ReprValue, Value},
+};
+
+impl RubyHandle {
+    fn class_iseq(&self) -> RClass {
+        *memoize!(RClass: {
+            let vm: RClass = self.class_object().const_get("RubyVM").unwrap();
+            vm.const_get("InstructionSequence").unwrap()
+        })
+    }
+
+    pub fn iseq_compile(&self, source: &str, file: &str, line: usize) -> Result<Iseq, Error> {
+        self.class_iseq()
+            .funcall("compile", (source, file, file, line))
+    }
+
+    pub fn iseq_load_from_binary(&self, binary: &[u8]) -> Result<Iseq, Error> {
+        self.class_iseq()
+            .funcall("load_from_binary", (RString::from_slice(binary),))
+    }
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's
+/// `RubyVM::InstructionSequence` class.
+///
+/// An instruction sequence is Ruby code that has been compiled to the
+/// bytecode run by Ruby's virtual machine. Compiling code once and
+/// [evaluating](Iseq::eval) it many times avoids parsing the source on each
+/// evaluation, and compiled code can be serialised with
+/// [`to_binary`](Iseq::to_binary) to ship precompiled bytecode.
+///
+/// Instruction sequences are specific to CRuby, and binaries are only
+/// loadable by the same version of Ruby that created them.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, Iseq, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let _: Value = eval("$count = 0").unwrap();
+/// let iseq = Iseq::compile("$count += 1", "counter.rb", 1).unwrap();
+/// for _ in 0..3 {
+///     let _: Value = iseq.eval().unwrap();
+/// }
+/// assert_eq!(eval::<i64>("$count").unwrap(), 3);
+/// ```
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Iseq(NonZeroValue);
+
+impl Iseq {
+    /// Return `Some(Iseq)` if `val` is an `Iseq`, `None` otherwise.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(get_ruby!().class_iseq())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    /// Compile `source` to an instruction sequence.
+    ///
+    /// `file` and `line` are the file name and first line number that will be
+    /// used for `__FILE__`, `__LINE__`, and backtraces when the code is run.
+    ///
+    /// Returns `Err` containing a `SyntaxError` if `source` is not valid Ruby
+    /// code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn compile(source: &str, file: &str, line: usize) -> Result<Self, Error> {
+        get_ruby!().iseq_compile(source, file, line)
+    }
+
+    /// Load an instruction sequence serialised with
+    /// [`to_binary`](Iseq::to_binary).
+    ///
+    /// Returns `Err` if `binary` is not a valid serialised instruction
+    /// sequence, or was created by a different version of Ruby.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::Iseq;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let binary = Iseq::compile("[1, 2, 3].sum", "sum.rb", 1)
+    ///     .unwrap()
+    ///     .to_binary()
+    ///     .unwrap();
+    ///
+    /// let iseq = Iseq::load_from_binary(&binary).unwrap();
+    /// assert_eq!(iseq.eval::<i64>().unwrap(), 6);
+    /// ```
+    pub fn load_from_binary(binary: &[u8]) -> Result<Self, Error> {
+        get_ruby!().iseq_load_from_binary(binary)
+    }
+
+    /// Run the instruction sequence at the top level, converting the result
+    /// to a `T`.
+    pub fn eval<T>(self) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.funcall("eval", ())
+    }
+
+    /// Serialise the instruction sequence, to be loaded later with
+    /// [`load_from_binary`](Iseq::load_from_binary).
+    pub fn to_binary(self) -> Result<Vec<u8>, Error> {
+        let binary: RString = self.funcall("to_binary", ())?;
+        Ok(unsafe { binary.as_slice() }.to_vec())
+    }
+
+    /// Return a human-readable disassembly of the instruction sequence.
+    pub fn disasm(self) -> Result<String, Error> {
+        self.funcall("disasm", ())
+    }
+}
+
+impl Deref for Iseq {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for Iseq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Iseq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for Iseq {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<Iseq> for Value {
+    fn from(val: Iseq) -> Self {
+        *val
+    }
+}
+
+impl Object for Iseq {}
+
+unsafe impl private::ReprValue for Iseq {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for Iseq {}
+
+impl TryConvert for Iseq {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Iseq", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
@@ -0,0 +1,120 @@
+//! Functions for printing with Ruby's `Kernel` methods.
+//!
+//! These call Ruby's `Kernel#puts`, `#print`, `#p`, and `#pp`, which write to
+//! the current value of `$stdout`, so output respects any redirection at the
+//! Ruby level, such as `$stdout` being reassigned to a `StringIO` to capture
+//! output in tests. Writing to the process's standard output directly (e.g.
+//! with Rust's `println!`) bypasses this.
+
+use crate::{
+    error::Error, module::Module, ruby_handle::RubyHandle, try_convert::ArgList, value::Value,
+};
+
+impl RubyHandle {
+    pub fn kernel_puts<A>(&self, args: A) -> Result<(), Error>
+    where
+        A: ArgList,
+    {
+        self.module_kernel()
+            .funcall::<_, _, Value>("puts", args)
+            .map(|_| ())
+    }
+
+    pub fn kernel_print<A>(&self, args: A) -> Result<(), Error>
+    where
+        A: ArgList,
+    {
+        self.module_kernel()
+            .funcall::<_, _, Value>("print", args)
+            .map(|_| ())
+    }
+
+    pub fn kernel_p<A>(&self, args: A) -> Result<(), Error>
+    where
+        A: ArgList,
+    {
+        self.module_kernel()
+            .funcall::<_, _, Value>("p", args)
+            .map(|_| ())
+    }
+
+    pub fn kernel_pp<A>(&self, args: A) -> Result<(), Error>
+    where
+        A: ArgList,
+    {
+        self.module_kernel()
+            .funcall::<_, _, Value>("pp", args)
+            .map(|_| ())
+    }
+}
+
+/// Write each of `args` to `$stdout`, followed by a newline if it does not
+/// already end with one, as Ruby's `Kernel#puts`.
+///
+/// Returns `Err` if writing to `$stdout` raises an error.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, kernel, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let _: Value = eval("require 'stringio'; $stdout = StringIO.new").unwrap();
+/// kernel::puts(("hello", 1)).unwrap();
+/// let res: String = eval("$stdout.string").unwrap();
+/// let _: Value = eval("$stdout = STDOUT").unwrap();
+/// assert_eq!(res, "hello\n1\n");
+/// ```
+pub fn puts<A>(args: A) -> Result<(), Error>
+where
+    A: ArgList,
+{
+    get_ruby!().kernel_puts(args)
+}
+
+/// Write each of `args` to `$stdout`, as Ruby's `Kernel#print`.
+///
+/// Returns `Err` if writing to `$stdout` raises an error.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn print<A>(args: A) -> Result<(), Error>
+where
+    A: ArgList,
+{
+    get_ruby!().kernel_print(args)
+}
+
+/// Write the result of calling `#inspect` on each of `args` to `$stdout`,
+/// each followed by a newline, as Ruby's `Kernel#p`.
+///
+/// Returns `Err` if `#inspect` or writing to `$stdout` raises an error.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn p<A>(args: A) -> Result<(), Error>
+where
+    A: ArgList,
+{
+    get_ruby!().kernel_p(args)
+}
+
+/// Pretty print each of `args` to `$stdout`, as Ruby's `Kernel#pp`.
+///
+/// Returns `Err` if pretty printing or writing to `$stdout` raises an error.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+pub fn pp<A>(args: A) -> Result<(), Error>
+where
+    A: ArgList,
+{
+    get_ruby!().kernel_pp(args)
+}
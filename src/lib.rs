@@ -351,7 +351,7 @@
 // * `rb_char_to_option_kcode`:
 //! * `rb_check_arity`: [`scan_args::check_arity`].
 //! * `rb_check_array_type`:  See [`TryConvert`] and [`Value::try_convert`].
-// * `rb_check_convert_type`:
+//! * `rb_check_convert_type`: Similar to [`Value::check_convert`].
 // * `rb_check_copyable`:
 // * `rb_check_frozen`:
 // * `rb_check_frozen_inline`:
@@ -362,12 +362,12 @@
 //! * `rb_check_id_cstr`: [`Id::check`](value::Id::check).
 // * `rb_check_inheritable`:
 // * `rb_check_safe_str`:
-// * `rb_check_string_type`:
+//! * `rb_check_string_type`: See [`CheckConvert`] and [`Value::check_convert`].
 //! * `rb_check_symbol`: Similar to [`StaticSymbol::check`].
 //! * `rb_check_symbol_cstr`: [`StaticSymbol::check`].
 // * `rb_check_to_float`:
 // * `rb_check_to_int`:
-// * `rb_check_to_integer`:
+//! * `rb_check_to_integer`: See [`CheckConvert`] and [`Value::check_convert`].
 // * `rb_check_type`:
 //! * `rb_check_typeddata`: See [`TryConvert`] and [`Value::try_convert`].
 // * `RB_CHR2FIX`:
@@ -673,7 +673,7 @@
 // * `rb_external_str_new_with_enc`:
 // * `rb_extract_keywords`:
 // * `RB_EXT_RACTOR_SAFE`:
-// * `rb_ext_ractor_safe`:
+//! * `rb_ext_ractor_safe`: [`mark_ractor_safe`].
 //!
 //! ## `rb_f`
 //!
@@ -693,16 +693,16 @@
 // * `rb_fd_term`:
 // * `rb_fd_zero`:
 // * `rb_feature_provided`:
-// * `rb_fiber_alive_p`:
-// * `rb_fiber_current`:
-// * `rb_fiber_new`:
+//! * `rb_fiber_alive_p`: [`Fiber::is_alive`].
+//! * `rb_fiber_current`: [`Fiber::current`].
+//! * `rb_fiber_new`: Not implemented directly, see [`Fiber::new`].
 // * `rb_fiber_raise`:
-// * `rb_fiber_resume`:
+//! * `rb_fiber_resume`: [`Fiber::resume`].
 // * `rb_fiber_resume_kw`:
 // * `rb_fiber_scheduler_address_resolve`:
-// * `rb_fiber_scheduler_block`:
+//! * `rb_fiber_scheduler_block`: [`FiberScheduler::block`].
 // * `rb_fiber_scheduler_close`:
-// * `rb_fiber_scheduler_current`:
+//! * `rb_fiber_scheduler_current`: [`FiberScheduler::current`].
 // * `rb_fiber_scheduler_current_for_thread`:
 // * `rb_fiber_scheduler_get`:
 // * `rb_fiber_scheduler_io_close`:
@@ -712,20 +712,20 @@
 // * `rb_fiber_scheduler_io_read_memory`:
 // * `rb_fiber_scheduler_io_result`:
 // * `rb_fiber_scheduler_io_result_apply`:
-// * `rb_fiber_scheduler_io_wait`:
+//! * `rb_fiber_scheduler_io_wait`: [`FiberScheduler::io_wait`].
 // * `rb_fiber_scheduler_io_wait_readable`:
 // * `rb_fiber_scheduler_io_wait_writable`:
 // * `rb_fiber_scheduler_io_write`:
 // * `rb_fiber_scheduler_io_write_memory`:
-// * `rb_fiber_scheduler_kernel_sleep`:
+//! * `rb_fiber_scheduler_kernel_sleep`: [`FiberScheduler::kernel_sleep`].
 // * `rb_fiber_scheduler_kernel_sleepv`:
 // * `rb_fiber_scheduler_make_timeout`:
 // * `rb_fiber_scheduler_process_wait`:
 // * `rb_fiber_scheduler_set`:
-// * `rb_fiber_scheduler_unblock`:
-// * `rb_fiber_transfer`:
+//! * `rb_fiber_scheduler_unblock`: [`FiberScheduler::unblock`].
+//! * `rb_fiber_transfer`: [`Fiber::transfer`].
 // * `rb_fiber_transfer_kw`:
-// * `rb_fiber_yield`:
+//! * `rb_fiber_yield`: [`Fiber::yield_values`].
 // * `rb_fiber_yield_kw`:
 //! * `rb_filesystem_encindex`: [`encoding::Index::filesystem`].
 //! * `rb_filesystem_encoding`:
@@ -789,9 +789,9 @@
 // * `rb_frozen_class_p`:
 // * `rb_frozen_error_raise`:
 //! * `rb_funcall`: See [`Value::funcall`].
-//! * `rb_funcallv`: [`Value::funcall`].
+//! * `rb_funcallv`: [`Value::funcall`], [`Value::funcall_id`].
 // * `rb_funcallv_kw`:
-// * `rb_funcallv_public`:
+//! * `rb_funcallv_public`: [`Value::funcall_public`].
 // * `rb_funcallv_public_kw`:
 // * `rb_funcall_passing_block`:
 // * `rb_funcall_passing_block_kw`:
@@ -831,8 +831,8 @@
 //! * `rb_gc_stat`: [`gc::stat`] or [`gc::all_stats`].
 //! * `rb_gc_unregister_address`: [`gc::unregister_address`].
 // * `rb_gc_update_tbl_refs`:
-// * `rb_gc_writebarrier`:
-// * `rb_gc_writebarrier_unprotect`:
+//! * `rb_gc_writebarrier`: [`gc::writebarrier`].
+//! * `rb_gc_writebarrier_unprotect`: [`gc::writebarrier_unprotect`].
 // * `rb_generic_ivar_table`:
 // * `rb_genrand_int32`:
 // * `rb_genrand_real`:
@@ -920,6 +920,10 @@
 // * `rb_io_addstr`:
 // * `rb_io_ascii8bit_binmode`:
 // * `rb_io_binmode`:
+//! * `rb_io_buffer_get_bytes_for_reading`: See [`IoBuffer::get_bytes`].
+//! * `rb_io_buffer_get_bytes_for_writing`: See [`IoBuffer::set_bytes`].
+//! * `rb_io_buffer_lock`: [`IoBuffer::lock`].
+//! * `rb_io_buffer_unlock`: [`IoBuffer::lock`].
 // * `rb_io_bufwrite`:
 // * `rb_io_check_byte_readable`:
 // * `rb_io_check_char_readable`:
@@ -960,7 +964,7 @@
 // * `rb_io_synchronized`:
 // * `rb_io_ungetbyte`:
 // * `rb_io_ungetc`:
-// * `rb_io_wait`:
+//! * `rb_io_wait`: [`io::wait_io`].
 // * `rb_io_write`:
 //!
 //! ## `rb_is`-`rb_iz`
@@ -989,7 +993,7 @@
 // * `rb_iter_break`:
 // * `rb_iter_break_value`:
 // * `rb_ivar_count`:
-// * `rb_ivar_defined`:
+//! * `rb_ivar_defined`: [`Object::ivar_defined`].
 // * `rb_ivar_foreach`:
 //! * `rb_ivar_get`: [`Object::ivar_get`].
 //! * `rb_ivar_set`: [`Object::ivar_set`].
@@ -1011,7 +1015,7 @@
 // * `rb_ll2inum`:
 // * `RB_LL2NUM`:
 // * `rb_ll2num_inline`:
-// * `rb_load`:
+//! * `rb_load`: [`embed::Cleanup::load_file`].
 // * `rb_loaderror`:
 // * `rb_loaderror_with_path`:
 // * `rb_load_file`:
@@ -1079,7 +1083,7 @@
 // * `rb_mod_init_copy`:
 // * `rb_mod_method_arity`:
 // * `rb_mod_module_eval`:
-// * `rb_mod_module_exec`:
+//! * `rb_mod_module_exec`: [`Module::class_exec`].
 // * `rb_mod_name`:
 // * `rb_mod_remove_const`:
 // * `rb_mod_remove_cvar`:
@@ -1088,13 +1092,13 @@
 // * `rb_mod_sys_fail`:
 // * `rb_mod_sys_fail_str`:
 // * `rb_must_asciicompat`:
-// * `rb_mutex_lock`:
-// * `rb_mutex_locked_p`:
-// * `rb_mutex_new`:
+//! * `rb_mutex_lock`: [`Mutex::lock`](thread::Mutex::lock).
+//! * `rb_mutex_locked_p`: [`Mutex::is_locked`](thread::Mutex::is_locked).
+//! * `rb_mutex_new`: [`Mutex::new`](thread::Mutex::new).
 // * `rb_mutex_sleep`:
 // * `rb_mutex_synchronize`:
-// * `rb_mutex_trylock`:
-// * `rb_mutex_unlock`:
+//! * `rb_mutex_trylock`: [`Mutex::try_lock`](thread::Mutex::try_lock).
+//! * `rb_mutex_unlock`: [`Mutex::unlock`](thread::Mutex::unlock).
 //!
 //! ## `rb_n`
 //!
@@ -1167,8 +1171,8 @@
 // * `rb_obj_call_init_kw`:
 // * `rb_obj_class`:
 //! * `rb_obj_classname`: [`Value::classname`].
-// * `rb_obj_clone`:
-// * `rb_obj_dup`:
+//! * `rb_obj_clone`: [`Value::clone_value`].
+//! * `rb_obj_dup`: [`Value::dup`].
 // * `rb_obj_encoding`:
 // * `RB_OBJ_FREEZE`:
 //! * `rb_obj_freeze`: [`Value::freeze`].
@@ -1178,14 +1182,14 @@
 // * `rb_obj_frozen_p`:
 // * `RB_OBJ_FROZEN_RAW`:
 // * `rb_obj_hide`:
-// * `rb_obj_id`:
+//! * `rb_obj_id`: [`Value::object_id`].
 // * `RB_OBJ_INIT_COPY`:
 // * `rb_obj_init_copy`:
 // * `rb_obj_instance_eval`:
-// * `rb_obj_instance_exec`:
-// * `rb_obj_instance_variables`:
-// * `rb_obj_is_fiber`:
-// * `rb_obj_is_instance_of`:
+//! * `rb_obj_instance_exec`: [`Value::instance_exec`].
+//! * `rb_obj_instance_variables`: [`Object::instance_variables`].
+//! * `rb_obj_is_fiber`: [`Fiber::from_value`].
+//! * `rb_obj_is_instance_of`: [`Value::is_instance_of`].
 //! * `rb_obj_is_kind_of`: [`Value::is_kind_of`].
 // * `rb_obj_is_method`:
 //! * `rb_obj_is_proc`: [`Proc::from_value`](block::Proc::from_value).
@@ -1208,14 +1212,16 @@
 //!
 //! ## `rb_p`
 //!
-// * `rb_p`:
+//! * `rb_p`: Similar to [`kernel::p`], which calls Ruby's `Kernel#p`.
 // * `rb_path2class`:
 // * `rb_path_check`:
 // * `rb_path_to_class`:
 // * `rb_pipe`:
 // * `RB_POSFIXABLE`:
+//! * `rb_postponed_job_preregister`: See [`enqueue_on_ruby_thread`].
 // * `rb_postponed_job_register`:
-// * `rb_postponed_job_register_one`:
+//! * `rb_postponed_job_register_one`: See [`enqueue_on_ruby_thread`].
+//! * `rb_postponed_job_trigger`: See [`enqueue_on_ruby_thread`].
 // * `rb_prepend_module`: [`Module::prepend_module`].
 //! * `rb_proc_arity`: [`Proc::arity`](block::Proc::arity).
 //! * `rb_proc_call`: [`Proc::call`](block::Proc::call).
@@ -1251,9 +1257,9 @@
 // * `rb_ractor_local_storage_value_lookup`:
 // * `rb_ractor_local_storage_value_newkey`:
 // * `rb_ractor_local_storage_value_set`:
-// * `rb_ractor_make_shareable`:
+//! * `rb_ractor_make_shareable`: [`Ractor::make_shareable`].
 // * `rb_ractor_make_shareable_copy`:
-// * `rb_ractor_shareable_p`:
+//! * `rb_ractor_shareable_p`: [`Ractor::is_shareable`].
 // * `rb_ractor_stderr`:
 // * `rb_ractor_stderr_set`:
 // * `rb_ractor_stdin`:
@@ -1337,7 +1343,7 @@
 // * `rb_scan_args_length_mismatch`:
 // * `rb_set_class_path`:
 // * `rb_set_class_path_string`:
-// * `rb_set_end_proc`:
+//! * `rb_set_end_proc`: [`embed::set_end_proc`].
 // * `rb_set_errinfo`:
 //! * `rb_singleton_class`: [`Object::singleton_class`].
 // * `rb_singleton_class_attached`:
@@ -1515,26 +1521,26 @@
 //!
 //! ## `rb_t`
 //!
-//! * `RB_TEST`: [`Value::to_bool`] / [`TryConvert`] / [`Value::try_convert`].
+//! * `RB_TEST`: [`Value::to_bool`] / [`Lax`].
 // * `rb_thread_add_event_hook`:
 // * `rb_thread_add_event_hook2`:
 // * `rb_thread_alone`:
-// * `rb_thread_atfork`:
+//! * `rb_thread_atfork`: See [`embed::after_fork`].
 // * `rb_thread_atfork_before_exec`:
-// * `rb_thread_call_without_gvl`:
+//! * `rb_thread_call_without_gvl`: [`without_gvl`].
 // * `rb_thread_call_without_gvl2`:
-// * `rb_thread_call_with_gvl`:
-// * `rb_thread_check_ints`:
-// * `rb_thread_create`:
-// * `rb_thread_current`:
+//! * `rb_thread_call_with_gvl`: [`thread::with_gvl`].
+//! * `rb_thread_check_ints`: [`thread::check_interrupts`].
+//! * `rb_thread_create`: [`Thread::create`].
+//! * `rb_thread_current`: [`Thread::current`].
 // * `rb_thread_fd_close`:
-// * `rb_thread_fd_select`:
+//! * `rb_thread_fd_select`: [`io::select`], [`io::wait_fd`].
 // * `rb_thread_fd_writable`:
 // * `rb_thread_interrupted`:
-// * `rb_thread_kill`:
-// * `rb_thread_local_aref`:
-// * `rb_thread_local_aset`:
-// * `rb_thread_main`:
+//! * `rb_thread_kill`: [`Thread::kill`].
+//! * `rb_thread_local_aref`: [`Fiber::local_get`].
+//! * `rb_thread_local_aset`: [`Fiber::local_set`].
+//! * `rb_thread_main`: [`Thread::main`].
 // * `rb_thread_remove_event_hook`:
 // * `rb_thread_remove_event_hook_with_data`:
 // * `rb_thread_run`:
@@ -1545,20 +1551,20 @@
 // * `rb_thread_stop`:
 // * `rb_thread_wait_fd`:
 // * `rb_thread_wait_for`:
-// * `rb_thread_wakeup`:
+//! * `rb_thread_wakeup`: [`Thread::wakeup`].
 // * `rb_thread_wakeup_alive`:
 // * `rb_throw`:
 // * `rb_throw_obj`:
 // * `rb_timespec_now`:
 // * `rb_time_interval`:
-// * `rb_time_nano_new`:
+//! * `rb_time_nano_new`: [`Time::new`].
 // * `rb_time_new`:
 // * `rb_time_num_new`:
-// * `rb_time_timespec`:
-// * `rb_time_timespec_interval`:
-// * `rb_time_timespec_new`:
+//! * `rb_time_timespec`: [`Time::timespec`].
+//! * `rb_time_timespec_interval`: [`TryConvert`] or [`Value::try_convert`].
+//! * `rb_time_timespec_new`: [`Time::new_utc`], [`Time::new_with_offset`].
 // * `rb_time_timeval`:
-// * `rb_time_utc_offset`:
+//! * `rb_time_utc_offset`: [`Time::utc_offset`].
 // * `rb_tolower`:
 // * `rb_toupper`:
 //! * `rb_to_encoding`: [`TryConvert`] or [`Value::try_convert`].
@@ -1580,10 +1586,10 @@
 // * `rb_tracearg_raised_exception`:
 // * `rb_tracearg_return_value`:
 // * `rb_tracearg_self`:
-// * `rb_tracepoint_disable`:
-// * `rb_tracepoint_enable`:
-// * `rb_tracepoint_enabled_p`:
-// * `rb_tracepoint_new`:
+//! * `rb_tracepoint_disable`: [`gc::EventHook::disable`].
+//! * `rb_tracepoint_enable`: [`gc::EventHook::enable`].
+//! * `rb_tracepoint_enabled_p`: [`gc::EventHook::is_enabled`].
+//! * `rb_tracepoint_new`: [`gc::add_event_hook`].
 // * `rb_trap_exit`:
 // * `rb_type`:
 // * `rb_typeddata_inherited_p`:
@@ -1686,7 +1692,7 @@
 // * `RSTRUCT_GET`:
 // * `RSTRUCT_LEN`:
 // * `RSTRUCT_SET`:
-//! * `RTEST`: [`Value::to_bool`] / [`TryConvert`] / [`Value::try_convert`].
+//! * `RTEST`: [`Value::to_bool`] / [`Lax`].
 // * `RTYPEDDATA`:
 // * `RTYPEDDATA_DATA`:
 // * `RTYPEDDATA_P`:
@@ -1743,7 +1749,7 @@
 // * `ruby_init_stack`:
 // * `ruby_malloc_size_overflow`:
 // * `RUBY_METHOD_FUNC`:
-// * `ruby_native_thread_p`:
+//! * `ruby_native_thread_p`: See [`thread::with_gvl`].
 // * `RUBY_NDEBUG`:
 // * `ruby_options`:
 // * `ruby_posix_signal`:
@@ -1757,7 +1763,7 @@
 //! * `ruby_script`: Similar to [`embed::ruby_script`].
 // * `ruby_setenv`:
 //! * `ruby_setup`: See [`embed::init`].
-// * `ruby_set_argv`:
+//! * `ruby_set_argv`: See [`embed::init_with_args`].
 //! * `ruby_set_script_name`: [`embed::ruby_script`].
 // * `ruby_show_copyright`:
 // * `ruby_show_version`:
@@ -1804,9 +1810,20 @@
 #[macro_use]
 mod ruby_handle;
 
+/// Items used by the code generated by this crate's macros. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::{
+        into_value::IntoValue,
+        ruby_handle::RubyHandle,
+        value::{private::ReprValue, NonZeroValue},
+    };
+}
+
 mod binding;
 pub mod block;
 pub mod class;
+mod condition_variable;
 #[cfg(feature = "embed")]
 #[cfg_attr(docsrs, doc(cfg(feature = "embed")))]
 pub mod embed;
@@ -1814,17 +1831,29 @@ pub mod encoding;
 mod enumerator;
 pub mod error;
 pub mod exception;
+pub mod executor;
+mod fiber;
+#[cfg(any(ruby_gte_3_0, docsrs))]
+mod fiber_scheduler;
 mod float;
 pub mod gc;
 mod integer;
 mod into_value;
+pub mod io;
+#[cfg(any(ruby_gte_3_1, docsrs))]
+mod io_buffer;
+mod iseq;
+pub mod kernel;
 pub mod method;
 pub mod module;
+mod mutex;
 mod object;
+pub mod object_space;
 /// Traits that commonly should be in scope.
 pub mod prelude {
     pub use crate::{class::Class, module::Module, object::Object};
 }
+mod queue;
 mod r_array;
 mod r_bignum;
 mod r_complex;
@@ -1835,15 +1864,23 @@ mod r_match;
 mod r_object;
 mod r_rational;
 pub mod r_regexp;
+mod r_set;
 pub mod r_string;
 pub mod r_struct;
 mod r_typed_data;
+#[cfg(any(ruby_gte_3_0, docsrs))]
+mod ractor;
 mod range;
 #[cfg(feature = "rb-sys-interop")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rb-sys-interop")))]
 pub mod rb_sys;
 pub mod scan_args;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde;
 mod symbol;
+pub mod thread;
+pub mod time;
 mod try_convert;
 pub mod typed_data;
 pub mod value;
@@ -1859,18 +1896,31 @@ use ::rb_sys::{
     rb_define_global_function, rb_define_module, rb_define_variable, rb_errinfo,
     rb_eval_string_protect, rb_set_errinfo, VALUE,
 };
-pub use magnus_macros::{init, wrap, DataTypeFunctions, TypedData};
+pub use magnus_macros::{
+    init, wrap, DataTypeFunctions, IntoError, IntoHash, SymbolEnum, TryConvert, TypedData,
+};
 
+#[cfg(any(ruby_gte_3_0, docsrs))]
+#[cfg_attr(docsrs, doc(cfg(ruby_gte_3_0)))]
+pub use crate::fiber_scheduler::FiberScheduler;
+#[cfg(any(ruby_gte_3_1, docsrs))]
+#[cfg_attr(docsrs, doc(cfg(ruby_gte_3_1)))]
+pub use crate::io_buffer::IoBuffer;
+#[cfg(any(ruby_gte_3_0, docsrs))]
+#[cfg_attr(docsrs, doc(cfg(ruby_gte_3_0)))]
+pub use crate::ractor::{mark_ractor_safe, Ractor};
 #[cfg(ruby_use_flonum)]
 pub use crate::value::Flonum;
 pub use crate::{
     binding::Binding,
     class::{Class, RClass},
-    enumerator::Enumerator,
+    enumerator::{Enumerator, Lazy, Yielder},
     error::Error,
     exception::{Exception, ExceptionClass},
+    fiber::Fiber,
     float::Float,
     integer::Integer,
+    iseq::Iseq,
     module::{Attr, Module, RModule},
     object::Object,
     r_array::RArray,
@@ -1883,12 +1933,15 @@ pub use crate::{
     r_object::RObject,
     r_rational::RRational,
     r_regexp::RRegexp,
+    r_set::RSet,
     r_string::RString,
     r_struct::RStruct,
     r_typed_data::RTypedData,
     range::Range,
     symbol::Symbol,
-    try_convert::{ArgList, TryConvert},
+    thread::{block_on, enqueue_on_ruby_thread, without_gvl, Thread},
+    time::Time,
+    try_convert::{ArgList, CheckConvert, Coerced, Lax, Strict, TryConvert},
     typed_data::{DataType, DataTypeFunctions, TypedData},
     value::{Fixnum, StaticSymbol, Value, QFALSE, QNIL, QTRUE},
 };
@@ -2036,6 +2089,8 @@ impl RubyHandle {
         T: TryConvert,
     {
         let mut state = 0;
+        // restored after catching an exception, see `protect`
+        let errinfo = unsafe { rb_errinfo() };
         // safe ffi to Ruby, captures raised errors (+ brake, throw, etc) as state
         let result = unsafe {
             let s = CString::new(s)
@@ -2049,7 +2104,7 @@ impl RubyHandle {
             // Tag::Raise
             6 => unsafe {
                 let ex = Exception::from_rb_value_unchecked(rb_errinfo());
-                rb_set_errinfo(QNIL.as_rb_value());
+                rb_set_errinfo(errinfo);
                 Err(Error::Exception(ex))
             },
             other => Err(Error::Jump(unsafe { transmute(other) })),
@@ -2080,6 +2135,18 @@ pub fn define_module(name: &str) -> Result<RModule, Error> {
 /// # Panics
 ///
 /// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_error, exception, prelude::*};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let error = define_error("ExampleError", exception::standard_error()).unwrap();
+/// let parse_error = define_error("ExampleParseError", error).unwrap();
+/// assert!(parse_error.is_inherited(error));
+/// assert!(parse_error.is_inherited(exception::standard_error()));
+/// ```
 pub fn define_error(name: &str, superclass: ExceptionClass) -> Result<ExceptionClass, Error> {
     get_ruby!().define_error(name, superclass)
 }
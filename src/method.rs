@@ -368,12 +368,13 @@ mod private {
         fn into_return_value(self) -> Result<Value, Error>;
     }
 
-    impl<T> ReturnValue for Result<T, Error>
+    impl<T, E> ReturnValue for Result<T, E>
     where
         T: Into<Value>,
+        E: Into<Error>,
     {
         fn into_return_value(self) -> Result<Value, Error> {
-            self.map(Into::into)
+            self.map(Into::into).map_err(Into::into)
         }
     }
 
@@ -382,7 +383,7 @@ mod private {
         T: Into<Value>,
     {
         fn into_return_value(self) -> Result<Value, Error> {
-            Ok(self).into_return_value()
+            Ok(self.into())
         }
     }
 
@@ -402,13 +403,17 @@ mod private {
         }
     }
 
-    impl<I, T> ReturnValue for Result<Yield<I>, Error>
+    impl<I, T, E> ReturnValue for Result<Yield<I>, E>
     where
         I: Iterator<Item = T>,
         T: Into<Value>,
+        E: Into<Error>,
     {
         fn into_return_value(self) -> Result<Value, Error> {
-            self?.into_return_value()
+            match self {
+                Ok(val) => val.into_return_value(),
+                Err(e) => Err(e.into()),
+            }
         }
     }
 
@@ -428,13 +433,17 @@ mod private {
         }
     }
 
-    impl<I, T> ReturnValue for Result<YieldValues<I>, Error>
+    impl<I, T, E> ReturnValue for Result<YieldValues<I>, E>
     where
         I: Iterator<Item = T>,
         T: ArgList,
+        E: Into<Error>,
     {
         fn into_return_value(self) -> Result<Value, Error> {
-            self?.into_return_value()
+            match self {
+                Ok(val) => val.into_return_value(),
+                Err(e) => Err(e.into()),
+            }
         }
     }
 
@@ -453,12 +462,41 @@ mod private {
         }
     }
 
-    impl<I> ReturnValue for Result<YieldSplat<I>, Error>
+    impl<I, E> ReturnValue for Result<YieldSplat<I>, E>
     where
         I: Iterator<Item = RArray>,
+        E: Into<Error>,
+    {
+        fn into_return_value(self) -> Result<Value, Error> {
+            match self {
+                Ok(val) => val.into_return_value(),
+                Err(e) => Err(e.into()),
+            }
+        }
+    }
+
+    impl<T> ReturnValue for Required<T>
+    where
+        T: Into<Value>,
+    {
+        fn into_return_value(self) -> Result<Value, Error> {
+            match self.0 {
+                Some(val) => Ok(val.into()),
+                None => Err(Error::runtime_error("expected a value, got None")),
+            }
+        }
+    }
+
+    impl<T, E> ReturnValue for Result<Required<T>, E>
+    where
+        T: Into<Value>,
+        E: Into<Error>,
     {
         fn into_return_value(self) -> Result<Value, Error> {
-            self?.into_return_value()
+            match self {
+                Ok(val) => val.into_return_value(),
+                Err(e) => Err(e.into()),
+            }
         }
     }
 
@@ -472,9 +510,12 @@ mod private {
         }
     }
 
-    impl InitReturn for Result<(), Error> {
+    impl<E> InitReturn for Result<(), E>
+    where
+        E: Into<Error>,
+    {
         fn into_init_return(self) -> Result<(), Error> {
-            self
+            self.map_err(Into::into)
         }
     }
 
@@ -537,15 +578,24 @@ impl<T> Method for T where T: private::Method {}
 /// * [`Yield<I>`]
 /// * [`YieldValues<I>`]
 /// * [`YieldSplat<I>`]
-/// * `Result<T, magnus::Error>`
-/// * `Result<Yield<I>, magnus::Error>`
-/// * `Result<YieldValues<I>, magnus::Error>`
-/// * `Result<YieldSplat<I>, magnus::Error>`
+/// * [`Required<T>`]
+/// * `Result<T, E>`
+/// * `Result<Yield<I>, E>`
+/// * `Result<YieldValues<I>, E>`
+/// * `Result<YieldSplat<I>, E>`
+/// * `Result<Required<T>, E>`
 ///
-/// where `I` implements `Iterator<Item = T>` and `T` implements `Into<Value>`.
+/// where `I` implements `Iterator<Item = T>`, `T` implements `Into<Value>`,
+/// and `E` implements `Into<magnus::Error>`.
 ///
-/// When is `Err(magnus::Error)` returned to Ruby it will be conveted to and
-/// raised as a Ruby exception.
+/// When `Err(E)` is returned to Ruby it will be conveted to a
+/// [`magnus::Error`](Error) and raised as a Ruby exception. This allows
+/// returning any error type with a conversion to `magnus::Error`, such as
+/// [`std::io::Error`], without converting at every return site.
+///
+/// `Option<T>` implements `Into<Value>`, with `None` converting to `nil`. To
+/// instead raise an exception when `None` is returned wrap the `Option` in
+/// [`Required`].
 ///
 /// [`Yield`], [`YieldValues`], and [`YieldSplat`] allow returning a Rust
 /// [`Iterator`] to be bridged to Ruby method that calls a block with the
@@ -557,16 +607,50 @@ pub trait ReturnValue: private::ReturnValue {}
 
 impl<T> ReturnValue for T where T: private::ReturnValue {}
 
+/// Wrapper for an [`Option`] returned to Ruby, raising an exception rather
+/// than returning `nil` when `None`.
+///
+/// `Some(T)` is returned to Ruby as `T`, `None` raises a `RuntimeError`.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_global_function, eval, function, method::Required, RArray};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn first(ary: RArray) -> Required<i64> {
+///     Required(ary.entry(0).ok())
+/// }
+///
+/// define_global_function("first", function!(first, 1));
+///
+/// let res: bool = eval("first([1, 2, 3]) == 1").unwrap();
+/// assert!(res);
+///
+/// let res: bool = eval("(first([]) rescue $!).is_a?(RuntimeError)").unwrap();
+/// assert!(res);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Required<T>(pub Option<T>);
+
+impl<T> From<Option<T>> for Required<T> {
+    fn from(val: Option<T>) -> Self {
+        Self(val)
+    }
+}
+
 /// Trait marking types that can be returned to Ruby from a library
 /// [`init`](magnus_macros::init) function.
 ///
 /// Implemented for the following types:
 ///
 /// * `()`
-/// * `Result<(), magnus::Error>`
+/// * `Result<(), E>`
 ///
-/// When is `Err(magnus::Error)` returned to Ruby it will be conveted to and
-/// raised as a Ruby exception.
+/// where `E` implements `Into<magnus::Error>`.
+///
+/// When `Err(E)` is returned to Ruby it will be conveted to a
+/// [`magnus::Error`](Error) and raised as a Ruby exception.
 ///
 /// Note: functions without a specified return value will return `()`. `()`
 /// implements `Into<Value>` (converting to `nil`).
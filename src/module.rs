@@ -12,16 +12,19 @@ use rb_sys::{
 };
 
 use crate::{
+    block::Proc,
     class::{Class, RClass},
     debug_assert_value,
     error::{protect, Error},
     exception::{self, ExceptionClass},
+    gc,
     into_value::IntoValue,
-    method::Method,
+    memoize,
+    method::{BlockReturn, Method},
     object::Object,
     r_array::RArray,
     ruby_handle::RubyHandle,
-    try_convert::TryConvert,
+    try_convert::{ArgList, TryConvert},
     value::{
         private::{self, ReprValue as _},
         Id, NonZeroValue, ReprValue, Value, QNIL,
@@ -137,6 +140,53 @@ impl RModule {
         })?;
         Ok(())
     }
+
+    /// Define a refinement of `class` in `self`'s scope, returning the
+    /// refinement module.
+    ///
+    /// Methods defined on the returned module (e.g. with
+    /// [`define_method`](Module::define_method)) will only be visible to Ruby
+    /// code that has activated the refinements in `self` with `using`. This
+    /// allows extending core classes without monkeypatching them globally.
+    ///
+    /// Calling this method multiple times with the same `class` will return
+    /// the same refinement module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class, define_module, eval, method, Module};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// fn shout(rb_self: String) -> String {
+    ///     rb_self.to_uppercase()
+    /// }
+    ///
+    /// let module = define_module("Shouting").unwrap();
+    /// let refinement = module.define_refinement(class::string()).unwrap();
+    /// refinement.define_method("shout", method!(shout, 0)).unwrap();
+    ///
+    /// assert!(eval::<bool>(r#"!"hello".respond_to?(:shout)"#).unwrap());
+    /// let res: String = eval(r#"using Shouting; "hello".shout"#).unwrap();
+    /// assert_eq!(res, "HELLO");
+    /// ```
+    pub fn define_refinement<T>(self, class: T) -> Result<RModule, Error>
+    where
+        T: Module,
+    {
+        debug_assert_value!(self);
+        // Module#refine requires a block of Ruby code (it rejects a block
+        // implemented in Rust), so is called from a Ruby lambda. We don't need
+        // to do anything in the block, as it's simpler to define methods with
+        // the returned module.
+        let refine = *memoize!(Proc: {
+            let refine = crate::eval::<Proc>("->(m, k) { m.module_eval { refine(k) { self } } }")
+                .unwrap();
+            gc::register_mark_object(refine);
+            refine
+        });
+        refine.call((*self, *class))
+    }
 }
 
 impl Deref for RModule {
@@ -261,6 +311,19 @@ pub trait Module: Object + Deref<Target = Value> + Copy {
     /// let inner = outer.define_error("InnerError", Default::default()).unwrap();
     /// assert!(inner.is_inherited(exception::standard_error()));
     /// ```
+    ///
+    /// Exception classes defined this way can be used as the superclass of
+    /// further exception classes, to build up an error hierarchy.
+    ///
+    /// ```
+    /// use magnus::{exception, define_module, Module};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let module = define_module("Example").unwrap();
+    /// let error = module.define_error("Error", exception::standard_error()).unwrap();
+    /// let parse_error = module.define_error("ParseError", error).unwrap();
+    /// assert!(parse_error.is_inherited(error));
+    /// ```
     fn define_error<T: Into<Id>>(
         self,
         name: T,
@@ -445,6 +508,42 @@ pub trait Module: Object + Deref<Target = Value> + Copy {
         unsafe { RArray::from_rb_value_unchecked(rb_mod_ancestors(self.as_rb_value())) }
     }
 
+    /// Evaluate `block` in the context of `self`, passing `args` to the block.
+    ///
+    /// This is the equivalent of Ruby's `class_exec`/`module_exec`. `block`
+    /// will receive `self` as its first argument, followed by `args`. Ruby
+    /// code evaluated within the block will define methods on `self`.
+    ///
+    /// `block` may return any `R` or `Result<R, Error>` where `R` implements
+    /// `Into<Value>`. Returning `Err(Error)` will raise the error as a Ruby
+    /// exception.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{define_class, eval, Module, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let class = define_class("Example", Default::default()).unwrap();
+    /// let _: Value = class
+    ///     .class_exec((), |rb_self, _args| {
+    ///         rb_self.funcall::<_, _, Value>("attr_accessor", ("name",))
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let res: bool = eval!("Example.new.respond_to?(:name=)").unwrap();
+    /// assert!(res);
+    /// ```
+    fn class_exec<A, F, R, T>(self, args: A, block: F) -> Result<T, Error>
+    where
+        A: ArgList,
+        F: 'static + Send + FnMut(Value, &[Value]) -> R,
+        R: BlockReturn,
+        T: TryConvert,
+    {
+        self.funcall_with_block("class_exec", args, Proc::from_fn_with_self(block))
+    }
+
     /// Define a method in `self`'s scope.
     ///
     /// # Examples
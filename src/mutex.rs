@@ -0,0 +1,222 @@
+use std::{
+    fmt,
+    ops::Deref,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    time::Duration,
+};
+
+use rb_sys::{
+    rb_mutex_lock, rb_mutex_locked_p, rb_mutex_new, rb_mutex_trylock, rb_mutex_unlock, VALUE,
+};
+
+use crate::{
+    class::RClass,
+    error::{protect, Error},
+    exception,
+    into_value::IntoValue,
+    memoize,
+    module::Module,
+    object::Object,
+    ruby_handle::RubyHandle,
+    try_convert::TryConvert,
+    value::{private, NonZeroValue, ReprValue, Value, QNIL},
+};
+
+impl RubyHandle {
+    pub fn mutex_new(&self) -> Mutex {
+        unsafe { Mutex::from_rb_value_unchecked(rb_mutex_new()) }
+    }
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's Mutex class.
+///
+/// This is Ruby's `Thread::Mutex`, and can be used to coordinate with Ruby
+/// threads. Unlike [`std::sync::Mutex`] it does not own the data it protects,
+/// and while waiting to lock it other Ruby threads can run.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Mutex(NonZeroValue);
+
+impl Mutex {
+    /// Return `Some(Mutex)` if `val` is a `Mutex`, `None` otherwise.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        let class = memoize!(RClass: get_ruby!().class_thread().const_get("Mutex").unwrap());
+        unsafe {
+            val.is_kind_of(*class)
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Create a new unlocked `Mutex`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::thread::Mutex;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let mutex = Mutex::new();
+    /// assert!(!mutex.is_locked());
+    /// ```
+    pub fn new() -> Self {
+        get_ruby!().mutex_new()
+    }
+
+    /// Lock `self`, waiting for it to become available if it is locked by
+    /// another thread.
+    ///
+    /// Returns `Err` if `self` is already locked by the current thread.
+    pub fn lock(self) -> Result<(), Error> {
+        unsafe { protect(|| Value::new(rb_mutex_lock(self.as_rb_value())))? };
+        Ok(())
+    }
+
+    /// Attempt to lock `self` without waiting.
+    ///
+    /// Returns whether the lock was acquired.
+    pub fn try_lock(self) -> bool {
+        unsafe { Value::new(rb_mutex_trylock(self.as_rb_value())).to_bool() }
+    }
+
+    /// Unlock `self`.
+    ///
+    /// Returns `Err` if `self` is not locked by the current thread.
+    pub fn unlock(self) -> Result<(), Error> {
+        unsafe { protect(|| Value::new(rb_mutex_unlock(self.as_rb_value())))? };
+        Ok(())
+    }
+
+    /// Returns whether `self` is currently locked by any thread.
+    pub fn is_locked(self) -> bool {
+        unsafe { Value::new(rb_mutex_locked_p(self.as_rb_value())).to_bool() }
+    }
+
+    /// Returns whether `self` is locked by the current thread.
+    pub fn is_owned(self) -> Result<bool, Error> {
+        self.funcall("owned?", ())
+    }
+
+    /// Lock `self`, call `func`, then unlock `self`.
+    ///
+    /// `self` will be unlocked even if `func` panics, the panic will then be
+    /// propagated to the caller of this function.
+    ///
+    /// Returns `Err` if `self` is already locked by the current thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::thread::Mutex;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let mutex = Mutex::new();
+    /// let res = mutex.synchronize(|| {
+    ///     assert!(mutex.is_locked());
+    ///     42
+    /// });
+    /// assert_eq!(res.unwrap(), 42);
+    /// assert!(!mutex.is_locked());
+    /// ```
+    pub fn synchronize<F, R>(self, func: F) -> Result<R, Error>
+    where
+        F: FnOnce() -> R,
+    {
+        self.lock()?;
+        let res = catch_unwind(AssertUnwindSafe(func));
+        let unlocked = self.unlock();
+        match res {
+            Ok(v) => unlocked.map(|_| v),
+            Err(e) => resume_unwind(e),
+        }
+    }
+
+    /// Unlock `self` and sleep, either until woken by another thread or until
+    /// `timeout` has passed, and then relock `self`.
+    ///
+    /// Returns `Err` if `self` is not locked by the current thread.
+    pub fn sleep(self, timeout: Option<Duration>) -> Result<(), Error> {
+        match timeout {
+            Some(t) => self.funcall::<_, _, Value>("sleep", (t.as_secs_f64(),))?,
+            None => self.funcall::<_, _, Value>("sleep", (QNIL,))?,
+        };
+        Ok(())
+    }
+}
+
+impl Default for Mutex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for Mutex {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for Mutex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Mutex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for Mutex {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<Mutex> for Value {
+    fn from(val: Mutex) -> Self {
+        *val
+    }
+}
+
+impl Object for Mutex {}
+
+unsafe impl private::ReprValue for Mutex {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for Mutex {}
+
+impl TryConvert for Mutex {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Mutex", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
@@ -1,17 +1,21 @@
 use std::{ffi::CString, mem::transmute, ops::Deref};
 
 use rb_sys::{
-    rb_define_singleton_method, rb_extend_object, rb_ivar_get, rb_ivar_set, rb_singleton_class,
+    rb_define_singleton_method, rb_extend_object, rb_ivar_defined, rb_ivar_get, rb_ivar_set,
+    rb_obj_instance_variables, rb_singleton_class,
 };
 
 use crate::{
+    block::Proc,
     class::RClass,
     debug_assert_value,
     error::{protect, Error},
-    method::Method,
+    method::{BlockReturn, Method},
     module::RModule,
+    r_array::RArray,
+    symbol::Symbol,
     try_convert::TryConvert,
-    value::{Id, Value, QNIL},
+    value::{Id, StaticSymbol, Value, QNIL},
 };
 
 /// Functions available all non-immediate values.
@@ -40,9 +44,56 @@ pub trait Object: Deref<Target = Value> + Copy {
         Ok(())
     }
 
+    /// Define a singleton method in `self`'s scope, implemented by a Rust
+    /// closure.
+    ///
+    /// Unlike [`define_singleton_method`](Object::define_singleton_method),
+    /// `func` may capture variables. The closure is kept alive for as long as
+    /// the method is defined.
+    ///
+    /// `func` will receive the method's receiver as its first argument,
+    /// followed by the arguments the method was called with. It may return
+    /// any `R` or `Result<R, Error>` where `R` implements `Into<Value>`.
+    /// Returning `Err(Error)` will raise the error as a Ruby exception.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Object, RObject};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let obj: RObject = eval("Object.new").unwrap();
+    /// let mut count = 0;
+    /// obj.define_singleton_method_fn("increment", move |_rb_self, _args| {
+    ///     count += 1;
+    ///     count
+    /// })
+    /// .unwrap();
+    ///
+    /// let res: bool = eval!("obj.increment; obj.increment == 2", obj).unwrap();
+    /// assert!(res);
+    /// ```
+    fn define_singleton_method_fn<T, F, R>(self, name: T, func: F) -> Result<(), Error>
+    where
+        T: Into<Id>,
+        F: 'static + Send + FnMut(Value, &[Value]) -> R,
+        R: BlockReturn,
+    {
+        debug_assert_value!(self);
+        let name = StaticSymbol::from(name.into());
+        self.singleton_class()?.funcall_with_block::<_, _, Value>(
+            "define_method",
+            (name,),
+            Proc::from_fn_with_self(func),
+        )?;
+        Ok(())
+    }
+
     /// Get the value for the instance variable `name` within `self`'s scope.
     ///
-    /// Note, the `@` is part of the name.
+    /// Note, the `@` is part of the name. `name` may be a
+    /// [`LazyId`](crate::value::LazyId) to avoid looking up the name on every
+    /// call.
     fn ivar_get<T, U>(self, name: T) -> Result<U, Error>
     where
         T: Into<Id>,
@@ -56,7 +107,9 @@ pub trait Object: Deref<Target = Value> + Copy {
 
     /// Set the value for the instance variable `name` within `self`'s scope.
     ///
-    /// Note, the `@` is part of the name.
+    /// Note, the `@` is part of the name. `name` may be a
+    /// [`LazyId`](crate::value::LazyId) to avoid looking up the name on every
+    /// call.
     fn ivar_set<T, U>(self, name: T, value: U) -> Result<(), Error>
     where
         T: Into<Id>,
@@ -77,6 +130,88 @@ pub trait Object: Deref<Target = Value> + Copy {
         Ok(())
     }
 
+    /// Returns whether the instance variable `name` is defined for `self`.
+    ///
+    /// Note, the `@` is part of the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Object, RObject};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let obj: RObject = eval("Object.new").unwrap();
+    /// assert!(!obj.ivar_defined("@example"));
+    /// obj.ivar_set("@example", 42).unwrap();
+    /// assert!(obj.ivar_defined("@example"));
+    /// ```
+    fn ivar_defined<T>(self, name: T) -> bool
+    where
+        T: Into<Id>,
+    {
+        debug_assert_value!(self);
+        let id = name.into();
+        unsafe { Value::new(rb_ivar_defined(self.as_rb_value(), id.as_rb_id())).to_bool() }
+    }
+
+    /// Remove the instance variable `name` from `self`, returning its value.
+    ///
+    /// Note, the `@` is part of the name.
+    ///
+    /// Returns `Err` if the instance variable is not defined, `self` is
+    /// frozen, or the value fails to convert to `U`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Object, RObject};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let obj: RObject = eval("Object.new").unwrap();
+    /// obj.ivar_set("@example", 42).unwrap();
+    /// assert_eq!(obj.remove_ivar::<_, i64>("@example").unwrap(), 42);
+    /// assert!(!obj.ivar_defined("@example"));
+    /// assert!(obj.remove_ivar::<_, i64>("@example").is_err());
+    /// ```
+    fn remove_ivar<T, U>(self, name: T) -> Result<U, Error>
+    where
+        T: Into<Id>,
+        U: TryConvert,
+    {
+        debug_assert_value!(self);
+        let id = name.into();
+        self.funcall("remove_instance_variable", (StaticSymbol::from(id),))
+    }
+
+    /// Returns the names of the instance variables defined for `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Object, RObject};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let obj: RObject = eval("Object.new").unwrap();
+    /// obj.ivar_set("@a", 1).unwrap();
+    /// obj.ivar_set("@b", 2).unwrap();
+    /// let names = obj
+    ///     .instance_variables()
+    ///     .iter()
+    ///     .map(|s| s.name().unwrap())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, ["@a", "@b"]);
+    /// ```
+    fn instance_variables(self) -> Vec<Symbol> {
+        debug_assert_value!(self);
+        unsafe {
+            RArray::from_rb_value_unchecked(rb_obj_instance_variables(self.as_rb_value()))
+                .as_slice()
+                .iter()
+                .map(|v| Symbol::from_value(*v).unwrap())
+                .collect()
+        }
+    }
+
     /// Finds or creates the singleton class of `self`.
     ///
     /// Returns `Err` if `self` can not have a singleton class.
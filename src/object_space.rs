@@ -0,0 +1,425 @@
+//! Functions for working with Ruby's `ObjectSpace`, for diagnostics and
+//! debugging, and types for holding weak references to Ruby objects.
+
+use std::{fmt, ops::Deref};
+
+use crate::{
+    class::{self, RClass},
+    enumerator::Enumerator,
+    error::Error,
+    exception,
+    integer::Integer,
+    into_value::IntoValue,
+    module::{Module, RModule},
+    object::Object,
+    ruby_handle::RubyHandle,
+    try_convert::TryConvert,
+    value::{private, NonZeroValue, ReprValue, Value},
+};
+
+impl RubyHandle {
+    fn module_object_space(&self) -> Result<RModule, Error> {
+        class::object().const_get("ObjectSpace")
+    }
+
+    pub fn object_space_each_object<T>(&self, module: T) -> Result<Enumerator, Error>
+    where
+        T: Module,
+    {
+        self.module_object_space()?
+            .funcall("each_object", (*module,))
+    }
+
+    pub fn object_space_memsize_of<T>(&self, val: T) -> Result<usize, Error>
+    where
+        T: ReprValue,
+    {
+        self.require("objspace")?;
+        self.module_object_space()?
+            .funcall("memsize_of", (val.to_value(),))
+    }
+
+    pub fn object_space_id2ref(&self, id: Integer) -> Result<Value, Error> {
+        self.module_object_space()?.funcall("_id2ref", (id,))
+    }
+}
+
+/// Returns an [`Enumerator`] over all living objects that are an instance of
+/// `module`, or one of its descendants.
+///
+/// This is equivalent to calling `ObjectSpace.each_object(module)` without a
+/// block.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_class, eval, object_space, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let class = define_class("Widget", Default::default()).unwrap();
+/// let _widgets: Value = eval("$widgets = [Widget.new, Widget.new]").unwrap();
+///
+/// let count = object_space::each_object(class).unwrap().count();
+/// assert_eq!(count, 2);
+/// ```
+pub fn each_object<T>(module: T) -> Result<Enumerator, Error>
+where
+    T: Module,
+{
+    get_ruby!().object_space_each_object(module)
+}
+
+/// Returns the memory usage of `val` in bytes, as reported by
+/// `ObjectSpace.memsize_of`.
+///
+/// The result is an estimate, and may not include memory allocated by
+/// extensions or memory shared with other objects.
+///
+/// This will require Ruby's `objspace` library if it has not already been
+/// loaded.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{object_space, RString};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let small = object_space::memsize_of(RString::new("")).unwrap();
+/// let large = object_space::memsize_of(RString::new(&"x".repeat(4096))).unwrap();
+/// assert!(large > small);
+/// ```
+pub fn memsize_of<T>(val: T) -> Result<usize, Error>
+where
+    T: ReprValue,
+{
+    get_ruby!().object_space_memsize_of(val)
+}
+
+/// Returns the object for the object id `id`.
+///
+/// This is equivalent to calling `ObjectSpace._id2ref`. Returns `Err` if
+/// `id` does not refer to a living object.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{object_space, RString};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let s = RString::new("example");
+/// let id = s.object_id();
+/// let found = object_space::id2ref(id).unwrap();
+/// assert!(found.equal(s).unwrap());
+/// ```
+pub fn id2ref(id: Integer) -> Result<Value, Error> {
+    get_ruby!().object_space_id2ref(id)
+}
+
+fn weak_map_class() -> Result<RClass, Error> {
+    class::object()
+        .const_get::<_, RModule>("ObjectSpace")?
+        .const_get("WeakMap")
+}
+
+fn weak_ref_class() -> Result<RClass, Error> {
+    class::object().const_get("WeakRef")
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's
+/// `ObjectSpace::WeakMap` class.
+///
+/// A `WeakMap` holds references to its keys and values that do not prevent
+/// them being garbage collected. Once either is collected the entry is
+/// removed from the map. This makes it useful for caches associating data
+/// with Ruby objects, without keeping those objects alive.
+///
+/// Note that keys are compared by identity, not with `#eql?`/`#hash`.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{object_space::WeakMap, RString};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let map = WeakMap::new().unwrap();
+/// let key = RString::new("key");
+/// map.aset(key, RString::new("value")).unwrap();
+///
+/// assert_eq!(map.get(key).unwrap().unwrap().to_string(), "value");
+/// assert!(map.get(RString::new("key")).unwrap().is_none());
+/// ```
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct WeakMap(NonZeroValue);
+
+impl WeakMap {
+    /// Return `Some(WeakMap)` if `val` is an `ObjectSpace::WeakMap`, `None`
+    /// otherwise.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            weak_map_class().ok().and_then(|c| {
+                val.is_kind_of(c)
+                    .then(|| Self(NonZeroValue::new_unchecked(val)))
+            })
+        }
+    }
+
+    /// Create a new empty `WeakMap`.
+    pub fn new() -> Result<Self, Error> {
+        weak_map_class()?
+            .new_instance(())
+            .map(|v| unsafe { Self(NonZeroValue::new_unchecked(v)) })
+    }
+
+    /// Associate `val` with `key`.
+    ///
+    /// Neither `key` or `val` will be prevented from being garbage collected.
+    pub fn aset<K, V>(self, key: K, val: V) -> Result<(), Error>
+    where
+        K: Into<Value>,
+        V: Into<Value>,
+    {
+        self.funcall::<_, _, Value>("[]=", (key.into(), val.into()))
+            .map(|_| ())
+    }
+
+    /// Return the value associated with `key`.
+    ///
+    /// Returns `Ok(None)` if there is no entry for `key`, or the value has
+    /// been garbage collected.
+    pub fn get<K>(self, key: K) -> Result<Option<Value>, Error>
+    where
+        K: Into<Value>,
+    {
+        let key = key.into();
+        if !self.contains_key(key)? {
+            return Ok(None);
+        }
+        self.funcall("[]", (key,)).map(Some)
+    }
+
+    /// Returns whether there is an entry for `key`.
+    pub fn contains_key<K>(self, key: K) -> Result<bool, Error>
+    where
+        K: Into<Value>,
+    {
+        self.funcall("key?", (key.into(),))
+    }
+
+    /// Return the number of entries in the map.
+    ///
+    /// Entries that have been garbage collected, but not yet removed from
+    /// the map, may be included in the count.
+    pub fn len(self) -> Result<usize, Error> {
+        self.funcall("size", ())
+    }
+
+    /// Returns whether the map has no entries.
+    pub fn is_empty(self) -> Result<bool, Error> {
+        self.len().map(|l| l == 0)
+    }
+}
+
+impl Deref for WeakMap {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for WeakMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for WeakMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for WeakMap {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<WeakMap> for Value {
+    fn from(val: WeakMap) -> Self {
+        *val
+    }
+}
+
+impl Object for WeakMap {}
+
+unsafe impl private::ReprValue for WeakMap {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for WeakMap {}
+
+impl TryConvert for WeakMap {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!(
+                    "no implicit conversion of {} into ObjectSpace::WeakMap",
+                    unsafe { val.classname() },
+                ),
+            )
+        })
+    }
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's `WeakRef`
+/// class.
+///
+/// A `WeakRef` holds a reference to an object that does not prevent that
+/// object being garbage collected.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{object_space::WeakRef, RString};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let s = RString::new("example");
+/// let weak = WeakRef::new(s).unwrap();
+/// assert!(weak.is_alive().unwrap());
+/// assert_eq!(weak.get().unwrap().unwrap().to_string(), "example");
+/// ```
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct WeakRef(NonZeroValue);
+
+impl WeakRef {
+    /// Return `Some(WeakRef)` if `val` is a `WeakRef`, `None` otherwise.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            weak_ref_class().ok().and_then(|c| {
+                val.is_kind_of(c)
+                    .then(|| Self(NonZeroValue::new_unchecked(val)))
+            })
+        }
+    }
+
+    /// Create a new `WeakRef` referencing `val`.
+    ///
+    /// This will require Ruby's `weakref` library if it has not already been
+    /// loaded.
+    pub fn new<T>(val: T) -> Result<Self, Error>
+    where
+        T: ReprValue,
+    {
+        crate::require("weakref")?;
+        weak_ref_class()?
+            .new_instance((val.to_value(),))
+            .map(|v| unsafe { Self(NonZeroValue::new_unchecked(v)) })
+    }
+
+    /// Return the referenced object, or `None` if it has been garbage
+    /// collected.
+    pub fn get(self) -> Result<Option<Value>, Error> {
+        match self.funcall("__getobj__", ()) {
+            Ok(v) => Ok(Some(v)),
+            Err(e) if e.is_kind_of(weak_ref_class()?.const_get::<_, RClass>("RefError")?) => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns whether the referenced object is still alive.
+    pub fn is_alive(self) -> Result<bool, Error> {
+        self.funcall::<_, _, Value>("weakref_alive?", ())
+            .map(|v| v.to_bool())
+    }
+}
+
+impl Deref for WeakRef {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for WeakRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for WeakRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for WeakRef {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<WeakRef> for Value {
+    fn from(val: WeakRef) -> Self {
+        *val
+    }
+}
+
+impl Object for WeakRef {}
+
+unsafe impl private::ReprValue for WeakRef {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for WeakRef {}
+
+impl TryConvert for WeakRef {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into WeakRef", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
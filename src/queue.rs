@@ -0,0 +1,482 @@
+use std::{fmt, ops::Deref};
+
+#[cfg(any(ruby_gte_3_2, docsrs))]
+use std::time::Duration;
+
+use crate::{
+    class::RClass,
+    error::Error,
+    exception,
+    into_value::IntoValue,
+    memoize,
+    module::Module,
+    object::Object,
+    ruby_handle::RubyHandle,
+    try_convert::TryConvert,
+    value::{private, NonZeroValue, ReprValue, Value},
+};
+#[cfg(any(ruby_gte_3_2, docsrs))]
+use crate::{r_hash::RHash, value::StaticSymbol};
+
+impl RubyHandle {
+    fn class_queue(&self) -> RClass {
+        *memoize!(RClass: self.class_thread().const_get("Queue").unwrap())
+    }
+
+    fn class_sized_queue(&self) -> RClass {
+        *memoize!(RClass: self.class_thread().const_get("SizedQueue").unwrap())
+    }
+
+    pub fn queue_new(&self) -> Queue {
+        let val = self.class_queue().new_instance(()).unwrap();
+        unsafe { Queue(NonZeroValue::new_unchecked(val)) }
+    }
+
+    pub fn sized_queue_new(&self, max: usize) -> Result<SizedQueue, Error> {
+        let val = self.class_sized_queue().new_instance((max,))?;
+        unsafe { Ok(SizedQueue(NonZeroValue::new_unchecked(val))) }
+    }
+}
+
+#[cfg(any(ruby_gte_3_2, docsrs))]
+fn timeout_kwargs(timeout: Duration) -> Result<RHash, Error> {
+    let kwargs = RHash::new();
+    kwargs.aset(StaticSymbol::new("timeout"), timeout.as_secs_f64())?;
+    Ok(kwargs)
+}
+
+/// Convert `Err(ThreadError)` (raised by Queue methods when called with
+/// `non_block = true`) to `Ok(None)`.
+fn non_block<T>(res: Result<T, Error>) -> Result<Option<T>, Error> {
+    match res {
+        Ok(v) => Ok(Some(v)),
+        Err(e) if e.is_kind_of(exception::thread_error()) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's Queue class.
+///
+/// This is Ruby's `Thread::Queue`, a first-in-first-out queue that can be
+/// used to pass values between Ruby threads.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{thread::Queue, Thread};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let queue = Queue::new();
+/// let producer = Thread::create(move || -> Result<(), magnus::Error> {
+///     for i in 0..3 {
+///         queue.push(i)?;
+///     }
+///     queue.close()
+/// })
+/// .unwrap();
+///
+/// let mut received = Vec::new();
+/// while let Some(i) = queue.pop::<Option<i64>>().unwrap() {
+///     received.push(i);
+/// }
+/// producer.join(None).unwrap();
+/// assert_eq!(received, vec![0, 1, 2]);
+/// ```
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Queue(NonZeroValue);
+
+impl Queue {
+    /// Return `Some(Queue)` if `val` is a `Queue`, `None` otherwise.
+    ///
+    /// A [`SizedQueue`] is also a `Queue`.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(get_ruby!().class_queue())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    /// Create a new empty `Queue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn new() -> Self {
+        get_ruby!().queue_new()
+    }
+
+    /// Add `val` to the end of `self`.
+    ///
+    /// Returns `Err` if `self` has been closed.
+    pub fn push<T>(self, val: T) -> Result<(), Error>
+    where
+        T: Into<Value>,
+    {
+        self.funcall::<_, _, Value>("push", (val.into(),))?;
+        Ok(())
+    }
+
+    /// Remove and return the value at the front of `self`, waiting for a
+    /// value to be pushed if `self` is empty.
+    ///
+    /// If `self` is empty and has been closed returns `nil` (e.g. `Ok(None)`
+    /// for `T = Option<_>`).
+    pub fn pop<T>(self) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.funcall("pop", ())
+    }
+
+    /// Remove and return the value at the front of `self`, without waiting.
+    ///
+    /// Returns `Ok(None)` if `self` is empty.
+    pub fn try_pop<T>(self) -> Result<Option<T>, Error>
+    where
+        T: TryConvert,
+    {
+        non_block(self.funcall("pop", (true,)))
+    }
+
+    /// Remove and return the value at the front of `self`, waiting at most
+    /// `timeout` for a value to be pushed if `self` is empty.
+    ///
+    /// Returns `nil` (e.g. `Ok(None)` for `T = Option<_>`) if the timeout
+    /// expires or `self` is empty and has been closed.
+    ///
+    /// Requires Ruby 3.2 or later.
+    #[cfg(any(ruby_gte_3_2, docsrs))]
+    #[cfg_attr(docsrs, doc(cfg(ruby_gte_3_2)))]
+    pub fn pop_timeout<T>(self, timeout: Duration) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.funcall_kw("pop", (timeout_kwargs(timeout)?,))
+    }
+
+    /// Close `self`, so no more values can be pushed.
+    ///
+    /// Any threads waiting to pop from `self` will be woken and receive
+    /// `nil`.
+    pub fn close(self) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("close", ())?;
+        Ok(())
+    }
+
+    /// Returns whether `self` has been closed.
+    pub fn is_closed(self) -> Result<bool, Error> {
+        self.funcall("closed?", ())
+    }
+
+    /// Return the number of values in `self`.
+    pub fn len(self) -> Result<usize, Error> {
+        self.funcall("length", ())
+    }
+
+    /// Returns whether `self` contains no values.
+    pub fn is_empty(self) -> Result<bool, Error> {
+        self.funcall("empty?", ())
+    }
+
+    /// Remove all values from `self`.
+    pub fn clear(self) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("clear", ())?;
+        Ok(())
+    }
+
+    /// Return the number of threads waiting on `self`.
+    pub fn num_waiting(self) -> Result<usize, Error> {
+        self.funcall("num_waiting", ())
+    }
+}
+
+impl Default for Queue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for Queue {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for Queue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Queue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for Queue {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<Queue> for Value {
+    fn from(val: Queue) -> Self {
+        *val
+    }
+}
+
+impl Object for Queue {}
+
+unsafe impl private::ReprValue for Queue {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for Queue {}
+
+impl TryConvert for Queue {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Queue", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's SizedQueue
+/// class.
+///
+/// This is Ruby's `Thread::SizedQueue`, a [`Queue`] with a maximum size.
+/// Pushing to a full `SizedQueue` will wait until space is available.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::thread::SizedQueue;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let queue = SizedQueue::new(1).unwrap();
+/// assert!(queue.try_push(1).unwrap());
+/// assert!(!queue.try_push(2).unwrap());
+/// assert_eq!(queue.pop::<i64>().unwrap(), 1);
+/// ```
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct SizedQueue(NonZeroValue);
+
+impl SizedQueue {
+    /// Return `Some(SizedQueue)` if `val` is a `SizedQueue`, `None`
+    /// otherwise.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(get_ruby!().class_sized_queue())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    /// Create a new empty `SizedQueue` that can hold at most `max` values.
+    ///
+    /// Returns `Err` if `max` is 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn new(max: usize) -> Result<Self, Error> {
+        get_ruby!().sized_queue_new(max)
+    }
+
+    /// Return `self` as a [`Queue`].
+    pub fn as_queue(self) -> Queue {
+        Queue(self.0)
+    }
+
+    /// Add `val` to the end of `self`, waiting for space if `self` is full.
+    ///
+    /// Returns `Err` if `self` has been closed.
+    pub fn push<T>(self, val: T) -> Result<(), Error>
+    where
+        T: Into<Value>,
+    {
+        self.as_queue().push(val)
+    }
+
+    /// Add `val` to the end of `self`, without waiting.
+    ///
+    /// Returns `Ok(false)` if `self` is full, or `Err` if `self` has been
+    /// closed.
+    pub fn try_push<T>(self, val: T) -> Result<bool, Error>
+    where
+        T: Into<Value>,
+    {
+        non_block(self.funcall::<_, _, Value>("push", (val.into(), true))).map(|v| v.is_some())
+    }
+
+    /// Add `val` to the end of `self`, waiting at most `timeout` for space if
+    /// `self` is full.
+    ///
+    /// Returns `Ok(false)` if the timeout expires, or `Err` if `self` has
+    /// been closed.
+    ///
+    /// Requires Ruby 3.2 or later.
+    #[cfg(any(ruby_gte_3_2, docsrs))]
+    #[cfg_attr(docsrs, doc(cfg(ruby_gte_3_2)))]
+    pub fn push_timeout<T>(self, val: T, timeout: Duration) -> Result<bool, Error>
+    where
+        T: Into<Value>,
+    {
+        let res: Value = self.funcall_kw("push", (val.into(), timeout_kwargs(timeout)?))?;
+        Ok(!res.is_nil())
+    }
+
+    /// Remove and return the value at the front of `self`, waiting for a
+    /// value to be pushed if `self` is empty.
+    ///
+    /// See [`Queue::pop`].
+    pub fn pop<T>(self) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.as_queue().pop()
+    }
+
+    /// Remove and return the value at the front of `self`, without waiting.
+    ///
+    /// See [`Queue::try_pop`].
+    pub fn try_pop<T>(self) -> Result<Option<T>, Error>
+    where
+        T: TryConvert,
+    {
+        self.as_queue().try_pop()
+    }
+
+    /// Remove and return the value at the front of `self`, waiting at most
+    /// `timeout` for a value to be pushed if `self` is empty.
+    ///
+    /// See [`Queue::pop_timeout`].
+    ///
+    /// Requires Ruby 3.2 or later.
+    #[cfg(any(ruby_gte_3_2, docsrs))]
+    #[cfg_attr(docsrs, doc(cfg(ruby_gte_3_2)))]
+    pub fn pop_timeout<T>(self, timeout: Duration) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.as_queue().pop_timeout(timeout)
+    }
+
+    /// Return the maximum number of values `self` can hold.
+    pub fn max(self) -> Result<usize, Error> {
+        self.funcall("max", ())
+    }
+
+    /// Set the maximum number of values `self` can hold.
+    ///
+    /// Returns `Err` if `max` is 0.
+    pub fn set_max(self, max: usize) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("max=", (max,))?;
+        Ok(())
+    }
+
+    /// Close `self`, so no more values can be pushed.
+    ///
+    /// See [`Queue::close`].
+    pub fn close(self) -> Result<(), Error> {
+        self.as_queue().close()
+    }
+
+    /// Return the number of values in `self`.
+    pub fn len(self) -> Result<usize, Error> {
+        self.as_queue().len()
+    }
+
+    /// Returns whether `self` contains no values.
+    pub fn is_empty(self) -> Result<bool, Error> {
+        self.as_queue().is_empty()
+    }
+}
+
+impl Deref for SizedQueue {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for SizedQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for SizedQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl From<SizedQueue> for Queue {
+    fn from(val: SizedQueue) -> Self {
+        val.as_queue()
+    }
+}
+
+impl IntoValue for SizedQueue {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<SizedQueue> for Value {
+    fn from(val: SizedQueue) -> Self {
+        *val
+    }
+}
+
+impl Object for SizedQueue {}
+
+unsafe impl private::ReprValue for SizedQueue {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for SizedQueue {}
+
+impl TryConvert for SizedQueue {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into SizedQueue", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
@@ -1636,6 +1636,363 @@ where
     }
 }
 
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12> IntoValue
+    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12)
+where
+    T0: Into<Value>,
+    T1: Into<Value>,
+    T2: Into<Value>,
+    T3: Into<Value>,
+    T4: Into<Value>,
+    T5: Into<Value>,
+    T6: Into<Value>,
+    T7: Into<Value>,
+    T8: Into<Value>,
+    T9: Into<Value>,
+    T10: Into<Value>,
+    T11: Into<Value>,
+    T12: Into<Value>,
+{
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let ary = [
+            self.0.into(),
+            self.1.into(),
+            self.2.into(),
+            self.3.into(),
+            self.4.into(),
+            self.5.into(),
+            self.6.into(),
+            self.7.into(),
+            self.8.into(),
+            self.9.into(),
+            self.10.into(),
+            self.11.into(),
+            self.12.into(),
+        ];
+        handle.ary_new_from_values(&ary).into()
+    }
+}
+
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12>
+    From<(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12)> for Value
+where
+    T0: Into<Value>,
+    T1: Into<Value>,
+    T2: Into<Value>,
+    T3: Into<Value>,
+    T4: Into<Value>,
+    T5: Into<Value>,
+    T6: Into<Value>,
+    T7: Into<Value>,
+    T8: Into<Value>,
+    T9: Into<Value>,
+    T10: Into<Value>,
+    T11: Into<Value>,
+    T12: Into<Value>,
+{
+    fn from(val: (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12)) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13> IntoValue
+    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13)
+where
+    T0: Into<Value>,
+    T1: Into<Value>,
+    T2: Into<Value>,
+    T3: Into<Value>,
+    T4: Into<Value>,
+    T5: Into<Value>,
+    T6: Into<Value>,
+    T7: Into<Value>,
+    T8: Into<Value>,
+    T9: Into<Value>,
+    T10: Into<Value>,
+    T11: Into<Value>,
+    T12: Into<Value>,
+    T13: Into<Value>,
+{
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let ary = [
+            self.0.into(),
+            self.1.into(),
+            self.2.into(),
+            self.3.into(),
+            self.4.into(),
+            self.5.into(),
+            self.6.into(),
+            self.7.into(),
+            self.8.into(),
+            self.9.into(),
+            self.10.into(),
+            self.11.into(),
+            self.12.into(),
+            self.13.into(),
+        ];
+        handle.ary_new_from_values(&ary).into()
+    }
+}
+
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13>
+    From<(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13)> for Value
+where
+    T0: Into<Value>,
+    T1: Into<Value>,
+    T2: Into<Value>,
+    T3: Into<Value>,
+    T4: Into<Value>,
+    T5: Into<Value>,
+    T6: Into<Value>,
+    T7: Into<Value>,
+    T8: Into<Value>,
+    T9: Into<Value>,
+    T10: Into<Value>,
+    T11: Into<Value>,
+    T12: Into<Value>,
+    T13: Into<Value>,
+{
+    fn from(val: (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13)) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14> IntoValue
+    for (
+        T0,
+        T1,
+        T2,
+        T3,
+        T4,
+        T5,
+        T6,
+        T7,
+        T8,
+        T9,
+        T10,
+        T11,
+        T12,
+        T13,
+        T14,
+    )
+where
+    T0: Into<Value>,
+    T1: Into<Value>,
+    T2: Into<Value>,
+    T3: Into<Value>,
+    T4: Into<Value>,
+    T5: Into<Value>,
+    T6: Into<Value>,
+    T7: Into<Value>,
+    T8: Into<Value>,
+    T9: Into<Value>,
+    T10: Into<Value>,
+    T11: Into<Value>,
+    T12: Into<Value>,
+    T13: Into<Value>,
+    T14: Into<Value>,
+{
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let ary = [
+            self.0.into(),
+            self.1.into(),
+            self.2.into(),
+            self.3.into(),
+            self.4.into(),
+            self.5.into(),
+            self.6.into(),
+            self.7.into(),
+            self.8.into(),
+            self.9.into(),
+            self.10.into(),
+            self.11.into(),
+            self.12.into(),
+            self.13.into(),
+            self.14.into(),
+        ];
+        handle.ary_new_from_values(&ary).into()
+    }
+}
+
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14>
+    From<(
+        T0,
+        T1,
+        T2,
+        T3,
+        T4,
+        T5,
+        T6,
+        T7,
+        T8,
+        T9,
+        T10,
+        T11,
+        T12,
+        T13,
+        T14,
+    )> for Value
+where
+    T0: Into<Value>,
+    T1: Into<Value>,
+    T2: Into<Value>,
+    T3: Into<Value>,
+    T4: Into<Value>,
+    T5: Into<Value>,
+    T6: Into<Value>,
+    T7: Into<Value>,
+    T8: Into<Value>,
+    T9: Into<Value>,
+    T10: Into<Value>,
+    T11: Into<Value>,
+    T12: Into<Value>,
+    T13: Into<Value>,
+    T14: Into<Value>,
+{
+    fn from(
+        val: (
+            T0,
+            T1,
+            T2,
+            T3,
+            T4,
+            T5,
+            T6,
+            T7,
+            T8,
+            T9,
+            T10,
+            T11,
+            T12,
+            T13,
+            T14,
+        ),
+    ) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15> IntoValue
+    for (
+        T0,
+        T1,
+        T2,
+        T3,
+        T4,
+        T5,
+        T6,
+        T7,
+        T8,
+        T9,
+        T10,
+        T11,
+        T12,
+        T13,
+        T14,
+        T15,
+    )
+where
+    T0: Into<Value>,
+    T1: Into<Value>,
+    T2: Into<Value>,
+    T3: Into<Value>,
+    T4: Into<Value>,
+    T5: Into<Value>,
+    T6: Into<Value>,
+    T7: Into<Value>,
+    T8: Into<Value>,
+    T9: Into<Value>,
+    T10: Into<Value>,
+    T11: Into<Value>,
+    T12: Into<Value>,
+    T13: Into<Value>,
+    T14: Into<Value>,
+    T15: Into<Value>,
+{
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let ary = [
+            self.0.into(),
+            self.1.into(),
+            self.2.into(),
+            self.3.into(),
+            self.4.into(),
+            self.5.into(),
+            self.6.into(),
+            self.7.into(),
+            self.8.into(),
+            self.9.into(),
+            self.10.into(),
+            self.11.into(),
+            self.12.into(),
+            self.13.into(),
+            self.14.into(),
+            self.15.into(),
+        ];
+        handle.ary_new_from_values(&ary).into()
+    }
+}
+
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15>
+    From<(
+        T0,
+        T1,
+        T2,
+        T3,
+        T4,
+        T5,
+        T6,
+        T7,
+        T8,
+        T9,
+        T10,
+        T11,
+        T12,
+        T13,
+        T14,
+        T15,
+    )> for Value
+where
+    T0: Into<Value>,
+    T1: Into<Value>,
+    T2: Into<Value>,
+    T3: Into<Value>,
+    T4: Into<Value>,
+    T5: Into<Value>,
+    T6: Into<Value>,
+    T7: Into<Value>,
+    T8: Into<Value>,
+    T9: Into<Value>,
+    T10: Into<Value>,
+    T11: Into<Value>,
+    T12: Into<Value>,
+    T13: Into<Value>,
+    T14: Into<Value>,
+    T15: Into<Value>,
+{
+    fn from(
+        val: (
+            T0,
+            T1,
+            T2,
+            T3,
+            T4,
+            T5,
+            T6,
+            T7,
+            T8,
+            T9,
+            T10,
+            T11,
+            T12,
+            T13,
+            T14,
+            T15,
+        ),
+    ) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
 impl<T> IntoValue for Vec<T>
 where
     T: Into<Value>,
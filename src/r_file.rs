@@ -1,4 +1,10 @@
 use std::{fmt, ops::Deref};
+#[cfg(unix)]
+use std::{
+    fs::File,
+    mem::ManuallyDrop,
+    os::unix::io::{FromRawFd, IntoRawFd, RawFd},
+};
 
 use rb_sys::ruby_value_type;
 
@@ -12,6 +18,23 @@ use crate::{
     value::{private, NonZeroValue, ReprValue, Value},
 };
 
+impl RubyHandle {
+    #[cfg(unix)]
+    pub unsafe fn file_from_raw_fd(&self, fd: RawFd, mode: &str) -> Result<RFile, Error> {
+        self.class_file().funcall("for_fd", (fd, mode))
+    }
+
+    #[cfg(unix)]
+    pub fn file_from_file(&self, file: File, mode: &str) -> Result<RFile, Error> {
+        let fd = file.into_raw_fd();
+        unsafe { self.file_from_raw_fd(fd, mode) }.map_err(|e| {
+            // Ruby didn't take ownership, so close the file
+            drop(unsafe { File::from_raw_fd(fd) });
+            e
+        })
+    }
+}
+
 /// A Value pointer to a RFile struct, Ruby's internal representation of files.
 ///
 /// All [`Value`] methods should be available on this type through [`Deref`],
@@ -29,6 +52,114 @@ impl RFile {
                 .then(|| Self(NonZeroValue::new_unchecked(val)))
         }
     }
+
+    /// Create a new Ruby `File` taking ownership of the file descriptor `fd`.
+    ///
+    /// `mode` is a Ruby mode string, such as `"r"`, `"w"`, `"r+"`, or `"a"`,
+    /// and must be compatible with the mode `fd` was opened with.
+    ///
+    /// # Safety
+    ///
+    /// `fd` must be an open file descriptor, and ownership of it is
+    /// transferred to Ruby, which will close it when the `File` is closed or
+    /// garbage collected. It must not be used or closed elsewhere after this
+    /// call succeeds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub unsafe fn from_raw_fd(fd: RawFd, mode: &str) -> Result<Self, Error> {
+        get_ruby!().file_from_raw_fd(fd, mode)
+    }
+
+    /// Create a new Ruby `File` from a Rust [`File`], without reopening the
+    /// path.
+    ///
+    /// `mode` is a Ruby mode string, such as `"r"`, `"w"`, `"r+"`, or `"a"`,
+    /// and must be compatible with the mode `file` was opened with. Ruby takes
+    /// ownership of the underlying file descriptor, which is closed if this
+    /// returns `Err`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write;
+    ///
+    /// use magnus::{eval, RFile};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let path = std::env::temp_dir().join("magnus_rfile_from_file.txt");
+    /// let mut file = std::fs::File::create(&path).unwrap();
+    /// file.write_all(b"example").unwrap();
+    ///
+    /// let file = std::fs::File::open(&path).unwrap();
+    /// let rfile = RFile::from_file(file, "r").unwrap();
+    /// let res: String = eval!("rfile.read", rfile).unwrap();
+    /// assert_eq!(res, "example");
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn from_file(file: File, mode: &str) -> Result<Self, Error> {
+        get_ruby!().file_from_file(file, mode)
+    }
+
+    /// Return the file descriptor backing `self`.
+    ///
+    /// The file descriptor remains owned by Ruby, and will be closed when
+    /// `self` is closed or garbage collected. Returns `Err` if `self` is
+    /// closed.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn as_raw_fd(self) -> Result<RawFd, Error> {
+        self.funcall("fileno", ())
+    }
+
+    /// Return a Rust [`File`] for a duplicate of the file descriptor backing
+    /// `self`.
+    ///
+    /// The returned `File` shares the file offset and status flags with
+    /// `self`, but can be closed independently. Returns `Err` if `self` is
+    /// closed, or the file descriptor can not be duplicated.
+    ///
+    /// Any data buffered by Ruby is not visible through the returned `File`,
+    /// flush `self` before writing with the `File`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Read;
+    ///
+    /// use magnus::{eval, RFile};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let path = std::env::temp_dir().join("magnus_rfile_try_clone.txt");
+    /// std::fs::write(&path, "example").unwrap();
+    /// let path = path.to_str().unwrap();
+    ///
+    /// let rfile: RFile = eval!("File.open(path)", path).unwrap();
+    /// let mut file = rfile.try_clone_to_std().unwrap();
+    /// // the duplicate remains open after the Ruby File is closed
+    /// let _: magnus::Value = rfile.funcall("close", ()).unwrap();
+    /// let mut buf = String::new();
+    /// file.read_to_string(&mut buf).unwrap();
+    /// assert_eq!(buf, "example");
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn try_clone_to_std(self) -> Result<File, Error> {
+        let fd = self.as_raw_fd()?;
+        // fd is owned by Ruby, so must not be closed by dropping this File
+        let file = ManuallyDrop::new(unsafe { File::from_raw_fd(fd) });
+        Ok(file.try_clone()?)
+    }
 }
 
 impl Deref for RFile {
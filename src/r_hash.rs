@@ -28,6 +28,7 @@ use crate::{
     into_value::IntoValue,
     object::Object,
     ruby_handle::RubyHandle,
+    scan_args::Maybe,
     try_convert::{TryConvert, TryConvertOwned},
     value::{private, Fixnum, NonZeroValue, ReprValue, Value, QNIL, QUNDEF},
 };
@@ -378,6 +379,44 @@ impl RHash {
         .and_then(|v| (!v.is_undef()).then(|| v))
     }
 
+    /// Return the value for `key`, converting it to `U`, distinguishing a
+    /// missing key from one with a `nil` value.
+    ///
+    /// Returns [`Maybe::Absent`] if `key` is missing, [`Maybe::Nil`] if the
+    /// value is `nil`, and [`Maybe::Present`] otherwise. Any default value or
+    /// proc set on the hash is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, scan_args::Maybe, RHash};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let hash = eval::<RHash>(r#"{"answer" => 42, "question" => nil}"#).unwrap();
+    /// assert_eq!(hash.lookup_maybe::<_, i64>("answer").unwrap(), Maybe::Present(42));
+    /// assert_eq!(hash.lookup_maybe::<_, i64>("question").unwrap(), Maybe::Nil);
+    /// assert_eq!(hash.lookup_maybe::<_, i64>("missing").unwrap(), Maybe::Absent);
+    /// ```
+    pub fn lookup_maybe<T, U>(self, key: T) -> Result<Maybe<U>, Error>
+    where
+        T: Into<Value>,
+        U: TryConvert,
+    {
+        let key = key.into();
+        let val = protect(|| unsafe {
+            Value::new(rb_hash_lookup2(
+                self.as_rb_value(),
+                key.as_rb_value(),
+                QUNDEF.to_value().as_rb_value(),
+            ))
+        })?;
+        if val.is_undef() {
+            Ok(Maybe::Absent)
+        } else {
+            val.try_convert()
+        }
+    }
+
     /// Return the value for `key`, converting it to `U`.
     ///
     /// Returns `Err` if `key` is missing. See also [`aref`](RHash::aref),
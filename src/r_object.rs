@@ -90,3 +90,152 @@ impl TryConvert for RObject {
         })
     }
 }
+
+/// Generate a wrapper type for instances of a Ruby class, such as a class
+/// defined by a Ruby library or a host application.
+///
+/// The generated type is a `Copy` newtype around a [`Value`] that is checked
+/// to be an instance of the class (or a subclass) on conversion, with the
+/// same representation as Magnus' own wrapper types. It implements
+/// [`Deref<Target = Value>`](Deref), [`Object`],
+/// [`ReprValue`](crate::value::ReprValue), [`TryConvert`](crate::TryConvert),
+/// `Display`, and `Debug`, can be converted into a [`Value`] anywhere the
+/// built-in types can, and has the functions:
+///
+/// * `class() -> RClass` returning the wrapped class, looked up by name the
+///   first time it is called.
+/// * `from_value(val: Value) -> Option<Self>` checking if `val` is an instance
+///   of the class.
+///
+/// # Panics
+///
+/// The functions of the generated type will panic if the class does not
+/// exist when first used.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, ruby_class, Error, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// eval::<Value>(
+///     r#"
+///     module Shop
+///       class Item
+///         attr_reader :name
+///
+///         def initialize(name)
+///           @name = name
+///         end
+///       end
+///     end
+///     "#,
+/// )
+/// .unwrap();
+///
+/// ruby_class!(
+///     /// An instance of `Shop::Item`.
+///     pub ShopItem, "Shop::Item"
+/// );
+///
+/// fn item_name(item: ShopItem) -> Result<String, Error> {
+///     item.funcall("name", ())
+/// }
+///
+/// let item: ShopItem = eval(r#"Shop::Item.new("apple")"#).unwrap();
+/// assert_eq!(item_name(item).unwrap(), "apple");
+///
+/// assert!(eval::<ShopItem>(r#""apple""#).is_err());
+/// ```
+#[macro_export]
+macro_rules! ruby_class {
+    ($(#[$meta:meta])* $vis:vis $name:ident, $class:expr) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy)]
+        #[repr(transparent)]
+        $vis struct $name($crate::__private::NonZeroValue);
+
+        impl $name {
+            /// Returns the Ruby class wrapped by this type.
+            #[allow(dead_code)]
+            pub fn class() -> $crate::RClass {
+                *$crate::memoize!($crate::RClass: {
+                    $crate::RClass::default()
+                        .funcall("const_get", ($class,))
+                        .unwrap()
+                })
+            }
+
+            /// Return `Some(Self)` if `val` is an instance of the wrapped
+            /// class, `None` otherwise.
+            #[allow(dead_code)]
+            pub fn from_value(val: $crate::Value) -> Option<Self> {
+                unsafe {
+                    val.is_kind_of(Self::class())
+                        .then(|| Self($crate::__private::NonZeroValue::new_unchecked(val)))
+                }
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = $crate::Value;
+
+            fn deref(&self) -> &Self::Target {
+                self.0.get_ref()
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Display::fmt(self.0.get_ref(), f)
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Debug::fmt(self.0.get_ref(), f)
+            }
+        }
+
+        impl $crate::__private::IntoValue for $name {
+            fn into_value(self, _: &$crate::__private::RubyHandle) -> $crate::Value {
+                self.0.get()
+            }
+        }
+
+        impl From<$name> for $crate::Value {
+            fn from(val: $name) -> Self {
+                val.0.get()
+            }
+        }
+
+        unsafe impl $crate::__private::ReprValue for $name {
+            fn to_value(self) -> $crate::Value {
+                self.0.get()
+            }
+
+            unsafe fn from_value_unchecked(val: $crate::Value) -> Self {
+                Self($crate::__private::NonZeroValue::new_unchecked(val))
+            }
+        }
+
+        impl $crate::value::ReprValue for $name {}
+
+        impl $crate::Object for $name {}
+
+        impl $crate::TryConvert for $name {
+            fn try_convert(val: $crate::Value) -> Result<Self, $crate::Error> {
+                Self::from_value(val).ok_or_else(|| {
+                    $crate::Error::new(
+                        $crate::exception::type_error(),
+                        format!(
+                            "no implicit conversion of {} into {}",
+                            unsafe { val.classname() },
+                            $class,
+                        ),
+                    )
+                })
+            }
+        }
+    };
+}
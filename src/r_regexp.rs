@@ -16,7 +16,7 @@ use crate::{
     exception,
     into_value::IntoValue,
     ruby_handle::RubyHandle,
-    try_convert::TryConvert,
+    try_convert::{Coerced, TryConvert},
     value::{private, NonZeroValue, ReprValue, Value},
     RString,
 };
@@ -199,6 +199,26 @@ impl TryConvert for RRegexp {
     }
 }
 
+impl TryConvert for Coerced<RRegexp> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        if let Some(re) = RRegexp::from_value(val) {
+            return Ok(Self(re));
+        }
+        get_ruby!()
+            .class_regexp()
+            .funcall::<_, _, Option<RRegexp>>("try_convert", (val,))?
+            .map(Self)
+            .ok_or_else(|| {
+                Error::new(
+                    exception::type_error(),
+                    format!("no implicit conversion of {} into Regexp", unsafe {
+                        val.classname()
+                    },),
+                )
+            })
+    }
+}
+
 /// Options for creating [`RRegexp`].
 #[derive(Clone, Copy)]
 pub struct Opts(c_uint);
@@ -0,0 +1,319 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt,
+    hash::Hash,
+    iter::FromIterator,
+    ops::Deref,
+};
+
+use crate::{
+    class::RClass,
+    error::Error,
+    exception,
+    into_value::IntoValue,
+    memoize,
+    module::Module,
+    object::Object,
+    r_array::RArray,
+    ruby_handle::RubyHandle,
+    try_convert::{TryConvert, TryConvertOwned},
+    value::{private, NonZeroValue, ReprValue, Value},
+};
+
+impl RubyHandle {
+    fn class_set(&self) -> RClass {
+        *memoize!(RClass: {
+            self.require("set").unwrap();
+            self.class_object().const_get("Set").unwrap()
+        })
+    }
+
+    pub fn set_new(&self) -> RSet {
+        let val = self.class_set().new_instance(()).unwrap();
+        unsafe { RSet(NonZeroValue::new_unchecked(val)) }
+    }
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's `Set` class.
+///
+/// `Set` is part of Ruby's standard library, and will be loaded if it isn't
+/// already (it is loaded by default from Ruby 3.2).
+///
+/// `RSet` can be converted to and from Rust's [`HashSet`] and [`BTreeSet`].
+/// Unlike converting to and from an `Array`, duplicate elements are
+/// removed, and membership tests use the element's `#hash` and `#eql?`
+/// methods rather than a linear search.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+///
+/// use magnus::{eval, RSet};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let set: RSet = eval("Set[1, 2, 3]").unwrap();
+/// assert!(set.contains(2).unwrap());
+/// assert!(!set.contains(4).unwrap());
+///
+/// let res: HashSet<i64> = set.to_hash_set().unwrap();
+/// assert_eq!(res, [1, 2, 3].iter().copied().collect());
+/// ```
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct RSet(NonZeroValue);
+
+impl RSet {
+    /// Return `Some(RSet)` if `val` is a `Set`, `None` otherwise.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(get_ruby!().class_set())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    /// Create a new empty `RSet`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn new() -> Self {
+        get_ruby!().set_new()
+    }
+
+    /// Return the number of elements in the set.
+    pub fn len(self) -> usize {
+        self.funcall("size", ()).unwrap()
+    }
+
+    /// Return whether the set is empty.
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return whether the set contains `val`.
+    ///
+    /// Returns `Err` if `val`'s `#hash` or `#eql?` methods raise an error.
+    pub fn contains<T>(self, val: T) -> Result<bool, Error>
+    where
+        T: Into<Value>,
+    {
+        self.funcall("include?", (val.into(),))
+    }
+
+    /// Add `val` to the set.
+    ///
+    /// Returns `Err` if the set is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::RSet;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let set = RSet::new();
+    /// set.add("a").unwrap();
+    /// set.add("a").unwrap();
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    pub fn add<T>(self, val: T) -> Result<(), Error>
+    where
+        T: Into<Value>,
+    {
+        self.funcall::<_, _, Value>("add", (val.into(),))
+            .map(|_| ())
+    }
+
+    /// Remove `val` from the set, returning whether it was present.
+    ///
+    /// Returns `Err` if the set is frozen.
+    pub fn delete<T>(self, val: T) -> Result<bool, Error>
+    where
+        T: Into<Value>,
+    {
+        self.funcall::<_, _, Option<Value>>("delete?", (val.into(),))
+            .map(|v| v.is_some())
+    }
+
+    /// Remove all elements from the set.
+    ///
+    /// Returns `Err` if the set is frozen.
+    pub fn clear(self) -> Result<(), Error> {
+        self.funcall::<_, _, Value>("clear", ()).map(|_| ())
+    }
+
+    /// Return the elements of the set as a new `RArray`.
+    pub fn to_r_array(self) -> RArray {
+        self.funcall("to_a", ()).unwrap()
+    }
+
+    /// Convert `self` to a Rust [`HashSet`].
+    ///
+    /// Errors if any element can not be converted to `T`.
+    pub fn to_hash_set<T>(self) -> Result<HashSet<T>, Error>
+    where
+        T: TryConvertOwned + Eq + Hash,
+    {
+        Ok(self.to_r_array().to_vec()?.into_iter().collect())
+    }
+
+    /// Convert `self` to a Rust [`BTreeSet`].
+    ///
+    /// Errors if any element can not be converted to `T`.
+    pub fn to_btree_set<T>(self) -> Result<BTreeSet<T>, Error>
+    where
+        T: TryConvertOwned + Ord,
+    {
+        Ok(self.to_r_array().to_vec()?.into_iter().collect())
+    }
+}
+
+impl Default for RSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for RSet {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for RSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for RSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl<T> FromIterator<T> for RSet
+where
+    T: Into<Value>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let set = RSet::new();
+        for val in iter {
+            // new set, can't be frozen
+            set.add(val).unwrap();
+        }
+        set
+    }
+}
+
+impl IntoValue for RSet {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<RSet> for Value {
+    fn from(val: RSet) -> Self {
+        *val
+    }
+}
+
+impl Object for RSet {}
+
+unsafe impl private::ReprValue for RSet {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for RSet {}
+
+impl TryConvert for RSet {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Set", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
+
+impl<T> IntoValue for HashSet<T>
+where
+    T: Into<Value>,
+{
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let set = handle.set_new();
+        for val in self {
+            set.add(val).unwrap();
+        }
+        *set
+    }
+}
+
+impl<T> From<HashSet<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(val: HashSet<T>) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+impl<T> IntoValue for BTreeSet<T>
+where
+    T: Into<Value>,
+{
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let set = handle.set_new();
+        for val in self {
+            set.add(val).unwrap();
+        }
+        *set
+    }
+}
+
+impl<T> From<BTreeSet<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(val: BTreeSet<T>) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+impl<T> TryConvert for HashSet<T>
+where
+    T: TryConvertOwned + Eq + Hash,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        RSet::try_convert(val)?.to_hash_set()
+    }
+}
+impl<T> TryConvertOwned for HashSet<T> where T: TryConvertOwned + Eq + Hash {}
+
+impl<T> TryConvert for BTreeSet<T>
+where
+    T: TryConvertOwned + Ord,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        RSet::try_convert(val)?.to_btree_set()
+    }
+}
+impl<T> TryConvertOwned for BTreeSet<T> where T: TryConvertOwned + Ord {}
@@ -3,7 +3,8 @@
 use std::{
     borrow::Cow,
     cmp::Ordering,
-    ffi::CString,
+    convert::TryFrom,
+    ffi::{CStr, CString, OsStr, OsString},
     fmt, io,
     iter::Iterator,
     mem::transmute,
@@ -74,6 +75,11 @@ impl RubyHandle {
         unsafe { RString::from_rb_value_unchecked(rb_str_new(ptr as *const c_char, len as c_long)) }
     }
 
+    #[cfg(feature = "uuid")]
+    pub fn str_from_uuid_bytes(&self, uuid: uuid::Uuid) -> RString {
+        self.str_from_slice(uuid.as_bytes())
+    }
+
     pub fn enc_str_new<T, E>(&self, s: T, enc: E) -> RString
     where
         T: AsRef<[u8]>,
@@ -96,6 +102,22 @@ impl RubyHandle {
         self.str_new(c.encode_utf8(&mut buf[..]))
     }
 
+    #[cfg(unix)]
+    pub fn str_from_os_str(&self, s: &OsStr) -> Result<RString, Error> {
+        use std::os::unix::ffi::OsStrExt;
+        Ok(self.str_from_slice(s.as_bytes()))
+    }
+
+    #[cfg(not(unix))]
+    pub fn str_from_os_str(&self, s: &OsStr) -> Result<RString, Error> {
+        s.to_str().map(|s| self.str_new(s)).ok_or_else(|| {
+            Error::new(
+                self.exception_encoding_error(),
+                format!("{:?} is not valid Unicode", s),
+            )
+        })
+    }
+
     pub fn chr<T>(&self, code: u32, enc: T) -> Result<RString, Error>
     where
         T: Into<RbEncoding>,
@@ -232,6 +254,9 @@ impl RString {
     /// Create a new Ruby string from the Rust slice `s`.
     ///
     /// The encoding of the Ruby string will be set to ASCII-8BIT (aka BINARY).
+    /// The bytes are copied as-is, and the string will never be transcoded by
+    /// Ruby, so is suitable for arbitrary binary data. See
+    /// [`RString::to_binary_bytes`] for the inverse.
     ///
     /// # Panics
     ///
@@ -251,6 +276,36 @@ impl RString {
         get_ruby!().str_from_slice(s)
     }
 
+    /// Create a new Ruby string containing the 16 byte binary form of `uuid`.
+    ///
+    /// The encoding of the Ruby string will be set to ASCII-8BIT (aka BINARY),
+    /// which is how [`TryConvert`] for `uuid::Uuid` recognises the binary
+    /// form. Converting a `uuid::Uuid` with [`From`]/[`Into`] produces the
+    /// hyphenated text form.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RString};
+    /// use uuid::Uuid;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let uuid = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+    /// let s = RString::from_uuid_bytes(uuid);
+    /// let res: bool = eval!("s.bytesize == 16 && s.encoding == Encoding::BINARY", s).unwrap();
+    /// assert!(res);
+    /// assert_eq!(s.try_convert::<Uuid>().unwrap(), uuid);
+    /// ```
+    #[cfg(feature = "uuid")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+    pub fn from_uuid_bytes(uuid: uuid::Uuid) -> Self {
+        get_ruby!().str_from_uuid_bytes(uuid)
+    }
+
     /// Create a new Ruby string from the value `s` with the encoding `enc`.
     ///
     /// # Panics
@@ -894,6 +949,28 @@ impl RString {
             .map_err(|e| Error::new(exception::encoding_error(), format!("{}", e)))
     }
 
+    /// Returns a copy of the bytes of `self`.
+    ///
+    /// The bytes are returned exactly as stored by Ruby, whatever the
+    /// string's encoding, and are never transcoded. See
+    /// [`RString::from_slice`] for the inverse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RString};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let s: RString = eval(r#""\xFF\x00\x80".b"#).unwrap();
+    /// assert_eq!(s.to_binary_bytes(), [255, 0, 128]);
+    ///
+    /// let s: RString = eval(r#""caf\u00e9".encode("ISO-8859-1")"#).unwrap();
+    /// assert_eq!(s.to_binary_bytes(), [99, 97, 102, 233]);
+    /// ```
+    pub fn to_binary_bytes(self) -> Vec<u8> {
+        unsafe { self.as_slice().to_vec() }
+    }
+
     /// Converts `self` to a [`char`]. Errors if the string is more than one
     /// character or can not be encoded as UTF-8.
     ///
@@ -1523,6 +1600,50 @@ impl From<&Path> for Value {
     }
 }
 
+/// On Unix the bytes of `val` are copied as-is, elsewhere this returns `Err`
+/// if `val` is not valid Unicode.
+impl TryFrom<&OsStr> for RString {
+    type Error = Error;
+
+    fn try_from(val: &OsStr) -> Result<Self, Self::Error> {
+        get_ruby!().str_from_os_str(val)
+    }
+}
+
+/// On Unix the bytes of `val` are copied as-is, elsewhere this returns `Err`
+/// if `val` is not valid Unicode.
+impl TryFrom<OsString> for RString {
+    type Error = Error;
+
+    fn try_from(val: OsString) -> Result<Self, Self::Error> {
+        get_ruby!().str_from_os_str(&val)
+    }
+}
+
+impl IntoValue for &CStr {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        handle.str_from_slice(self.to_bytes()).into()
+    }
+}
+
+impl From<&CStr> for Value {
+    fn from(val: &CStr) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+impl IntoValue for CString {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        handle.into_value(self.as_c_str())
+    }
+}
+
+impl From<CString> for Value {
+    fn from(val: CString) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
 impl IntoValue for PathBuf {
     fn into_value(self, handle: &RubyHandle) -> Value {
         handle.into_value(self.as_path())
@@ -1535,6 +1656,37 @@ impl From<PathBuf> for Value {
     }
 }
 
+#[cfg(feature = "uuid")]
+impl IntoValue for uuid::Uuid {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let mut buf = uuid::Uuid::encode_buffer();
+        handle
+            .str_new(self.hyphenated().encode_lower(&mut buf))
+            .into()
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for Value {
+    fn from(val: uuid::Uuid) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl IntoValue for bytes::Bytes {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        handle.str_from_slice(&self).into()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<bytes::Bytes> for Value {
+    fn from(val: bytes::Bytes) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
 impl Object for RString {}
 
 unsafe impl private::ReprValue for RString {
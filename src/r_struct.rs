@@ -286,9 +286,31 @@ impl RubyHandle {
 
 /// Define a Ruby Struct class.
 ///
+/// If `name` is `Some` the class will be defined as a constant under `Struct`,
+/// otherwise the class will be anonymous.
+///
+/// `members` may be a tuple of up to 12 `&str`s, or a slice of `&str`s.
+///
 /// # Panics
 ///
 /// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{r_struct::{define_struct, RStruct}, Class};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let point = define_struct(Some("Point"), ("x", "y")).unwrap();
+/// let obj = RStruct::from_value(point.new_instance((1, 2)).unwrap()).unwrap();
+/// assert_eq!(obj.get::<i64>(0).unwrap(), 1);
+/// assert_eq!(obj.getmember::<_, i64>("y").unwrap(), 2);
+///
+/// let fields = vec!["r", "g", "b"];
+/// let color = define_struct(None, fields.as_slice()).unwrap();
+/// let obj = RStruct::from_value(color.new_instance((255, 0, 0)).unwrap()).unwrap();
+/// assert_eq!(obj.members().unwrap(), ["r", "g", "b"]);
+/// ```
 pub fn define_struct<T>(name: Option<&str>, members: T) -> Result<RClass, Error>
 where
     T: StructMembers,
@@ -305,6 +327,20 @@ mod private {
 }
 use private::StructMembers;
 
+impl StructMembers for &[&str] {
+    fn define(self, name: Option<&str>) -> Result<RClass, Error> {
+        // rb_struct_define is variadic, so can't be called with a member
+        // count only known at runtime. Struct.new with a name as the first
+        // argument is equivalent.
+        let mut args = Vec::with_capacity(self.len() + 1);
+        if let Some(name) = name {
+            args.push(Value::from(name));
+        }
+        args.extend(self.iter().map(|m| Value::from(Symbol::new(m))));
+        crate::class::struct_class().funcall("new", args.as_slice())
+    }
+}
+
 impl StructMembers for (&str,) {
     fn define(self, name: Option<&str>) -> Result<RClass, Error> {
         let name = name.map(|n| CString::new(n).unwrap());
@@ -0,0 +1,216 @@
+//! Types and functions for working with Ruby's Ractor class.
+
+use std::{fmt, ops::Deref};
+
+use rb_sys::{rb_ext_ractor_safe, rb_ractor_make_shareable};
+
+use crate::{
+    class::RClass,
+    error::{protect, Error},
+    exception,
+    into_value::IntoValue,
+    memoize,
+    module::Module,
+    object::Object,
+    ruby_handle::RubyHandle,
+    try_convert::TryConvert,
+    value::{private, NonZeroValue, ReprValue, Value},
+};
+
+impl RubyHandle {
+    pub fn mark_ractor_safe(&self) {
+        unsafe { rb_ext_ractor_safe(true) };
+    }
+
+    fn class_ractor(&self) -> RClass {
+        *memoize!(RClass: self.class_object().const_get("Ractor").unwrap())
+    }
+
+    pub fn ractor_current(&self) -> Ractor {
+        self.class_ractor().funcall("current", ()).unwrap()
+    }
+
+    pub fn ractor_make_shareable<T>(&self, val: T) -> Result<T, Error>
+    where
+        T: ReprValue,
+    {
+        protect(|| unsafe { Value::new(rb_ractor_make_shareable(val.to_value().as_rb_value())) })?;
+        Ok(val)
+    }
+
+    pub fn ractor_is_shareable<T>(&self, val: T) -> bool
+    where
+        T: Into<Value>,
+    {
+        self.class_ractor()
+            .funcall("shareable?", (val.into(),))
+            .unwrap()
+    }
+}
+
+/// Mark the extension as safe to use from Ractors other than the main Ractor.
+///
+/// This should be called from the extension's `init` function, before any
+/// methods are defined. Methods defined by an extension that has not been
+/// marked as Ractor safe will raise an error if called from a non-main
+/// Ractor.
+///
+/// Only mark an extension as Ractor safe if all the methods it defines are
+/// safe to call concurrently from multiple Ractors. Rust types wrapped as
+/// Ruby objects should be [`Sync`], and must not allow any global state (such
+/// as a static `Value`) to be shared between Ractors.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// #[magnus::init]
+/// fn init() {
+///     magnus::mark_ractor_safe();
+///     // define methods...
+/// }
+/// ```
+pub fn mark_ractor_safe() {
+    get_ruby!().mark_ractor_safe()
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's Ractor class.
+///
+/// Ractors allow Ruby code to run in parallel. Objects can only be shared
+/// between Ractors if they are 'shareable', which generally means they are
+/// deeply frozen. Ractors are only available in Ruby 3.0 and later.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Ractor(NonZeroValue);
+
+impl Ractor {
+    /// Return `Some(Ractor)` if `val` is a `Ractor`, `None` otherwise.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(get_ruby!().class_ractor())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    /// Return the currently running Ractor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn current() -> Self {
+        get_ruby!().ractor_current()
+    }
+
+    /// Make `val` shareable between Ractors, by deeply freezing it.
+    ///
+    /// Returns `Err` if `val` (or any object it references) can not be made
+    /// shareable, such as a [`Proc`](crate::block::Proc) that references
+    /// unshareable objects.
+    ///
+    /// Wrapped Rust types can only be made shareable if their [`DataType`]
+    /// was built with
+    /// [`frozen_shareable`](crate::typed_data::DataTypeBuilder::frozen_shareable).
+    ///
+    /// [`DataType`]: crate::typed_data::DataType
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray, Ractor};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary: RArray = eval(r#"["a", "b"]"#).unwrap();
+    /// assert!(!Ractor::is_shareable(ary));
+    /// let ary = Ractor::make_shareable(ary).unwrap();
+    /// assert!(Ractor::is_shareable(ary));
+    /// assert!(ary.is_frozen());
+    /// ```
+    pub fn make_shareable<T>(val: T) -> Result<T, Error>
+    where
+        T: ReprValue,
+    {
+        get_ruby!().ractor_make_shareable(val)
+    }
+
+    /// Returns whether `val` can be shared between Ractors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn is_shareable<T>(val: T) -> bool
+    where
+        T: Into<Value>,
+    {
+        get_ruby!().ractor_is_shareable(val)
+    }
+}
+
+impl Deref for Ractor {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for Ractor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Ractor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for Ractor {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<Ractor> for Value {
+    fn from(val: Ractor) -> Self {
+        *val
+    }
+}
+
+impl Object for Ractor {}
+
+unsafe impl private::ReprValue for Ractor {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for Ractor {}
+
+impl TryConvert for Ractor {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Ractor", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
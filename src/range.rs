@@ -12,13 +12,14 @@ use rb_sys::{rb_range_beg_len, rb_range_new};
 
 use crate::{
     class,
+    enumerator::Enumerator,
     error::{protect, Error},
     exception,
     into_value::IntoValue,
     object::Object,
     r_struct::RStruct,
     ruby_handle::RubyHandle,
-    try_convert::TryConvert,
+    try_convert::{TryConvert, TryConvertOwned},
     value::{private, ReprValue, Value, QNIL},
 };
 
@@ -158,6 +159,79 @@ impl Range {
         self.0.get::<Value>(2).unwrap().to_bool()
     }
 
+    /// Return the number of elements in the range, converting it to a `T`.
+    ///
+    /// The size of an endless numeric range is `Float::INFINITY`, and the
+    /// size of a non-numeric range, such as `"a".."z"`, is `nil` (`T` can be
+    /// an [`Option`] to accept this).
+    ///
+    /// On Ruby 3.3+ this returns `Err` with a `TypeError` for ranges that
+    /// can't be iterated, such as those with a `Float` or `nil` beginning.
+    /// Earlier versions return the numeric size of these ranges, e.g. `2` for
+    /// `0.5..2`, or `Float::INFINITY` for `..2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::eval;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let range = eval::<magnus::Range>("2..7").unwrap();
+    /// assert_eq!(range.size::<usize>().unwrap(), 6);
+    ///
+    /// let range = eval::<magnus::Range>("2...7").unwrap();
+    /// assert_eq!(range.size::<usize>().unwrap(), 5);
+    ///
+    /// let range = eval::<magnus::Range>("2..").unwrap();
+    /// assert_eq!(range.size::<f64>().unwrap(), f64::INFINITY);
+    /// ```
+    pub fn size<T>(self) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.funcall("size", ())
+    }
+
+    /// Return an iterator over the range, stepping by `step`, with the values
+    /// converted to `T`.
+    ///
+    /// Returns `Err` if the range can not be iterated with `step`, for
+    /// example a `step` of `0`, or a beginless range. Iterating an endless
+    /// range will continue forever, so the iterator should be limited with
+    /// [`Iterator::take`] or similar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::eval;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let range = eval::<magnus::Range>("1..10").unwrap();
+    /// let res = range
+    ///     .to_seq_iter::<i64, _>(3)
+    ///     .unwrap()
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// assert_eq!(res, vec![1, 4, 7, 10]);
+    ///
+    /// let range = eval::<magnus::Range>("0.0..").unwrap();
+    /// let res = range
+    ///     .to_seq_iter::<f64, _>(0.5)
+    ///     .unwrap()
+    ///     .take(3)
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// assert_eq!(res, vec![0.0, 0.5, 1.0]);
+    /// ```
+    pub fn to_seq_iter<T, S>(self, step: S) -> Result<impl Iterator<Item = Result<T, Error>>, Error>
+    where
+        T: TryConvert,
+        S: Into<Value>,
+    {
+        let enumerator: Enumerator = self.funcall("step", (step.into(),))?;
+        Ok(enumerator.map(|res| res.and_then(|v| v.try_convert())))
+    }
+
     /// Given a total `length`, returns a beginning index and length of the
     /// range within that total length.
     ///
@@ -393,6 +467,99 @@ where
     }
 }
 
+/// Error for a Ruby `Range` that doesn't have the shape of the Rust range
+/// type `expected`.
+fn mismatch(range: Range, expected: &str) -> Error {
+    Error::new(
+        exception::type_error(),
+        format!("can't convert {} into {}", range.inspect(), expected),
+    )
+}
+
+/// Return the beginning and end of `val`, or `None` for beginless/endless
+/// ranges, along with whether the end is excluded.
+fn parts(val: Value) -> Result<(Range, Option<Value>, Option<Value>, bool), Error> {
+    let range = Range::try_convert(val)?;
+    let beg: Option<Value> = range.beg()?;
+    let end: Option<Value> = range.end()?;
+    Ok((range, beg, end, range.excl()))
+}
+
+impl<T> TryConvert for StdRange<T>
+where
+    T: TryConvert,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        match parts(val)? {
+            (_, Some(beg), Some(end), true) => Ok(beg.try_convert()?..end.try_convert()?),
+            (range, ..) => Err(mismatch(range, "std::ops::Range")),
+        }
+    }
+}
+impl<T> TryConvertOwned for StdRange<T> where T: TryConvertOwned {}
+
+impl<T> TryConvert for RangeFrom<T>
+where
+    T: TryConvert,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        match parts(val)? {
+            (_, Some(beg), None, _) => Ok(beg.try_convert()?..),
+            (range, ..) => Err(mismatch(range, "std::ops::RangeFrom")),
+        }
+    }
+}
+impl<T> TryConvertOwned for RangeFrom<T> where T: TryConvertOwned {}
+
+impl TryConvert for RangeFull {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        match parts(val)? {
+            (_, None, None, _) => Ok(..),
+            (range, ..) => Err(mismatch(range, "std::ops::RangeFull")),
+        }
+    }
+}
+impl TryConvertOwned for RangeFull {}
+
+impl<T> TryConvert for RangeInclusive<T>
+where
+    T: TryConvert,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        match parts(val)? {
+            (_, Some(beg), Some(end), false) => Ok(beg.try_convert()?..=end.try_convert()?),
+            (range, ..) => Err(mismatch(range, "std::ops::RangeInclusive")),
+        }
+    }
+}
+impl<T> TryConvertOwned for RangeInclusive<T> where T: TryConvertOwned {}
+
+impl<T> TryConvert for RangeTo<T>
+where
+    T: TryConvert,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        match parts(val)? {
+            (_, None, Some(end), true) => Ok(..end.try_convert()?),
+            (range, ..) => Err(mismatch(range, "std::ops::RangeTo")),
+        }
+    }
+}
+impl<T> TryConvertOwned for RangeTo<T> where T: TryConvertOwned {}
+
+impl<T> TryConvert for RangeToInclusive<T>
+where
+    T: TryConvert,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        match parts(val)? {
+            (_, None, Some(end), false) => Ok(..=end.try_convert()?),
+            (range, ..) => Err(mismatch(range, "std::ops::RangeToInclusive")),
+        }
+    }
+}
+impl<T> TryConvertOwned for RangeToInclusive<T> where T: TryConvertOwned {}
+
 impl Object for Range {}
 
 unsafe impl private::ReprValue for Range {
@@ -26,16 +26,19 @@
 //! them with care.
 use std::panic::UnwindSafe;
 
-use rb_sys::{ID, VALUE};
+use rb_sys::{rb_data_type_t, ID, VALUE};
 
 use crate::{
     error::{self, raise, Error},
-    value::{Id, Value},
+    typed_data::DataType,
+    value::{private, Id, ReprValue, Value},
 };
 
-/// Converts from a [`Value`] to a raw [`VALUE`].
+/// Converts from a [`Value`], or any other Magnus type wrapping a Ruby
+/// object, to a raw [`VALUE`].
 pub trait AsRawValue {
-    /// Convert [`magnus::Value`](Value) to [`rb_sys::VALUE`](VALUE).
+    /// Convert [`magnus::Value`](Value), or a wrapper type such as
+    /// [`RString`](crate::RString), to [`rb_sys::VALUE`](VALUE).
     ///
     /// ```
     /// # let _cleanup = unsafe { magnus::embed::init() };
@@ -52,15 +55,22 @@ pub trait AsRawValue {
     fn as_raw(self) -> VALUE;
 }
 
-/// Converts from a raw [`VALUE`] to a [`Value`].
+/// Converts from a raw [`VALUE`] to a [`Value`], or any other Magnus type
+/// wrapping a Ruby object.
 pub trait FromRawValue {
-    /// Convert [`rb_sys::VALUE`](VALUE) to [`magnus::Value`](Value).
+    /// Convert [`rb_sys::VALUE`](VALUE) to [`magnus::Value`](Value), or a
+    /// wrapper type such as [`RString`](crate::RString).
+    ///
     /// # Safety
     ///
     /// You must only supply a valid [`VALUE`] obtained from [rb-sys](rb_sys) to
     /// this function. Using a invalid [`Value`] produced from this function will
     /// void all saftey guarantees provided by Magnus.
     ///
+    /// When converting to a wrapper type the [`VALUE`] must be an instance of
+    /// the wrapped Ruby type, e.g. a `String` for [`RString`](crate::RString).
+    /// This is not checked.
+    ///
     /// ```
     /// # let _cleanup = unsafe { magnus::embed::init() };
     ///
@@ -73,15 +83,21 @@ pub trait FromRawValue {
     unsafe fn from_raw(value: VALUE) -> Self;
 }
 
-impl AsRawValue for Value {
+impl<T> AsRawValue for T
+where
+    T: ReprValue,
+{
     fn as_raw(self) -> VALUE {
-        self.as_rb_value()
+        private::ReprValue::to_value(self).as_rb_value()
     }
 }
 
-impl FromRawValue for Value {
-    unsafe fn from_raw(val: VALUE) -> Value {
-        Value::new(val.into())
+impl<T> FromRawValue for T
+where
+    T: ReprValue,
+{
+    unsafe fn from_raw(val: VALUE) -> T {
+        <T as private::ReprValue>::from_value_unchecked(Value::new(val.into()))
     }
 }
 
@@ -139,6 +155,36 @@ impl FromRawId for Id {
     }
 }
 
+/// Trait to access the raw [`rb_data_type_t`] of a [`DataType`].
+pub trait AsRawDataType {
+    /// Return a pointer to the [`rb_data_type_t`] for use with rb-sys
+    /// functions such as `rb_data_typed_object_wrap` or `rb_check_typeddata`.
+    ///
+    /// The pointer is valid for as long as the `DataType` is.
+    ///
+    /// ```
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// use magnus::{rb_sys::AsRawDataType, TypedData};
+    ///
+    /// #[magnus::wrap(class = "Point")]
+    /// struct Point {
+    ///     x: isize,
+    ///     y: isize,
+    /// }
+    ///
+    /// let raw = <Point as TypedData>::data_type().as_raw();
+    /// assert!(!raw.is_null());
+    /// ```
+    fn as_raw(&self) -> *const rb_data_type_t;
+}
+
+impl AsRawDataType for DataType {
+    fn as_raw(&self) -> *const rb_data_type_t {
+        self.as_rb_data_type()
+    }
+}
+
 /// Calls the given closure, catching all cases of unwinding from Ruby
 /// returning them as an [`Error`].
 ///
@@ -1,4 +1,9 @@
-use std::{cell::RefCell, error::Error, fmt, marker::PhantomData};
+use std::{
+    cell::{Cell, RefCell},
+    error::Error,
+    fmt,
+    marker::PhantomData,
+};
 
 use rb_sys::ruby_native_thread_p;
 
@@ -19,7 +24,6 @@ pub enum RubyUnavailableError {
     /// Current thread is not a Ruby thread.
     NonRubyThread,
     /// GVL is not locked.
-    #[allow(dead_code)]
     GvlUnlocked,
 }
 
@@ -43,6 +47,13 @@ enum IsRubyThread {
 
 thread_local! {
     static IS_RUBY_THREAD: RefCell<IsRubyThread> = RefCell::new(IsRubyThread::Unknown);
+    static GVL_RELEASED: Cell<bool> = Cell::new(false);
+}
+
+/// Record whether the current thread has released the GVL, returning the
+/// previous state.
+pub(crate) fn set_gvl_released(released: bool) -> bool {
+    GVL_RELEASED.with(|cell| cell.replace(released))
 }
 
 /// A handle to access Ruby's API.
@@ -63,12 +74,16 @@ impl RubyHandle {
     ///
     /// If the Ruby API is not useable, returns `Err(RubyUnavailableError)`.
     //
-    // TODO This currently does not verify the GVL is held, so will erroneously
-    // return a handle when the GVL has been released.
+    // Only tracks the GVL being released by this library's own functions, so
+    // will erroneously return a handle if the GVL has been released by other
+    // means.
     pub fn get() -> Result<Self, RubyUnavailableError> {
         IS_RUBY_THREAD.with(|is_ruby_thread| {
             let x = *is_ruby_thread.borrow();
             match x {
+                IsRubyThread::Yes if GVL_RELEASED.with(Cell::get) => {
+                    Err(RubyUnavailableError::GvlUnlocked)
+                }
                 IsRubyThread::Yes => Ok(Self(PhantomData)),
                 IsRubyThread::Unknown => {
                     if unsafe { ruby_native_thread_p() } != 0 {
@@ -491,19 +491,42 @@ mod private {
         }
     }
 
-    impl<T0> ScanArgsOpt for (Option<T0>,)
+    pub trait ScanArgsOptArg: Sized {
+        fn from_opt_arg(val: Option<Value>) -> Result<Self, Error>;
+    }
+
+    impl<T> ScanArgsOptArg for Option<T>
     where
-        T0: TryConvert,
+        T: TryConvert,
+    {
+        fn from_opt_arg(val: Option<Value>) -> Result<Self, Error> {
+            val.filter(|v| !v.is_undef())
+                .map(|v| v.try_convert())
+                .transpose()
+        }
+    }
+
+    impl<T> ScanArgsOptArg for Maybe<T>
+    where
+        T: TryConvert,
+    {
+        fn from_opt_arg(val: Option<Value>) -> Result<Self, Error> {
+            match val.filter(|v| !v.is_undef()) {
+                Some(v) => v.try_convert(),
+                None => Ok(Maybe::Absent),
+            }
+        }
+    }
+
+    impl<T0> ScanArgsOpt for (T0,)
+    where
+        T0: ScanArgsOptArg,
     {
         const LEN: usize = 1;
 
         fn from_slice(vals: &[Value]) -> Result<Self, Error> {
             if vals.len() <= <Self as ScanArgsOpt>::LEN {
-                Ok((vals
-                    .get(0)
-                    .filter(|v| !v.is_undef())
-                    .map(|v| v.try_convert())
-                    .transpose()?,))
+                Ok((T0::from_opt_arg(vals.get(0).copied())?,))
             } else {
                 panic!(
                     "unexpected arguments, expected {} got {}",
@@ -514,24 +537,18 @@ mod private {
         }
     }
 
-    impl<T0, T1> ScanArgsOpt for (Option<T0>, Option<T1>)
+    impl<T0, T1> ScanArgsOpt for (T0, T1)
     where
-        T0: TryConvert,
-        T1: TryConvert,
+        T0: ScanArgsOptArg,
+        T1: ScanArgsOptArg,
     {
         const LEN: usize = 2;
 
         fn from_slice(vals: &[Value]) -> Result<Self, Error> {
             if vals.len() <= <Self as ScanArgsOpt>::LEN {
                 Ok((
-                    vals.get(0)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(1)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
+                    T0::from_opt_arg(vals.get(0).copied())?,
+                    T1::from_opt_arg(vals.get(1).copied())?,
                 ))
             } else {
                 panic!(
@@ -543,29 +560,20 @@ mod private {
         }
     }
 
-    impl<T0, T1, T2> ScanArgsOpt for (Option<T0>, Option<T1>, Option<T2>)
+    impl<T0, T1, T2> ScanArgsOpt for (T0, T1, T2)
     where
-        T0: TryConvert,
-        T1: TryConvert,
-        T2: TryConvert,
+        T0: ScanArgsOptArg,
+        T1: ScanArgsOptArg,
+        T2: ScanArgsOptArg,
     {
         const LEN: usize = 3;
 
         fn from_slice(vals: &[Value]) -> Result<Self, Error> {
             if vals.len() <= <Self as ScanArgsOpt>::LEN {
                 Ok((
-                    vals.get(0)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(1)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(2)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
+                    T0::from_opt_arg(vals.get(0).copied())?,
+                    T1::from_opt_arg(vals.get(1).copied())?,
+                    T2::from_opt_arg(vals.get(2).copied())?,
                 ))
             } else {
                 panic!(
@@ -577,34 +585,22 @@ mod private {
         }
     }
 
-    impl<T0, T1, T2, T3> ScanArgsOpt for (Option<T0>, Option<T1>, Option<T2>, Option<T3>)
+    impl<T0, T1, T2, T3> ScanArgsOpt for (T0, T1, T2, T3)
     where
-        T0: TryConvert,
-        T1: TryConvert,
-        T2: TryConvert,
-        T3: TryConvert,
+        T0: ScanArgsOptArg,
+        T1: ScanArgsOptArg,
+        T2: ScanArgsOptArg,
+        T3: ScanArgsOptArg,
     {
         const LEN: usize = 4;
 
         fn from_slice(vals: &[Value]) -> Result<Self, Error> {
             if vals.len() <= <Self as ScanArgsOpt>::LEN {
                 Ok((
-                    vals.get(0)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(1)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(2)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(3)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
+                    T0::from_opt_arg(vals.get(0).copied())?,
+                    T1::from_opt_arg(vals.get(1).copied())?,
+                    T2::from_opt_arg(vals.get(2).copied())?,
+                    T3::from_opt_arg(vals.get(3).copied())?,
                 ))
             } else {
                 panic!(
@@ -616,40 +612,24 @@ mod private {
         }
     }
 
-    impl<T0, T1, T2, T3, T4> ScanArgsOpt
-        for (Option<T0>, Option<T1>, Option<T2>, Option<T3>, Option<T4>)
+    impl<T0, T1, T2, T3, T4> ScanArgsOpt for (T0, T1, T2, T3, T4)
     where
-        T0: TryConvert,
-        T1: TryConvert,
-        T2: TryConvert,
-        T3: TryConvert,
-        T4: TryConvert,
+        T0: ScanArgsOptArg,
+        T1: ScanArgsOptArg,
+        T2: ScanArgsOptArg,
+        T3: ScanArgsOptArg,
+        T4: ScanArgsOptArg,
     {
         const LEN: usize = 5;
 
         fn from_slice(vals: &[Value]) -> Result<Self, Error> {
             if vals.len() <= <Self as ScanArgsOpt>::LEN {
                 Ok((
-                    vals.get(0)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(1)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(2)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(3)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(4)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
+                    T0::from_opt_arg(vals.get(0).copied())?,
+                    T1::from_opt_arg(vals.get(1).copied())?,
+                    T2::from_opt_arg(vals.get(2).copied())?,
+                    T3::from_opt_arg(vals.get(3).copied())?,
+                    T4::from_opt_arg(vals.get(4).copied())?,
                 ))
             } else {
                 panic!(
@@ -661,52 +641,26 @@ mod private {
         }
     }
 
-    impl<T0, T1, T2, T3, T4, T5> ScanArgsOpt
-        for (
-            Option<T0>,
-            Option<T1>,
-            Option<T2>,
-            Option<T3>,
-            Option<T4>,
-            Option<T5>,
-        )
+    impl<T0, T1, T2, T3, T4, T5> ScanArgsOpt for (T0, T1, T2, T3, T4, T5)
     where
-        T0: TryConvert,
-        T1: TryConvert,
-        T2: TryConvert,
-        T3: TryConvert,
-        T4: TryConvert,
-        T5: TryConvert,
+        T0: ScanArgsOptArg,
+        T1: ScanArgsOptArg,
+        T2: ScanArgsOptArg,
+        T3: ScanArgsOptArg,
+        T4: ScanArgsOptArg,
+        T5: ScanArgsOptArg,
     {
         const LEN: usize = 6;
 
         fn from_slice(vals: &[Value]) -> Result<Self, Error> {
             if vals.len() <= <Self as ScanArgsOpt>::LEN {
                 Ok((
-                    vals.get(0)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(1)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(2)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(3)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(4)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(5)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
+                    T0::from_opt_arg(vals.get(0).copied())?,
+                    T1::from_opt_arg(vals.get(1).copied())?,
+                    T2::from_opt_arg(vals.get(2).copied())?,
+                    T3::from_opt_arg(vals.get(3).copied())?,
+                    T4::from_opt_arg(vals.get(4).copied())?,
+                    T5::from_opt_arg(vals.get(5).copied())?,
                 ))
             } else {
                 panic!(
@@ -718,58 +672,28 @@ mod private {
         }
     }
 
-    impl<T0, T1, T2, T3, T4, T5, T6> ScanArgsOpt
-        for (
-            Option<T0>,
-            Option<T1>,
-            Option<T2>,
-            Option<T3>,
-            Option<T4>,
-            Option<T5>,
-            Option<T6>,
-        )
+    impl<T0, T1, T2, T3, T4, T5, T6> ScanArgsOpt for (T0, T1, T2, T3, T4, T5, T6)
     where
-        T0: TryConvert,
-        T1: TryConvert,
-        T2: TryConvert,
-        T3: TryConvert,
-        T4: TryConvert,
-        T5: TryConvert,
-        T6: TryConvert,
+        T0: ScanArgsOptArg,
+        T1: ScanArgsOptArg,
+        T2: ScanArgsOptArg,
+        T3: ScanArgsOptArg,
+        T4: ScanArgsOptArg,
+        T5: ScanArgsOptArg,
+        T6: ScanArgsOptArg,
     {
         const LEN: usize = 7;
 
         fn from_slice(vals: &[Value]) -> Result<Self, Error> {
             if vals.len() <= <Self as ScanArgsOpt>::LEN {
                 Ok((
-                    vals.get(0)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(1)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(2)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(3)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(4)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(5)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(6)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
+                    T0::from_opt_arg(vals.get(0).copied())?,
+                    T1::from_opt_arg(vals.get(1).copied())?,
+                    T2::from_opt_arg(vals.get(2).copied())?,
+                    T3::from_opt_arg(vals.get(3).copied())?,
+                    T4::from_opt_arg(vals.get(4).copied())?,
+                    T5::from_opt_arg(vals.get(5).copied())?,
+                    T6::from_opt_arg(vals.get(6).copied())?,
                 ))
             } else {
                 panic!(
@@ -781,64 +705,30 @@ mod private {
         }
     }
 
-    impl<T0, T1, T2, T3, T4, T5, T6, T7> ScanArgsOpt
-        for (
-            Option<T0>,
-            Option<T1>,
-            Option<T2>,
-            Option<T3>,
-            Option<T4>,
-            Option<T5>,
-            Option<T6>,
-            Option<T7>,
-        )
+    impl<T0, T1, T2, T3, T4, T5, T6, T7> ScanArgsOpt for (T0, T1, T2, T3, T4, T5, T6, T7)
     where
-        T0: TryConvert,
-        T1: TryConvert,
-        T2: TryConvert,
-        T3: TryConvert,
-        T4: TryConvert,
-        T5: TryConvert,
-        T6: TryConvert,
-        T7: TryConvert,
+        T0: ScanArgsOptArg,
+        T1: ScanArgsOptArg,
+        T2: ScanArgsOptArg,
+        T3: ScanArgsOptArg,
+        T4: ScanArgsOptArg,
+        T5: ScanArgsOptArg,
+        T6: ScanArgsOptArg,
+        T7: ScanArgsOptArg,
     {
         const LEN: usize = 8;
 
         fn from_slice(vals: &[Value]) -> Result<Self, Error> {
             if vals.len() <= <Self as ScanArgsOpt>::LEN {
                 Ok((
-                    vals.get(0)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(1)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(2)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(3)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(4)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(5)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(6)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(7)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
+                    T0::from_opt_arg(vals.get(0).copied())?,
+                    T1::from_opt_arg(vals.get(1).copied())?,
+                    T2::from_opt_arg(vals.get(2).copied())?,
+                    T3::from_opt_arg(vals.get(3).copied())?,
+                    T4::from_opt_arg(vals.get(4).copied())?,
+                    T5::from_opt_arg(vals.get(5).copied())?,
+                    T6::from_opt_arg(vals.get(6).copied())?,
+                    T7::from_opt_arg(vals.get(7).copied())?,
                 ))
             } else {
                 panic!(
@@ -850,70 +740,32 @@ mod private {
         }
     }
 
-    impl<T0, T1, T2, T3, T4, T5, T6, T7, T8> ScanArgsOpt
-        for (
-            Option<T0>,
-            Option<T1>,
-            Option<T2>,
-            Option<T3>,
-            Option<T4>,
-            Option<T5>,
-            Option<T6>,
-            Option<T7>,
-            Option<T8>,
-        )
+    impl<T0, T1, T2, T3, T4, T5, T6, T7, T8> ScanArgsOpt for (T0, T1, T2, T3, T4, T5, T6, T7, T8)
     where
-        T0: TryConvert,
-        T1: TryConvert,
-        T2: TryConvert,
-        T3: TryConvert,
-        T4: TryConvert,
-        T5: TryConvert,
-        T6: TryConvert,
-        T7: TryConvert,
-        T8: TryConvert,
+        T0: ScanArgsOptArg,
+        T1: ScanArgsOptArg,
+        T2: ScanArgsOptArg,
+        T3: ScanArgsOptArg,
+        T4: ScanArgsOptArg,
+        T5: ScanArgsOptArg,
+        T6: ScanArgsOptArg,
+        T7: ScanArgsOptArg,
+        T8: ScanArgsOptArg,
     {
         const LEN: usize = 9;
 
         fn from_slice(vals: &[Value]) -> Result<Self, Error> {
             if vals.len() <= <Self as ScanArgsOpt>::LEN {
                 Ok((
-                    vals.get(0)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(1)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(2)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(3)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(4)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(5)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(6)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(7)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
-                    vals.get(8)
-                        .filter(|v| !v.is_undef())
-                        .map(|v| v.try_convert())
-                        .transpose()?,
+                    T0::from_opt_arg(vals.get(0).copied())?,
+                    T1::from_opt_arg(vals.get(1).copied())?,
+                    T2::from_opt_arg(vals.get(2).copied())?,
+                    T3::from_opt_arg(vals.get(3).copied())?,
+                    T4::from_opt_arg(vals.get(4).copied())?,
+                    T5::from_opt_arg(vals.get(5).copied())?,
+                    T6::from_opt_arg(vals.get(6).copied())?,
+                    T7::from_opt_arg(vals.get(7).copied())?,
+                    T8::from_opt_arg(vals.get(8).copied())?,
                 ))
             } else {
                 panic!(
@@ -1028,6 +880,98 @@ mod private {
     }
 }
 
+/// An optional argument or Hash value, distinguishing a missing value from
+/// an explicit `nil`.
+///
+/// `Option<T>` can't express the difference between a keyword argument or
+/// Hash key that was not given, and one given as `nil`. This matters for APIs
+/// where `nil` means 'clear this value', while omitting it means 'leave this
+/// value unchanged'.
+///
+/// `Maybe<T>` can be used in place of `Option<T>` as an optional argument to
+/// [`scan_args`] or [`get_kwargs`], and as the result of
+/// [`RHash::lookup_maybe`]. Converting a [`Value`] directly to `Maybe<T>`
+/// (e.g. with [`TryConvert`]) will never return `Absent`.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{
+///     define_global_function, eval, function, scan_args::{get_kwargs, Maybe}, Error, RHash,
+/// };
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn update(kw: RHash) -> Result<&'static str, Error> {
+///     let args = get_kwargs::<_, (), (Maybe<String>,), ()>(kw, &[], &["name"])?;
+///     let (name,) = args.optional;
+///     Ok(match name {
+///         Maybe::Absent => "unchanged",
+///         Maybe::Nil => "cleared",
+///         Maybe::Present(_) => "set",
+///     })
+/// }
+///
+/// define_global_function("update", function!(update, 1));
+///
+/// assert_eq!(eval::<String>("update({})").unwrap(), "unchanged");
+/// assert_eq!(eval::<String>("update(name: nil)").unwrap(), "cleared");
+/// assert_eq!(eval::<String>(r#"update(name: "a")"#).unwrap(), "set");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Maybe<T> {
+    /// The value was not given.
+    Absent,
+    /// The value was given as `nil`.
+    Nil,
+    /// The value was given.
+    Present(T),
+}
+
+impl<T> Maybe<T> {
+    /// Return whether the value was not given.
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Self::Absent)
+    }
+
+    /// Return whether the value was given as `nil`.
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Self::Nil)
+    }
+
+    /// Return whether a non-`nil` value was given.
+    pub fn is_present(&self) -> bool {
+        matches!(self, Self::Present(_))
+    }
+
+    /// Convert to an `Option<T>`, treating `Absent` and `Nil` as `None`.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Present(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Default for Maybe<T> {
+    fn default() -> Self {
+        Self::Absent
+    }
+}
+
+impl<T> TryConvert for Maybe<T>
+where
+    T: TryConvert,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        if val.is_nil() {
+            Ok(Self::Nil)
+        } else {
+            val.try_convert().map(Self::Present)
+        }
+    }
+}
+impl<T> TryConvertOwned for Maybe<T> where T: TryConvertOwned {}
+
 /// Trait implemented for types that can be retrieved as required arguments by
 /// [`scan_args`].
 ///
@@ -1044,7 +988,7 @@ impl<T> ScanArgsRequired for T where T: private::ScanArgsRequired {}
 ///
 /// This trait is implemented for `(Option<T0>,)`, `(Option<T0>, Option<T1>)`,
 /// etc, through to a length of 9, where `T0`, `T1`, etc implement
-/// [`TryConvert`].
+/// [`TryConvert`]. [`Maybe<T>`] can be used in place of any `Option<T>`.
 ///
 /// `()` also impliments this trait as a placeholder indicating no optional
 /// arguments are required.
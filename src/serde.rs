@@ -0,0 +1,748 @@
+//! Convert Rust types implementing [serde]'s `Serialize` and `Deserialize`
+//! traits to and from Ruby objects.
+//!
+//! Rust values are mapped to Ruby objects as follows:
+//!
+//! | Rust                                      | Ruby                       |
+//! |-------------------------------------------|----------------------------|
+//! | `bool`                                    | `true` / `false`           |
+//! | integers                                  | `Integer`                  |
+//! | `f32`, `f64`                              | `Float`                    |
+//! | `char`, `String`, `&str`                  | `String`                   |
+//! | bytes                                     | `String` (binary encoding) |
+//! | `None`, `()`, unit structs                | `nil`                      |
+//! | `Some(v)`, newtype structs                | the inner value            |
+//! | `Vec`, slices, tuples, tuple structs      | `Array`                    |
+//! | maps                                      | `Hash`                     |
+//! | structs                                   | `Hash` with `Symbol` keys  |
+//! | unit enum variants                        | `Symbol`                   |
+//! | newtype, tuple, and struct enum variants  | `{variant: value}`         |
+//!
+//! When deserialising, struct fields and enum variants may be given as either
+//! `Symbol`s or `String`s.
+//!
+//! [serde]: https://serde.rs
+
+use std::fmt::Display;
+
+use serde::{
+    de::{
+        self, DeserializeOwned, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess,
+        Visitor,
+    },
+    forward_to_deserialize_any,
+    ser::{self, Serialize},
+};
+
+use crate::{
+    error::Error,
+    exception,
+    float::Float,
+    integer::Integer,
+    r_array::RArray,
+    r_hash::RHash,
+    r_string::RString,
+    symbol::Symbol,
+    value::{Fixnum, Value, QNIL},
+};
+
+/// Convert `value` to a Ruby object.
+///
+/// See the [module level documentation](self) for how Rust types are
+/// represented in Ruby.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, serde::serialize};
+/// use serde::Serialize;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// #[derive(Serialize)]
+/// struct Point {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// let point = serialize(&Point { x: 1, y: 2 }).unwrap();
+/// let res: bool = eval!("point == {x: 1, y: 2}", point).unwrap();
+/// assert!(res);
+/// ```
+pub fn serialize<T>(value: &T) -> Result<Value, Error>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer)
+}
+
+/// Convert the Ruby object `value` to a `T`.
+///
+/// See the [module level documentation](self) for how Ruby objects are
+/// mapped to Rust types.
+///
+/// Returns `Err` containing a `TypeError` if `value` does not match the
+/// structure of `T`.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, serde::deserialize, Value};
+/// use serde::Deserialize;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// #[serde(rename_all = "snake_case")]
+/// enum Shape {
+///     Circle { radius: f64 },
+///     Square(f64),
+///     Empty,
+/// }
+///
+/// let val: Value = eval("[{circle: {radius: 1.5}}, {square: 2.0}, :empty]").unwrap();
+/// let shapes: Vec<Shape> = deserialize(val).unwrap();
+/// assert_eq!(
+///     shapes,
+///     vec![
+///         Shape::Circle { radius: 1.5 },
+///         Shape::Square(2.0),
+///         Shape::Empty
+///     ]
+/// );
+/// ```
+pub fn deserialize<T>(value: Value) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(Deserializer(value))
+}
+
+impl ser::Error for Error {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        Error::new(exception::arg_error(), msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        Error::new(exception::type_error(), msg.to_string())
+    }
+}
+
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeVariant<SerializeArray>;
+    type SerializeMap = SerializeHash;
+    type SerializeStruct = SerializeHash;
+    type SerializeStructVariant = SerializeVariant<SerializeHash>;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+        Ok(RString::from_slice(v).into())
+    }
+
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(QNIL.into())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(QNIL.into())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+        Ok(QNIL.into())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(Symbol::new(variant).into())
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Value, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        variant_hash(variant, value.serialize(self)?)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray::new(len.unwrap_or(0)))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray::new(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray::new(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeArray>, Error> {
+        Ok(SerializeVariant::new(variant, SerializeArray::new(len)))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeHash, Error> {
+        Ok(SerializeHash::new())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<SerializeHash, Error> {
+        Ok(SerializeHash::new())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeVariant<SerializeHash>, Error> {
+        Ok(SerializeVariant::new(variant, SerializeHash::new()))
+    }
+}
+
+struct SerializeArray(RArray);
+
+impl SerializeArray {
+    fn new(capacity: usize) -> Self {
+        Self(RArray::with_capacity(capacity))
+    }
+
+    fn push<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.0.push(value.serialize(Serializer)?)
+    }
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(self.0.into())
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(self.0.into())
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(self.0.into())
+    }
+}
+
+struct SerializeHash {
+    hash: RHash,
+    key: Option<Value>,
+}
+
+impl SerializeHash {
+    fn new() -> Self {
+        Self {
+            hash: RHash::new(),
+            key: None,
+        }
+    }
+
+    fn insert<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.hash
+            .aset(Symbol::new(key), value.serialize(Serializer)?)
+    }
+}
+
+impl ser::SerializeMap for SerializeHash {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ser::Error::custom("serialize_value called before serialize_key"))?;
+        self.hash.aset(key, value.serialize(Serializer)?)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(self.hash.into())
+    }
+}
+
+impl ser::SerializeStruct for SerializeHash {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(self.hash.into())
+    }
+}
+
+/// Serialises the contents of a tuple or struct enum variant with `S`,
+/// wrapping the result with [`variant_hash`].
+struct SerializeVariant<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl<S> SerializeVariant<S> {
+    fn new(variant: &'static str, inner: S) -> Self {
+        Self { variant, inner }
+    }
+}
+
+/// Wrap `value` in a single entry `Hash` keyed by the `variant` name.
+fn variant_hash(variant: &'static str, value: Value) -> Result<Value, Error> {
+    let hash = RHash::new();
+    hash.aset(Symbol::new(variant), value)?;
+    Ok(hash.into())
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeArray> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        variant_hash(self.variant, self.inner.0.into())
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeHash> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.inner.insert(key, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        variant_hash(self.variant, self.inner.hash.into())
+    }
+}
+
+struct Deserializer(Value);
+
+impl Deserializer {
+    fn invalid_type(&self, expected: &str) -> Error {
+        Error::new(
+            exception::type_error(),
+            format!(
+                "no implicit conversion of {} into {}",
+                unsafe { self.0.classname() },
+                expected
+            ),
+        )
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let val = self.0;
+        if val.is_nil() {
+            return visitor.visit_unit();
+        }
+        if val.is_true() {
+            return visitor.visit_bool(true);
+        }
+        if val.is_false() {
+            return visitor.visit_bool(false);
+        }
+        if let Some(fixnum) = Fixnum::from_value(val) {
+            return visitor.visit_i64(fixnum.to_i64());
+        }
+        if let Some(integer) = Integer::from_value(val) {
+            return match integer.to_i64() {
+                Ok(i) => visitor.visit_i64(i),
+                Err(_) => visitor.visit_u64(integer.to_u64()?),
+            };
+        }
+        if let Some(float) = Float::from_value(val) {
+            return visitor.visit_f64(float.to_f64());
+        }
+        if let Some(s) = RString::from_value(val) {
+            return match s.to_string() {
+                Ok(s) => visitor.visit_string(s),
+                Err(_) => visitor.visit_byte_buf(unsafe { s.as_slice() }.to_vec()),
+            };
+        }
+        if let Some(sym) = Symbol::from_value(val) {
+            return visitor.visit_string(sym.name()?.into_owned());
+        }
+        if let Some(ary) = RArray::from_value(val) {
+            return visitor.visit_seq(SeqDeserializer(ary.to_vec::<Value>()?.into_iter()));
+        }
+        if let Some(hash) = RHash::from_value(val) {
+            return visitor.visit_map(MapDeserializer {
+                iter: hash.to_vec::<Value, Value>()?.into_iter(),
+                value: None,
+            });
+        }
+        Err(self.invalid_type("a serde supported type"))
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match RString::from_value(self.0) {
+            Some(s) => visitor.visit_byte_buf(unsafe { s.as_slice() }.to_vec()),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.is_nil() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if Symbol::from_value(self.0).is_some() || RString::from_value(self.0).is_some() {
+            return visitor.visit_enum(EnumDeserializer {
+                variant: self.0,
+                value: None,
+            });
+        }
+        if let Some(hash) = RHash::from_value(self.0) {
+            let mut entries = hash.to_vec::<Value, Value>()?;
+            if entries.len() == 1 {
+                let (variant, value) = entries.remove(0);
+                return visitor.visit_enum(EnumDeserializer {
+                    variant,
+                    value: Some(value),
+                });
+            }
+        }
+        Err(self.invalid_type("enum"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+struct SeqDeserializer(std::vec::IntoIter<Value>);
+
+impl<'de> SeqAccess<'de> for SeqDeserializer {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.0
+            .next()
+            .map(|val| seed.deserialize(Deserializer(val)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct MapDeserializer {
+    iter: std::vec::IntoIter<(Value, Value)>,
+    value: Option<Value>,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(Deserializer(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("next_value_seed called before next_key_seed"))?;
+        seed.deserialize(Deserializer(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct EnumDeserializer {
+    variant: Value,
+    value: Option<Value>,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer {
+    type Error = Error;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, VariantDeserializer), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(Deserializer(self.variant))?;
+        Ok((variant, VariantDeserializer(self.value)))
+    }
+}
+
+struct VariantDeserializer(Option<Value>);
+
+impl VariantDeserializer {
+    fn value(self) -> Result<Deserializer, Error> {
+        self.0
+            .map(Deserializer)
+            .ok_or_else(|| de::Error::custom("expected enum variant with a value"))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.0 {
+            None => Ok(()),
+            Some(val) if val.is_nil() => Ok(()),
+            Some(_) => Err(de::Error::custom("expected unit enum variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.value()?)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.value()?, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self.value()?, visitor)
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
     into_value::IntoValue,
     r_string::RString,
     ruby_handle::RubyHandle,
-    try_convert::TryConvert,
+    try_convert::{Coerced, TryConvert},
     value::{private, Id, NonZeroValue, ReprValue, StaticSymbol, Value},
 };
 
@@ -261,3 +261,14 @@ impl TryConvert for Symbol {
         })
     }
 }
+
+impl TryConvert for Coerced<Symbol> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        if let Some(sym) = Symbol::from_value(val) {
+            return Ok(Self(sym));
+        }
+        debug_assert_value!(val);
+        protect(|| unsafe { Symbol::from_rb_value_unchecked(rb_to_symbol(val.as_rb_value())) })
+            .map(Self)
+    }
+}
@@ -0,0 +1,1040 @@
+//! Types and functions for working with Ruby's Thread class.
+
+use std::{
+    ffi::c_void,
+    fmt,
+    future::Future,
+    ops::Deref,
+    os::raw::c_int,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    ptr,
+    sync::{Arc, Condvar},
+    task::{Context, Poll, Wake, Waker},
+    thread,
+    time::Duration,
+};
+
+#[cfg(ruby_lt_3_3)]
+use rb_sys::rb_postponed_job_register_one;
+use rb_sys::{
+    rb_block_call, rb_thread_call_with_gvl, rb_thread_call_without_gvl, rb_thread_check_ints,
+    rb_thread_create, rb_thread_current, rb_thread_kill, rb_thread_main, rb_thread_wakeup, VALUE,
+};
+#[cfg(ruby_gte_3_3)]
+use rb_sys::{rb_postponed_job_handle_t, rb_postponed_job_preregister, rb_postponed_job_trigger};
+
+use crate::{
+    block::Proc,
+    class,
+    error::{bug_from_panic, protect, Error},
+    exception,
+    into_value::IntoValue,
+    memoize,
+    method::{Block, BlockReturn},
+    object::Object,
+    r_string::RString,
+    ruby_handle::{set_gvl_released, RubyHandle, RubyUnavailableError},
+    try_convert::TryConvert,
+    value::{private, Id, NonZeroValue, ReprValue, StaticSymbol, Value, QNIL},
+};
+pub use crate::{
+    condition_variable::ConditionVariable,
+    mutex::Mutex,
+    queue::{Queue, SizedQueue},
+};
+
+impl RubyHandle {
+    pub fn thread_create<F, R>(&self, func: F) -> Result<Thread, Error>
+    where
+        F: 'static + Send + FnOnce() -> R,
+        R: BlockReturn,
+    {
+        unsafe extern "C" fn call<F, R>(arg: *mut c_void) -> VALUE
+        where
+            F: FnOnce() -> R,
+            R: BlockReturn,
+        {
+            let mut func = Some(*Box::from_raw(arg as *mut F));
+            let args: [Value; 0] = [];
+            Block::new(|_: &[Value], _: Option<Proc>| (func.take().unwrap())())
+                .call_handle_error(0, args.as_ptr(), QNIL.into())
+                .as_rb_value()
+        }
+
+        let ptr = Box::into_raw(Box::new(func));
+        let res = unsafe {
+            protect(|| {
+                Thread::from_rb_value_unchecked(rb_thread_create(
+                    Some(call::<F, R>),
+                    ptr as *mut c_void,
+                ))
+            })
+        };
+        if res.is_err() {
+            // thread wasn't created, so closure will never be called
+            unsafe { drop(Box::from_raw(ptr)) };
+        }
+        res
+    }
+
+    pub fn thread_current(&self) -> Thread {
+        unsafe { Thread::from_rb_value_unchecked(rb_thread_current()) }
+    }
+
+    pub fn thread_main(&self) -> Thread {
+        unsafe { Thread::from_rb_value_unchecked(rb_thread_main()) }
+    }
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's Thread class.
+///
+/// Ruby threads are native threads managed by the Ruby VM, and so, unlike
+/// threads created with [`std::thread`], may call Ruby. Only one Ruby thread
+/// runs at a time, see [`Thread::create`].
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Thread(NonZeroValue);
+
+impl Thread {
+    /// Return `Some(Thread)` if `val` is a `Thread`, `None` otherwise.
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(class::thread())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Create and start a new Ruby thread running `func`.
+    ///
+    /// Ruby has a Global VM Lock (GVL), so only one Ruby thread is executing
+    /// at any one time. The new thread will run when the current thread
+    /// releases the GVL, e.g. when it [joins](Thread::join) the new thread,
+    /// sleeps, or waits on IO.
+    ///
+    /// `func` may return any `R` or `Result<R, Error>` where `R` implements
+    /// `Into<Value>`. The result is available with [`Thread::value`].
+    /// Returning `Err(Error)` will raise the error in the new thread,
+    /// terminating it, and it will be re-raised by [`Thread::join`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::Thread;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let thread = Thread::create(|| (1..=10).sum::<i64>()).unwrap();
+    /// assert_eq!(thread.value::<i64>().unwrap(), 55);
+    /// ```
+    pub fn create<F, R>(func: F) -> Result<Self, Error>
+    where
+        F: 'static + Send + FnOnce() -> R,
+        R: BlockReturn,
+    {
+        get_ruby!().thread_create(func)
+    }
+
+    /// Return the currently executing thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn current() -> Self {
+        get_ruby!().thread_current()
+    }
+
+    /// Return the main thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn main() -> Self {
+        get_ruby!().thread_main()
+    }
+
+    /// Wait for `self` to finish.
+    ///
+    /// If `timeout` is `Some`, waits at most that long, returning `Ok(false)`
+    /// if `self` had not finished by then. Returns `Ok(true)` once `self` has
+    /// finished.
+    ///
+    /// Returns `Err` if `self` terminated with an exception.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use magnus::{eval, Thread, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let thread = Thread::create(|| eval::<Value>("sleep")).unwrap();
+    /// assert!(!thread.join(Some(Duration::from_millis(10))).unwrap());
+    /// thread.wakeup().unwrap();
+    /// assert!(thread.join(None).unwrap());
+    /// ```
+    pub fn join(self, timeout: Option<Duration>) -> Result<bool, Error> {
+        let res: Value = match timeout {
+            Some(t) => self.funcall("join", (t.as_secs_f64(),))?,
+            None => self.funcall("join", ())?,
+        };
+        Ok(!res.is_nil())
+    }
+
+    /// Wait for `self` to finish, and return its result.
+    ///
+    /// Returns `Err` if `self` terminated with an exception, or the result
+    /// fails to convert to a `T`.
+    pub fn value<T>(self) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.funcall("value", ())
+    }
+
+    /// Terminate `self`.
+    ///
+    /// `ensure` blocks within `self` will still be run.
+    pub fn kill(self) -> Result<(), Error> {
+        unsafe { protect(|| Value::new(rb_thread_kill(self.as_rb_value())))? };
+        Ok(())
+    }
+
+    /// Mark `self` as eligible for scheduling, if it is sleeping.
+    ///
+    /// This does not cause `self` to run immediately. Returns `Err` if `self`
+    /// has finished.
+    pub fn wakeup(self) -> Result<(), Error> {
+        unsafe { protect(|| Value::new(rb_thread_wakeup(self.as_rb_value())))? };
+        Ok(())
+    }
+
+    /// Return the current status of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{thread::ThreadStatus, Thread};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert_eq!(Thread::current().status().unwrap(), ThreadStatus::Run);
+    ///
+    /// let thread = Thread::create(|| ()).unwrap();
+    /// thread.join(None).unwrap();
+    /// assert_eq!(thread.status().unwrap(), ThreadStatus::Finished);
+    /// ```
+    pub fn status(self) -> Result<ThreadStatus, Error> {
+        let status: Value = self.funcall("status", ())?;
+        if status.is_false() {
+            return Ok(ThreadStatus::Finished);
+        }
+        if status.is_nil() {
+            return Ok(ThreadStatus::Errored);
+        }
+        let status: RString = status.try_convert()?;
+        match unsafe { status.as_slice() } {
+            b"run" => Ok(ThreadStatus::Run),
+            b"sleep" => Ok(ThreadStatus::Sleep),
+            b"aborting" => Ok(ThreadStatus::Aborting),
+            _ => Err(Error::new(
+                exception::runtime_error(),
+                format!("unknown thread status {}", status),
+            )),
+        }
+    }
+
+    /// Returns whether `self` is running or sleeping.
+    pub fn is_alive(self) -> Result<bool, Error> {
+        self.funcall("alive?", ())
+    }
+
+    /// Get the value of the thread-local variable `key` of `self`.
+    ///
+    /// This is the equivalent of Ruby's `Thread#thread_variable_get`. Unlike
+    /// [`Fiber::local_get`](crate::Fiber::local_get) these variables are
+    /// shared by all fibers in the thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::Thread;
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let thread = Thread::current();
+    /// assert!(thread.local_get::<_, Option<i64>>("example").unwrap().is_none());
+    /// thread.local_set("example", 1).unwrap();
+    /// assert_eq!(thread.local_get::<_, i64>("example").unwrap(), 1);
+    /// ```
+    pub fn local_get<K, T>(self, key: K) -> Result<T, Error>
+    where
+        K: Into<Id>,
+        T: TryConvert,
+    {
+        let key = StaticSymbol::from(key.into());
+        self.funcall("thread_variable_get", (key,))
+    }
+
+    /// Set the value of the thread-local variable `key` of `self`.
+    ///
+    /// See [`Thread::local_get`].
+    pub fn local_set<K, T>(self, key: K, val: T) -> Result<(), Error>
+    where
+        K: Into<Id>,
+        T: Into<Value>,
+    {
+        let key = StaticSymbol::from(key.into());
+        self.funcall::<_, _, Value>("thread_variable_set", (key, val.into()))?;
+        Ok(())
+    }
+}
+
+/// The status of a [`Thread`].
+///
+/// See [`Thread::status`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThreadStatus {
+    /// The thread is runnable.
+    Run,
+    /// The thread is sleeping or waiting on IO.
+    Sleep,
+    /// The thread is being killed.
+    Aborting,
+    /// The thread finished normally.
+    Finished,
+    /// The thread terminated with an exception.
+    Errored,
+}
+
+impl Deref for Thread {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for Thread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Thread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.deref().inspect())
+    }
+}
+
+impl IntoValue for Thread {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<Thread> for Value {
+    fn from(val: Thread) -> Self {
+        *val
+    }
+}
+
+impl Object for Thread {}
+
+unsafe impl private::ReprValue for Thread {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for Thread {}
+
+impl TryConvert for Thread {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Thread", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
+
+impl RubyHandle {
+    pub unsafe fn without_gvl<F, R, U>(&self, func: F, unblock: Option<U>) -> Result<R, Error>
+    where
+        F: FnOnce() -> R,
+        U: FnMut() + Send,
+    {
+        unsafe extern "C" fn call<F, R>(data: *mut c_void) -> *mut c_void
+        where
+            F: FnOnce() -> R,
+        {
+            let data = &mut *(data as *mut (Option<F>, Option<thread::Result<R>>));
+            let func = data.0.take().unwrap();
+            let prev = set_gvl_released(true);
+            data.1 = Some(catch_unwind(AssertUnwindSafe(func)));
+            set_gvl_released(prev);
+            ptr::null_mut()
+        }
+
+        unsafe extern "C" fn call_unblock<U>(data: *mut c_void)
+        where
+            U: FnMut(),
+        {
+            let func = &mut *(data as *mut U);
+            if let Err(e) = catch_unwind(AssertUnwindSafe(func)) {
+                bug_from_panic(e, "panic in unblock function")
+            }
+        }
+
+        let mut data: (Option<F>, Option<thread::Result<R>>) = (Some(func), None);
+        let mut unblock = unblock;
+        let (ubf, ubf_data) = match unblock.as_mut() {
+            Some(u) => (
+                Some(call_unblock::<U> as unsafe extern "C" fn(*mut c_void)),
+                u as *mut U as *mut c_void,
+            ),
+            None => (None, ptr::null_mut()),
+        };
+        // Ruby may raise pending interrupts (e.g. `Thread#raise`) once it
+        // has reacquired the GVL, so this needs to be protected.
+        let res = protect(|| {
+            rb_thread_call_without_gvl(
+                Some(call::<F, R>),
+                &mut data as *mut _ as *mut c_void,
+                ubf,
+                ubf_data,
+            );
+            QNIL
+        });
+        match data.1 {
+            Some(Err(e)) => resume_unwind(e),
+            Some(Ok(v)) => res.map(|_| v),
+            None => res.and_then(|_| {
+                Err(Error::new(
+                    exception::runtime_error(),
+                    "function not called without GVL",
+                ))
+            }),
+        }
+    }
+
+    pub fn check_interrupts(&self) -> Result<(), Error> {
+        protect(|| unsafe {
+            rb_thread_check_ints();
+            QNIL
+        })?;
+        Ok(())
+    }
+
+    pub fn handle_interrupt<F, R>(&self, timing: InterruptTiming, func: F) -> Result<R, Error>
+    where
+        F: FnOnce() -> Result<R, Error>,
+    {
+        unsafe extern "C" fn call<F, R>(
+            _yielded_arg: VALUE,
+            callback_arg: VALUE,
+            _argc: c_int,
+            _argv: *const VALUE,
+            _blockarg: VALUE,
+        ) -> VALUE
+        where
+            F: FnOnce() -> Result<R, Error>,
+        {
+            let data =
+                &mut *(callback_arg as *mut (Option<F>, Option<thread::Result<Result<R, Error>>>));
+            let func = data.0.take().unwrap();
+            data.1 = Some(catch_unwind(AssertUnwindSafe(func)));
+            QNIL.as_rb_value()
+        }
+
+        let mask = self.hash_new();
+        mask.aset(self.class_object(), self.sym_new(timing.as_str()))?;
+        let mut data: (Option<F>, Option<thread::Result<Result<R, Error>>>) = (Some(func), None);
+        let call_func =
+            call::<F, R> as unsafe extern "C" fn(VALUE, VALUE, c_int, *const VALUE, VALUE) -> VALUE;
+        #[cfg(ruby_lt_2_7)]
+        let call_func: unsafe extern "C" fn() -> VALUE = unsafe { std::mem::transmute(call_func) };
+
+        // interrupts deferred while `func` was running are raised as the
+        // block returns, so this needs to be protected.
+        let res = protect(|| unsafe {
+            let args = [mask.as_rb_value()];
+            Value::new(rb_block_call(
+                self.class_thread().as_rb_value(),
+                Id::from("handle_interrupt").as_rb_id(),
+                args.len() as c_int,
+                args.as_ptr(),
+                Some(call_func),
+                &mut data as *mut _ as VALUE,
+            ))
+        });
+        match data.1 {
+            Some(Err(e)) => resume_unwind(e),
+            Some(Ok(Err(e))) => Err(e),
+            Some(Ok(Ok(v))) => res.map(|_| v),
+            None => res.and_then(|_| {
+                Err(Error::new(
+                    exception::runtime_error(),
+                    "handle_interrupt did not call block",
+                ))
+            }),
+        }
+    }
+}
+
+/// Call `func` with Ruby's Global VM Lock (GVL) released, allowing other Ruby
+/// threads to run.
+///
+/// Only one thread can run Ruby code at a time, so long-running or blocking
+/// Rust code will stall every other Ruby thread. Running that code with this
+/// function releases the GVL for the duration of `func`.
+///
+/// `unblock` is called (potentially from another thread) if Ruby needs to
+/// interrupt the current thread while `func` is running, e.g. to handle
+/// `Thread#kill` or a signal. It should make `func` return as soon as
+/// possible, for example by setting an [`AtomicBool`](std::sync::atomic::AtomicBool)
+/// that `func` checks periodically. `unblock` **must not** panic. The process
+/// will abort if `unblock` panics. If `unblock` is `None` Ruby will not be
+/// able to interrupt the current thread until `func` returns.
+///
+/// A panic in `func` will be propagated to the caller of this function.
+///
+/// Returns `Err` if Ruby raised an interrupt (e.g. from `Thread#raise`) while
+/// `func` was running. In this case the result of `func` is discarded.
+///
+/// # Safety
+///
+/// As the GVL is released `func` and `unblock` **must not** call any Ruby API
+/// functions, allocate Ruby objects, or access any Ruby values (including
+/// those held by their captured variables), other than from within
+/// [`with_gvl`]. Any data needed from Ruby should be converted to Rust types
+/// before calling this function.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::without_gvl;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn fib(n: u64) -> u64 {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// }
+///
+/// let res = unsafe { without_gvl(|| fib(20), None::<fn()>) }.unwrap();
+/// assert_eq!(res, 6765);
+/// ```
+///
+/// With an unblock function:
+///
+/// ```
+/// use std::{
+///     sync::{
+///         atomic::{AtomicBool, Ordering},
+///         Arc,
+///     },
+///     time::Duration,
+/// };
+///
+/// use magnus::without_gvl;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let cancelled = Arc::new(AtomicBool::new(false));
+/// let flag = cancelled.clone();
+/// let res = unsafe {
+///     without_gvl(
+///         || {
+///             for _ in 0..10 {
+///                 if cancelled.load(Ordering::SeqCst) {
+///                     return false;
+///                 }
+///                 std::thread::sleep(Duration::from_millis(1));
+///             }
+///             true
+///         },
+///         Some(move || flag.store(true, Ordering::SeqCst)),
+///     )
+/// }
+/// .unwrap();
+/// assert!(res);
+/// ```
+pub unsafe fn without_gvl<F, R, U>(func: F, unblock: Option<U>) -> Result<R, Error>
+where
+    F: FnOnce() -> R,
+    U: FnMut() + Send,
+{
+    get_ruby!().without_gvl(func, unblock)
+}
+
+/// Call `func` with Ruby's Global VM Lock (GVL) held.
+///
+/// This reacquires the GVL from within a function passed to [`without_gvl`],
+/// so that Ruby API functions may be called. If the current thread already
+/// holds the GVL `func` is called immediately.
+///
+/// Returns `Err(RubyUnavailableError::NonRubyThread)` if the current thread is
+/// not a Ruby thread. Threads not created by Ruby (such as those created with
+/// [`std::thread`]) can not acquire the GVL, and so can never call Ruby.
+///
+/// A panic in `func` will be propagated to the caller of this function.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{thread::with_gvl, without_gvl, RString};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let res = unsafe {
+///     without_gvl(
+///         || {
+///             let sum = (1..=10).sum::<i64>();
+///             with_gvl(|| RString::new(&sum.to_string()).to_string()).unwrap()
+///         },
+///         None::<fn()>,
+///     )
+/// }
+/// .unwrap();
+/// assert_eq!(res.unwrap(), "55");
+///
+/// // not a Ruby thread
+/// let res = std::thread::spawn(|| with_gvl(|| ()).is_err()).join().unwrap();
+/// assert!(res);
+/// ```
+pub fn with_gvl<F, R>(func: F) -> Result<R, RubyUnavailableError>
+where
+    F: FnOnce() -> R,
+{
+    unsafe extern "C" fn call<F, R>(data: *mut c_void) -> *mut c_void
+    where
+        F: FnOnce() -> R,
+    {
+        let data = &mut *(data as *mut (Option<F>, Option<thread::Result<R>>));
+        let func = data.0.take().unwrap();
+        let prev = set_gvl_released(false);
+        data.1 = Some(catch_unwind(AssertUnwindSafe(func)));
+        set_gvl_released(prev);
+        ptr::null_mut()
+    }
+
+    match RubyHandle::get() {
+        Ok(_) => Ok(func()),
+        Err(RubyUnavailableError::GvlUnlocked) => {
+            let mut data: (Option<F>, Option<thread::Result<R>>) = (Some(func), None);
+            unsafe {
+                rb_thread_call_with_gvl(Some(call::<F, R>), &mut data as *mut _ as *mut c_void)
+            };
+            match data.1 {
+                Some(Ok(v)) => Ok(v),
+                Some(Err(e)) => resume_unwind(e),
+                None => unreachable!(),
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Check for and handle pending interrupts.
+///
+/// Ruby only handles interrupts (such as those from `Thread#raise`,
+/// `Thread#kill`, `Timeout`, or a signal like `SIGINT`) at certain points,
+/// such as between method calls. A long-running loop in Rust that holds the
+/// GVL will not be interrupted unless it calls this function periodically.
+///
+/// Returns `Err` if there was a pending interrupt that raised an exception.
+/// This error should be propagated back to Ruby (e.g. with `?`) as soon as
+/// possible.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{thread::check_interrupts, Error};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// fn sum_to(n: u64) -> Result<u64, Error> {
+///     let mut total = 0;
+///     for i in 0..=n {
+///         if i % 1024 == 0 {
+///             check_interrupts()?;
+///         }
+///         total += i;
+///     }
+///     Ok(total)
+/// }
+///
+/// assert_eq!(sum_to(100_000).unwrap(), 5_000_050_000);
+/// ```
+pub fn check_interrupts() -> Result<(), Error> {
+    get_ruby!().check_interrupts()
+}
+
+/// When asynchronous interrupts should be handled within
+/// [`handle_interrupt`].
+///
+/// See Ruby's `Thread.handle_interrupt`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterruptTiming {
+    /// Interrupts are handled immediately.
+    Immediate,
+    /// Interrupts are handled only at blocking operations, or explicit calls
+    /// to [`check_interrupts`].
+    OnBlocking,
+    /// Interrupts are deferred until the end of the [`handle_interrupt`]
+    /// call.
+    Never,
+}
+
+impl InterruptTiming {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Immediate => "immediate",
+            Self::OnBlocking => "on_blocking",
+            Self::Never => "never",
+        }
+    }
+}
+
+/// Call `func` with the handling of asynchronous interrupts controlled by
+/// `timing`.
+///
+/// This is the equivalent of Ruby's
+/// `Thread.handle_interrupt(Object => timing) { func() }`. It allows a
+/// section of Rust code to choose the points at which it can be interrupted,
+/// for example with [`InterruptTiming::OnBlocking`] interrupts will only be
+/// raised from calls to [`check_interrupts`], so Rust code can be sure that
+/// any other call to Ruby within `func` will not be interrupted part way
+/// through an operation.
+///
+/// Interrupts deferred while `func` was running will be handled when `func`
+/// returns. If this raises an exception `Err` is returned and the result of
+/// `func` is discarded.
+///
+/// A panic in `func` will be propagated to the caller of this function.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{
+///     eval,
+///     thread::{check_interrupts, handle_interrupt, InterruptTiming},
+///     Value,
+/// };
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let res = handle_interrupt(InterruptTiming::Never, || {
+///     let _: Value = eval("Thread.current.raise('interrupted')")?;
+///     // the interrupt is deferred, so execution continues
+///     check_interrupts()?;
+///     Ok(42)
+/// });
+/// // and the interrupt is raised once `func` returns
+/// assert!(res.is_err());
+/// ```
+pub fn handle_interrupt<F, R>(timing: InterruptTiming, func: F) -> Result<R, Error>
+where
+    F: FnOnce() -> Result<R, Error>,
+{
+    get_ruby!().handle_interrupt(timing, func)
+}
+
+#[derive(Default)]
+struct WakeState {
+    woken: bool,
+    interrupted: bool,
+}
+
+/// Waker that unparks a thread blocked in [`block_on`].
+#[derive(Default)]
+struct ThreadWaker {
+    state: std::sync::Mutex<WakeState>,
+    cond: Condvar,
+}
+
+impl ThreadWaker {
+    /// Wait to be woken. Returns `false` if interrupted.
+    fn wait(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        while !state.woken && !state.interrupted {
+            state = self.cond.wait(state).unwrap();
+        }
+        state.woken = false;
+        !std::mem::replace(&mut state.interrupted, false)
+    }
+
+    fn interrupt(&self) {
+        self.state.lock().unwrap().interrupted = true;
+        self.cond.notify_one();
+    }
+}
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.state.lock().unwrap().woken = true;
+        self.cond.notify_one();
+    }
+}
+
+/// Run `future` to completion, allowing other Ruby threads to run while it is
+/// pending.
+///
+/// `future` is polled with the GVL released (see [`without_gvl`]). The GVL
+/// is reacquired to return the result. If Ruby interrupts the current
+/// thread (e.g. with `Thread#raise`, `Timeout`, or a signal) while waiting,
+/// the interrupt is handled and, if it raised an exception, `Err` is returned
+/// and `future` is dropped.
+///
+/// If called from a non-blocking fiber with a
+/// [Fiber scheduler](crate::FiberScheduler) set (Ruby 3.0 and later) this
+/// instead uses [`FiberScheduler::block_on`](crate::FiberScheduler::block_on)
+/// to allow other fibers to run, in which case `future` is polled with the
+/// GVL held.
+///
+/// `future` must be driven to completion by something other than the
+/// current thread, such as an async runtime running on other threads. See
+/// [`block_on_tokio`] for futures that need to run in the context of a
+/// Tokio runtime.
+///
+/// A panic in `future` will be propagated to the caller of this function.
+///
+/// # Safety
+///
+/// As the GVL may be released `future` **must not** call any Ruby API
+/// functions, allocate Ruby objects, or access any Ruby values (including
+/// those held by `future` itself).
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::block_on;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let res = unsafe { block_on(async { 1 + 2 }) }.unwrap();
+/// assert_eq!(res, 3);
+/// ```
+pub unsafe fn block_on<F>(future: F) -> Result<F::Output, Error>
+where
+    F: Future,
+{
+    #[cfg(ruby_gte_3_0)]
+    {
+        if let Some(scheduler) = crate::FiberScheduler::current() {
+            return scheduler.block_on(future);
+        }
+    }
+
+    let mut future = Box::pin(future);
+    let notify = Arc::new(ThreadWaker::default());
+    let waker = Waker::from(notify.clone());
+    loop {
+        let res = without_gvl(
+            || {
+                let mut cx = Context::from_waker(&waker);
+                loop {
+                    if let Poll::Ready(v) = future.as_mut().poll(&mut cx) {
+                        return Some(v);
+                    }
+                    if !notify.wait() {
+                        return None;
+                    }
+                }
+            },
+            Some(|| notify.interrupt()),
+        )?;
+        match res {
+            Some(v) => return Ok(v),
+            None => check_interrupts()?,
+        }
+    }
+}
+
+/// Run `future` to completion in the context of the Tokio runtime `handle`,
+/// allowing other Ruby threads to run while it is pending.
+///
+/// This allows the use of futures (such as those from `reqwest` or `tonic`)
+/// that require a Tokio runtime, the runtime should be a multi-threaded
+/// runtime so that `future` is driven by its worker threads. See
+/// [`block_on`] for details.
+///
+/// # Safety
+///
+/// `future` **must not** call any Ruby API functions, allocate Ruby objects,
+/// or access any Ruby values. See [`block_on`].
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::thread::block_on_tokio;
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let runtime = tokio::runtime::Builder::new_multi_thread()
+///     .enable_time()
+///     .build()
+///     .unwrap();
+///
+/// let res = unsafe {
+///     block_on_tokio(runtime.handle(), async {
+///         tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+///         42
+///     })
+/// }
+/// .unwrap();
+/// assert_eq!(res, 42);
+/// ```
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub unsafe fn block_on_tokio<F>(
+    handle: &tokio::runtime::Handle,
+    future: F,
+) -> Result<F::Output, Error>
+where
+    F: Future,
+{
+    let _guard = handle.enter();
+    block_on(future)
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+fn job_queue() -> &'static std::sync::Mutex<Vec<Job>> {
+    memoize!(std::sync::Mutex<Vec<Job>>: std::sync::Mutex::new(Vec::new()))
+}
+
+/// Run the functions queued with [`enqueue_on_ruby_thread`].
+unsafe extern "C" fn run_jobs(_: *mut c_void) {
+    let jobs = std::mem::take(&mut *job_queue().lock().unwrap());
+    let prev = set_gvl_released(false);
+    for job in jobs {
+        if let Err(e) = catch_unwind(AssertUnwindSafe(job)) {
+            bug_from_panic(e, "panic in function passed to enqueue_on_ruby_thread")
+        }
+    }
+    set_gvl_released(prev);
+}
+
+/// Check jobs can be scheduled from the current thread.
+#[cfg(ruby_gte_3_3)]
+fn check_can_schedule_jobs() -> Result<(), RubyUnavailableError> {
+    // preregistered jobs can be triggered from any thread
+    Ok(())
+}
+
+/// Check jobs can be scheduled from the current thread.
+#[cfg(ruby_lt_3_3)]
+fn check_can_schedule_jobs() -> Result<(), RubyUnavailableError> {
+    // before Ruby 3.3 registering a job requires the current thread's
+    // execution context, so is only possible from a Ruby thread
+    match RubyHandle::get() {
+        Ok(_) | Err(RubyUnavailableError::GvlUnlocked) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Ask Ruby to run the queued jobs at the next safe point.
+#[cfg(ruby_gte_3_3)]
+fn schedule_jobs() {
+    let handle = memoize!(rb_postponed_job_handle_t: unsafe {
+        rb_postponed_job_preregister(0, Some(run_jobs), ptr::null_mut())
+    });
+    unsafe { rb_postponed_job_trigger(*handle) };
+}
+
+/// Ask Ruby to run the queued jobs at the next safe point.
+#[cfg(ruby_lt_3_3)]
+fn schedule_jobs() {
+    // if Ruby's job buffer is full registration fails, but the queued job
+    // will be run along with the next one to be successfully registered.
+    unsafe { rb_postponed_job_register_one(0, Some(run_jobs), ptr::null_mut()) };
+}
+
+/// Schedule `func` to be run by Ruby at the next safe point.
+///
+/// This allows code running without the GVL (see [`without_gvl`]), or on
+/// another thread, to hand work back to Ruby, such as delivering a result,
+/// raising an error in a waiting thread, or calling a stored [`Proc`]. `func`
+/// will be run, with the GVL held, the next time Ruby checks for interrupts,
+/// and so may call Ruby API functions. Queued functions are run in the order
+/// they were queued.
+///
+/// `func` **must not** panic. The process will abort if `func` panics.
+///
+/// With Ruby 3.3 and later this can be called from any thread. With earlier
+/// versions of Ruby this returns `Err(RubyUnavailableError::NonRubyThread)`
+/// if the current thread is not a Ruby thread, in which case `func` is
+/// dropped without being run. This can always be called from a Ruby thread
+/// that has released the GVL, so to offload work to a background thread that
+/// can schedule work back on to Ruby with earlier versions of Ruby, create
+/// the thread with [`Thread::create`] and release the GVL with
+/// [`without_gvl`].
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{enqueue_on_ruby_thread, eval, without_gvl};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// unsafe {
+///     without_gvl(
+///         || {
+///             enqueue_on_ruby_thread(|| {
+///                 let _: magnus::Value = eval("$result = 42").unwrap();
+///             })
+///             .unwrap();
+///         },
+///         None::<fn()>,
+///     )
+/// }
+/// .unwrap();
+///
+/// // Ruby runs the queued function once it reaches a safe point
+/// let res: i64 = eval("Thread.pass; $result").unwrap();
+/// assert_eq!(res, 42);
+/// ```
+pub fn enqueue_on_ruby_thread<F>(func: F) -> Result<(), RubyUnavailableError>
+where
+    F: 'static + Send + FnOnce(),
+{
+    check_can_schedule_jobs()?;
+    job_queue().lock().unwrap().push(Box::new(func));
+    schedule_jobs();
+    Ok(())
+}
@@ -0,0 +1,997 @@
+//! Types and functions for working with Ruby's `Time` class, and
+//! conversions between Ruby and Rust times and durations.
+//!
+//! With the `chrono` feature enabled, `chrono::NaiveDate` can be converted to
+//! and from Ruby's `Date`, and `chrono::NaiveDateTime` from Ruby's `Date` and
+//! `DateTime` (and to a `DateTime` with [`AsDateTime`]). These conversions
+//! require Ruby's `date` library, which will be loaded if it isn't already.
+//!
+//! Ruby's `Date` defaults to the Julian calendar for dates before the
+//! Gregorian reform of 1582, whereas chrono uses the proleptic Gregorian
+//! calendar for all dates. `Date`s and `DateTime`s created from chrono types
+//! use the proleptic Gregorian calendar, so their year, month, and day always
+//! match those of the chrono type. Conversions from Ruby use the Julian Day
+//! Number, so are correct regardless of the calendar in use.
+
+use std::{
+    convert::TryFrom,
+    fmt,
+    ops::Deref,
+    os::raw::c_int,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rb_sys::{
+    rb_rational_new, rb_time_nano_new, rb_time_timespec, rb_time_timespec_interval,
+    rb_time_timespec_new, rb_time_utc_offset, timespec, VALUE,
+};
+
+use crate::{
+    error::{protect, Error},
+    exception,
+    into_value::IntoValue,
+    object::Object,
+    r_string::RString,
+    ruby_handle::RubyHandle,
+    try_convert::{Lax, TryConvert, TryConvertOwned},
+    value::{private, NonZeroValue, ReprValue, Value, QNIL},
+};
+
+/// Offset passed to `rb_time_timespec_new` to create a time in UTC.
+const UTC_OFFSET: c_int = c_int::MAX - 1;
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Normalise `nanos` to be less than one second, carrying any excess (e.g.
+/// from a leap second) to `secs`.
+fn normalise(secs: i64, nanos: u32) -> timespec {
+    timespec {
+        tv_sec: (secs + (nanos / NANOS_PER_SEC) as i64) as _,
+        tv_nsec: (nanos % NANOS_PER_SEC) as _,
+    }
+}
+
+impl RubyHandle {
+    pub fn time_nano_new(&self, secs: i64, nanos: u32) -> Time {
+        let ts = normalise(secs, nanos);
+        unsafe { Time::from_rb_value_unchecked(rb_time_nano_new(ts.tv_sec, ts.tv_nsec)) }
+    }
+
+    pub fn time_utc_new(&self, secs: i64, nanos: u32) -> Time {
+        let ts = normalise(secs, nanos);
+        unsafe { Time::from_rb_value_unchecked(rb_time_timespec_new(&ts, UTC_OFFSET)) }
+    }
+
+    pub fn time_offset_new(&self, secs: i64, nanos: u32, offset: i32) -> Result<Time, Error> {
+        let ts = normalise(secs, nanos);
+        protect(|| unsafe { Time::from_rb_value_unchecked(rb_time_timespec_new(&ts, offset)) })
+    }
+
+    fn rational_secs(&self, secs: i64, nanos: i64) -> Value {
+        let frac = unsafe {
+            Value::new(rb_rational_new(
+                self.into_value(nanos).as_rb_value(),
+                self.into_value(NANOS_PER_SEC).as_rb_value(),
+            ))
+        };
+        // adding an Integer and a Rational can't fail
+        self.into_value(secs).funcall("+", (frac,)).unwrap()
+    }
+}
+
+/// A Value known to be an instance of Ruby's `Time` class.
+///
+/// `Time` can be converted to and from [`SystemTime`], with a `SystemTime`
+/// converting to a `Time` in the local time zone, as with Ruby's `Time.now`.
+///
+/// With the `chrono` feature enabled `Time` can be converted to and from
+/// `chrono::DateTime<Utc>`, `chrono::DateTime<FixedOffset>`, and
+/// `chrono::NaiveDateTime`, preserving nanoseconds and UTC offsets. A
+/// `NaiveDateTime` converted to Ruby is treated as UTC, and one converted
+/// from Ruby is the date and time as shown on the Ruby `Time`'s clock.
+///
+/// With the `time` feature enabled `Time` can be converted to and from
+/// `time::OffsetDateTime`. Ruby can't represent UTC offsets of a day or more,
+/// so an `OffsetDateTime` with such an offset is converted to a UTC `Time`.
+/// The `time` feature also enables converting `time::Duration` to a `Float`
+/// number of seconds, and from any Ruby `Numeric` number of seconds.
+///
+/// All [`Value`] methods should be available on this type through [`Deref`],
+/// but some may be missed by this documentation.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Time(NonZeroValue);
+
+impl Time {
+    /// Return `Some(Time)` if `val` is a `Time`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Time};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(Time::from_value(eval("Time.now").unwrap()).is_some());
+    /// assert!(Time::from_value(eval("0").unwrap()).is_none());
+    /// ```
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        unsafe {
+            val.is_kind_of(get_ruby!().class_time())
+                .then(|| Self(NonZeroValue::new_unchecked(val)))
+        }
+    }
+
+    #[inline]
+    pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
+        Self(NonZeroValue::new_unchecked(Value::new(val)))
+    }
+
+    /// Create a new `Time` in the local time zone, `secs` seconds and
+    /// `nanos` nanoseconds after the Unix epoch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Time};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t = Time::new(1_000_000_000, 500);
+    /// let res: bool = eval!("t == Time.at(1_000_000_000, 500, :nsec)", t).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn new(secs: i64, nanos: u32) -> Self {
+        get_ruby!().time_nano_new(secs, nanos)
+    }
+
+    /// Create a new `Time` in UTC, `secs` seconds and `nanos` nanoseconds
+    /// after the Unix epoch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Time};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t = Time::new_utc(0, 0);
+    /// assert!(t.is_utc());
+    /// let res: bool = eval!(r#"t.to_s == "1970-01-01 00:00:00 UTC""#, t).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn new_utc(secs: i64, nanos: u32) -> Self {
+        get_ruby!().time_utc_new(secs, nanos)
+    }
+
+    /// Create a new `Time` with a fixed offset of `offset` seconds from UTC,
+    /// `secs` seconds and `nanos` nanoseconds after the Unix epoch.
+    ///
+    /// Returns `Err` if `offset` is not less than a day in either direction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Time};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t = Time::new_with_offset(0, 0, 3600).unwrap();
+    /// assert_eq!(t.utc_offset().unwrap(), 3600);
+    /// let res: bool = eval!(r#"t.to_s == "1970-01-01 01:00:00 +0100""#, t).unwrap();
+    /// assert!(res);
+    ///
+    /// assert!(Time::new_with_offset(0, 0, 86400).is_err());
+    /// ```
+    pub fn new_with_offset(secs: i64, nanos: u32, offset: i32) -> Result<Self, Error> {
+        get_ruby!().time_offset_new(secs, nanos, offset)
+    }
+
+    /// Return the number of seconds and nanoseconds since the Unix epoch.
+    ///
+    /// The nanoseconds are always positive, so for times before the epoch
+    /// the seconds are rounded down.
+    ///
+    /// Returns `Err` if the time is out of range for the platform's `time_t`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Time};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t: Time = eval("Time.at(-1, 250, :millisecond)").unwrap();
+    /// assert_eq!(t.timespec().unwrap(), (-1, 250_000_000));
+    /// ```
+    pub fn timespec(self) -> Result<(i64, u32), Error> {
+        let mut res = timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        protect(|| {
+            res = unsafe { rb_time_timespec(self.as_rb_value()) };
+            QNIL
+        })?;
+        Ok((res.tv_sec as i64, res.tv_nsec as u32))
+    }
+
+    /// Return the offset of the time's time zone from UTC, in seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Time};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let t: Time = eval(r#"Time.new(2000, 1, 1, 0, 0, 0, "-05:30")"#).unwrap();
+    /// assert_eq!(t.utc_offset().unwrap(), -19800);
+    /// ```
+    pub fn utc_offset(self) -> Result<i32, Error> {
+        let offset = protect(|| unsafe { Value::new(rb_time_utc_offset(self.as_rb_value())) })?;
+        offset.try_convert()
+    }
+
+    /// Return whether the time is in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Time};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(eval::<Time>("Time.now.utc").unwrap().is_utc());
+    /// assert!(!eval::<Time>(r#"Time.now.localtime("+01:00")"#).unwrap().is_utc());
+    /// ```
+    pub fn is_utc(self) -> bool {
+        self.funcall("utc?", ()).unwrap_or(false)
+    }
+}
+
+impl Deref for Time {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.get_ref()
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl IntoValue for Time {
+    fn into_value(self, _: &RubyHandle) -> Value {
+        *self
+    }
+}
+
+impl From<Time> for Value {
+    fn from(val: Time) -> Self {
+        *val
+    }
+}
+
+impl Object for Time {}
+
+unsafe impl private::ReprValue for Time {
+    fn to_value(self) -> Value {
+        *self
+    }
+
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+}
+
+impl ReprValue for Time {}
+
+impl TryConvert for Time {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Time", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
+
+fn out_of_range() -> Error {
+    Error::new(exception::range_error(), "time out of range")
+}
+
+/// Adapter to convert a [`Duration`] to a Ruby `Rational` number of seconds.
+///
+/// A `Duration` converts to a Ruby `Float` number of seconds, matching the
+/// values accepted and returned by methods such as `sleep` and
+/// `Process.clock_gettime`, but a `Float` can't exactly represent all
+/// nanosecond values. `Exact<Duration>` converts to a `Rational`, which can.
+///
+/// Conversions from Ruby to both `Duration` and `Exact<Duration>` accept any
+/// Ruby number of seconds, `Integer`, `Float`, or `Rational`.
+///
+/// With the `time` feature enabled, `Exact<time::Duration>` can also be
+/// used.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use magnus::{eval, time::Exact, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let val = Value::from(Duration::new(1, 1));
+/// let res: bool = eval!("val == 1.000000001", val).unwrap();
+/// assert!(res);
+///
+/// let val = Value::from(Exact(Duration::new(1, 1)));
+/// let res: bool = eval!("val == Rational(1_000_000_001, 1_000_000_000)", val).unwrap();
+/// assert!(res);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Exact<T>(pub T);
+
+impl IntoValue for Duration {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        handle.into_value(self.as_secs_f64())
+    }
+}
+
+impl From<Duration> for Value {
+    fn from(val: Duration) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+impl IntoValue for Exact<Duration> {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        handle.rational_secs(self.0.as_secs() as i64, self.0.subsec_nanos() as i64)
+    }
+}
+
+impl From<Exact<Duration>> for Value {
+    fn from(val: Exact<Duration>) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+impl TryConvert for Duration {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let mut res = timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        protect(|| {
+            res = unsafe { rb_time_timespec_interval(val.as_rb_value()) };
+            QNIL
+        })?;
+        Ok(Duration::new(res.tv_sec as u64, res.tv_nsec as u32))
+    }
+}
+impl TryConvertOwned for Duration {}
+
+impl TryConvert for Exact<Duration> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Duration::try_convert(val).map(Exact)
+    }
+}
+impl TryConvertOwned for Exact<Duration> {}
+
+/// Seconds in the lengths of year and month used by ActiveSupport, an
+/// average Gregorian year, and a twelfth of that.
+const SECS_PER_YEAR: u128 = 31_556_952;
+const SECS_PER_MONTH: u128 = SECS_PER_YEAR / 12;
+
+const DATE_DESIGNATORS: &[(u8, u128)] = &[
+    (b'Y', SECS_PER_YEAR),
+    (b'M', SECS_PER_MONTH),
+    (b'W', 604_800),
+    (b'D', 86_400),
+];
+const TIME_DESIGNATORS: &[(u8, u128)] = &[(b'H', 3_600), (b'M', 60), (b'S', 1)];
+
+/// Parse a decimal number, with `.` or `,` as the decimal separator, to
+/// billionths.
+fn parse_decimal(s: &str) -> Option<u128> {
+    let (int, frac) = match s.find(|c| c == '.' || c == ',') {
+        Some(i) => (&s[..i], Some(&s[i + 1..])),
+        None => (s, None),
+    };
+    if int.is_empty() || !int.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut res = int
+        .parse::<u128>()
+        .ok()?
+        .checked_mul(NANOS_PER_SEC as u128)?;
+    if let Some(frac) = frac {
+        if frac.is_empty() || !frac.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let mut scale = NANOS_PER_SEC as u128;
+        for b in frac.bytes().take(9) {
+            scale /= 10;
+            res += (b - b'0') as u128 * scale;
+        }
+    }
+    Some(res)
+}
+
+/// Parse an ISO 8601 duration, such as `P1DT12H` or `PT0.5S`, to a number of
+/// nanoseconds.
+///
+/// Years and months are converted to seconds with the same average lengths
+/// as ActiveSupport. A leading `-` negates the duration.
+fn parse_iso8601_duration(s: &str) -> Option<i128> {
+    let (neg, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let mut s = s.strip_prefix('P')?;
+    let mut designators = DATE_DESIGNATORS;
+    let mut in_time = false;
+    let mut empty = true;
+    let mut nanos: u128 = 0;
+    while !s.is_empty() {
+        if !in_time && s.starts_with('T') {
+            s = &s[1..];
+            if s.is_empty() {
+                return None;
+            }
+            designators = TIME_DESIGNATORS;
+            in_time = true;
+            continue;
+        }
+        let end = s.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))?;
+        let (num, rest) = s.split_at(end);
+        let i = designators
+            .iter()
+            .position(|(d, _)| *d == rest.as_bytes()[0])?;
+        let secs = designators[i].1;
+        designators = &designators[i + 1..];
+        nanos = nanos.checked_add(parse_decimal(num)?.checked_mul(secs)?)?;
+        s = &rest[1..];
+        empty = false;
+    }
+    if empty {
+        return None;
+    }
+    let nanos = i128::try_from(nanos).ok()?;
+    Some(if neg { -nanos } else { nanos })
+}
+
+/// A duration given to a [`Lax`] conversion.
+enum LaxDuration {
+    /// Nanoseconds parsed from an ISO 8601 string.
+    Nanos(i128),
+    /// A Ruby number of seconds.
+    Numeric(Value),
+}
+
+fn lax_duration(val: Value) -> Result<LaxDuration, Error> {
+    let handle = get_ruby!();
+    if val.is_kind_of(handle.class_numeric()) {
+        return Ok(LaxDuration::Numeric(val));
+    }
+    if let Some(s) = RString::from_value(val) {
+        let s = s.to_string()?;
+        return parse_iso8601_duration(&s)
+            .map(LaxDuration::Nanos)
+            .ok_or_else(|| {
+                Error::new(
+                    exception::arg_error(),
+                    format!("invalid ISO 8601 duration: {:?}", s),
+                )
+            });
+    }
+    let object = handle.class_object();
+    if object.funcall("const_defined?", ("ActiveSupport::Duration",))? {
+        let class: Value = object.funcall("const_get", ("ActiveSupport::Duration",))?;
+        if val.funcall("is_a?", (class,))? {
+            return val.funcall("value", ()).map(LaxDuration::Numeric);
+        }
+    }
+    Ok(LaxDuration::Numeric(val))
+}
+
+/// Conversions to `Lax<Duration>` accept any Ruby number of seconds, an
+/// `ActiveSupport::Duration` (such as `5.minutes`), or an ISO 8601 duration
+/// string (such as `"PT5M"`).
+impl TryConvert for Lax<Duration> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        match lax_duration(val)? {
+            LaxDuration::Numeric(val) => Duration::try_convert(val).map(Lax),
+            LaxDuration::Nanos(nanos) if nanos < 0 => Err(Error::new(
+                exception::arg_error(),
+                "time interval must not be negative",
+            )),
+            LaxDuration::Nanos(nanos) => {
+                let secs =
+                    u64::try_from(nanos / NANOS_PER_SEC as i128).map_err(|_| out_of_range())?;
+                Ok(Lax(Duration::new(
+                    secs,
+                    (nanos % NANOS_PER_SEC as i128) as u32,
+                )))
+            }
+        }
+    }
+}
+impl TryConvertOwned for Lax<Duration> {}
+
+impl IntoValue for SystemTime {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let (secs, nanos) = match self.duration_since(UNIX_EPOCH) {
+            Ok(d) => (d.as_secs() as i64, d.subsec_nanos()),
+            Err(e) => {
+                let d = e.duration();
+                match d.subsec_nanos() {
+                    0 => (-(d.as_secs() as i64), 0),
+                    n => (-(d.as_secs() as i64) - 1, NANOS_PER_SEC - n),
+                }
+            }
+        };
+        handle.time_nano_new(secs, nanos).into()
+    }
+}
+
+impl From<SystemTime> for Value {
+    fn from(val: SystemTime) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+impl TryConvert for SystemTime {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let (secs, nanos) = Time::try_convert(val)?.timespec()?;
+        let time = if secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))
+        };
+        time.and_then(|t| t.checked_add(Duration::from_nanos(nanos as u64)))
+            .ok_or_else(out_of_range)
+    }
+}
+impl TryConvertOwned for SystemTime {}
+
+#[cfg(feature = "chrono")]
+impl IntoValue for chrono::DateTime<chrono::Utc> {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        handle
+            .time_utc_new(self.timestamp(), self.timestamp_subsec_nanos())
+            .into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Value {
+    fn from(val: chrono::DateTime<chrono::Utc>) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl IntoValue for chrono::DateTime<chrono::FixedOffset> {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        handle
+            .time_offset_new(
+                self.timestamp(),
+                self.timestamp_subsec_nanos(),
+                self.offset().local_minus_utc(),
+            )
+            // chrono's offsets are always less than a day, as Ruby requires
+            .unwrap()
+            .into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::FixedOffset>> for Value {
+    fn from(val: chrono::DateTime<chrono::FixedOffset>) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl IntoValue for chrono::NaiveDateTime {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        use chrono::TimeZone;
+
+        handle.into_value(chrono::Utc.from_utc_datetime(&self))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDateTime> for Value {
+    fn from(val: chrono::NaiveDateTime) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryConvert for chrono::DateTime<chrono::Utc> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        use chrono::TimeZone;
+
+        let (secs, nanos) = Time::try_convert(val)?.timespec()?;
+        chrono::Utc
+            .timestamp_opt(secs, nanos)
+            .single()
+            .ok_or_else(out_of_range)
+    }
+}
+#[cfg(feature = "chrono")]
+impl TryConvertOwned for chrono::DateTime<chrono::Utc> {}
+
+#[cfg(feature = "chrono")]
+impl TryConvert for chrono::DateTime<chrono::FixedOffset> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        use chrono::TimeZone;
+
+        let time = Time::try_convert(val)?;
+        let (secs, nanos) = time.timespec()?;
+        chrono::FixedOffset::east_opt(time.utc_offset()?)
+            .and_then(|offset| offset.timestamp_opt(secs, nanos).single())
+            .ok_or_else(out_of_range)
+    }
+}
+#[cfg(feature = "chrono")]
+impl TryConvertOwned for chrono::DateTime<chrono::FixedOffset> {}
+
+#[cfg(feature = "chrono")]
+impl TryConvert for chrono::NaiveDateTime {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        if Time::from_value(val).is_none() && val.is_kind_of(get_ruby!().class_date()?) {
+            return naive_date_time_from_date(val);
+        }
+        chrono::DateTime::<chrono::FixedOffset>::try_convert(val).map(|dt| dt.naive_local())
+    }
+}
+#[cfg(feature = "chrono")]
+impl TryConvertOwned for chrono::NaiveDateTime {}
+
+/// The Julian Day Number of 0001-01-01 in the proleptic Gregorian calendar,
+/// minus one, to convert between Ruby's `Date#jd` and chrono's
+/// `num_days_from_ce`.
+#[cfg(feature = "chrono")]
+const JD_CE_OFFSET: i64 = 1_721_425;
+
+#[cfg(feature = "chrono")]
+impl RubyHandle {
+    fn class_date(&self) -> Result<crate::RClass, Error> {
+        self.require("date")?;
+        self.class_object().const_get("Date")
+    }
+
+    fn class_date_time(&self) -> Result<crate::RClass, Error> {
+        self.require("date")?;
+        self.class_object().const_get("DateTime")
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn jd_from_naive_date(date: chrono::NaiveDate) -> i64 {
+    use chrono::Datelike;
+
+    date.num_days_from_ce() as i64 + JD_CE_OFFSET
+}
+
+#[cfg(feature = "chrono")]
+fn naive_date_from_jd(jd: i64) -> Result<chrono::NaiveDate, Error> {
+    use std::convert::TryFrom;
+
+    i32::try_from(jd - JD_CE_OFFSET)
+        .ok()
+        .and_then(chrono::NaiveDate::from_num_days_from_ce_opt)
+        .ok_or_else(out_of_range)
+}
+
+/// Convert a Ruby `Date` or `DateTime` to the date and time shown on its
+/// clock.
+#[cfg(feature = "chrono")]
+fn naive_date_time_from_date(val: Value) -> Result<chrono::NaiveDateTime, Error> {
+    let date = naive_date_from_jd(val.funcall("jd", ())?)?;
+    if !val.is_kind_of(get_ruby!().class_date_time()?) {
+        return date.and_hms_opt(0, 0, 0).ok_or_else(out_of_range);
+    }
+    let frac: Value = val.funcall("sec_fraction", ())?;
+    let nanos: Value = frac.funcall("*", (NANOS_PER_SEC,))?;
+    date.and_hms_nano_opt(
+        val.funcall("hour", ())?,
+        val.funcall("minute", ())?,
+        val.funcall("second", ())?,
+        nanos.funcall("round", ())?,
+    )
+    .ok_or_else(out_of_range)
+}
+
+#[cfg(feature = "chrono")]
+impl IntoValue for chrono::NaiveDate {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let class = handle.class_date().unwrap();
+        let gregorian: Value = class.const_get("GREGORIAN").unwrap();
+        class
+            .funcall("jd", (jd_from_naive_date(self), gregorian))
+            .unwrap()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for Value {
+    fn from(val: chrono::NaiveDate) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryConvert for chrono::NaiveDate {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        if !val.is_kind_of(get_ruby!().class_date()?) {
+            return Err(Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into Date", unsafe {
+                    val.classname()
+                },),
+            ));
+        }
+        naive_date_from_jd(val.funcall("jd", ())?)
+    }
+}
+#[cfg(feature = "chrono")]
+impl TryConvertOwned for chrono::NaiveDate {}
+
+/// Adapter to convert a [`chrono::NaiveDateTime`] to a Ruby `DateTime`.
+///
+/// A `NaiveDateTime` converts to a Ruby `Time` in UTC. `AsDateTime` instead
+/// converts to a `DateTime` with a zero offset, using the proleptic Gregorian
+/// calendar as with [`chrono::NaiveDate`].
+///
+/// Conversions from Ruby to both `NaiveDateTime` and
+/// `AsDateTime<NaiveDateTime>` accept a `Time`, `Date`, or `DateTime`.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use magnus::{eval, time::AsDateTime, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let dt = NaiveDate::from_ymd_opt(1500, 2, 3)
+///     .unwrap()
+///     .and_hms_milli_opt(4, 5, 6, 789)
+///     .unwrap();
+/// let val = Value::from(AsDateTime(dt));
+/// let res: bool = eval!(
+///     r#"val.is_a?(DateTime) && val.strftime("%F %T.%L") == "1500-02-03 04:05:06.789""#,
+///     val
+/// )
+/// .unwrap();
+/// assert!(res);
+/// ```
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AsDateTime<T>(pub T);
+
+#[cfg(feature = "chrono")]
+impl IntoValue for AsDateTime<chrono::NaiveDateTime> {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        use chrono::Timelike;
+
+        let dt = self.0;
+        let class = handle.class_date_time().unwrap();
+        let gregorian: Value = class.const_get("GREGORIAN").unwrap();
+        // Ruby has no representation for leap seconds
+        let nanos = dt.nanosecond().min(NANOS_PER_SEC - 1);
+        let secs = handle.rational_secs(dt.second() as i64, nanos as i64);
+        class
+            .funcall(
+                "jd",
+                (
+                    jd_from_naive_date(dt.date()),
+                    dt.hour(),
+                    dt.minute(),
+                    secs,
+                    0,
+                    gregorian,
+                ),
+            )
+            .unwrap()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<AsDateTime<chrono::NaiveDateTime>> for Value {
+    fn from(val: AsDateTime<chrono::NaiveDateTime>) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryConvert for AsDateTime<chrono::NaiveDateTime> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        chrono::NaiveDateTime::try_convert(val).map(AsDateTime)
+    }
+}
+#[cfg(feature = "chrono")]
+impl TryConvertOwned for AsDateTime<chrono::NaiveDateTime> {}
+
+#[cfg(feature = "chrono")]
+impl IntoValue for chrono::Duration {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let secs = self.num_seconds();
+        let nanos = (self - chrono::Duration::seconds(secs))
+            .num_nanoseconds()
+            .unwrap_or(0);
+        handle.into_value(secs as f64 + nanos as f64 / NANOS_PER_SEC as f64)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::Duration> for Value {
+    fn from(val: chrono::Duration) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn chrono_duration(secs: i64, nanos: u32) -> Result<chrono::Duration, Error> {
+    let whole = chrono::Duration::from_std(Duration::from_secs(secs.unsigned_abs()))
+        .map_err(|_| out_of_range())?;
+    let whole = if secs < 0 { -whole } else { whole };
+    whole
+        .checked_add(&chrono::Duration::nanoseconds(nanos as i64))
+        .ok_or_else(out_of_range)
+}
+
+#[cfg(feature = "chrono")]
+impl TryConvert for chrono::Duration {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let (secs, nanos) = numeric_to_secs_nanos(val)?;
+        chrono_duration(secs, nanos)
+    }
+}
+#[cfg(feature = "chrono")]
+impl TryConvertOwned for chrono::Duration {}
+
+/// Conversions to `Lax<chrono::Duration>` accept any Ruby number of seconds,
+/// an `ActiveSupport::Duration` (such as `5.minutes`), or an ISO 8601
+/// duration string (such as `"PT5M"`).
+#[cfg(feature = "chrono")]
+impl TryConvert for Lax<chrono::Duration> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        match lax_duration(val)? {
+            LaxDuration::Numeric(val) => chrono::Duration::try_convert(val).map(Lax),
+            LaxDuration::Nanos(nanos) => {
+                let secs = i64::try_from(nanos.div_euclid(NANOS_PER_SEC as i128))
+                    .map_err(|_| out_of_range())?;
+                let nanos = nanos.rem_euclid(NANOS_PER_SEC as i128) as u32;
+                chrono_duration(secs, nanos).map(Lax)
+            }
+        }
+    }
+}
+#[cfg(feature = "chrono")]
+impl TryConvertOwned for Lax<chrono::Duration> {}
+
+/// Split a Ruby `Numeric` number of seconds in to whole seconds, rounded
+/// down, and nanoseconds.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn numeric_to_secs_nanos(val: Value) -> Result<(i64, u32), Error> {
+    if !val.is_kind_of(get_ruby!().class_numeric()) {
+        return Err(Error::new(
+            exception::type_error(),
+            format!("can't convert {} into time interval", unsafe {
+                val.classname()
+            },),
+        ));
+    }
+    let (secs, frac): (i64, Value) = val.funcall("divmod", (1,))?;
+    let nanos: Value = frac.funcall("*", (NANOS_PER_SEC,))?;
+    let nanos: u32 = nanos.funcall("round", ())?;
+    Ok((secs, nanos))
+}
+
+#[cfg(feature = "time")]
+impl IntoValue for ::time::OffsetDateTime {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        let secs = self.unix_timestamp();
+        let nanos = self.nanosecond();
+        let offset = self.offset().whole_seconds();
+        if self.offset().is_utc() || offset.abs() >= 86400 {
+            handle.time_utc_new(secs, nanos).into()
+        } else {
+            handle.time_offset_new(secs, nanos, offset).unwrap().into()
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<::time::OffsetDateTime> for Value {
+    fn from(val: ::time::OffsetDateTime) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryConvert for ::time::OffsetDateTime {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let time = Time::try_convert(val)?;
+        let (secs, nanos) = time.timespec()?;
+        let offset = ::time::UtcOffset::from_whole_seconds(time.utc_offset()?)
+            .map_err(|_| out_of_range())?;
+        ::time::OffsetDateTime::from_unix_timestamp(secs)
+            .and_then(|t| t.replace_nanosecond(nanos))
+            .map(|t| t.to_offset(offset))
+            .map_err(|_| out_of_range())
+    }
+}
+#[cfg(feature = "time")]
+impl TryConvertOwned for ::time::OffsetDateTime {}
+
+#[cfg(feature = "time")]
+impl IntoValue for ::time::Duration {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        handle.into_value(self.as_seconds_f64())
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<::time::Duration> for Value {
+    fn from(val: ::time::Duration) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryConvert for ::time::Duration {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let (secs, nanos) = numeric_to_secs_nanos(val)?;
+        Ok(::time::Duration::seconds(secs) + ::time::Duration::nanoseconds(nanos as i64))
+    }
+}
+#[cfg(feature = "time")]
+impl TryConvertOwned for ::time::Duration {}
+
+#[cfg(feature = "time")]
+impl IntoValue for Exact<::time::Duration> {
+    fn into_value(self, handle: &RubyHandle) -> Value {
+        handle.rational_secs(self.0.whole_seconds(), self.0.subsec_nanoseconds() as i64)
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<Exact<::time::Duration>> for Value {
+    fn from(val: Exact<::time::Duration>) -> Self {
+        get_ruby!().into_value(val)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryConvert for Exact<::time::Duration> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        ::time::Duration::try_convert(val).map(Exact)
+    }
+}
+#[cfg(feature = "time")]
+impl TryConvertOwned for Exact<::time::Duration> {}
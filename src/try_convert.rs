@@ -1,7 +1,12 @@
 use std::path::PathBuf;
 
-use rb_sys::{rb_get_path, rb_num2dbl};
+use rb_sys::{
+    rb_check_array_type, rb_check_hash_type, rb_check_string_type, rb_check_to_integer,
+    rb_get_path, rb_num2dbl,
+};
 
+#[cfg(feature = "uuid")]
+use crate::encoding::{self, EncodingCapable};
 #[cfg(ruby_use_flonum)]
 use crate::value::Flonum;
 use crate::{
@@ -58,11 +63,162 @@ where
 
 impl TryConvert for bool {
     fn try_convert(val: Value) -> Result<Self, Error> {
-        Ok(val.to_bool())
+        if val.is_true() {
+            Ok(true)
+        } else if val.is_false() {
+            Ok(false)
+        } else {
+            Err(Error::new(
+                exception::type_error(),
+                format!("no implicit conversion of {} into true or false", unsafe {
+                    val.classname()
+                }),
+            ))
+        }
     }
 }
 impl TryConvertOwned for bool {}
 
+/// Adapter for lenient conversions from [`Value`].
+///
+/// Conversions to `Lax<T>` accept a wider range of values than those to `T`.
+/// Useful as an argument type for functions bound with
+/// [`method!`](crate::method!)/[`function!`](crate::function!) to match the
+/// behaviour of Ruby methods that accept "any truthy value".
+///
+/// `Lax<bool>` follows Ruby's truthiness rules (see [`Value::to_bool`]),
+/// `nil` and `false` convert to `false`, all other values to `true`, whereas
+/// `bool` only accepts `true` or `false`.
+///
+/// `Lax<Duration>` (and `Lax<chrono::Duration>` with the `chrono` feature)
+/// accepts an `ActiveSupport::Duration`, such as `5.minutes`, or an ISO 8601
+/// duration string, such as `"PT5M"`, as well as any Ruby number of seconds.
+/// ISO 8601 years and months are converted with the same average lengths as
+/// ActiveSupport.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, Lax, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let val: Value = eval("0").unwrap();
+/// assert!(val.try_convert::<bool>().is_err());
+/// assert!(val.try_convert::<Lax<bool>>().unwrap().0);
+///
+/// let val: Value = eval("nil").unwrap();
+/// assert!(!val.try_convert::<Lax<bool>>().unwrap().0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Lax<T>(pub T);
+
+impl<T> Lax<T> {
+    /// Return the converted value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl TryConvert for Lax<bool> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Ok(Self(val.to_bool()))
+    }
+}
+impl TryConvertOwned for Lax<bool> {}
+
+/// Adapter for strict conversions from [`Value`].
+///
+/// Conversions to `Strict<T>` raise an error where the conversion to `T`
+/// would silently lose information.
+///
+/// `Strict<f32>` raises a `RangeError` when the Ruby number is finite but
+/// too large in magnitude to be represented as an `f32`, or is non-zero but
+/// too small to be represented other than as zero. Values within range are
+/// still rounded to the nearest representable `f32`, as with the conversion
+/// to `f32`. Infinities and `NaN` are passed through unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, Strict, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let val: Value = eval("1e40").unwrap();
+/// assert_eq!(val.try_convert::<f32>().unwrap(), f32::INFINITY);
+/// assert!(val.try_convert::<Strict<f32>>().is_err());
+///
+/// let val: Value = eval("0.5").unwrap();
+/// assert_eq!(val.try_convert::<Strict<f32>>().unwrap().0, 0.5);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Strict<T>(pub T);
+
+impl<T> Strict<T> {
+    /// Return the converted value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl TryConvert for Strict<f32> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let f = f64::try_convert(val)?;
+        let res = f as f32;
+        if f.is_finite() && res.is_infinite() {
+            return Err(Error::new(
+                exception::range_error(),
+                format!("float {} out of range of `f32`", f),
+            ));
+        }
+        if f != 0.0 && res == 0.0 {
+            return Err(Error::new(
+                exception::range_error(),
+                format!("float {} too small to convert into `f32`", f),
+            ));
+        }
+        Ok(Self(res))
+    }
+}
+impl TryConvertOwned for Strict<f32> {}
+
+/// Adapter for conversions from [`Value`] using Ruby's implicit conversion
+/// protocol.
+///
+/// Most conversions already follow the same protocol as Ruby's core methods,
+/// for example [`RString`], `String`, and `PathBuf` accept any object
+/// responding to `#to_str`, [`RArray`] and `Vec<T>` `#to_ary`, `RHash`
+/// `#to_hash`, and [`Integer`] and the Rust integer types `#to_int`.
+/// `Coerced<T>` extends this to types that otherwise only accept an instance
+/// of the exact Ruby class.
+///
+/// `Coerced<Symbol>` accepts a `Symbol`, or a `String` or any object
+/// responding to `#to_str`, which will be converted to a `Symbol`, matching
+/// methods such as `Object#send` and `Module#const_get`.
+///
+/// `Coerced<RRegexp>` accepts a `Regexp` or any object responding to
+/// `#to_regexp`, matching `Regexp.try_convert`.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, Coerced, Symbol, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let val: Value = eval(r#""example""#).unwrap();
+/// assert!(val.try_convert::<Symbol>().is_err());
+/// let sym = val.try_convert::<Coerced<Symbol>>().unwrap().0;
+/// assert_eq!(sym.name().unwrap(), "example");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Coerced<T>(pub T);
+
+impl<T> Coerced<T> {
+    /// Return the converted value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
 impl TryConvert for i8 {
     fn try_convert(val: Value) -> Result<Self, Error> {
         Integer::try_convert(val)?.to_i8()
@@ -133,6 +289,12 @@ impl TryConvert for usize {
 }
 impl TryConvertOwned for usize {}
 
+/// Converts via [`f64`], rounding to the nearest representable `f32`.
+///
+/// Values too large in magnitude for `f32` become infinite, and values too
+/// small become zero, retaining their sign. `NaN` is preserved. See
+/// [`Strict<f32>`](Strict) to raise an error rather than overflow or
+/// underflow.
 impl TryConvert for f32 {
     fn try_convert(val: Value) -> Result<Self, Error> {
         f64::try_convert(val).map(|f| f as f32)
@@ -692,6 +854,342 @@ where
 {
 }
 
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12> TryConvert
+    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12)
+where
+    T0: TryConvert,
+    T1: TryConvert,
+    T2: TryConvert,
+    T3: TryConvert,
+    T4: TryConvert,
+    T5: TryConvert,
+    T6: TryConvert,
+    T7: TryConvert,
+    T8: TryConvert,
+    T9: TryConvert,
+    T10: TryConvert,
+    T11: TryConvert,
+    T12: TryConvert,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        debug_assert_value!(val);
+        let array = RArray::try_convert(val)?;
+        let slice = unsafe { array.as_slice() };
+        if slice.len() != 13 {
+            return Err(Error::new(
+                exception::type_error(),
+                "expected Array of length 13",
+            ));
+        }
+        Ok((
+            slice[0].try_convert()?,
+            slice[1].try_convert()?,
+            slice[2].try_convert()?,
+            slice[3].try_convert()?,
+            slice[4].try_convert()?,
+            slice[5].try_convert()?,
+            slice[6].try_convert()?,
+            slice[7].try_convert()?,
+            slice[8].try_convert()?,
+            slice[9].try_convert()?,
+            slice[10].try_convert()?,
+            slice[11].try_convert()?,
+            slice[12].try_convert()?,
+        ))
+    }
+}
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12> TryConvertOwned
+    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12)
+where
+    T0: TryConvert,
+    T1: TryConvert,
+    T2: TryConvert,
+    T3: TryConvert,
+    T4: TryConvert,
+    T5: TryConvert,
+    T6: TryConvert,
+    T7: TryConvert,
+    T8: TryConvert,
+    T9: TryConvert,
+    T10: TryConvert,
+    T11: TryConvert,
+    T12: TryConvert,
+{
+}
+
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13> TryConvert
+    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13)
+where
+    T0: TryConvert,
+    T1: TryConvert,
+    T2: TryConvert,
+    T3: TryConvert,
+    T4: TryConvert,
+    T5: TryConvert,
+    T6: TryConvert,
+    T7: TryConvert,
+    T8: TryConvert,
+    T9: TryConvert,
+    T10: TryConvert,
+    T11: TryConvert,
+    T12: TryConvert,
+    T13: TryConvert,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        debug_assert_value!(val);
+        let array = RArray::try_convert(val)?;
+        let slice = unsafe { array.as_slice() };
+        if slice.len() != 14 {
+            return Err(Error::new(
+                exception::type_error(),
+                "expected Array of length 14",
+            ));
+        }
+        Ok((
+            slice[0].try_convert()?,
+            slice[1].try_convert()?,
+            slice[2].try_convert()?,
+            slice[3].try_convert()?,
+            slice[4].try_convert()?,
+            slice[5].try_convert()?,
+            slice[6].try_convert()?,
+            slice[7].try_convert()?,
+            slice[8].try_convert()?,
+            slice[9].try_convert()?,
+            slice[10].try_convert()?,
+            slice[11].try_convert()?,
+            slice[12].try_convert()?,
+            slice[13].try_convert()?,
+        ))
+    }
+}
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13> TryConvertOwned
+    for (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13)
+where
+    T0: TryConvert,
+    T1: TryConvert,
+    T2: TryConvert,
+    T3: TryConvert,
+    T4: TryConvert,
+    T5: TryConvert,
+    T6: TryConvert,
+    T7: TryConvert,
+    T8: TryConvert,
+    T9: TryConvert,
+    T10: TryConvert,
+    T11: TryConvert,
+    T12: TryConvert,
+    T13: TryConvert,
+{
+}
+
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14> TryConvert
+    for (
+        T0,
+        T1,
+        T2,
+        T3,
+        T4,
+        T5,
+        T6,
+        T7,
+        T8,
+        T9,
+        T10,
+        T11,
+        T12,
+        T13,
+        T14,
+    )
+where
+    T0: TryConvert,
+    T1: TryConvert,
+    T2: TryConvert,
+    T3: TryConvert,
+    T4: TryConvert,
+    T5: TryConvert,
+    T6: TryConvert,
+    T7: TryConvert,
+    T8: TryConvert,
+    T9: TryConvert,
+    T10: TryConvert,
+    T11: TryConvert,
+    T12: TryConvert,
+    T13: TryConvert,
+    T14: TryConvert,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        debug_assert_value!(val);
+        let array = RArray::try_convert(val)?;
+        let slice = unsafe { array.as_slice() };
+        if slice.len() != 15 {
+            return Err(Error::new(
+                exception::type_error(),
+                "expected Array of length 15",
+            ));
+        }
+        Ok((
+            slice[0].try_convert()?,
+            slice[1].try_convert()?,
+            slice[2].try_convert()?,
+            slice[3].try_convert()?,
+            slice[4].try_convert()?,
+            slice[5].try_convert()?,
+            slice[6].try_convert()?,
+            slice[7].try_convert()?,
+            slice[8].try_convert()?,
+            slice[9].try_convert()?,
+            slice[10].try_convert()?,
+            slice[11].try_convert()?,
+            slice[12].try_convert()?,
+            slice[13].try_convert()?,
+            slice[14].try_convert()?,
+        ))
+    }
+}
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14> TryConvertOwned
+    for (
+        T0,
+        T1,
+        T2,
+        T3,
+        T4,
+        T5,
+        T6,
+        T7,
+        T8,
+        T9,
+        T10,
+        T11,
+        T12,
+        T13,
+        T14,
+    )
+where
+    T0: TryConvert,
+    T1: TryConvert,
+    T2: TryConvert,
+    T3: TryConvert,
+    T4: TryConvert,
+    T5: TryConvert,
+    T6: TryConvert,
+    T7: TryConvert,
+    T8: TryConvert,
+    T9: TryConvert,
+    T10: TryConvert,
+    T11: TryConvert,
+    T12: TryConvert,
+    T13: TryConvert,
+    T14: TryConvert,
+{
+}
+
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15> TryConvert
+    for (
+        T0,
+        T1,
+        T2,
+        T3,
+        T4,
+        T5,
+        T6,
+        T7,
+        T8,
+        T9,
+        T10,
+        T11,
+        T12,
+        T13,
+        T14,
+        T15,
+    )
+where
+    T0: TryConvert,
+    T1: TryConvert,
+    T2: TryConvert,
+    T3: TryConvert,
+    T4: TryConvert,
+    T5: TryConvert,
+    T6: TryConvert,
+    T7: TryConvert,
+    T8: TryConvert,
+    T9: TryConvert,
+    T10: TryConvert,
+    T11: TryConvert,
+    T12: TryConvert,
+    T13: TryConvert,
+    T14: TryConvert,
+    T15: TryConvert,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        debug_assert_value!(val);
+        let array = RArray::try_convert(val)?;
+        let slice = unsafe { array.as_slice() };
+        if slice.len() != 16 {
+            return Err(Error::new(
+                exception::type_error(),
+                "expected Array of length 16",
+            ));
+        }
+        Ok((
+            slice[0].try_convert()?,
+            slice[1].try_convert()?,
+            slice[2].try_convert()?,
+            slice[3].try_convert()?,
+            slice[4].try_convert()?,
+            slice[5].try_convert()?,
+            slice[6].try_convert()?,
+            slice[7].try_convert()?,
+            slice[8].try_convert()?,
+            slice[9].try_convert()?,
+            slice[10].try_convert()?,
+            slice[11].try_convert()?,
+            slice[12].try_convert()?,
+            slice[13].try_convert()?,
+            slice[14].try_convert()?,
+            slice[15].try_convert()?,
+        ))
+    }
+}
+impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15> TryConvertOwned
+    for (
+        T0,
+        T1,
+        T2,
+        T3,
+        T4,
+        T5,
+        T6,
+        T7,
+        T8,
+        T9,
+        T10,
+        T11,
+        T12,
+        T13,
+        T14,
+        T15,
+    )
+where
+    T0: TryConvert,
+    T1: TryConvert,
+    T2: TryConvert,
+    T3: TryConvert,
+    T4: TryConvert,
+    T5: TryConvert,
+    T6: TryConvert,
+    T7: TryConvert,
+    T8: TryConvert,
+    T9: TryConvert,
+    T10: TryConvert,
+    T11: TryConvert,
+    T12: TryConvert,
+    T13: TryConvert,
+    T14: TryConvert,
+    T15: TryConvert,
+{
+}
+
 impl<K, V> TryConvert for std::collections::HashMap<K, V>
 where
     K: TryConvertOwned + Eq + std::hash::Hash,
@@ -734,6 +1232,107 @@ impl TryConvert for PathBuf {
 
 impl TryConvertOwned for PathBuf {}
 
+#[cfg(unix)]
+impl TryConvert for std::ffi::OsString {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        use std::os::unix::ffi::OsStringExt;
+
+        let r_string = RString::try_convert(val)?;
+        let bytes = unsafe { r_string.as_slice() }.to_owned();
+        Ok(std::ffi::OsString::from_vec(bytes))
+    }
+}
+
+#[cfg(not(unix))]
+impl TryConvert for std::ffi::OsString {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        RString::try_convert(val)?.to_string().map(Into::into)
+    }
+}
+
+impl TryConvertOwned for std::ffi::OsString {}
+
+impl TryConvert for std::ffi::CString {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let r_string = RString::try_convert(val)?;
+        let bytes = unsafe { r_string.as_slice() }.to_owned();
+        Ok(std::ffi::CString::new(bytes)?)
+    }
+}
+impl TryConvertOwned for std::ffi::CString {}
+
+#[cfg(feature = "uuid")]
+impl TryConvert for uuid::Uuid {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let r_string = RString::try_convert(val)?;
+        let bytes = unsafe { r_string.as_slice() };
+        // the 16 byte binary form is only accepted from a binary string, any
+        // other encoding is parsed as text
+        let res = if r_string.enc_get() == encoding::Index::ascii8bit() && bytes.len() == 16 {
+            uuid::Uuid::from_slice(bytes)
+        } else {
+            uuid::Uuid::parse_str(&r_string.to_string()?)
+        };
+        res.map_err(|e| Error::new(exception::arg_error(), e.to_string()))
+    }
+}
+#[cfg(feature = "uuid")]
+impl TryConvertOwned for uuid::Uuid {}
+
+#[cfg(feature = "bytes")]
+impl TryConvert for bytes::Bytes {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Ok(RString::try_convert(val)?.to_binary_bytes().into())
+    }
+}
+#[cfg(feature = "bytes")]
+impl TryConvertOwned for bytes::Bytes {}
+
+/// Conversions from [`Value`] to Ruby types using Ruby's implicit conversion
+/// protocols, such as `#to_str` and `#to_ary`.
+///
+/// Unlike [`TryConvert`], if the value can not be implicitly converted this
+/// returns `Ok(None)` rather than an error.
+///
+/// See [`Value::check_convert`].
+pub trait CheckConvert: Sized {
+    /// Convert `val` into `Self`, if `val` can be implicitly converted.
+    fn check_convert(val: Value) -> Result<Option<Self>, Error>;
+}
+
+impl CheckConvert for RString {
+    fn check_convert(val: Value) -> Result<Option<Self>, Error> {
+        protect(|| unsafe { Value::new(rb_check_string_type(val.as_rb_value())) })
+            .map(RString::from_value)
+    }
+}
+
+impl CheckConvert for RArray {
+    fn check_convert(val: Value) -> Result<Option<Self>, Error> {
+        protect(|| unsafe { Value::new(rb_check_array_type(val.as_rb_value())) })
+            .map(RArray::from_value)
+    }
+}
+
+impl CheckConvert for RHash {
+    fn check_convert(val: Value) -> Result<Option<Self>, Error> {
+        protect(|| unsafe { Value::new(rb_check_hash_type(val.as_rb_value())) })
+            .map(RHash::from_value)
+    }
+}
+
+impl CheckConvert for Integer {
+    fn check_convert(val: Value) -> Result<Option<Self>, Error> {
+        protect(|| unsafe {
+            Value::new(rb_check_to_integer(
+                val.as_rb_value(),
+                "to_int\0".as_ptr() as *const _,
+            ))
+        })
+        .map(Integer::from_value)
+    }
+}
+
 /// Trait for types that can be used as an arguments list when calling Ruby
 /// methods.
 pub trait ArgList {
@@ -1035,6 +1634,169 @@ where
     }
 }
 
+impl<A, B, C, D, E, F, G, H, I, J, K, L, M> ArgList for (A, B, C, D, E, F, G, H, I, J, K, L, M)
+where
+    A: Into<Value>,
+    B: Into<Value>,
+    C: Into<Value>,
+    D: Into<Value>,
+    E: Into<Value>,
+    F: Into<Value>,
+    G: Into<Value>,
+    H: Into<Value>,
+    I: Into<Value>,
+    J: Into<Value>,
+    K: Into<Value>,
+    L: Into<Value>,
+    M: Into<Value>,
+{
+    type Output = [Value; 13];
+
+    fn into_arg_list(self) -> Self::Output {
+        [
+            self.0.into(),
+            self.1.into(),
+            self.2.into(),
+            self.3.into(),
+            self.4.into(),
+            self.5.into(),
+            self.6.into(),
+            self.7.into(),
+            self.8.into(),
+            self.9.into(),
+            self.10.into(),
+            self.11.into(),
+            self.12.into(),
+        ]
+    }
+}
+
+impl<A, B, C, D, E, F, G, H, I, J, K, L, M, N> ArgList
+    for (A, B, C, D, E, F, G, H, I, J, K, L, M, N)
+where
+    A: Into<Value>,
+    B: Into<Value>,
+    C: Into<Value>,
+    D: Into<Value>,
+    E: Into<Value>,
+    F: Into<Value>,
+    G: Into<Value>,
+    H: Into<Value>,
+    I: Into<Value>,
+    J: Into<Value>,
+    K: Into<Value>,
+    L: Into<Value>,
+    M: Into<Value>,
+    N: Into<Value>,
+{
+    type Output = [Value; 14];
+
+    fn into_arg_list(self) -> Self::Output {
+        [
+            self.0.into(),
+            self.1.into(),
+            self.2.into(),
+            self.3.into(),
+            self.4.into(),
+            self.5.into(),
+            self.6.into(),
+            self.7.into(),
+            self.8.into(),
+            self.9.into(),
+            self.10.into(),
+            self.11.into(),
+            self.12.into(),
+            self.13.into(),
+        ]
+    }
+}
+
+impl<A, B, C, D, E, F, G, H, I, J, K, L, M, N, O> ArgList
+    for (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O)
+where
+    A: Into<Value>,
+    B: Into<Value>,
+    C: Into<Value>,
+    D: Into<Value>,
+    E: Into<Value>,
+    F: Into<Value>,
+    G: Into<Value>,
+    H: Into<Value>,
+    I: Into<Value>,
+    J: Into<Value>,
+    K: Into<Value>,
+    L: Into<Value>,
+    M: Into<Value>,
+    N: Into<Value>,
+    O: Into<Value>,
+{
+    type Output = [Value; 15];
+
+    fn into_arg_list(self) -> Self::Output {
+        [
+            self.0.into(),
+            self.1.into(),
+            self.2.into(),
+            self.3.into(),
+            self.4.into(),
+            self.5.into(),
+            self.6.into(),
+            self.7.into(),
+            self.8.into(),
+            self.9.into(),
+            self.10.into(),
+            self.11.into(),
+            self.12.into(),
+            self.13.into(),
+            self.14.into(),
+        ]
+    }
+}
+
+impl<A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P> ArgList
+    for (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P)
+where
+    A: Into<Value>,
+    B: Into<Value>,
+    C: Into<Value>,
+    D: Into<Value>,
+    E: Into<Value>,
+    F: Into<Value>,
+    G: Into<Value>,
+    H: Into<Value>,
+    I: Into<Value>,
+    J: Into<Value>,
+    K: Into<Value>,
+    L: Into<Value>,
+    M: Into<Value>,
+    N: Into<Value>,
+    O: Into<Value>,
+    P: Into<Value>,
+{
+    type Output = [Value; 16];
+
+    fn into_arg_list(self) -> Self::Output {
+        [
+            self.0.into(),
+            self.1.into(),
+            self.2.into(),
+            self.3.into(),
+            self.4.into(),
+            self.5.into(),
+            self.6.into(),
+            self.7.into(),
+            self.8.into(),
+            self.9.into(),
+            self.10.into(),
+            self.11.into(),
+            self.12.into(),
+            self.13.into(),
+            self.14.into(),
+            self.15.into(),
+        ]
+    }
+}
+
 impl<const N: usize> ArgList for [Value; N] {
     type Output = [Value; N];
 
@@ -26,10 +26,13 @@ const RUBY_TYPED_FREE_IMMEDIATELY: u32 = 1;
 const RUBY_TYPED_WB_PROTECTED: u32 = rb_sys::ruby_fl_type::RUBY_FL_WB_PROTECTED as u32;
 
 use crate::{
+    block::{block_given, Yield},
     class::RClass,
     error::{bug_from_panic, Error},
     exception,
     into_value::IntoValue,
+    method::{Method0, Method1},
+    module::{self, Module},
     object::Object,
     r_typed_data::RTypedData,
     ruby_handle::RubyHandle,
@@ -115,6 +118,37 @@ where
     ///
     /// This function **must not** panic. The process will abort if this
     /// function panics.
+    ///
+    /// # Examples
+    ///
+    /// Values marked with [`gc::mark_movable`](crate::gc::mark_movable) in
+    /// [`mark`](DataTypeFunctions::mark) must be updated with
+    /// [`gc::location`](crate::gc::location) here. As this function only has
+    /// access to `&self` this requires interior mutability.
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    ///
+    /// use magnus::{gc, DataTypeFunctions, TypedData, Value};
+    ///
+    /// # #[cfg(ruby_gte_2_7)]
+    /// #[derive(TypedData)]
+    /// #[magnus(class = "Holder", mark, compact)]
+    /// struct Holder {
+    ///     value: Cell<Value>,
+    /// }
+    ///
+    /// # #[cfg(ruby_gte_2_7)]
+    /// impl DataTypeFunctions for Holder {
+    ///     fn mark(&self) {
+    ///         gc::mark_movable(&self.value.get());
+    ///     }
+    ///
+    ///     fn compact(&self) {
+    ///         self.value.set(gc::location(self.value.get()));
+    ///     }
+    /// }
+    /// ```
     fn compact(&self) {}
 
     /// Extern wrapper for `free`. Don't define or call.
@@ -237,7 +271,9 @@ where
 
     /// Enable the 'write barrier protected' flag.
     ///
-    /// You almost certainly don't want to enable this.
+    /// You almost certainly don't want to enable this. If you do, any Ruby
+    /// objects stored in your type after it has been wrapped must be stored
+    /// with a write barrier, see [`gc::WriteBarrier`](crate::gc::WriteBarrier).
     pub fn wb_protected(&mut self) {
         self.wb_protected = true;
     }
@@ -245,8 +281,17 @@ where
     /// Enable the 'frozen_shareable' flag.
     ///
     /// Set this if your type is thread safe when the Ruby wrapper object is
-    /// frozen.
-    pub fn frozen_shareable(&mut self) {
+    /// frozen. This allows frozen instances of your type to be shared between
+    /// Ractors, where they may be accessed from multiple threads at once, so
+    /// `T` must be [`Sync`]. `T` must also not allow mutation through a shared
+    /// reference (e.g. with a [`RefCell`](std::cell::RefCell)) that would be
+    /// visible to Ruby after the object is frozen.
+    ///
+    /// See [`Ractor::make_shareable`](crate::Ractor::make_shareable).
+    pub fn frozen_shareable(&mut self)
+    where
+        T: Sync,
+    {
         self.frozen_shareable = true;
     }
 
@@ -787,3 +832,206 @@ where
             .unwrap_or(false)
     }
 }
+
+/// Trait for a Ruby-compatible `#each` method.
+///
+/// Implement this for a wrapped type, then call [`define_enumerable`] to
+/// define `#each` and include `Enumerable` in the type's class, giving it
+/// `#map`, `#select`, `#to_a`, etc.
+///
+/// The iterator returned by [`each`](Each::each) must own its data (e.g. by
+/// cloning), as the wrapped data may be mutated by the block it's being
+/// yielded to.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_class, embed::init, eval, function, typed_data, Module, Object};
+///
+/// #[magnus::wrap(class = "NumberList")]
+/// struct NumberList(Vec<i64>);
+///
+/// impl NumberList {
+///     fn new() -> Self {
+///         Self(vec![1, 2, 3])
+///     }
+/// }
+///
+/// impl typed_data::Each for NumberList {
+///     type Item = i64;
+///     type Iter = std::vec::IntoIter<i64>;
+///
+///     fn each(&self) -> Self::Iter {
+///         self.0.clone().into_iter()
+///     }
+/// }
+///
+/// let _cleanup = unsafe { init() };
+///
+/// let class = define_class("NumberList", Default::default()).unwrap();
+/// class
+///     .define_singleton_method("new", function!(NumberList::new, 0))
+///     .unwrap();
+/// typed_data::define_enumerable::<NumberList>().unwrap();
+///
+/// assert!(eval::<bool>("NumberList.new.map { |i| i * 2 } == [2, 4, 6]").unwrap());
+/// assert!(eval::<bool>("NumberList.new.select(&:odd?) == [1, 3]").unwrap());
+/// assert!(eval::<bool>("NumberList.new.each.next == 1").unwrap());
+/// ```
+pub trait Each {
+    /// The type of the values yielded to the block.
+    type Item: Into<Value>;
+    /// The iterator used to produce values.
+    type Iter: Iterator<Item = Self::Item>;
+
+    /// Returns an iterator over the values to yield from `#each`.
+    fn each(&self) -> Self::Iter;
+}
+
+unsafe extern "C" fn each<T>(rb_self: Value) -> Value
+where
+    T: Each + TypedData,
+{
+    Method0::new(|rb_self: Value| -> Result<Yield<T::Iter>, Error> {
+        if block_given() {
+            Ok(Yield::Iter(rb_self.try_convert::<&T>()?.each()))
+        } else {
+            Ok(Yield::Enumerator(rb_self.enumeratorize("each", ())))
+        }
+    })
+    .call_handle_error(rb_self)
+}
+
+/// Define `#each` from `T`'s implementation of [`Each`] in `T`'s class, and
+/// include `Enumerable` in the class.
+///
+/// `#each` will return an `Enumerator` when called without a block.
+///
+/// `T`'s class must have been defined before calling this function.
+///
+/// See [`Each`] for an example.
+pub fn define_enumerable<T>() -> Result<(), Error>
+where
+    T: Each + TypedData,
+{
+    let class = T::class();
+    class.define_method("each", each::<T> as unsafe extern "C" fn(Value) -> Value)?;
+    class.include_module(module::enumerable())
+}
+
+/// Trait for a Ruby-compatible `#<=>` method.
+///
+/// Automatically implemented for any type implementing [`PartialOrd`] and
+/// [`TryConvert`].
+///
+/// Returns `None` (`nil` in Ruby) when `other` is not the same type, or the
+/// values can not be compared.
+///
+/// See [`define_comparable`] to define `#<=>` and include `Comparable` in a
+/// wrapped type's class in one step.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_class, embed::init, eval, function, method, typed_data, Module, Object};
+///
+/// #[magnus::wrap(class = "Version")]
+/// #[derive(PartialEq, PartialOrd)]
+/// struct Version(u32, u32, u32);
+///
+/// impl Version {
+///     fn new(major: u32, minor: u32, patch: u32) -> Self {
+///         Self(major, minor, patch)
+///     }
+/// }
+///
+/// let _cleanup = unsafe { init() };
+///
+/// let class = define_class("Version", Default::default()).unwrap();
+/// class
+///     .define_singleton_method("new", function!(Version::new, 3))
+///     .unwrap();
+/// class
+///     .define_method("<=>", method!(<Version as typed_data::Cmp>::compare, 1))
+///     .unwrap();
+///
+/// assert!(eval::<bool>("(Version.new(1, 2, 3) <=> Version.new(1, 10, 0)) == -1").unwrap());
+/// assert!(eval::<bool>("(Version.new(1, 2, 3) <=> 1).nil?").unwrap());
+/// ```
+pub trait Cmp {
+    // Docs at trait level.
+    #![allow(missing_docs)]
+    fn compare(&self, other: Value) -> Option<i64>;
+}
+
+impl<'a, T> Cmp for T
+where
+    T: PartialOrd + 'a,
+    &'a T: TryConvert,
+{
+    fn compare(&self, other: Value) -> Option<i64> {
+        other
+            .try_convert::<&'a T>()
+            .ok()
+            .and_then(|o| self.partial_cmp(o))
+            .map(|o| o as i64)
+    }
+}
+
+unsafe extern "C" fn compare<T>(rb_self: Value, other: Value) -> Value
+where
+    T: Cmp + TypedData,
+{
+    Method1::new(|rb_self: &T, other: Value| rb_self.compare(other))
+        .call_handle_error(rb_self, other)
+}
+
+/// Define `#<=>` from `T`'s implementation of [`Cmp`] in `T`'s class, and
+/// include `Comparable` in the class.
+///
+/// This gives the class `#<`, `#<=`, `#==`, `#>`, `#>=`, `#between?`, and
+/// `#clamp`.
+///
+/// `T`'s class must have been defined before calling this function.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{define_class, embed::init, eval, function, typed_data, Module, Object};
+///
+/// #[magnus::wrap(class = "Version")]
+/// #[derive(PartialEq, Eq, PartialOrd, Ord)]
+/// struct Version(u32, u32, u32);
+///
+/// impl Version {
+///     fn new(major: u32, minor: u32, patch: u32) -> Self {
+///         Self(major, minor, patch)
+///     }
+/// }
+///
+/// let _cleanup = unsafe { init() };
+///
+/// let class = define_class("Version", Default::default()).unwrap();
+/// class
+///     .define_singleton_method("new", function!(Version::new, 3))
+///     .unwrap();
+/// typed_data::define_comparable::<Version>().unwrap();
+///
+/// assert!(eval::<bool>("Version.new(1, 2, 3) < Version.new(1, 10, 0)").unwrap());
+/// assert!(eval::<bool>("Version.new(1, 2, 3) == Version.new(1, 2, 3)").unwrap());
+/// assert!(eval::<bool>(
+///     "Version.new(1, 5, 0).between?(Version.new(1, 0, 0), Version.new(2, 0, 0))"
+/// )
+/// .unwrap());
+/// ```
+pub fn define_comparable<T>() -> Result<(), Error>
+where
+    T: Cmp + TypedData,
+{
+    let class = T::class();
+    class.define_method(
+        "<=>",
+        compare::<T> as unsafe extern "C" fn(Value, Value) -> Value,
+    )?;
+    class.include_module(module::comparable())
+}
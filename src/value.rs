@@ -5,14 +5,21 @@ mod flonum;
 
 use std::{
     borrow::Cow,
+    cmp::Ordering,
+    collections::HashMap,
     convert::TryFrom,
     ffi::CStr,
     fmt,
+    marker::PhantomData,
     mem::transmute,
     num::NonZeroUsize,
     ops::{Deref, DerefMut},
     os::raw::{c_char, c_int, c_long, c_ulong},
     ptr,
+    sync::{
+        atomic::{self, AtomicUsize},
+        Mutex,
+    },
 };
 
 #[cfg(ruby_use_flonum)]
@@ -20,10 +27,11 @@ pub use flonum::Flonum;
 use rb_sys::{
     rb_any_to_s, rb_block_call, rb_check_funcall, rb_check_id, rb_check_id_cstr,
     rb_check_symbol_cstr, rb_enumeratorize_with_size, rb_eql, rb_equal, rb_funcall_with_block,
-    rb_funcallv, rb_gc_register_address, rb_gc_unregister_address, rb_hash, rb_id2name, rb_id2sym,
-    rb_inspect, rb_intern3, rb_ll2inum, rb_obj_as_string, rb_obj_classname, rb_obj_freeze,
-    rb_obj_is_kind_of, rb_obj_respond_to, rb_sym2id, rb_ull2inum, ruby_fl_type,
-    ruby_special_consts, ruby_value_type, RBasic, ID, VALUE,
+    rb_funcallv, rb_funcallv_public, rb_gc_register_address, rb_gc_unregister_address, rb_hash,
+    rb_id2name, rb_id2sym, rb_inspect, rb_intern3, rb_ll2inum, rb_obj_as_string, rb_obj_classname,
+    rb_obj_clone, rb_obj_dup, rb_obj_freeze, rb_obj_id, rb_obj_is_instance_of, rb_obj_is_kind_of,
+    rb_obj_respond_to, rb_sym2id, rb_ull2inum, ruby_fl_type, ruby_special_consts, ruby_value_type,
+    RBasic, ID, VALUE,
 };
 
 // These don't seem to appear consistently in bindgen output, not sure if they
@@ -41,13 +49,15 @@ use crate::{
     exception,
     integer::{Integer, IntegerType},
     into_value::IntoValue,
+    memoize,
     method::{Block, BlockReturn},
     module::Module,
     r_bignum::RBignum,
+    r_hash::RHash,
     r_string::RString,
-    ruby_handle::RubyHandle,
+    ruby_handle::{RubyHandle, RubyUnavailableError},
     symbol::Symbol,
-    try_convert::{ArgList, TryConvert, TryConvertOwned},
+    try_convert::{ArgList, CheckConvert, TryConvert, TryConvertOwned},
 };
 
 /// Debug assertation that the Value hasn't been garbage collected.
@@ -119,8 +129,23 @@ impl Value {
         unsafe { (!self.is_immediate()).then(|| self.r_basic_unchecked()) }
     }
 
+    /// Returns whether `self` is Ruby's `false` value.
+    ///
+    /// Note that this only checks for `false`, not falsiness, see
+    /// [`to_bool`](Value::to_bool) to test falsiness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(eval::<Value>("false").unwrap().is_false());
+    /// assert!(!eval::<Value>("nil").unwrap().is_false());
+    /// assert!(!eval::<Value>("true").unwrap().is_false());
+    /// ```
     #[inline]
-    fn is_false(self) -> bool {
+    pub fn is_false(self) -> bool {
         self.as_rb_value() == ruby_special_consts::RUBY_Qfalse as VALUE
     }
 
@@ -142,13 +167,42 @@ impl Value {
         self.as_rb_value() == ruby_special_consts::RUBY_Qnil as VALUE
     }
 
+    /// Returns whether `self` is Ruby's `true` value.
+    ///
+    /// Note that this only checks for `true`, not truthiness, see
+    /// [`to_bool`](Value::to_bool) to test truthiness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(eval::<Value>("true").unwrap().is_true());
+    /// assert!(!eval::<Value>("1").unwrap().is_true());
+    /// assert!(!eval::<Value>("false").unwrap().is_true());
+    /// ```
     #[inline]
-    fn is_true(self) -> bool {
+    pub fn is_true(self) -> bool {
         self.as_rb_value() == ruby_special_consts::RUBY_Qtrue as VALUE
     }
 
+    /// Returns whether `self` is Ruby's internal `undef` value.
+    ///
+    /// `undef` is never visible to Ruby code, but may be encountered when
+    /// working with low-level APIs, such as optional arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::value::{QNIL, QUNDEF};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// assert!(unsafe { QUNDEF.to_value() }.is_undef());
+    /// assert!(!QNIL.is_undef());
+    /// ```
     #[inline]
-    pub(crate) fn is_undef(self) -> bool {
+    pub fn is_undef(self) -> bool {
         self.as_rb_value() == ruby_special_consts::RUBY_Qundef as VALUE
     }
 
@@ -302,6 +356,194 @@ impl Value {
         }
     }
 
+    /// Checks for equality, delegating to the Ruby method `#==`.
+    ///
+    /// This is the same as [`Value::equal`], but accepts any type that can be
+    /// converted to a Ruby value, such as Rust integers and strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let val: Value = eval("1.0").unwrap();
+    /// assert!(val.op_eq(1).unwrap());
+    /// assert!(!val.op_eq("1").unwrap());
+    /// ```
+    pub fn op_eq<T>(self, other: T) -> Result<bool, Error>
+    where
+        T: Into<Value>,
+    {
+        self.equal(&other.into())
+    }
+
+    /// Compare `self` and `other`, delegating to the Ruby method `#<=>`.
+    ///
+    /// Returns `Ok(None)` if `self` and `other` are not comparable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    ///
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let val: Value = eval("2").unwrap();
+    /// assert_eq!(val.op_cmp(1).unwrap(), Some(Ordering::Greater));
+    /// assert_eq!(val.op_cmp(2.0).unwrap(), Some(Ordering::Equal));
+    /// assert_eq!(val.op_cmp("2").unwrap(), None);
+    /// ```
+    pub fn op_cmp<T>(self, other: T) -> Result<Option<Ordering>, Error>
+    where
+        T: Into<Value>,
+    {
+        let res: Option<Integer> = self.funcall("<=>", (other.into(),))?;
+        res.map(|i| Ok(i.to_i64()?.cmp(&0))).transpose()
+    }
+
+    /// Checks if `self` is less than `other`, delegating to the Ruby method
+    /// `#<`.
+    ///
+    /// Returns `Err` if `self` and `other` are not comparable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let val: Value = eval("1").unwrap();
+    /// assert!(val.op_lt(2).unwrap());
+    /// assert!(!val.op_lt(1).unwrap());
+    /// assert!(val.op_lt("2").is_err());
+    /// ```
+    pub fn op_lt<T>(self, other: T) -> Result<bool, Error>
+    where
+        T: Into<Value>,
+    {
+        self.funcall::<_, _, Value>("<", (other.into(),))
+            .map(Value::to_bool)
+    }
+
+    /// Checks if `self` is less than or equal to `other`, delegating to the
+    /// Ruby method `#<=`.
+    ///
+    /// Returns `Err` if `self` and `other` are not comparable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let val: Value = eval("1").unwrap();
+    /// assert!(val.op_le(1).unwrap());
+    /// assert!(!val.op_le(0).unwrap());
+    /// ```
+    pub fn op_le<T>(self, other: T) -> Result<bool, Error>
+    where
+        T: Into<Value>,
+    {
+        self.funcall::<_, _, Value>("<=", (other.into(),))
+            .map(Value::to_bool)
+    }
+
+    /// Checks if `self` is greater than `other`, delegating to the Ruby
+    /// method `#>`.
+    ///
+    /// Returns `Err` if `self` and `other` are not comparable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let val: Value = eval(r#""b""#).unwrap();
+    /// assert!(val.op_gt("a").unwrap());
+    /// assert!(!val.op_gt("c").unwrap());
+    /// ```
+    pub fn op_gt<T>(self, other: T) -> Result<bool, Error>
+    where
+        T: Into<Value>,
+    {
+        self.funcall::<_, _, Value>(">", (other.into(),))
+            .map(Value::to_bool)
+    }
+
+    /// Checks if `self` is greater than or equal to `other`, delegating to
+    /// the Ruby method `#>=`.
+    ///
+    /// Returns `Err` if `self` and `other` are not comparable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let val: Value = eval("1.5").unwrap();
+    /// assert!(val.op_ge(1.5).unwrap());
+    /// assert!(!val.op_ge(2).unwrap());
+    /// ```
+    pub fn op_ge<T>(self, other: T) -> Result<bool, Error>
+    where
+        T: Into<Value>,
+    {
+        self.funcall::<_, _, Value>(">=", (other.into(),))
+            .map(Value::to_bool)
+    }
+
+    /// Checks if `self` and `other` are the same object.
+    ///
+    /// This is equivalent to Ruby's `#equal?` method, but can not be
+    /// overridden, and never calls a Ruby method, so is much faster than
+    /// [`Value::equal`] (`#==`) or [`Value::eql`] (`#eql?`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RString, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let a = RString::new("example");
+    /// let b = RString::new("example");
+    /// assert!(a.is_equal(a));
+    /// assert!(!a.is_equal(b));
+    /// assert!(a.equal(b).unwrap());
+    ///
+    /// assert!(eval::<Value>(":foo").unwrap().is_equal(eval::<Value>(":foo").unwrap()));
+    /// ```
+    #[inline]
+    pub fn is_equal<T>(self, other: T) -> bool
+    where
+        T: Deref<Target = Value>,
+    {
+        self.as_rb_value() == other.as_rb_value()
+    }
+
+    /// Returns an integer uniquely identifying `self` for the lifetime of the
+    /// object.
+    ///
+    /// This is equivalent to Ruby's `#object_id` method, but can not be
+    /// overridden.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RString};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let s = RString::new("example");
+    /// let res: bool = eval!("s.object_id == id", s, id = s.object_id()).unwrap();
+    /// assert!(res);
+    /// ```
+    pub fn object_id(self) -> Integer {
+        unsafe { Integer::from_rb_value_unchecked(rb_obj_id(self.as_rb_value())) }
+    }
+
     /// Returns an integer non-uniquely identifying `self`.
     ///
     /// The return value is not stable between different Ruby processes.
@@ -446,6 +688,54 @@ impl Value {
         unsafe { rb_obj_freeze(self.as_rb_value()) };
     }
 
+    /// Return a shallow copy of `self`.
+    ///
+    /// This is equivalent to Ruby's `Object#dup`. The copy will not be frozen,
+    /// and will not copy `self`'s singleton class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let ary: RArray = eval("[1, 2, 3].freeze").unwrap();
+    /// let copy: RArray = ary.dup().unwrap().try_convert().unwrap();
+    /// assert!(!copy.is_frozen());
+    /// assert!(copy.eql(ary).unwrap());
+    /// ```
+    pub fn dup(self) -> Result<Value, Error> {
+        protect(|| unsafe { Value::new(rb_obj_dup(self.as_rb_value())) })
+    }
+
+    /// Return a shallow copy of `self`, including its singleton class.
+    ///
+    /// This is equivalent to Ruby's `Object#clone`. With `None` the copy will
+    /// be frozen if `self` is frozen, `Some(false)` will return an unfrozen
+    /// copy, and `Some(true)` a frozen copy (`Some(true)` requires Ruby 3.0 or
+    /// later).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let val: Value = eval(r#""example".freeze"#).unwrap();
+    /// assert!(val.clone_value(None).unwrap().is_frozen());
+    /// assert!(!val.clone_value(Some(false)).unwrap().is_frozen());
+    /// ```
+    pub fn clone_value(self, freeze: Option<bool>) -> Result<Value, Error> {
+        match freeze {
+            None => protect(|| unsafe { Value::new(rb_obj_clone(self.as_rb_value())) }),
+            Some(freeze) => {
+                let kwargs = RHash::new();
+                kwargs.aset(Symbol::new("freeze"), freeze)?;
+                self.funcall_kw("clone", (kwargs,))
+            }
+        }
+    }
+
     /// Convert `self` to a `bool`, following Ruby's rules of `false` and `nil`
     /// as boolean `false` and everything else boolean `true`.
     ///
@@ -507,6 +797,111 @@ impl Value {
         }
     }
 
+    /// Call the method `id` on `self` with `args`.
+    ///
+    /// This is a non-generic version of [`funcall`](Value::funcall), useful in
+    /// hot paths where the method [`Id`] has been looked up ahead of time (see
+    /// [`LazyId`]) and the arguments are already in a slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, value::LazyId, RArray, RString};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// static JOIN: LazyId = LazyId::new("join");
+    ///
+    /// let values = eval::<RArray>(r#"["foo", 1, :bar]"#).unwrap();
+    /// let sep = RString::new(" & ").into();
+    /// let result: String = values.funcall_id(JOIN.get(), &[sep]).unwrap();
+    /// assert_eq!(result, "foo & 1 & bar");
+    /// ```
+    #[inline]
+    pub fn funcall_id<T>(self, id: Id, args: &[Value]) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.funcall(id, args)
+    }
+
+    /// Call the public method named `method` on `self` with `args`.
+    ///
+    /// Like [`funcall`](Value::funcall), but will raise `NoMethodError` if
+    /// the method is private or protected, the same as calling a method with
+    /// an explicit receiver from Ruby.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let obj: Value = eval("Object.new").unwrap();
+    /// let res: String = obj.funcall_public("to_s", ()).unwrap();
+    /// assert!(res.starts_with("#<Object"));
+    /// assert!(obj.funcall_public::<_, _, Value>("puts", ()).is_err());
+    /// ```
+    pub fn funcall_public<M, A, T>(self, method: M, args: A) -> Result<T, Error>
+    where
+        M: Into<Id>,
+        A: ArgList,
+        T: TryConvert,
+    {
+        unsafe {
+            let id = method.into();
+            let args = args.into_arg_list();
+            let slice = args.as_ref();
+            protect(|| {
+                Value::new(rb_funcallv_public(
+                    self.as_rb_value(),
+                    id.as_rb_id(),
+                    slice.len() as c_int,
+                    slice.as_ptr() as *const VALUE,
+                ))
+            })
+            .and_then(|v| v.try_convert())
+        }
+    }
+
+    /// Call the method named `method` on `self` with `args`, passing the
+    /// final argument (which must be a Hash) as keyword arguments.
+    #[cfg(ruby_gte_2_7)]
+    pub(crate) fn funcall_kw<M, A, T>(self, method: M, args: A) -> Result<T, Error>
+    where
+        M: Into<Id>,
+        A: ArgList,
+        T: TryConvert,
+    {
+        unsafe {
+            let id = method.into();
+            let args = args.into_arg_list();
+            let slice = args.as_ref();
+            protect(|| {
+                Value::new(rb_sys::rb_funcallv_kw(
+                    self.as_rb_value(),
+                    id.as_rb_id(),
+                    slice.len() as c_int,
+                    slice.as_ptr() as *const VALUE,
+                    1,
+                ))
+            })
+            .and_then(|v| v.try_convert())
+        }
+    }
+
+    /// Call the method named `method` on `self` with `args`, passing the
+    /// final argument (which must be a Hash) as keyword arguments.
+    #[cfg(ruby_lt_2_7)]
+    pub(crate) fn funcall_kw<M, A, T>(self, method: M, args: A) -> Result<T, Error>
+    where
+        M: Into<Id>,
+        A: ArgList,
+        T: TryConvert,
+    {
+        // a trailing Hash is implicitly treated as keywords
+        self.funcall(method, args)
+    }
+
     /// If `self` responds to the method named `method`, call it with `args`.
     ///
     /// Returns `Some(Ok(T))` if the method exists and returns without error,
@@ -671,6 +1066,43 @@ impl Value {
         .and_then(|v| v.try_convert())
     }
 
+    /// Evaluate `block` with `self` as the block's `self`, passing `args` to
+    /// the block.
+    ///
+    /// This is the equivalent of Ruby's `instance_exec`. `block` will receive
+    /// `self` as its first argument, followed by `args`. This enables
+    /// DSL-style evaluation where methods called on the block's `self` from
+    /// Ruby code resolve against `self`.
+    ///
+    /// `block` may return any `R` or `Result<R, Error>` where `R` implements
+    /// `Into<Value>`. Returning `Err(Error)` will raise the error as a Ruby
+    /// exception.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Error, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let obj: Value = eval("o = Object.new; o.instance_variable_set(:@x, 2); o").unwrap();
+    /// let res: i64 = obj
+    ///     .instance_exec((3,), |rb_self, args| -> Result<i64, Error> {
+    ///         let x: i64 = rb_self.funcall("instance_variable_get", ("@x",))?;
+    ///         Ok(x * args[0].try_convert::<i64>()?)
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(res, 6);
+    /// ```
+    pub fn instance_exec<A, F, R, T>(self, args: A, block: F) -> Result<T, Error>
+    where
+        A: ArgList,
+        F: 'static + Send + FnMut(Value, &[Value]) -> R,
+        R: BlockReturn,
+        T: TryConvert,
+    {
+        self.funcall_with_block("instance_exec", args, Proc::from_fn_with_self(block))
+    }
+
     /// Check if `self` responds to the given Ruby method.
     ///
     /// The `include_private` agument controls whether `self`'s private methods
@@ -708,6 +1140,46 @@ impl Value {
         Ok(res)
     }
 
+    /// Convert `self` to the Ruby type `T` using Ruby's implicit conversion
+    /// protocol for `T`, if `self` supports it.
+    ///
+    /// For example, converting to [`RString`] will call `#to_str` if `self`
+    /// is not already a `String` and responds to `#to_str`.
+    ///
+    /// Returns `Ok(None)` if `self` does not support the conversion, and
+    /// `Err` if the conversion method raises or returns the wrong type.
+    ///
+    /// See the types that [`CheckConvert`] is implemented on for what this
+    /// method can convert to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, RArray, RString, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let val: Value = eval(
+    ///     r#"
+    ///     o = Object.new
+    ///     def o.to_str
+    ///       "example"
+    ///     end
+    ///     o
+    ///     "#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let s = val.check_convert::<RString>().unwrap().unwrap();
+    /// assert_eq!(s.to_string().unwrap(), "example");
+    /// assert!(val.check_convert::<RArray>().unwrap().is_none());
+    /// ```
+    pub fn check_convert<T>(self) -> Result<Option<T>, Error>
+    where
+        T: CheckConvert,
+    {
+        T::check_convert(self)
+    }
+
     /// Convert `self` to a Ruby `String`.
     ///
     /// If `self` is already a `String` is it wrapped as a `RString`, otherwise
@@ -855,6 +1327,35 @@ impl Value {
         unsafe { Value::new(rb_obj_is_kind_of(self.as_rb_value(), class.as_rb_value())).to_bool() }
     }
 
+    /// Returns whether or not `self` is a direct instance of `class`, not
+    /// including subclasses.
+    ///
+    /// See [`Value::is_kind_of`] to include subclasses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class, eval, Value};
+    /// # let _cleanup = unsafe { magnus::embed::init() };
+    ///
+    /// let value = eval::<Value>("[]").unwrap();
+    /// assert!(value.is_instance_of(class::array()));
+    /// assert!(!value.is_instance_of(class::object()));
+    /// assert!(value.is_kind_of(class::object()));
+    /// ```
+    pub fn is_instance_of<T>(self, class: T) -> bool
+    where
+        T: Deref<Target = Value> + Module,
+    {
+        unsafe {
+            Value::new(rb_obj_is_instance_of(
+                self.as_rb_value(),
+                class.as_rb_value(),
+            ))
+            .to_bool()
+        }
+    }
+
     /// Generate an [`Enumerator`] from `method` on `self`, passing `args` to
     /// `method`.
     ///
@@ -934,6 +1435,82 @@ impl fmt::Debug for Value {
     }
 }
 
+/// Adapter to format a Ruby value with Ruby's `#inspect` method.
+///
+/// Both the [`Display`](fmt::Display) and [`Debug`](fmt::Debug)
+/// implementations of this type will use `#inspect`, making it useful when
+/// logging Ruby values where only `Display` is used. The alternate flag
+/// (`{:#}` or `{:#?}`) will use `#pretty_inspect` from Ruby's `pp` library.
+///
+/// If the Ruby method raises, a description including the class name and
+/// address of the object is used instead.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, value::Inspect, RString, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let s = RString::new("example");
+/// assert_eq!(format!("{}", s), "example");
+/// assert_eq!(format!("{}", Inspect(s)), r#""example""#);
+///
+/// let val: Value = eval(
+///     r#"
+///     o = Object.new
+///     def o.inspect
+///       raise "oops"
+///     end
+///     o
+///     "#,
+/// )
+/// .unwrap();
+/// assert!(format!("{}", Inspect(val)).starts_with("#<Object:0x"));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Inspect<T>(pub T);
+
+impl<T> Inspect<T>
+where
+    T: ReprValue,
+{
+    fn pretty_inspect(&self) -> String {
+        let val = self.0.to_value();
+        let _ = crate::require("pp");
+        match val.funcall::<_, _, RString>("pretty_inspect", ()) {
+            Ok(s) => s
+                .conv_enc(RbEncoding::utf8())
+                .unwrap_or(s)
+                .to_string_lossy()
+                .trim_end()
+                .to_owned(),
+            Err(_) => val.inspect(),
+        }
+    }
+}
+
+impl<T> fmt::Display for Inspect<T>
+where
+    T: ReprValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{}", self.pretty_inspect())
+        } else {
+            write!(f, "{}", self.0.to_value().inspect())
+        }
+    }
+}
+
+impl<T> fmt::Debug for Inspect<T>
+where
+    T: ReprValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
 impl IntoValue for i8 {
     fn into_value(self, handle: &RubyHandle) -> Value {
         handle.integer_from_i64(self as i64).into()
@@ -1060,6 +1637,8 @@ impl IntoValue for f32 {
     }
 }
 
+/// Converts to a Ruby `Float`. Every `f32` is exactly representable as a
+/// Ruby `Float`, so no rounding occurs.
 impl From<f32> for Value {
     fn from(value: f32) -> Self {
         get_ruby!().into_value(value)
@@ -1084,7 +1663,9 @@ impl TryConvert for Value {
     }
 }
 
-pub(crate) mod private {
+/// Sealed traits, used by code generated by this crate's macros.
+#[doc(hidden)]
+pub mod private {
     use super::*;
 
     /// Marker trait for types that have the same representation as [`Value`].
@@ -1133,21 +1714,31 @@ unsafe impl private::ReprValue for Value {
 
 impl ReprValue for Value {}
 
+/// A [`Value`] known not to be `false`, used by code generated by this crate's
+/// macros.
+#[doc(hidden)]
 #[derive(Clone, Copy)]
 #[repr(transparent)]
-pub(crate) struct NonZeroValue(NonZeroUsize);
+pub struct NonZeroValue(NonZeroUsize);
 
 impl NonZeroValue {
+    /// Wraps `val`.
+    ///
+    /// # Safety
+    ///
+    /// `val` must not be `false`.
     #[inline]
-    pub(crate) const unsafe fn new_unchecked(val: Value) -> Self {
+    pub const unsafe fn new_unchecked(val: Value) -> Self {
         Self(NonZeroUsize::new_unchecked(val.as_rb_value() as usize))
     }
 
-    pub(crate) const fn get(self) -> Value {
+    /// Returns the wrapped `Value`.
+    pub const fn get(self) -> Value {
         Value::new(self.0.get() as VALUE)
     }
 
-    pub(crate) fn get_ref(&self) -> &Value {
+    /// Returns a reference to the wrapped `Value`.
+    pub fn get_ref(&self) -> &Value {
         let self_ptr = self as *const Self;
         let value_ptr = self_ptr as *const Value;
         // we just got this pointer from &self, so we know it's valid to deref
@@ -1157,11 +1748,50 @@ impl NonZeroValue {
 
 /// Protects a Ruby Value from the garbage collector.
 ///
+/// The value is registered with the garbage collector when the `BoxValue` is
+/// created, and unregistered when it is dropped. This makes it safe to store
+/// Ruby objects on the heap, e.g. in a long-lived Rust struct or collection,
+/// where Ruby's garbage collector would otherwise not be able to see them.
+///
+/// Values stored within a Ruby object wrapping a Rust type should instead be
+/// marked with [`gc::mark`](crate::gc::mark), see
+/// [`DataTypeFunctions::mark`](crate::typed_data::DataTypeFunctions::mark).
+///
 /// See also [`gc::register_mark_object`](crate::gc::register_mark_object) for
 /// a value that should be permanently excluded from garbage collection.
 ///
 /// All [`Value`] methods should be available on this type through [`Deref`],
 /// but some may be missed by this documentation.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use magnus::{block::Proc, eval, gc, value::BoxValue, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// struct Callbacks(HashMap<String, BoxValue<Proc>>);
+///
+/// impl Callbacks {
+///     fn register(&mut self, name: &str, callback: Proc) {
+///         self.0.insert(name.to_owned(), BoxValue::new(callback));
+///     }
+///
+///     fn call(&self, name: &str, arg: i64) -> Option<i64> {
+///         self.0.get(name).map(|f| f.call((arg,)).unwrap())
+///     }
+/// }
+///
+/// let mut callbacks = Callbacks(HashMap::new());
+/// callbacks.register("double", eval("proc { |i| i * 2 }").unwrap());
+///
+/// # // make some garbage
+/// # eval::<Value>(r#"1024.times.map {|i| "test#{i}"}"#).unwrap();
+/// gc::start();
+///
+/// assert_eq!(callbacks.call("double", 21), Some(42));
+/// ```
 pub struct BoxValue<T>(Box<T>);
 
 impl<T> BoxValue<T>
@@ -1205,6 +1835,17 @@ where
     }
 }
 
+impl<T> Clone for BoxValue<T>
+where
+    T: ReprValue,
+{
+    /// Returns a new `BoxValue` for the same object, registered separately
+    /// with the garbage collector.
+    fn clone(&self) -> Self {
+        Self::new(*self.0)
+    }
+}
+
 impl<T> Drop for BoxValue<T> {
     fn drop(&mut self) {
         unsafe {
@@ -1275,6 +1916,275 @@ where
     }
 }
 
+/// `Opaque` values that were dropped without the GVL, waiting to be
+/// unregistered with the garbage collector.
+fn opaque_release_queue() -> &'static Mutex<Vec<Box<Value>>> {
+    memoize!(Mutex<Vec<Box<Value>>>: Mutex::new(Vec::new()))
+}
+
+/// Unregister any `Opaque` values that were dropped without the GVL.
+///
+/// Must be called with the GVL held.
+unsafe fn release_opaque_values() {
+    let released = std::mem::take(&mut *opaque_release_queue().lock().unwrap());
+    for mut boxed in released {
+        rb_gc_unregister_address(boxed.as_mut() as *mut _ as *mut VALUE);
+    }
+}
+
+/// A handle to a Ruby object that can be moved between threads.
+///
+/// Like [`BoxValue`] the object is registered with the garbage collector, so
+/// it will not be collected while the `Opaque` exists. Unlike [`BoxValue`],
+/// `Opaque` can be sent to and dropped on any thread, but the object can
+/// only be retrieved with [`get`](Opaque::get) on a Ruby thread holding the
+/// GVL. This makes it suitable for storing a Ruby object (such as a
+/// [`Proc`] to use as a callback) in a Rust structure shared with
+/// other threads.
+///
+/// If dropped without the GVL the object will be unregistered with the
+/// garbage collector the next time an `Opaque` is created or retrieved with
+/// the GVL held.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{block::Proc, eval, gc, value::Opaque, Value};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let callback = Opaque::new(eval::<Proc>("proc { |i| i * 2 }").unwrap());
+///
+/// let callback = std::thread::spawn(move || {
+///     // Ruby objects can't be used from a non-Ruby thread
+///     assert!(callback.get().is_err());
+///     callback
+/// })
+/// .join()
+/// .unwrap();
+///
+/// # // make some garbage
+/// # eval::<Value>(r#"1024.times.map {|i| "test#{i}"}"#).unwrap();
+/// gc::start();
+///
+/// let res: i64 = callback.get().unwrap().call((21,)).unwrap();
+/// assert_eq!(res, 42);
+/// ```
+pub struct Opaque<T> {
+    boxed: Option<Box<Value>>,
+    phantom: PhantomData<T>,
+}
+
+impl<T> Opaque<T>
+where
+    T: ReprValue,
+{
+    /// Create a new `Opaque`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn new(val: T) -> Self {
+        get_ruby!().opaque_new(val)
+    }
+
+    /// Retrieve the wrapped object.
+    ///
+    /// Returns `Err(RubyUnavailableError)` if the current thread is not a
+    /// Ruby thread, or the GVL has been released.
+    pub fn get(&self) -> Result<T, RubyUnavailableError> {
+        let handle = RubyHandle::get()?;
+        Ok(handle.opaque_get(self))
+    }
+}
+
+impl RubyHandle {
+    pub fn opaque_new<T>(&self, val: T) -> Opaque<T>
+    where
+        T: ReprValue,
+    {
+        unsafe { release_opaque_values() };
+        let mut boxed = Box::new(val.to_value());
+        unsafe { rb_gc_register_address(boxed.as_mut() as *mut _ as *mut VALUE) };
+        Opaque {
+            boxed: Some(boxed),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn opaque_get<T>(&self, opaque: &Opaque<T>) -> T
+    where
+        T: ReprValue,
+    {
+        unsafe {
+            release_opaque_values();
+            T::from_value_unchecked(**opaque.boxed.as_ref().unwrap())
+        }
+    }
+}
+
+impl<T> Drop for Opaque<T> {
+    fn drop(&mut self) {
+        let mut boxed = match self.boxed.take() {
+            Some(v) => v,
+            None => return,
+        };
+        if RubyHandle::get().is_ok() {
+            unsafe { rb_gc_unregister_address(boxed.as_mut() as *mut _ as *mut VALUE) };
+        } else {
+            opaque_release_queue().lock().unwrap().push(boxed);
+        }
+    }
+}
+
+impl<T> fmt::Debug for Opaque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Opaque").finish()
+    }
+}
+
+/// A registry of Ruby objects, stored under integer tokens.
+///
+/// C and Rust libraries that accept a callback often only allow a `usize`
+/// (or `void *`) of 'user data' to be passed along with it. A
+/// `CallbackRegistry` can store a Ruby object such as a [`Proc`] and return a
+/// token suitable to use as that user data, which can later be resolved back
+/// to the object.
+///
+/// Stored objects are registered with the garbage collector, so will not be
+/// collected while they remain in the registry. The registry can be shared
+/// with and used from any thread (e.g. wrapped in an [`Arc`](std::sync::Arc)),
+/// but objects can only be retrieved on a Ruby thread holding the GVL. Tokens are never `0`, and are not reused.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{block::Proc, eval, value::CallbackRegistry};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// let registry = CallbackRegistry::new();
+/// let token = registry.insert(eval::<Proc>("proc { |i| i * 2 }").unwrap());
+///
+/// // `token` could now be passed to a library as user data
+///
+/// let callback = registry.get(token).unwrap().unwrap();
+/// let res: i64 = callback.call((21,)).unwrap();
+/// assert_eq!(res, 42);
+///
+/// assert!(registry.remove(token));
+/// assert!(registry.get(token).unwrap().is_none());
+/// ```
+pub struct CallbackRegistry<T> {
+    next_token: AtomicUsize,
+    entries: Mutex<HashMap<usize, Opaque<T>>>,
+}
+
+impl<T> CallbackRegistry<T>
+where
+    T: ReprValue,
+{
+    /// Create a new empty `CallbackRegistry`.
+    ///
+    /// This does not require Ruby, so can be called from any thread.
+    pub fn new() -> Self {
+        Self {
+            next_token: AtomicUsize::new(1),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Store `val` in the registry, returning a token that can be used to
+    /// retrieve or remove it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread.
+    pub fn insert(&self, val: T) -> usize {
+        get_ruby!().callback_registry_insert(self, val)
+    }
+
+    /// Retrieve the object stored under `token`.
+    ///
+    /// Returns `Ok(None)` if there is no object stored under `token`, and
+    /// `Err(RubyUnavailableError)` if the current thread is not a Ruby
+    /// thread, or the GVL has been released.
+    pub fn get(&self, token: usize) -> Result<Option<T>, RubyUnavailableError> {
+        let handle = RubyHandle::get()?;
+        Ok(handle.callback_registry_get(self, token))
+    }
+
+    /// Remove the object stored under `token`, allowing it to be garbage
+    /// collected.
+    ///
+    /// Returns whether an object was stored under `token`. This can be called
+    /// from any thread.
+    pub fn remove(&self, token: usize) -> bool {
+        let removed = self.entries.lock().unwrap().remove(&token);
+        removed.is_some()
+    }
+
+    /// Returns whether `token` has an object stored under it.
+    pub fn contains(&self, token: usize) -> bool {
+        self.entries.lock().unwrap().contains_key(&token)
+    }
+
+    /// Returns the number of objects in the registry.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns whether the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().unwrap().is_empty()
+    }
+
+    /// Remove all objects from the registry.
+    pub fn clear(&self) {
+        let removed = std::mem::take(&mut *self.entries.lock().unwrap());
+        drop(removed);
+    }
+}
+
+impl<T> Default for CallbackRegistry<T>
+where
+    T: ReprValue,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RubyHandle {
+    pub fn callback_registry_insert<T>(&self, registry: &CallbackRegistry<T>, val: T) -> usize
+    where
+        T: ReprValue,
+    {
+        let opaque = self.opaque_new(val);
+        let token = registry.next_token.fetch_add(1, atomic::Ordering::Relaxed);
+        registry.entries.lock().unwrap().insert(token, opaque);
+        token
+    }
+
+    pub fn callback_registry_get<T>(
+        &self,
+        registry: &CallbackRegistry<T>,
+        token: usize,
+    ) -> Option<T>
+    where
+        T: ReprValue,
+    {
+        let entries = registry.entries.lock().unwrap();
+        entries.get(&token).map(|opaque| self.opaque_get(opaque))
+    }
+}
+
+impl<T> fmt::Debug for CallbackRegistry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallbackRegistry")
+            .field("len", &self.entries.lock().unwrap().len())
+            .finish()
+    }
+}
+
 /// Ruby's `false` value.
 ///
 /// See [`QFALSE`] to obtain a value of this type.
@@ -2377,6 +3287,74 @@ impl Id {
     }
 }
 
+/// An [`Id`] that can be assigned to a `static` and is only interned on
+/// first use.
+///
+/// Converting a `&str` to an `Id` requires looking up (and possibly
+/// allocating) the name in Ruby's symbol table. Functions taking an
+/// `Into<Id>` argument will accept `&LazyId`, which does this lookup just
+/// once.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{eval, value::LazyId, Object, RObject};
+/// # let _cleanup = unsafe { magnus::embed::init() };
+///
+/// static COUNT: LazyId = LazyId::new("@count");
+///
+/// fn increment(obj: RObject) -> Result<i64, magnus::Error> {
+///     let count = obj.ivar_get::<_, Option<i64>>(&COUNT)?.unwrap_or(0) + 1;
+///     obj.ivar_set(&COUNT, count)?;
+///     Ok(count)
+/// }
+///
+/// let obj: RObject = eval("Object.new").unwrap();
+/// increment(obj).unwrap();
+/// assert_eq!(increment(obj).unwrap(), 2);
+/// ```
+pub struct LazyId {
+    name: &'static str,
+    id: AtomicUsize,
+}
+
+impl LazyId {
+    /// Create a new `LazyId` for `name`.
+    ///
+    /// The name will not be interned until the `LazyId` is first used.
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            id: AtomicUsize::new(0),
+        }
+    }
+
+    /// Return the [`Id`] for the name, interning it on first use.
+    pub fn get(&self) -> Id {
+        match self.id.load(atomic::Ordering::Acquire) {
+            0 => {
+                let id = Id::from(self.name);
+                self.id
+                    .store(id.as_rb_id() as usize, atomic::Ordering::Release);
+                id
+            }
+            id => Id::new(id as ID),
+        }
+    }
+}
+
+impl fmt::Debug for LazyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LazyId").field(&self.name).finish()
+    }
+}
+
+impl From<&LazyId> for Id {
+    fn from(lazy: &LazyId) -> Self {
+        lazy.get()
+    }
+}
+
 impl From<&str> for Id {
     fn from(s: &str) -> Self {
         Self::new(unsafe {
@@ -0,0 +1,41 @@
+use magnus::{eval, exception::Frame, Value};
+
+#[test]
+fn it_returns_structured_frames() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let err = eval::<Value>(
+        r#"
+        def inner
+          raise "oops"
+        end
+
+        def outer
+          inner
+        end
+
+        outer
+        "#,
+    )
+    .unwrap_err();
+    let ex = err.value().unwrap();
+
+    let frames = ex.backtrace_frames().unwrap().unwrap();
+    assert_eq!(frames[0].label.as_deref(), Some("inner"));
+    assert_eq!(frames[0].lineno, Some(3));
+    assert_eq!(frames[1].label.as_deref(), Some("outer"));
+    assert_eq!(frames[1].lineno, Some(7));
+    assert_eq!(frames[0].to_string(), "eval:3:in `inner'");
+
+    let ex: magnus::Exception = eval("RuntimeError.new('not raised')").unwrap();
+    assert_eq!(ex.backtrace_frames().unwrap(), None::<Vec<Frame>>);
+
+    #[cfg(feature = "backtrace")]
+    {
+        std::env::set_var("RUST_LIB_BACKTRACE", "1");
+        let err = magnus::Error::with_backtrace(magnus::exception::runtime_error(), "rust");
+        let backtrace = err.value().unwrap().backtrace().unwrap().unwrap();
+        let first: String = backtrace.entry(0).unwrap();
+        assert!(first.contains("backtrace_frames"));
+    }
+}
@@ -0,0 +1,37 @@
+use magnus::{eval, RString, Value};
+
+#[test]
+fn it_round_trips_binary_strings() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let data: Vec<u8> = (0..=255).collect();
+
+    let s = RString::from_slice(&data);
+    let res: bool = eval!(
+        "s.encoding == Encoding::BINARY && s.bytes == (0..255).to_a",
+        s
+    )
+    .unwrap();
+    assert!(res);
+    assert_eq!(s.to_binary_bytes(), data);
+
+    // never transcoded, even with a default internal encoding set
+    let s: RString = eval(
+        r#"
+        Encoding.default_internal = Encoding::UTF_8
+        "\xFF\xFE".force_encoding("UTF-16LE")
+        "#,
+    )
+    .unwrap();
+    assert_eq!(s.to_binary_bytes(), [255, 254]);
+    let _: Value = eval("Encoding.default_internal = nil").unwrap();
+
+    #[cfg(feature = "bytes")]
+    {
+        let bytes = bytes::Bytes::from(data.clone());
+        let val = Value::from(bytes.clone());
+        let res: bool = eval!("val.encoding == Encoding::BINARY", val).unwrap();
+        assert!(res);
+        assert_eq!(val.try_convert::<bytes::Bytes>().unwrap(), bytes);
+    }
+}
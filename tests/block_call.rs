@@ -1,4 +1,4 @@
-use magnus::{RArray, Value};
+use magnus::{eval, exception, Error, RArray, Value};
 
 #[test]
 fn it_can_call_method_with_block() {
@@ -12,4 +12,23 @@ fn it_can_call_method_with_block() {
         .unwrap();
 
     assert_eq!(ary.to_vec::<i64>().unwrap(), vec![4, 8, 12]);
+
+    let found: i64 = ary
+        .block_call("find", (), |args, _| {
+            args[0].try_convert::<i64>().map(|i| i > 5)
+        })
+        .unwrap();
+    assert_eq!(found, 8);
+
+    // errors returned from the block are raised in Ruby, and then returned
+    // from block_call
+    let res: Result<Value, Error> = ary.block_call("each", (), |_, _| {
+        Err::<Value, _>(Error::new(exception::arg_error(), "stop"))
+    });
+    assert!(res.unwrap_err().is_kind_of(exception::arg_error()));
+
+    // errors raised by the method are returned
+    let obj: Value = eval("o = Object.new; def o.each; raise IndexError; end; o").unwrap();
+    let res: Result<Value, Error> = obj.block_call("each", (), |_, _| ());
+    assert!(res.unwrap_err().is_kind_of(exception::index_error()));
 }
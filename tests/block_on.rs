@@ -0,0 +1,88 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use magnus::{block_on, eval, Thread, Value};
+
+#[derive(Default)]
+struct State {
+    done: bool,
+    waker: Option<Waker>,
+}
+
+// completed by a non-Ruby thread, as if from an async runtime
+struct Delay(Arc<Mutex<State>>);
+
+impl Delay {
+    fn new(duration: Duration) -> Self {
+        let state = Arc::new(Mutex::new(State::default()));
+        let thread_state = state.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(duration);
+            let mut state = thread_state.lock().unwrap();
+            state.done = true;
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+        Self(state)
+    }
+}
+
+impl Future for Delay {
+    type Output = i64;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.0.lock().unwrap();
+        if state.done {
+            Poll::Ready(42)
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[test]
+fn it_blocks_on_futures() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    // other Ruby threads can run while the future is pending
+    let _: Value = eval("$counter = 0").unwrap();
+    let thread = Thread::create(|| eval::<Value>("loop { $counter += 1; sleep 0.001 }")).unwrap();
+    assert_eq!(
+        unsafe { block_on(Delay::new(Duration::from_millis(100))) }.unwrap(),
+        42
+    );
+    let count: i64 = eval("$counter").unwrap();
+    assert!(count > 0);
+    thread.kill().unwrap();
+    thread.join(None).unwrap();
+
+    // interrupts are handled while waiting
+    let waiter =
+        Thread::create(|| unsafe { block_on(Delay::new(Duration::from_secs(10))) }).unwrap();
+    assert!(!waiter.join(Some(Duration::from_millis(20))).unwrap());
+    waiter.kill().unwrap();
+    assert!(waiter.join(Some(Duration::from_secs(1))).unwrap());
+
+    #[cfg(feature = "tokio")]
+    {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let res = unsafe {
+            magnus::thread::block_on_tokio(runtime.handle(), async {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                "done"
+            })
+        }
+        .unwrap();
+        assert_eq!(res, "done");
+    }
+}
@@ -0,0 +1,30 @@
+use magnus::{define_global_function, eval, function, Lax, Value};
+
+fn strict(val: bool) -> bool {
+    val
+}
+
+fn lax(val: Lax<bool>) -> bool {
+    val.into_inner()
+}
+
+#[test]
+fn it_converts_bools() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    define_global_function("strict", function!(strict, 1));
+    define_global_function("lax", function!(lax, 1));
+
+    assert!(eval::<bool>("strict(true)").unwrap());
+    assert!(!eval::<bool>("strict(false)").unwrap());
+    assert!(eval::<Value>("strict(nil)").is_err());
+    assert!(eval::<Value>("strict(1)").is_err());
+
+    assert!(eval::<bool>("lax(1)").unwrap());
+    assert!(eval::<bool>("lax(Object.new)").unwrap());
+    assert!(!eval::<bool>("lax(nil)").unwrap());
+    assert!(!eval::<bool>("lax(false)").unwrap());
+
+    let res: bool = eval("begin; strict(0); rescue TypeError; true; end").unwrap();
+    assert!(res);
+}
@@ -0,0 +1,19 @@
+use magnus::{eval, gc, value::BoxValue, RString, Value};
+
+#[test]
+fn it_keeps_boxed_values_alive() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let values = (0..16)
+        .map(|i| BoxValue::new(RString::new(&format!("value {}", i))))
+        .collect::<Vec<_>>();
+    let copies = values.clone();
+    drop(values);
+
+    eval::<Value>(r#"1024.times.map {|i| "test#{i}"}"#).unwrap();
+    gc::start();
+
+    for (i, value) in copies.iter().enumerate() {
+        assert_eq!(value.to_string(), format!("value {}", i));
+    }
+}
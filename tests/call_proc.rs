@@ -6,4 +6,13 @@ fn it_calls_proc() {
 
     let p: Proc = eval("Proc.new {|i| i + 1}").unwrap();
     assert_eq!(43, p.call((42,)).unwrap());
+    assert_eq!(1, p.arity());
+    assert!(!p.is_lambda());
+
+    let l: Proc = eval("->(a, b) { a * b }").unwrap();
+    assert!(l.is_lambda());
+    let curried = l.curry(None).unwrap();
+    let double: Proc = curried.call((2,)).unwrap();
+    assert_eq!(42, double.call((21,)).unwrap());
+    assert!(l.curry(Some(3)).is_err());
 }
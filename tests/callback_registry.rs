@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use magnus::{block::Proc, eval, gc, value::CallbackRegistry, Value};
+
+#[test]
+fn it_stores_callbacks_under_tokens() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let registry = Arc::new(CallbackRegistry::new());
+    let double = registry.insert(eval::<Proc>("proc { |i| i * 2 }").unwrap());
+    let square = registry.insert(eval::<Proc>("proc { |i| i * i }").unwrap());
+    assert_ne!(double, 0);
+    assert_ne!(double, square);
+    assert_eq!(registry.len(), 2);
+
+    // make some garbage
+    eval::<Value>(r#"1024.times.map {|i| "test#{i}"}"#).unwrap();
+    gc::start();
+
+    let res: i64 = registry.get(square).unwrap().unwrap().call((7,)).unwrap();
+    assert_eq!(res, 49);
+
+    // tokens can be checked and removed from any thread, but not resolved
+    let thread_registry = registry.clone();
+    let res = std::thread::spawn(move || {
+        let unavailable = thread_registry.get(double).is_err();
+        (unavailable, thread_registry.remove(square))
+    })
+    .join()
+    .unwrap();
+    assert_eq!(res, (true, true));
+
+    assert!(registry.get(square).unwrap().is_none());
+    assert!(!registry.remove(square));
+    let res: i64 = registry.get(double).unwrap().unwrap().call((21,)).unwrap();
+    assert_eq!(res, 42);
+
+    registry.clear();
+    assert!(registry.is_empty());
+    assert!(!registry.contains(double));
+}
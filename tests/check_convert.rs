@@ -0,0 +1,76 @@
+use magnus::{eval, Integer, RArray, RHash, RString, Value};
+
+#[test]
+fn it_checks_implicit_conversions() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let val: Value = eval(
+        r#"
+        class Duck
+          def to_str
+            "quack"
+          end
+
+          def to_ary
+            [1, 2]
+          end
+
+          def to_hash
+            {a: 1}
+          end
+
+          def to_int
+            42
+          end
+
+          private
+
+          def secret
+          end
+        end
+        Duck.new
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        val.check_convert::<RString>()
+            .unwrap()
+            .unwrap()
+            .to_string()
+            .unwrap(),
+        "quack"
+    );
+    assert_eq!(val.check_convert::<RArray>().unwrap().unwrap().len(), 2);
+    assert_eq!(val.check_convert::<RHash>().unwrap().unwrap().len(), 1);
+    assert_eq!(
+        val.check_convert::<Integer>()
+            .unwrap()
+            .unwrap()
+            .to_i64()
+            .unwrap(),
+        42
+    );
+
+    let plain: Value = eval("Object.new").unwrap();
+    assert!(plain.check_convert::<RString>().unwrap().is_none());
+    assert!(plain.check_convert::<RArray>().unwrap().is_none());
+    assert!(plain.check_convert::<RHash>().unwrap().is_none());
+    assert!(plain.check_convert::<Integer>().unwrap().is_none());
+
+    let bad: Value = eval(
+        r#"
+        o = Object.new
+        def o.to_str
+          42
+        end
+        o
+        "#,
+    )
+    .unwrap();
+    assert!(bad.check_convert::<RString>().is_err());
+
+    assert!(val.respond_to("to_str", false).unwrap());
+    assert!(!val.respond_to("secret", false).unwrap());
+    assert!(val.respond_to("secret", true).unwrap());
+}
@@ -0,0 +1,48 @@
+use magnus::{
+    eval, exception,
+    thread::{check_interrupts, handle_interrupt, InterruptTiming},
+    Error, Thread, Value,
+};
+
+#[test]
+fn it_checks_interrupts() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    // a busy Rust loop holding the GVL can be killed
+    let worker = Thread::create(|| -> Result<(), Error> {
+        loop {
+            check_interrupts()?;
+        }
+    })
+    .unwrap();
+    let _: Value = eval("sleep 0.01").unwrap();
+    worker.kill().unwrap();
+    worker.join(None).unwrap();
+    assert!(!worker.is_alive().unwrap());
+
+    // deferred interrupts are raised at the end of the block
+    let mut reached = false;
+    let res = handle_interrupt(InterruptTiming::Never, || {
+        let _: Value = eval("Thread.current.raise('interrupted')")?;
+        check_interrupts()?;
+        reached = true;
+        Ok(())
+    });
+    assert!(reached);
+    assert!(res.unwrap_err().is_kind_of(exception::runtime_error()));
+
+    // with on_blocking interrupts are raised by check_interrupts
+    let mut reached = false;
+    let res = handle_interrupt(InterruptTiming::OnBlocking, || {
+        let _: Value = eval("Thread.current.raise('interrupted')")?;
+        check_interrupts()?;
+        reached = true;
+        Ok(())
+    });
+    assert!(!reached);
+    assert!(res.is_err());
+
+    // without interrupts the result of the block is returned
+    let res = handle_interrupt(InterruptTiming::Immediate, || Ok(42)).unwrap();
+    assert_eq!(res, 42);
+}
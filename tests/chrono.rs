@@ -0,0 +1,44 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use magnus::{eval, Time, Value};
+
+#[test]
+fn it_converts_times_with_chrono() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let utc = Utc.timestamp_opt(1_234_567_890, 123_456_789).unwrap();
+    let val = Value::from(utc);
+    assert!(Time::from_value(val).unwrap().is_utc());
+    let res: bool = eval!("val == Time.at(1_234_567_890, 123_456_789, :nsec)", val).unwrap();
+    assert!(res);
+    assert_eq!(val.try_convert::<DateTime<Utc>>().unwrap(), utc);
+
+    let val: Value =
+        eval(r#"Time.new(2001, 2, 3, 4, 5, Rational(6_000_000_007, 1_000_000_000), "+05:30")"#)
+            .unwrap();
+    let fixed: DateTime<FixedOffset> = val.try_convert().unwrap();
+    assert_eq!(fixed.offset().local_minus_utc(), 19800);
+    assert_eq!(fixed.to_rfc3339(), "2001-02-03T04:05:06.000000007+05:30");
+    let res: bool = eval!(
+        "val == fixed && val.utc_offset == fixed.utc_offset",
+        val,
+        fixed
+    )
+    .unwrap();
+    assert!(res);
+
+    let naive: NaiveDateTime = val.try_convert().unwrap();
+    assert_eq!(naive.to_string(), "2001-02-03 04:05:06.000000007");
+    let res: bool = eval!(
+        r#"naive.utc? && naive.strftime("%F %T.%N") == "2001-02-03 04:05:06.000000007""#,
+        naive
+    )
+    .unwrap();
+    assert!(res);
+
+    assert!(eval::<Value>("1")
+        .unwrap()
+        .try_convert::<DateTime<Utc>>()
+        .is_err());
+}
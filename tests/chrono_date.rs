@@ -0,0 +1,62 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{NaiveDate, NaiveDateTime};
+use magnus::{eval, time::AsDateTime, Value};
+
+#[test]
+fn it_converts_dates_with_chrono() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let date = NaiveDate::from_ymd_opt(1500, 2, 3).unwrap();
+    let val = Value::from(date);
+    let res: bool = eval!(
+        "val.is_a?(Date) && [val.year, val.month, val.day] == [1500, 2, 3]",
+        val
+    )
+    .unwrap();
+    assert!(res);
+    assert_eq!(val.try_convert::<NaiveDate>().unwrap(), date);
+
+    // Ruby's default calendar is Julian before 1582, chrono is always
+    // Gregorian
+    let res: NaiveDate = eval("Date.new(1500, 2, 3)").unwrap();
+    assert_eq!(res, NaiveDate::from_ymd_opt(1500, 2, 13).unwrap());
+    let res: NaiveDate = eval("Date.new(2001, 2, 3)").unwrap();
+    assert_eq!(res, NaiveDate::from_ymd_opt(2001, 2, 3).unwrap());
+
+    let res: NaiveDateTime =
+        eval(r#"DateTime.new(2001, 2, 3, 4, 5, Rational(61, 10), "+07:00")"#).unwrap();
+    assert_eq!(
+        res,
+        NaiveDate::from_ymd_opt(2001, 2, 3)
+            .unwrap()
+            .and_hms_milli_opt(4, 5, 6, 100)
+            .unwrap()
+    );
+    let res: NaiveDateTime = eval("Date.new(2001, 2, 3)").unwrap();
+    assert_eq!(
+        res,
+        NaiveDate::from_ymd_opt(2001, 2, 3)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    );
+
+    let dt = NaiveDate::from_ymd_opt(1066, 10, 14)
+        .unwrap()
+        .and_hms_nano_opt(9, 30, 15, 123_456_789)
+        .unwrap();
+    let val = Value::from(AsDateTime(dt));
+    let res: bool = eval!(
+        r#"val.is_a?(DateTime) && val.offset == 0 && val.strftime("%F %T.%N") == "1066-10-14 09:30:15.123456789""#,
+        val
+    )
+    .unwrap();
+    assert!(res);
+    assert_eq!(
+        val.try_convert::<AsDateTime<NaiveDateTime>>().unwrap().0,
+        dt
+    );
+
+    assert!(eval::<NaiveDate>("Time.now").is_err());
+}
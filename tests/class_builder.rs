@@ -0,0 +1,68 @@
+use magnus::{
+    class, define_module, eval, function, method, Attr, Error, Module, Object, RClass, RObject,
+    Value,
+};
+
+fn area(rb_self: RObject) -> Result<i64, Error> {
+    let width: i64 = rb_self.ivar_get("@width")?;
+    let height: i64 = rb_self.ivar_get("@height")?;
+    Ok(width * height)
+}
+
+fn unit() -> Result<Value, Error> {
+    eval("Shapes::Rect.new(1, 1)")
+}
+
+fn initialize(rb_self: RObject, width: i64, height: i64) -> Result<(), Error> {
+    rb_self.ivar_set("@width", width)?;
+    rb_self.ivar_set("@height", height)?;
+    Ok(())
+}
+
+#[test]
+fn it_builds_a_class() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let shapes = define_module("Shapes").unwrap();
+    let base = shapes.define_class("Shape", Default::default()).unwrap();
+
+    let class: RClass = class::builder("Rect")
+        .under(shapes)
+        .superclass(base)
+        .constant("SIDES", 4)
+        .include_module(magnus::module::comparable())
+        .private_method("initialize", method!(initialize, 2))
+        .method("area", method!(area, 0))
+        .alias("size", "area")
+        .attr("width", Attr::Read)
+        .singleton_method("unit", function!(unit, 0))
+        .build()
+        .unwrap();
+
+    assert!(class.equal(eval::<Value>("Shapes::Rect").unwrap()).unwrap());
+    assert!(eval::<bool>("Shapes::Rect.superclass == Shapes::Shape").unwrap());
+    assert!(eval::<bool>("Shapes::Rect::SIDES == 4").unwrap());
+    assert!(eval::<bool>("Shapes::Rect.include?(Comparable)").unwrap());
+    assert!(eval::<bool>("Shapes::Rect.new(2, 3).area == 6").unwrap());
+    assert!(eval::<bool>("Shapes::Rect.new(2, 3).size == 6").unwrap());
+    assert!(eval::<bool>("Shapes::Rect.new(2, 3).width == 2").unwrap());
+    assert!(eval::<bool>("Shapes::Rect.unit.area == 1").unwrap());
+
+    let res = class::builder("Broken")
+        .alias("missing_alias", "does_not_exist")
+        .build();
+
+    assert!(res.is_err());
+
+    let err = class::builder("VeryBroken")
+        .alias("first_alias", "first_missing")
+        .constant("VALID", 1)
+        .alias("second_alias", "second_missing")
+        .build()
+        .unwrap_err();
+    assert!(err.is_name_error());
+    let message = err.to_string();
+    assert!(message.contains("first_missing"));
+    assert!(message.contains("second_missing"));
+    assert!(eval::<bool>("VeryBroken::VALID == 1").unwrap());
+}
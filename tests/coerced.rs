@@ -0,0 +1,47 @@
+use magnus::{eval, Coerced, RRegexp, Symbol, Value};
+
+#[test]
+fn it_converts_with_implicit_conversion() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let val: Value = eval(
+        r#"
+        class Name
+          def to_str
+            "name"
+          end
+        end
+        Name.new
+        "#,
+    )
+    .unwrap();
+    assert!(val.try_convert::<Symbol>().is_err());
+    let sym = val.try_convert::<Coerced<Symbol>>().unwrap().into_inner();
+    assert_eq!(sym.name().unwrap(), "name");
+
+    let val: Value = eval(":sym").unwrap();
+    let sym = val.try_convert::<Coerced<Symbol>>().unwrap().into_inner();
+    assert_eq!(sym.name().unwrap(), "sym");
+
+    let val: Value = eval("1").unwrap();
+    assert!(val.try_convert::<Coerced<Symbol>>().is_err());
+
+    let val: Value = eval(
+        r#"
+        class Pattern
+          def to_regexp
+            /a+/
+          end
+        end
+        Pattern.new
+        "#,
+    )
+    .unwrap();
+    assert!(val.try_convert::<RRegexp>().is_err());
+    let re = val.try_convert::<Coerced<RRegexp>>().unwrap().into_inner();
+    let res: bool = eval!("re.match?('aaa')", re).unwrap();
+    assert!(res);
+
+    let val: Value = eval(r#""a+""#).unwrap();
+    assert!(val.try_convert::<Coerced<RRegexp>>().is_err());
+}
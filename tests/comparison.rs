@@ -0,0 +1,41 @@
+use std::cmp::Ordering;
+
+use magnus::{eval, Value};
+
+#[test]
+fn it_compares_values() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let one: Value = eval("1").unwrap();
+    assert!(one.op_eq(1).unwrap());
+    assert!(one.op_eq(1.0).unwrap());
+    assert!(!one.op_eq(2).unwrap());
+    assert_eq!(one.op_cmp(2).unwrap(), Some(Ordering::Less));
+    assert_eq!(one.op_cmp(1).unwrap(), Some(Ordering::Equal));
+    assert_eq!(one.op_cmp(0).unwrap(), Some(Ordering::Greater));
+    assert_eq!(one.op_cmp("1").unwrap(), None);
+    assert!(one.op_lt(2).unwrap());
+    assert!(one.op_le(1).unwrap());
+    assert!(one.op_gt(0).unwrap());
+    assert!(one.op_ge(1).unwrap());
+    assert!(one.op_lt("2").is_err());
+
+    let val: Value = eval(
+        r#"
+        class Weird
+          def <=>(other)
+            -100
+          end
+
+          def ==(other)
+            raise "no"
+          end
+        end
+        Weird.new
+        "#,
+    )
+    .unwrap();
+    assert_eq!(val.op_cmp(1).unwrap(), Some(Ordering::Less));
+    assert!(val.op_eq(1).is_err());
+    assert!(val.op_lt(1).is_err());
+}
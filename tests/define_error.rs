@@ -0,0 +1,40 @@
+use magnus::{define_module, eval, exception, prelude::*, Error, ExceptionClass, Value};
+
+#[test]
+fn it_defines_an_error_hierarchy() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let module = define_module("MyGem").unwrap();
+    let error = module
+        .define_error("Error", exception::standard_error())
+        .unwrap();
+    let parse_error = module.define_error("ParseError", error).unwrap();
+
+    assert!(eval::<bool>("MyGem::ParseError.ancestors.include?(MyGem::Error)").unwrap());
+    assert!(eval::<bool>("MyGem::Error.superclass == StandardError").unwrap());
+
+    let class: ExceptionClass = module.const_get("ParseError").unwrap();
+    assert!(class.equal(parse_error).unwrap());
+
+    let err = Error::new(parse_error, "bad input");
+    assert!(err.is_kind_of(error));
+    assert!(err.is_kind_of(exception::standard_error()));
+    assert!(!err.is_kind_of(exception::type_error()));
+
+    let res: bool = eval!(
+        r#"
+            begin
+              raise parse_error, "bad input"
+            rescue MyGem::Error => e
+              e.message == "bad input"
+            end
+        "#,
+        parse_error
+    )
+    .unwrap();
+    assert!(res);
+
+    assert!(eval::<Value>("MyGem::Error")
+        .unwrap()
+        .is_kind_of(magnus::class::class()));
+}
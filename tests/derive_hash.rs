@@ -0,0 +1,66 @@
+use magnus::{eval, IntoHash, RHash, TryConvert, Value};
+
+#[derive(Debug, PartialEq, IntoHash, TryConvert)]
+struct Point {
+    x: i64,
+    #[magnus(rename = "y_pos")]
+    y: i64,
+    label: Option<String>,
+}
+
+#[derive(Debug, PartialEq, TryConvert)]
+#[magnus(deny_unknown_fields)]
+struct Options {
+    #[magnus(default)]
+    retries: u32,
+    strict: bool,
+}
+
+#[test]
+fn it_derives_hash_conversions() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let val = Value::from(Point {
+        x: 1,
+        y: 2,
+        label: None,
+    });
+    let res: bool = eval!("val == {x: 1, y_pos: 2, label: nil}", val).unwrap();
+    assert!(res);
+    let hash = RHash::from(Point {
+        x: 1,
+        y: 2,
+        label: Some("a".to_owned()),
+    });
+    assert_eq!(hash.len(), 3);
+
+    let point: Point = eval("{x: 3, y_pos: 4}").unwrap();
+    assert_eq!(
+        point,
+        Point {
+            x: 3,
+            y: 4,
+            label: None
+        }
+    );
+    let point: Point = eval(r#"{"x" => 5, "y_pos" => 6, "label" => "a", "z" => 7}"#).unwrap();
+    assert_eq!(point.label.as_deref(), Some("a"));
+
+    assert!(eval::<Point>("{x: 1}").unwrap_err().is_arg_error());
+    assert!(eval::<Point>("{x: 1, y_pos: 'b'}")
+        .unwrap_err()
+        .is_type_error());
+    assert!(eval::<Point>("[1, 2]").unwrap_err().is_type_error());
+
+    let opts: Options = eval("{strict: true}").unwrap();
+    assert_eq!(
+        opts,
+        Options {
+            retries: 0,
+            strict: true
+        }
+    );
+    assert!(eval::<Options>("{strict: true, retry: 1}")
+        .unwrap_err()
+        .is_arg_error());
+}
@@ -0,0 +1,15 @@
+use magnus::eval;
+
+#[test]
+fn it_passes_script_name_and_args() {
+    let _cleanup = unsafe { magnus::embed::init_with_args("my_tool", &["-v", "--", "in.txt"]) };
+
+    assert_eq!(eval::<String>("$0").unwrap(), "my_tool");
+    assert_eq!(eval::<String>("$PROGRAM_NAME").unwrap(), "my_tool");
+    assert_eq!(
+        eval::<Vec<String>>("ARGV").unwrap(),
+        vec!["-v", "--", "in.txt"]
+    );
+    // Ruby's verbose mode is not enabled by the "-v" argument
+    assert!(eval::<Option<bool>>("$VERBOSE").unwrap() != Some(true));
+}
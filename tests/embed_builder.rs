@@ -0,0 +1,26 @@
+use magnus::{embed, eval};
+
+#[test]
+fn it_configures_load_path_and_gems() {
+    let gem_home = std::env::temp_dir().join("magnus_gem_home");
+    let gem_home = gem_home.to_str().unwrap();
+    let _cleanup = unsafe {
+        embed::builder()
+            .load_path("lib")
+            .gems(false)
+            .gem_home(gem_home)
+            .script_name("builder_test")
+            .args(vec!["a", "b"])
+            .init()
+    };
+
+    assert!(eval::<bool>("$LOAD_PATH.first == File.expand_path('lib')").unwrap());
+    assert!(!eval::<bool>("defined?(Gem) == 'constant'").unwrap());
+    assert!(!eval::<bool>("defined?(DidYouMean) == 'constant'").unwrap());
+    assert_eq!(eval::<String>("ENV['GEM_HOME']").unwrap(), gem_home);
+    assert_eq!(eval::<String>("$0").unwrap(), "builder_test");
+    assert_eq!(eval::<Vec<String>>("ARGV").unwrap(), vec!["a", "b"]);
+
+    embed::push_load_path("vendor").unwrap();
+    assert!(eval::<bool>("$LOAD_PATH.last == File.expand_path('vendor')").unwrap());
+}
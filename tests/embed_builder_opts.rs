@@ -0,0 +1,21 @@
+use magnus::{embed::Builder, eval, Value};
+
+#[test]
+fn it_passes_options_to_the_vm() {
+    let _cleanup = unsafe {
+        Builder::new()
+            .opt("--disable-gems")
+            .frozen_string_literal(true)
+            .warning_level(0)
+            .env("RUBY_GC_HEAP_GROWTH_FACTOR", "1.5")
+            .init()
+    };
+
+    assert!(!eval::<bool>("defined?(Gem) == 'constant'").unwrap());
+    assert!(eval::<bool>(r#""literal".frozen?"#).unwrap());
+    assert!(eval::<Value>("$VERBOSE").unwrap().is_nil());
+    assert_eq!(
+        eval::<String>("ENV['RUBY_GC_HEAP_GROWTH_FACTOR']").unwrap(),
+        "1.5"
+    );
+}
@@ -0,0 +1,18 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use magnus::{embed, eval, Value};
+
+static END_PROC_RAN: AtomicBool = AtomicBool::new(false);
+
+#[test]
+fn it_runs_end_procs_and_returns_exit_status() {
+    let cleanup = unsafe { magnus::embed::init() };
+
+    embed::set_end_proc(|| END_PROC_RAN.store(true, Ordering::SeqCst));
+    let _: Value = eval("at_exit { exit 7 }").unwrap();
+    assert!(!END_PROC_RAN.load(Ordering::SeqCst));
+
+    let status = unsafe { cleanup.finish() };
+    assert!(END_PROC_RAN.load(Ordering::SeqCst));
+    assert_eq!(status, 7);
+}
@@ -0,0 +1,42 @@
+#![cfg(unix)]
+
+use magnus::{define_global_function, embed, eval, function};
+
+fn forked() -> bool {
+    embed::is_forked()
+}
+
+#[test]
+fn it_tracks_forks() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    define_global_function("magnus_forked?", function!(forked, 0));
+    assert!(!embed::is_forked());
+
+    let script = r#"
+        r, w = IO.pipe
+        pid = fork do
+          r.close
+          w.write(magnus_forked?.to_s)
+          w.close
+          exit!(0)
+        end
+        w.close
+        Process.wait(pid)
+        r.read
+    "#;
+
+    // without tracking, a fork made by Ruby looks like any other fork
+    let res: String = eval(script).unwrap();
+    assert_eq!(res, "true");
+
+    // Ruby 3.1+ can tell when the fork was made by Ruby
+    #[cfg(ruby_gte_3_1)]
+    {
+        embed::track_forks().unwrap();
+        let res: String = eval(script).unwrap();
+        assert_eq!(res, "false");
+    }
+
+    assert!(!embed::is_forked());
+}
@@ -0,0 +1,69 @@
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use magnus::{embed, eval, Value};
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Buffer {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct Broken;
+
+impl Write for Broken {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::Other, "broken pipe"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn it_redirects_output() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let stdout = Buffer::default();
+    let stderr = Buffer::default();
+    embed::redirect_stdout(stdout.clone()).unwrap();
+    embed::redirect_stderr(stderr.clone()).unwrap();
+
+    let _: Value = eval(
+        r#"
+        puts "line", 1
+        puts ["x", [nil]]
+        print "a", "b"
+        printf("%03d", 7)
+        $stdout << "!" << :sym
+        $stdout.flush
+        warn "careful"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(stdout.contents(), "line\n1\nx\n\nab007!sym");
+    assert_eq!(stderr.contents(), "careful\n");
+    assert!(!eval::<bool>("$stdout.tty?").unwrap());
+
+    embed::redirect_stdout(Broken).unwrap();
+    let err = eval::<Value>(r#"puts "lost""#).unwrap_err();
+    assert!(err.is_kind_of(magnus::exception::io_error()));
+
+    let _: Value = eval("$stdout = STDOUT").unwrap();
+}
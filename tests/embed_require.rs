@@ -0,0 +1,44 @@
+use std::fs;
+
+use magnus::eval;
+
+#[test]
+fn it_requires_and_loads_files() {
+    let cleanup = unsafe { magnus::embed::init() };
+
+    assert!(cleanup.require("English").unwrap());
+    assert!(!cleanup.require("English").unwrap());
+    assert!(cleanup.require("not_a_real_feature").is_err());
+
+    let dir = std::env::temp_dir().join(format!("magnus_embed_require_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("relative.rb"),
+        "$relative = (($relative || 0) + 1)",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("loaded.rb"),
+        "$loaded = (($loaded || 0) + 1)\nTOP_LEVEL = 1",
+    )
+    .unwrap();
+    std::env::set_current_dir(&dir).unwrap();
+
+    assert!(cleanup.require_relative("relative").unwrap());
+    assert!(!cleanup.require_relative("./relative.rb").unwrap());
+    assert_eq!(eval::<i64>("$relative").unwrap(), 1);
+
+    // wrapped, so constants don't leak in to the top level
+    assert!(cleanup.load_file("loaded.rb", true).unwrap());
+    assert!(cleanup.load_file("loaded.rb", true).unwrap());
+    assert_eq!(eval::<i64>("$loaded").unwrap(), 2);
+    assert!(!eval::<bool>("defined?(TOP_LEVEL) == 'constant'").unwrap());
+
+    assert!(cleanup.load_file("missing.rb", false).is_err());
+
+    fs::write(dir.join("raises.rb"), "raise 'oops'").unwrap();
+    let err = cleanup.load_file("raises.rb", false).unwrap_err();
+    assert!(err.to_string().contains("oops"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,42 @@
+use magnus::{
+    embed::{self, VmError},
+    eval, Value,
+};
+
+#[test]
+fn it_runs_ruby_on_a_dedicated_thread() {
+    let vm = embed::spawn();
+
+    let _: Value = vm.eval("$calls = []").unwrap();
+    let workers = (0..4)
+        .map(|i| {
+            let vm = vm.clone();
+            std::thread::spawn(move || {
+                vm.call::<_, Value>("eval", (format!("$calls << {}", i),))
+                    .unwrap();
+            })
+        })
+        .collect::<Vec<_>>();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    let mut calls: Vec<i64> = vm.eval("$calls").unwrap();
+    calls.sort_unstable();
+    assert_eq!(calls, vec![0, 1, 2, 3]);
+
+    let res = vm
+        .run(|| eval::<String>("'from closure'").unwrap())
+        .unwrap();
+    assert_eq!(res, "from closure");
+
+    match vm.eval::<Value>("raise ArgumentError, 'bad'") {
+        Err(VmError::Ruby(e)) => {
+            assert_eq!(e.class_name(), "ArgumentError");
+            assert_eq!(e.message(), "bad");
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    vm.shutdown();
+    assert!(matches!(vm.eval::<Value>("1"), Err(VmError::Stopped)));
+}
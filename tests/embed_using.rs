@@ -0,0 +1,30 @@
+use magnus::{
+    class, define_global_function, define_module, embed, eval, function, method, Error, Module,
+};
+
+fn shout(rb_self: String) -> String {
+    rb_self.to_uppercase()
+}
+
+fn activate() -> Result<(), Error> {
+    embed::using(define_module("Shouting")?)
+}
+
+#[test]
+fn it_activates_refinements_at_the_top_level() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let module = define_module("Shouting").unwrap();
+    let refinement = module.define_refinement(class::string()).unwrap();
+    refinement
+        .define_method("shout", method!(shout, 0))
+        .unwrap();
+
+    define_global_function("activate", function!(activate, 0));
+    let err = eval::<bool>("activate").unwrap_err();
+    assert!(err.to_string().contains("only at toplevel"));
+
+    embed::using(module).unwrap();
+    let res: String = eval(r#""hello".shout"#).unwrap();
+    assert_eq!(res, "HELLO");
+}
@@ -0,0 +1,40 @@
+use std::{
+    cell::Cell,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use magnus::{error::ensure, eval, Error, Value};
+
+#[test]
+fn it_runs_cleanup() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let count = Cell::new(0);
+
+    let res: Result<i64, Error> = ensure(|| eval("1 + 1"), || count.set(count.get() + 1));
+    assert_eq!(res.unwrap(), 2);
+    assert_eq!(count.get(), 1);
+
+    let res = ensure(
+        || eval::<Value>("raise ArgumentError"),
+        || count.set(count.get() + 1),
+    );
+    assert!(res.unwrap_err().is_arg_error());
+    assert_eq!(count.get(), 2);
+
+    let res = ensure(
+        || eval::<Value>("throw :done"),
+        || count.set(count.get() + 1),
+    );
+    assert!(res.is_err());
+    assert_eq!(count.get(), 3);
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        ensure(
+            || -> Result<(), Error> { panic!("boom") },
+            || count.set(count.get() + 1),
+        )
+    }));
+    assert!(res.is_err());
+    assert_eq!(count.get(), 4);
+}
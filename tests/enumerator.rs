@@ -1,4 +1,4 @@
-use magnus::{eval, RArray};
+use magnus::{eval, exception, Enumerator, RArray};
 
 #[test]
 fn enumerator_impls_iterator() {
@@ -27,4 +27,12 @@ fn enumerator_impls_iterator() {
         3
     );
     assert!(e.next().is_none());
+
+    let e: Enumerator = eval("Enumerator.new { |y| y << 1; raise IndexError }").unwrap();
+    let vals = e.take(2).collect::<Vec<_>>();
+    assert!(vals[0].is_ok());
+    assert!(vals[1]
+        .as_ref()
+        .unwrap_err()
+        .is_kind_of(exception::index_error()));
 }
@@ -0,0 +1,40 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use magnus::{eval, Enumerator, Error};
+
+#[test]
+fn it_creates_lazy_enumerators() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let produced = Arc::new(AtomicUsize::new(0));
+    let counter = produced.clone();
+    let enumerator = Enumerator::from_fn(move |yielder| {
+        for i in 0.. {
+            counter.fetch_add(1, Ordering::SeqCst);
+            yielder.yield_value(i)?;
+        }
+        Ok(())
+    });
+
+    let res: bool = eval!("e.first(3) == [0, 1, 2]", e = enumerator).unwrap();
+    assert!(res);
+    assert_eq!(produced.load(Ordering::SeqCst), 3);
+
+    let vals = enumerator
+        .take(2)
+        .map(|v| v.and_then(|v| v.try_convert::<i64>()))
+        .collect::<Result<Vec<_>, Error>>()
+        .unwrap();
+    assert_eq!(vals, vec![0, 1]);
+
+    let pairs = Enumerator::from_fn(|yielder| {
+        yielder.yield_values(("a", 1))?;
+        yielder.yield_values(("b", 2))?;
+        Ok(())
+    });
+    let res: bool = eval!("pairs.to_h == {'a' => 1, 'b' => 2}", pairs).unwrap();
+    assert!(res);
+}
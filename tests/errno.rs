@@ -0,0 +1,32 @@
+use magnus::{eval, exception, Error, ExceptionClass};
+
+#[test]
+fn it_creates_errno_errors() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let enoent: ExceptionClass = eval("Errno::ENOENT").unwrap();
+    let errno: i32 = eval("Errno::ENOENT::Errno").unwrap();
+    let err = Error::from_errno(errno);
+    assert!(err.is_kind_of(enoent));
+    assert!(err.is_kind_of(exception::system_call_error()));
+
+    let eagain: ExceptionClass = eval("Errno::EAGAIN").unwrap();
+    let errno: i32 = eval("Errno::EAGAIN::Errno").unwrap();
+    assert!(Error::from_errno(errno).is_kind_of(eagain));
+
+    let res = std::fs::File::open("/path/does/not/exist");
+    assert!(res.is_err());
+    assert!(Error::last_os_error().is_kind_of(enoent));
+
+    let res: bool = eval(
+        r#"
+        begin
+          raise Errno::ENOENT
+        rescue SystemCallError => e
+          e.errno == Errno::ENOENT::Errno
+        end
+        "#,
+    )
+    .unwrap();
+    assert!(res);
+}
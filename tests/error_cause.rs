@@ -0,0 +1,67 @@
+use magnus::{define_global_function, eval, exception, function, Error, Value};
+
+fn wrap_failure() -> Result<Value, Error> {
+    eval("Integer('abc')")
+        .map_err(|e| Error::with_cause(exception::runtime_error(), "wrapped failure", e))
+}
+
+fn fail_while_handling() -> Result<Value, Error> {
+    // an exception caught in Rust doesn't clobber `$!`
+    let _ = eval::<Value>("Integer('abc')");
+    Err(Error::new(exception::runtime_error(), "while handling"))
+}
+
+#[test]
+fn it_chains_causes() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let err = wrap_failure().unwrap_err();
+    let ex = err.value().unwrap();
+    assert_eq!(ex.message().unwrap(), "wrapped failure");
+    assert!(ex
+        .cause()
+        .unwrap()
+        .unwrap()
+        .is_kind_of(exception::arg_error()));
+
+    let err = Error::with_cause(
+        exception::type_error(),
+        "outer",
+        Error::new(exception::key_error(), "inner"),
+    );
+    let cause = err.value().unwrap().cause().unwrap().unwrap();
+    assert!(cause.is_kind_of(exception::key_error()));
+    assert_eq!(cause.message().unwrap(), "inner");
+
+    define_global_function("wrap_failure", function!(wrap_failure, 0));
+    let res: bool = eval(
+        r#"
+        begin
+          wrap_failure
+        rescue => e
+          e.message == "wrapped failure" && e.cause.is_a?(ArgumentError)
+        end
+        "#,
+    )
+    .unwrap();
+    assert!(res);
+
+    // errors raised while Ruby is handling an exception get it as their cause
+    define_global_function("fail_while_handling", function!(fail_while_handling, 0));
+    let res: bool = eval(
+        r#"
+        begin
+          begin
+            raise KeyError, "original"
+          rescue
+            fail_while_handling
+          end
+        rescue => e
+          e.message == "while handling" && e.cause.is_a?(KeyError)
+        end
+        "#,
+    )
+    .unwrap();
+    assert!(res);
+    assert!(eval::<Value>("$!").unwrap().is_nil());
+}
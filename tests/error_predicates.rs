@@ -0,0 +1,36 @@
+use magnus::{define_error, eval, exception, Error, Value};
+
+#[test]
+fn it_matches_errors_by_class() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let err = eval::<Value>("Integer('abc')").unwrap_err();
+    assert!(err.is_arg_error());
+    assert!(!err.is_type_error());
+
+    let err = eval::<Value>("nil.foo").unwrap_err();
+    assert!(err.is_no_method_error());
+    assert!(err.is_name_error());
+
+    let err = eval::<Value>("{}.fetch(:a)").unwrap_err();
+    assert!(err.is_key_error());
+    assert!(err.is_index_error());
+
+    assert!(eval::<Value>("1 / 0").unwrap_err().is_zero_div_error());
+    assert!(eval::<Value>("'a'.freeze << 'b'")
+        .unwrap_err()
+        .is_frozen_error());
+    assert!(eval::<Value>("[].each.next")
+        .unwrap_err()
+        .is_stop_iteration());
+
+    let err = Error::new(exception::range_error(), "out of range");
+    assert!(err.is_range_error());
+    assert!(!err.is_runtime_error());
+
+    let app_error = define_error("AppError", exception::runtime_error()).unwrap();
+    let err = eval::<Value>("raise AppError, 'oops'").unwrap_err();
+    assert!(err.is_kind_of(app_error));
+    assert!(err.is_runtime_error());
+    assert_eq!(err.value().unwrap().message().unwrap(), "oops");
+}
@@ -0,0 +1,20 @@
+use magnus::{embed, eval, Value};
+
+#[test]
+fn it_evaluates_scripts_with_file_names() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let _: Value = magnus::prelude!("ruby/prelude.rb").unwrap();
+    let (file, line, dir): (String, i64, String) = eval("prelude_location").unwrap();
+    assert_eq!(file, "ruby/prelude.rb");
+    assert_eq!(line, 2);
+    assert!(dir.ends_with("ruby"));
+
+    let err = eval::<Value>("prelude_fail").unwrap_err();
+    let backtrace: Vec<String> = err.value().unwrap().funcall("backtrace", ()).unwrap();
+    assert!(backtrace[0].starts_with("ruby/prelude.rb:6"));
+
+    let err = embed::eval_script::<Value>("\n\nraise 'oops'", "inline.rb").unwrap_err();
+    let backtrace: Vec<String> = err.value().unwrap().funcall("backtrace", ()).unwrap();
+    assert!(backtrace[0].starts_with("inline.rb:3"));
+}
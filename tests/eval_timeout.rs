@@ -0,0 +1,44 @@
+use std::time::{Duration, Instant};
+
+use magnus::{
+    embed::{self, CancelToken},
+    eval, exception, Value,
+};
+
+#[test]
+fn it_interrupts_long_running_code() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let res: i64 = embed::eval_with_timeout("6 * 7", Duration::from_secs(5)).unwrap();
+    assert_eq!(res, 42);
+
+    let start = Instant::now();
+    let err = embed::eval_with_timeout::<Value>(
+        "begin; loop {}; rescue => e; :rescued; end",
+        Duration::from_millis(50),
+    )
+    .unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(err.is_kind_of(exception::interrupt()));
+    assert!(err.to_string().contains("execution expired"));
+
+    // other errors are returned as normal
+    let err =
+        embed::eval_with_timeout::<Value>("raise 'oops'", Duration::from_secs(5)).unwrap_err();
+    assert!(err.is_kind_of(exception::runtime_error()));
+
+    let token = CancelToken::new();
+    let canceller = token.clone();
+    let handle = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        canceller.cancel();
+    });
+    let err = embed::eval_with_cancel::<Value>("sleep 10", &token).unwrap_err();
+    assert!(err.is_kind_of(exception::interrupt()));
+    assert!(token.is_cancelled());
+    handle.join().unwrap();
+
+    // no interrupts are left pending
+    let res: i64 = eval("sleep 0.1; 1").unwrap();
+    assert_eq!(res, 1);
+}
@@ -0,0 +1,47 @@
+use magnus::{eval, exception, Error, Value};
+
+#[test]
+fn it_exposes_exception_details() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let err = eval::<Value>(
+        r#"
+            def parse(s)
+              Integer(s)
+            rescue ArgumentError
+              raise RangeError, "could not parse #{s}"
+            end
+
+            parse("abc")
+        "#,
+    )
+    .unwrap_err();
+
+    let ex = err.value().unwrap();
+    assert_eq!(ex.message().unwrap(), "could not parse abc");
+    assert!(ex
+        .exception_class()
+        .equal(exception::range_error())
+        .unwrap());
+
+    let backtrace = ex.backtrace().unwrap().unwrap();
+    let first: String = backtrace.entry(0).unwrap();
+    // Ruby 3.4 changed the frame label format from `parse' to 'Object#parse'
+    assert!(first.contains("parse'"));
+
+    let full = ex.full_message().unwrap();
+    assert!(full.contains("could not parse abc (RangeError)"));
+    assert!(!full.contains('\u{1b}'));
+
+    let cause = ex.cause().unwrap().unwrap();
+    assert!(cause
+        .exception_class()
+        .equal(exception::arg_error())
+        .unwrap());
+    assert!(cause.message().unwrap().contains("abc"));
+    assert!(cause.cause().unwrap().is_none());
+
+    assert!(Error::new(exception::type_error(), "test")
+        .value()
+        .is_none());
+}
@@ -0,0 +1,62 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use magnus::{
+    block_on, eval,
+    executor::{ExecutorStoppedError, RubyExecutor},
+    without_gvl, RArray,
+};
+
+#[test]
+fn it_runs_closures_from_other_threads() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let executor = RubyExecutor::spawn().unwrap();
+    let ary = eval::<RArray>("$results = []").unwrap();
+
+    // called on a Ruby thread with the GVL, so runs immediately
+    assert_eq!(executor.run(|| 1 + 1).unwrap(), 2);
+
+    let workers = (0..4)
+        .map(|i| {
+            let handle = executor.clone();
+            std::thread::spawn(move || {
+                handle
+                    .run(move || {
+                        eval::<RArray>("$results").unwrap().push(i).unwrap();
+                    })
+                    .unwrap();
+                // panics are propagated to the caller
+                catch_unwind(AssertUnwindSafe(|| handle.run(|| panic!("oops")))).is_err()
+            })
+        })
+        .collect::<Vec<_>>();
+    let panicked = unsafe {
+        without_gvl(
+            || workers.into_iter().map(|w| w.join().unwrap()).all(|p| p),
+            None::<fn()>,
+        )
+    }
+    .unwrap();
+    assert!(panicked);
+    let mut results = ary.to_vec::<i64>().unwrap();
+    results.sort_unstable();
+    assert_eq!(results, vec![0, 1, 2, 3]);
+
+    let task = executor.run_async(|| eval::<i64>("6 * 7").unwrap());
+    assert_eq!(unsafe { block_on(task) }.unwrap().unwrap(), 42);
+
+    executor.shutdown();
+    let handle = executor.clone();
+    let res = unsafe {
+        without_gvl(
+            || {
+                std::thread::spawn(move || handle.run(|| ()))
+                    .join()
+                    .unwrap()
+            },
+            None::<fn()>,
+        )
+    }
+    .unwrap();
+    assert!(matches!(res, Err(ExecutorStoppedError)));
+}
@@ -0,0 +1,58 @@
+use magnus::{eval, exception, Error, Fiber, Value};
+
+#[test]
+fn it_runs_fibers() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let fiber = Fiber::new(|args| -> Result<String, Error> {
+        let mut s = args[0].try_convert::<String>()?;
+        for _ in 0..2 {
+            let next: String = Fiber::yield_value(s.len())?;
+            s.push_str(&next);
+        }
+        Ok(s)
+    })
+    .unwrap();
+
+    assert!(fiber.is_alive());
+    assert_eq!(fiber.resume::<_, usize>(("ab",)).unwrap(), 2);
+    assert_eq!(fiber.resume::<_, usize>(("cd",)).unwrap(), 4);
+    assert_eq!(fiber.resume::<_, String>(("ef",)).unwrap(), "abcdef");
+    assert!(!fiber.is_alive());
+    assert!(fiber.resume::<_, Value>(()).is_err());
+
+    // fiber usable from Ruby
+    let res: bool = eval!("fiber.is_a?(Fiber)", fiber).unwrap();
+    assert!(res);
+
+    // errors propagate to the resuming fiber
+    let fiber =
+        Fiber::new(|_| Err::<Value, _>(Error::new(exception::arg_error(), "oops"))).unwrap();
+    assert!(fiber
+        .resume::<_, Value>(())
+        .unwrap_err()
+        .is_kind_of(exception::arg_error()));
+
+    // can't yield from the root fiber
+    assert!(Fiber::yield_values::<_, Value>(()).is_err());
+
+    let root = Fiber::current();
+    let fiber = Fiber::new(move |_| !Fiber::current().is_equal(root)).unwrap();
+    assert!(fiber.resume::<_, bool>(()).unwrap());
+
+    // control transferred back explicitly
+    let fiber = Fiber::new(move |args| root.transfer::<_, Value>((args[0],))).unwrap();
+    assert_eq!(fiber.transfer::<_, i64>((1,)).unwrap(), 1);
+
+    Fiber::local_set("example", 1).unwrap();
+    let fiber = Fiber::new(|_| {
+        Fiber::local_set("example", 2)?;
+        Fiber::local_get::<_, i64>("example")
+    })
+    .unwrap();
+    assert_eq!(fiber.resume::<_, i64>(()).unwrap(), 2);
+    assert_eq!(Fiber::local_get::<_, i64>("example").unwrap(), 1);
+
+    let res: Value = eval("Object.new").unwrap();
+    assert!(res.try_convert::<Fiber>().is_err());
+}
@@ -0,0 +1,148 @@
+#[cfg(ruby_gte_3_0)]
+mod fiber_scheduler {
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{Arc, Mutex},
+        task::{Context, Poll, Waker},
+        time::Duration,
+    };
+
+    use magnus::{define_global_function, eval, function, Error, FiberScheduler, Value};
+
+    #[derive(Default)]
+    struct State {
+        done: bool,
+        waker: Option<Waker>,
+    }
+
+    // completed by a non-Ruby thread, as if from an async runtime
+    struct Delay(Arc<Mutex<State>>);
+
+    impl Delay {
+        fn new(duration: Duration) -> Self {
+            let state = Arc::new(Mutex::new(State::default()));
+            let thread_state = state.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(duration);
+                let mut state = thread_state.lock().unwrap();
+                state.done = true;
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+            Self(state)
+        }
+    }
+
+    impl Future for Delay {
+        type Output = i64;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let mut state = self.0.lock().unwrap();
+            if state.done {
+                Poll::Ready(42)
+            } else {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    fn rust_delay() -> Result<i64, Error> {
+        let scheduler = FiberScheduler::current().unwrap();
+        unsafe { scheduler.block_on(Delay::new(Duration::from_millis(50))) }
+    }
+
+    fn rust_sleep() -> Result<(), Error> {
+        FiberScheduler::current()
+            .unwrap()
+            .kernel_sleep(Some(Duration::from_millis(10)))
+    }
+
+    #[test]
+    fn it_suspends_fibers() {
+        let _cleanup = unsafe { magnus::embed::init() };
+
+        define_global_function("rust_delay", function!(rust_delay, 0));
+        define_global_function("rust_sleep", function!(rust_sleep, 0));
+
+        let _: Value = eval(
+            r#"
+            class TestScheduler
+              def initialize
+                @waiting = {}
+                @ready = []
+              end
+
+              def fiber(&block)
+                fiber = Fiber.new(blocking: false, &block)
+                fiber.resume
+                fiber
+              end
+
+              def block(blocker, timeout = nil)
+                @waiting[Fiber.current] = timeout && now + timeout
+                Fiber.yield
+              end
+
+              def unblock(blocker, fiber)
+                @ready << fiber
+              end
+
+              def kernel_sleep(duration = nil)
+                block(:sleep, duration)
+              end
+
+              def io_wait(io, events, timeout)
+                raise NotImplementedError
+              end
+
+              def close
+                until @waiting.empty?
+                  ready = @ready
+                  @ready = []
+                  @waiting.each { |f, deadline| ready << f if deadline && deadline <= now }
+                  ready.uniq.each { |f| f.resume if @waiting.delete(f) }
+                  sleep 0.001
+                end
+              end
+
+              private
+
+              def now
+                Process.clock_gettime(Process::CLOCK_MONOTONIC)
+              end
+            end
+            "#,
+        )
+        .unwrap();
+
+        assert!(FiberScheduler::current().is_none());
+
+        let res: Vec<Value> = eval(
+            r#"
+            events = []
+            Thread.new do
+              Fiber.set_scheduler(TestScheduler.new)
+              Fiber.schedule { events << rust_delay }
+              Fiber.schedule do
+                3.times do
+                  events << :tick
+                  rust_sleep
+                end
+              end
+            end.join
+            events
+            "#,
+        )
+        .unwrap();
+        let ticks = res
+            .iter()
+            .filter(|v| !v.is_kind_of(magnus::class::integer()))
+            .count();
+        assert_eq!(ticks, 3);
+        // the other fiber could run while the future was pending
+        assert_eq!(res.last().unwrap().try_convert::<i64>().unwrap(), 42);
+    }
+}
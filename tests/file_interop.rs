@@ -0,0 +1,36 @@
+#![cfg(unix)]
+
+use std::{
+    fs::File,
+    io::{Read, Write},
+};
+
+use magnus::{eval, RFile};
+
+#[test]
+fn it_converts_between_rfile_and_std_file() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let path = std::env::temp_dir().join("magnus_file_interop.txt");
+    let file = File::create(&path).unwrap();
+
+    let rfile = RFile::from_file(file, "w").unwrap();
+    let fd = rfile.as_raw_fd().unwrap();
+    let res: i32 = eval!("rfile.fileno", rfile).unwrap();
+    assert_eq!(fd, res);
+
+    let mut clone = rfile.try_clone_to_std().unwrap();
+    let _: magnus::Value = eval!("rfile.close", rfile).unwrap();
+    assert!(rfile.as_raw_fd().is_err());
+    clone.write_all(b"written after close").unwrap();
+    drop(clone);
+
+    let mut buf = String::new();
+    File::open(&path).unwrap().read_to_string(&mut buf).unwrap();
+    assert_eq!(buf, "written after close");
+
+    let file = File::open(&path).unwrap();
+    assert!(RFile::from_file(file, "invalid").is_err());
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,40 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use magnus::{class, eval, gc, Value};
+
+#[test]
+fn it_runs_finalizers() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let finalized = Arc::new(AtomicUsize::new(0));
+
+    for _ in 0..100 {
+        let obj = class::object().new_instance(()).unwrap();
+        let counter = finalized.clone();
+        gc::define_finalizer(obj, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+    }
+
+    let obj = class::object().new_instance(()).unwrap();
+    gc::define_finalizer(obj, || panic!("finalizer should not run")).unwrap();
+    gc::undefine_finalizer(obj).unwrap();
+
+    for _ in 0..4 {
+        let _: Value = eval(r#"1024.times.map {|i| "test#{i}"}"#).unwrap();
+        gc::start();
+    }
+    // finalizers are deferred until Ruby next checks for interrupts
+    let _: Value = eval("nil").unwrap();
+
+    assert!(finalized.load(Ordering::SeqCst) > 0);
+
+    let immediate: Value = eval("42").unwrap();
+    assert!(gc::define_finalizer(immediate, || ()).is_err());
+    let frozen: Value = eval("Object.new.freeze").unwrap();
+    assert!(gc::define_finalizer(frozen, || ()).is_err());
+}
@@ -27,4 +27,30 @@ fn it_can_check_frozen() {
     rb_assert!("!val.frozen?", val);
     val.freeze();
     rb_assert!("val.frozen?", val);
+
+    let copy = val.dup().unwrap();
+    assert!(!copy.is_frozen());
+    rb_assert!("!copy.equal?(val)", copy, val);
+
+    let obj: Value = eval(
+        r#"
+        o = Object.new
+        def o.example
+          42
+        end
+        o.freeze
+        "#,
+    )
+    .unwrap();
+    let cloned = obj.clone_value(None).unwrap();
+    assert!(cloned.is_frozen());
+    rb_assert!("cloned.example == 42", cloned);
+    assert!(!obj.clone_value(Some(false)).unwrap().is_frozen());
+    assert!(!obj.dup().unwrap().respond_to("example", false).unwrap());
+
+    #[cfg(ruby_gte_3_0)]
+    {
+        let unfrozen: Value = eval("Object.new").unwrap();
+        assert!(unfrozen.clone_value(Some(true)).unwrap().is_frozen());
+    }
 }
@@ -0,0 +1,41 @@
+use magnus::{eval, value::Id, RString, Value};
+
+#[test]
+fn it_calls_methods_by_id() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let obj: Value = eval(
+        r#"
+        class Example
+          def add(a, b)
+            a + b
+          end
+
+          private
+
+          def secret
+            42
+          end
+        end
+        Example.new
+        "#,
+    )
+    .unwrap();
+
+    let add = Id::from("add");
+    let args = [eval::<Value>("1").unwrap(), eval::<Value>("2").unwrap()];
+    let res: i64 = obj.funcall_id(add, &args).unwrap();
+    assert_eq!(res, 3);
+
+    let res: i64 = obj.funcall_public(add, &args[..]).unwrap();
+    assert_eq!(res, 3);
+
+    let res: i64 = obj.funcall("secret", ()).unwrap();
+    assert_eq!(res, 42);
+    assert!(obj.funcall_public::<_, _, i64>("secret", ()).is_err());
+
+    let s: String = RString::new("abc")
+        .funcall_id(Id::from("upcase"), &[])
+        .unwrap();
+    assert_eq!(s, "ABC");
+}
@@ -0,0 +1,31 @@
+use magnus::{gc, Symbol};
+
+#[test]
+fn it_controls_gc() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    assert!(!gc::disable());
+    assert!(gc::disable());
+    assert!(gc::enable());
+    assert!(!gc::enable());
+
+    let count = gc::count();
+    gc::start();
+    assert!(gc::count() > count);
+
+    let minor = gc::stat("minor_gc_count").unwrap();
+    let major = gc::stat("major_gc_count").unwrap();
+    gc::start_with(false, true).unwrap();
+    assert!(gc::stat("minor_gc_count").unwrap() > minor);
+
+    gc::start_with(true, false).unwrap();
+    assert!(gc::stat("major_gc_count").unwrap() > major);
+
+    assert!(gc::stat("not_a_real_key").is_err());
+
+    let stats = gc::all_stats();
+    assert_eq!(
+        stats.fetch::<_, usize>(Symbol::new("count")).unwrap(),
+        gc::count()
+    );
+}
@@ -0,0 +1,13 @@
+use magnus::gc;
+
+#[test]
+fn it_reports_external_memory() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let before = gc::stat("malloc_increase_bytes").unwrap();
+    gc::adjust_memory_usage(4096);
+    assert!(gc::stat("malloc_increase_bytes").unwrap() >= before + 4096);
+
+    gc::adjust_memory_usage(-4096);
+    assert!(gc::stat("malloc_increase_bytes").unwrap() < before + 4096);
+}
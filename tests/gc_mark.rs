@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use magnus::{
+    define_class, eval,
+    gc::{self, Mark},
+    typed_data::Obj,
+    DataTypeFunctions, RString, TypedData, Value,
+};
+
+#[derive(TypedData)]
+#[magnus(class = "Holder", mark)]
+struct Holder {
+    list: Vec<RString>,
+    by_name: HashMap<String, RString>,
+    maybe: Option<RString>,
+    array: [Option<RString>; 2],
+    iter: Vec<RString>,
+}
+
+impl DataTypeFunctions for Holder {
+    fn mark(&self) {
+        self.list.mark();
+        self.by_name.mark();
+        self.maybe.mark();
+        self.array.mark();
+        gc::mark_iter(self.iter.iter().copied());
+    }
+}
+
+fn string(i: usize) -> RString {
+    RString::new(&format!("value {}", i))
+}
+
+#[test]
+fn it_marks_collections() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    define_class("Holder", Default::default()).unwrap();
+
+    let holder = Obj::wrap(Holder {
+        list: (0..8).map(string).collect(),
+        by_name: (0..8).map(|i| (i.to_string(), string(i))).collect(),
+        maybe: Some(string(0)),
+        array: [Some(string(0)), None],
+        iter: (0..8).map(string).collect(),
+    });
+    let _: Value = eval!("$holder = holder", holder).unwrap();
+
+    for _ in 0..4 {
+        let _: Value = eval(r#"1024.times.map {|i| "test#{i}"}"#).unwrap();
+        gc::start();
+    }
+
+    let holder = holder.get();
+    for (i, s) in holder.list.iter().copied().enumerate() {
+        assert_eq!(s.to_string().unwrap(), format!("value {}", i));
+    }
+    for (k, &s) in holder.by_name.iter() {
+        assert_eq!(s.to_string().unwrap(), format!("value {}", k));
+    }
+    assert_eq!(holder.maybe.unwrap().to_string().unwrap(), "value 0");
+    assert_eq!(holder.array[0].unwrap().to_string().unwrap(), "value 0");
+    for (i, s) in holder.iter.iter().copied().enumerate() {
+        assert_eq!(s.to_string().unwrap(), format!("value {}", i));
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use magnus::gc;
+
+static ENTER: AtomicUsize = AtomicUsize::new(0);
+static EXIT: AtomicUsize = AtomicUsize::new(0);
+
+fn on_gc(event: gc::Event) {
+    match event {
+        gc::Event::Enter => ENTER.fetch_add(1, Ordering::SeqCst),
+        gc::Event::Exit => EXIT.fetch_add(1, Ordering::SeqCst),
+        _ => 0,
+    };
+}
+
+#[test]
+fn it_profiles_gc() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    assert!(!gc::profiler_is_enabled().unwrap());
+    gc::profiler_enable().unwrap();
+    assert!(gc::profiler_is_enabled().unwrap());
+    gc::start();
+    assert!(gc::profiler_total_time().unwrap() > 0.0);
+    assert!(gc::profiler_result().unwrap().contains("GC"));
+    gc::profiler_clear().unwrap();
+    assert_eq!(gc::profiler_total_time().unwrap(), 0.0);
+    gc::profiler_disable().unwrap();
+    assert!(!gc::profiler_is_enabled().unwrap());
+
+    let hook = gc::add_event_hook(&[gc::Event::Enter, gc::Event::Exit], on_gc).unwrap();
+    assert!(hook.is_enabled());
+    gc::start();
+    let enter = ENTER.load(Ordering::SeqCst);
+    assert!(enter > 0);
+    assert_eq!(enter, EXIT.load(Ordering::SeqCst));
+
+    hook.disable().unwrap();
+    assert!(!hook.is_enabled());
+    gc::start();
+    assert_eq!(ENTER.load(Ordering::SeqCst), enter);
+}
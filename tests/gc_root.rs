@@ -0,0 +1,20 @@
+use magnus::{eval, gc, RString, Value};
+
+#[test]
+fn it_roots_values() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let mut root = gc::Root::new(RString::new("first"));
+
+    eval::<Value>(r#"1024.times.map {|i| "test#{i}"}"#).unwrap();
+    gc::start();
+    assert_eq!(root.to_string().unwrap(), "first");
+
+    let prev = root.set(RString::new("second"));
+    assert_eq!(prev.to_string().unwrap(), "first");
+
+    eval::<Value>(r#"1024.times.map {|i| "test#{i}"}"#).unwrap();
+    gc::start();
+    assert_eq!(root.get().to_string().unwrap(), "second");
+    assert_eq!(format!("{:?}", root), r#""second""#);
+}
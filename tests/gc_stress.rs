@@ -0,0 +1,34 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use magnus::{gc, RArray};
+
+#[test]
+fn it_stresses_gc() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    assert!(!gc::is_stress().unwrap());
+    let ary = gc::with_stress(|| {
+        assert!(gc::is_stress().unwrap());
+        let ary = RArray::new();
+        for i in 0..100 {
+            ary.push(format!("value {}", i)).unwrap();
+        }
+        ary
+    })
+    .unwrap();
+    assert!(!gc::is_stress().unwrap());
+    assert_eq!(ary.len(), 100);
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        gc::with_stress(|| panic!("test")).unwrap();
+    }));
+    assert!(res.is_err());
+    assert!(!gc::is_stress().unwrap());
+
+    #[cfg(ruby_gte_2_7)]
+    {
+        if gc::compact().is_ok() {
+            gc::verify_compaction_references().unwrap();
+        }
+    }
+}
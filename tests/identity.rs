@@ -0,0 +1,25 @@
+use magnus::{class, eval, RString, Value};
+
+#[test]
+fn it_checks_identity() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let a = RString::new("example");
+    let b = RString::new("example");
+    assert!(a.is_equal(a));
+    assert!(!a.is_equal(b));
+    assert!(a.equal(b).unwrap());
+
+    let (x, y): (Value, Value) = eval("s = 'shared'; [s, s]").unwrap();
+    assert!(x.is_equal(y));
+
+    let id = a.object_id();
+    let res: bool = eval!("a.object_id == id", a, id).unwrap();
+    assert!(res);
+    assert!(!a.object_id().equal(b.object_id()).unwrap());
+
+    let val: Value = eval("Class.new(Array).new").unwrap();
+    assert!(val.is_kind_of(class::array()));
+    assert!(!val.is_instance_of(class::array()));
+    assert!(val.is_instance_of(val.class()));
+}
@@ -0,0 +1,31 @@
+use magnus::{eval, value::Inspect, RArray, RString, Value};
+
+#[test]
+fn it_formats_with_inspect() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let s = RString::new("example");
+    assert_eq!(format!("{}", Inspect(s)), r#""example""#);
+    assert_eq!(format!("{:?}", Inspect(s)), r#""example""#);
+
+    let ary: RArray = eval(r#"Array.new(20) { |i| "item number #{i}" }"#).unwrap();
+    assert!(!format!("{}", Inspect(ary)).contains('\n'));
+    assert!(format!("{:#}", Inspect(ary)).contains('\n'));
+    assert!(format!("{:#?}", Inspect(ary)).starts_with(r#"["item number 0","#));
+
+    let val: Value = eval(
+        r#"
+        o = Object.new
+        def o.inspect
+          raise "oops"
+        end
+        def o.pretty_inspect
+          raise "oops"
+        end
+        o
+        "#,
+    )
+    .unwrap();
+    assert!(format!("{}", Inspect(val)).starts_with("#<Object:0x"));
+    assert!(format!("{:#}", Inspect(val)).starts_with("#<Object:0x"));
+}
@@ -0,0 +1,31 @@
+use magnus::{define_class, eval, exception, Error, Module, RString, Value};
+
+#[test]
+fn it_execs_closures_with_self() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let s = RString::new("example");
+    let res: bool = s
+        .instance_exec((1, 2), move |rb_self, args| {
+            rb_self.is_equal(s) && args.len() == 2
+        })
+        .unwrap();
+    assert!(res);
+
+    let obj: Value = eval("Object.new").unwrap();
+    let res: Result<Value, Error> = obj.instance_exec((), |_, _| {
+        Err::<Value, _>(Error::new(exception::runtime_error(), "failed"))
+    });
+    assert!(res.is_err());
+
+    let class = define_class("Example", Default::default()).unwrap();
+    let suffix = String::from("_name");
+    let _: Value = class
+        .class_exec(("first",), move |rb_self, args| {
+            let name = format!("{}{}", args[0].try_convert::<String>()?, suffix);
+            rb_self.funcall::<_, _, Value>("attr_accessor", (name,))
+        })
+        .unwrap();
+    let res: bool = eval("Example.new.respond_to?(:first_name=)").unwrap();
+    assert!(res);
+}
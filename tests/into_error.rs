@@ -0,0 +1,71 @@
+use std::fmt;
+
+use magnus::{define_module, eval, exception, Error, ExceptionClass, IntoError, Module};
+
+#[derive(Debug, IntoError)]
+#[magnus(class = "Config::Error")]
+enum ConfigError {
+    #[magnus(class = "ArgumentError")]
+    InvalidKey(String),
+    #[magnus(class = "Config::MissingError")]
+    Missing { key: String },
+    #[magnus(message = "config file corrupt")]
+    Corrupt,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidKey(key) => write!(f, "invalid key: {}", key),
+            Self::Missing { key } => write!(f, "missing key: {}", key),
+            Self::Corrupt => write!(f, "corrupt"),
+        }
+    }
+}
+
+#[derive(Debug, IntoError)]
+#[magnus(class = "TypeError", message = "wrong type")]
+struct WrongType;
+
+fn lookup(key: &str) -> Result<String, Error> {
+    match key {
+        "" => Err(ConfigError::InvalidKey(key.to_owned()).into()),
+        "corrupt" => Err(ConfigError::Corrupt.into()),
+        _ => Err(ConfigError::Missing {
+            key: key.to_owned(),
+        }
+        .into()),
+    }
+}
+
+#[test]
+fn it_converts_error_enums() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let config = define_module("Config").unwrap();
+    let base = config
+        .define_error("Error", exception::standard_error())
+        .unwrap();
+    let missing = config.define_error("MissingError", base).unwrap();
+
+    let err = lookup("").unwrap_err();
+    assert!(err.is_arg_error());
+    assert!(err.to_string().contains("invalid key"));
+
+    let err = lookup("name").unwrap_err();
+    assert!(err.is_kind_of(missing));
+    assert!(err.to_string().contains("missing key: name"));
+
+    let err = lookup("corrupt").unwrap_err();
+    assert!(err.is_kind_of(base));
+    assert!(!err.is_kind_of(missing));
+    assert!(err.to_string().contains("config file corrupt"));
+
+    let err = Error::from(WrongType);
+    assert!(err.is_type_error());
+    assert!(err.to_string().contains("wrong type"));
+
+    let err = Error::from(ConfigError::Corrupt);
+    let class: ExceptionClass = eval("Config::Error").unwrap();
+    assert!(err.is_kind_of(class));
+}
@@ -0,0 +1,47 @@
+use std::io::{Read, Write};
+
+use magnus::{eval, io::Io, RArray, Value};
+
+#[test]
+fn it_reads_and_writes_ruby_io() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let pipe: RArray = eval("IO.pipe").unwrap();
+    let (reader, writer): (Io, Io) = pipe.try_convert().unwrap();
+    assert!(reader.fileno().unwrap().is_some());
+
+    let mut w = writer;
+    w.write_all(b"\xFFhello").unwrap();
+    w.flush().unwrap();
+
+    // only what's available is read, rather than waiting to fill the buffer
+    let mut r = reader;
+    let mut buf = [0; 16];
+    assert_eq!(Read::read(&mut r, &mut buf).unwrap(), 6);
+    assert_eq!(&buf[..6], b"\xFFhello");
+
+    w.write_all(b"world").unwrap();
+    writer.close().unwrap();
+    let mut buf = Vec::new();
+    r.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"world");
+    assert_eq!(Read::read(&mut r, &mut [0; 4]).unwrap(), 0);
+    assert!(reader.read(Some(1)).unwrap().is_none());
+    reader.close().unwrap();
+
+    let val: Value = eval("require 'stringio'; StringIO.new(+'')").unwrap();
+    assert!(Io::from_value(val).is_none());
+    let io: Io = val.try_convert().unwrap();
+    assert_eq!(io.write("abc").unwrap(), 3);
+    assert!(io.fileno().unwrap().is_none());
+    let res: String = eval!("val.string", val).unwrap();
+    assert_eq!(res, "abc");
+
+    let mut closed = reader;
+    let err = Read::read(&mut closed, &mut [0; 4]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Other);
+
+    let val: Value = eval("1").unwrap();
+    assert!(Io::from_value(val).is_none());
+    assert!(val.try_convert::<Io>().is_err());
+}
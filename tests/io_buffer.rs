@@ -0,0 +1,53 @@
+#[cfg(all(unix, ruby_gte_3_1))]
+#[test]
+fn it_exchanges_bytes_with_io_buffer() {
+    use std::io::Write;
+
+    use magnus::{eval, IoBuffer, RFile, RString};
+
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let buffer = IoBuffer::new(16).unwrap();
+    assert_eq!(buffer.size(), 16);
+    assert!(buffer.is_internal());
+    assert!(!buffer.is_readonly());
+
+    buffer.set_bytes(4, b"\x00\xFFdata").unwrap();
+    let mut buf = [0; 6];
+    buffer.get_bytes(4, &mut buf).unwrap();
+    assert_eq!(&buf, b"\x00\xFFdata");
+    assert!(buffer.get_bytes(12, &mut buf).is_err());
+    assert!(buffer.set_bytes(14, b"data").is_err());
+
+    // slices share memory
+    let slice = buffer.slice(6, 4).unwrap();
+    slice.set_bytes(0, b"DATA").unwrap();
+    buffer.get_bytes(4, &mut buf).unwrap();
+    assert_eq!(&buf, b"\x00\xFFDATA");
+
+    let res = buffer.lock(|| {
+        assert!(buffer.is_locked());
+        buffer.lock(|| ()).is_err()
+    });
+    assert!(res.unwrap());
+    assert!(!buffer.is_locked());
+
+    let string = RString::new("example");
+    let buffer = IoBuffer::for_string(string).unwrap();
+    assert!(buffer.is_external());
+    assert!(buffer.set_bytes(0, b"x").is_err());
+
+    let path = std::env::temp_dir().join("magnus_io_buffer.txt");
+    std::fs::File::create(&path)
+        .unwrap()
+        .write_all(b"mapped")
+        .unwrap();
+    let file = RFile::from_file(std::fs::File::open(&path).unwrap(), "r").unwrap();
+    let buffer = IoBuffer::map(file, None, 0, true).unwrap();
+    assert!(buffer.is_mapped());
+    let mut buf = [0; 6];
+    buffer.get_bytes(0, &mut buf).unwrap();
+    assert_eq!(&buf, b"mapped");
+    let _: magnus::Value = eval!("file.close", file).unwrap();
+    std::fs::remove_file(path).unwrap();
+}
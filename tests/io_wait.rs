@@ -0,0 +1,83 @@
+#![cfg(unix)]
+
+use std::{io::Write, os::unix::io::AsRawFd, os::unix::net::UnixStream, time::Duration};
+
+use magnus::{
+    eval,
+    io::{select, wait_fd, wait_readable, wait_writable, IoEvents},
+    Thread, Value,
+};
+
+#[test]
+fn it_waits_on_io() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let (a, mut b) = UnixStream::pair().unwrap();
+    let timeout = Some(Duration::from_millis(10));
+
+    assert!(wait_fd(a.as_raw_fd(), IoEvents::READABLE, timeout)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        wait_fd(
+            b.as_raw_fd(),
+            IoEvents::READABLE | IoEvents::WRITABLE,
+            timeout
+        )
+        .unwrap(),
+        IoEvents::WRITABLE
+    );
+    assert!(!wait_readable(a.as_raw_fd(), timeout).unwrap());
+    assert!(wait_writable(b.as_raw_fd(), timeout).unwrap());
+    b.write_all(b"hello").unwrap();
+    assert!(wait_readable(a.as_raw_fd(), timeout).unwrap());
+    let ready = select(
+        &[
+            (a.as_raw_fd(), IoEvents::READABLE),
+            (b.as_raw_fd(), IoEvents::READABLE),
+        ],
+        None,
+    )
+    .unwrap();
+    assert_eq!(ready, vec![(a.as_raw_fd(), IoEvents::READABLE)]);
+
+    // other threads can run while waiting, and can interrupt the wait
+    let _: Value = eval("$counter = 0").unwrap();
+    let counter = Thread::create(|| eval::<Value>("loop { $counter += 1; sleep 0.001 }")).unwrap();
+    let (c, _d) = UnixStream::pair().unwrap();
+    let fd = c.as_raw_fd();
+    let waiter =
+        Thread::create(move || wait_fd(fd, IoEvents::READABLE, None).map(|e| e.bits())).unwrap();
+    assert!(!waiter.join(Some(Duration::from_millis(50))).unwrap());
+    let count: i64 = eval("$counter").unwrap();
+    assert!(count > 0);
+    waiter.kill().unwrap();
+    assert!(waiter.join(Some(Duration::from_secs(1))).unwrap());
+
+    // fd is not closed by waiting
+    let waiter = Thread::create(move || wait_readable(fd, Some(Duration::from_secs(5)))).unwrap();
+    assert!(!waiter.join(Some(Duration::from_millis(50))).unwrap());
+    waiter.kill().unwrap();
+    assert!(waiter.join(Some(Duration::from_secs(1))).unwrap());
+    assert!(wait_writable(fd, timeout).unwrap());
+
+    counter.kill().unwrap();
+    counter.join(None).unwrap();
+
+    #[cfg(ruby_gte_3_0)]
+    {
+        use magnus::{io::wait_io, RArray};
+
+        let pipe: RArray = eval("IO.pipe").unwrap();
+        let (reader, writer): (Value, Value) = pipe.try_convert().unwrap();
+        assert!(wait_io(reader, IoEvents::READABLE, timeout)
+            .unwrap()
+            .is_empty());
+        let _: Value = writer.funcall("write", ("x",)).unwrap();
+        assert_eq!(
+            wait_io(reader, IoEvents::READABLE, timeout).unwrap(),
+            IoEvents::READABLE
+        );
+        assert!(wait_io(1, IoEvents::READABLE, timeout).is_err());
+    }
+}
@@ -0,0 +1,26 @@
+use magnus::{eval, Iseq, Value};
+
+#[test]
+fn it_compiles_and_runs_instruction_sequences() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let iseq = Iseq::compile("[__FILE__, __LINE__]", "script.rb", 10).unwrap();
+    let (file, line): (String, i64) = iseq.eval().unwrap();
+    assert_eq!(file, "script.rb");
+    assert_eq!(line, 10);
+
+    let err = Iseq::compile("def", "broken.rb", 1).unwrap_err();
+    assert!(err.is_kind_of(magnus::exception::syntax_error()));
+
+    let binary = Iseq::compile("$ran = true; 1 + 1", "binary.rb", 1)
+        .unwrap()
+        .to_binary()
+        .unwrap();
+    let loaded = Iseq::load_from_binary(&binary).unwrap();
+    assert_eq!(loaded.eval::<i64>().unwrap(), 2);
+    assert!(eval::<bool>("$ran").unwrap());
+    assert!(loaded.disasm().unwrap().contains("binary.rb"));
+
+    assert!(Iseq::load_from_binary(b"not an iseq").is_err());
+    assert!(Iseq::from_value(eval::<Value>("1").unwrap()).is_none());
+}
@@ -22,5 +22,19 @@ fn it_modifies_ivars() {
 
     let _: Value = eval!(r#"val.instance_variable_set(:@example, "test")"#, val).unwrap();
 
-    assert_eq!("test", val.ivar_get::<_, String>("@example").unwrap())
+    assert_eq!("test", val.ivar_get::<_, String>("@example").unwrap());
+
+    assert!(val.ivar_defined("@test"));
+    assert!(!val.ivar_defined("@missing"));
+    let names = val
+        .instance_variables()
+        .into_iter()
+        .map(|s| s.name().unwrap().into_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["@test", "@example"]);
+
+    assert_eq!(val.remove_ivar::<_, i64>("@test").unwrap(), 42);
+    assert!(!val.ivar_defined("@test"));
+    assert!(val.remove_ivar::<_, Value>("@test").is_err());
+    assert_eq!(val.instance_variables().len(), 1);
 }
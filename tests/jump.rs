@@ -0,0 +1,45 @@
+use std::cell::Cell;
+
+use magnus::{
+    block::yield_value, define_global_function, error::Tag, eval, function, Error, Value, QNIL,
+};
+
+thread_local! {
+    static SEEN: Cell<Option<Tag>> = Cell::new(None);
+}
+
+fn call_block() -> Result<Value, Error> {
+    let res = yield_value::<_, Value>(QNIL);
+    if let Err(e) = &res {
+        SEEN.with(|s| s.set(e.tag()));
+    }
+    res
+}
+
+#[test]
+fn it_distinguishes_jumps_from_exceptions() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    define_global_function("call_block", function!(call_block, 0));
+
+    let res: i64 = eval("call_block { break 42 }").unwrap();
+    assert_eq!(res, 42);
+    assert_eq!(SEEN.with(|s| s.take()), Some(Tag::Break));
+
+    let res: i64 = eval("catch(:done) { call_block { throw :done, 7 } }").unwrap();
+    assert_eq!(res, 7);
+    assert_eq!(SEEN.with(|s| s.take()), Some(Tag::Throw));
+
+    let res: bool = eval(
+        r#"
+        begin
+          call_block { raise "oops" }
+        rescue => e
+          e.message == "oops"
+        end
+        "#,
+    )
+    .unwrap();
+    assert!(res);
+    assert_eq!(SEEN.with(|s| s.take()), None);
+}
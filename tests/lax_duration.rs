@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use magnus::{eval, Lax, Value};
+
+#[test]
+fn it_converts_lax_durations() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let _: Value = eval(
+        r#"
+        module ActiveSupport
+          class Duration
+            attr_reader :value
+
+            def initialize(value)
+              @value = value
+            end
+          end
+        end
+        "#,
+    )
+    .unwrap();
+
+    let res: Lax<Duration> = eval("1.5").unwrap();
+    assert_eq!(res.0, Duration::from_millis(1500));
+    let res: Lax<Duration> = eval("ActiveSupport::Duration.new(300)").unwrap();
+    assert_eq!(res.0, Duration::from_secs(300));
+    let res: Lax<Duration> = eval("'PT5M'").unwrap();
+    assert_eq!(res.0, Duration::from_secs(300));
+    let res: Lax<Duration> = eval("'P1DT1H1M1.25S'").unwrap();
+    assert_eq!(res.0, Duration::from_millis(90_061_250));
+    let res: Lax<Duration> = eval("'P1W'").unwrap();
+    assert_eq!(res.0, Duration::from_secs(604_800));
+    let res: Lax<Duration> = eval("'P1Y'").unwrap();
+    assert_eq!(res.0, Duration::from_secs(31_556_952));
+
+    assert!(eval::<Lax<Duration>>("'5 minutes'")
+        .unwrap_err()
+        .is_arg_error());
+    assert!(eval::<Lax<Duration>>("'P'").unwrap_err().is_arg_error());
+    assert!(eval::<Lax<Duration>>("'P1DT'").unwrap_err().is_arg_error());
+    assert!(eval::<Lax<Duration>>("'PT1S1M'")
+        .unwrap_err()
+        .is_arg_error());
+    assert!(eval::<Lax<Duration>>("'-PT1S'").unwrap_err().is_arg_error());
+    assert!(eval::<Duration>("'PT5M'").is_err());
+
+    #[cfg(feature = "chrono")]
+    {
+        let res: Lax<chrono::Duration> = eval("'-PT1.5S'").unwrap();
+        assert_eq!(res.0, chrono::Duration::milliseconds(-1500));
+        let res: Lax<chrono::Duration> = eval("ActiveSupport::Duration.new(-2)").unwrap();
+        assert_eq!(res.0, chrono::Duration::seconds(-2));
+        let res: chrono::Duration = eval("-0.25").unwrap();
+        assert_eq!(res, chrono::Duration::milliseconds(-250));
+
+        let val = Value::from(chrono::Duration::milliseconds(-1500));
+        let res: bool = eval!("val == -1.5", val).unwrap();
+        assert!(res);
+    }
+}
@@ -0,0 +1,36 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use magnus::{define_global_function, eval, function, Lazy, Value};
+
+fn naturals() -> Lazy<impl Iterator<Item = u64>> {
+    Lazy(1..)
+}
+
+#[test]
+fn it_converts_iterators_lazily() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    define_global_function("naturals", function!(naturals, 0));
+    let res: bool = eval("naturals.lazy.select(&:even?).first(3) == [2, 4, 6]").unwrap();
+    assert!(res);
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let counter = count.clone();
+    let val = Value::from(Lazy((0..10).inspect(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    })));
+    assert_eq!(count.load(Ordering::SeqCst), 0);
+
+    let res: Vec<i64> = eval!("val.first(2)", val).unwrap();
+    assert_eq!(res, vec![0, 1]);
+    assert!(count.load(Ordering::SeqCst) <= 3);
+
+    // continues from where the previous iteration stopped
+    let res: Vec<i64> = eval!("val.to_a", val).unwrap();
+    assert!(!res.is_empty());
+    assert_eq!(res.last(), Some(&9));
+    assert_eq!(count.load(Ordering::SeqCst), 10);
+}
@@ -0,0 +1,24 @@
+use magnus::{eval, value::LazyId, Object, RObject, Value};
+
+static COUNT: LazyId = LazyId::new("@count");
+static TO_S: LazyId = LazyId::new("to_s");
+
+#[test]
+fn it_interns_lazily() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let obj: RObject = eval("Object.new").unwrap();
+    for i in 1..=3 {
+        let count = obj.ivar_get::<_, Option<i64>>(&COUNT).unwrap().unwrap_or(0);
+        assert_eq!(count, i - 1);
+        obj.ivar_set(&COUNT, count + 1).unwrap();
+    }
+    let res: bool = eval!("obj.instance_variable_get(:@count) == 3", obj).unwrap();
+    assert!(res);
+
+    assert_eq!(COUNT.get(), COUNT.get());
+    assert_eq!(COUNT.get().name().unwrap(), "@count");
+
+    let s: String = eval::<Value>("42").unwrap().funcall(&TO_S, ()).unwrap();
+    assert_eq!(s, "42");
+}
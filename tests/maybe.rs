@@ -0,0 +1,56 @@
+use magnus::{
+    define_global_function, eval, function,
+    scan_args::{get_kwargs, scan_args, Maybe},
+    Error, RArray, RHash, Value,
+};
+
+fn describe(val: Maybe<i64>) -> String {
+    match val {
+        Maybe::Absent => String::from("absent"),
+        Maybe::Nil => String::from("nil"),
+        Maybe::Present(i) => i.to_string(),
+    }
+}
+
+fn example(args: &[Value]) -> Result<RArray, Error> {
+    let args = scan_args::<(), (Maybe<i64>, Option<i64>), (), (), RHash, ()>(args)?;
+    let (a, b) = args.optional;
+    let kw = get_kwargs::<_, (), (Maybe<i64>,), ()>(args.keywords, &[], &["c"])?;
+    let (c,) = kw.optional;
+    Ok(RArray::from_vec(vec![
+        describe(a),
+        format!("{:?}", b),
+        describe(c),
+    ]))
+}
+
+#[test]
+fn it_distinguishes_nil_from_absent() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    define_global_function("example", function!(example, -1));
+
+    let res: Vec<String> = eval("example").unwrap();
+    assert_eq!(res, vec!["absent", "None", "absent"]);
+    let res: Vec<String> = eval("example(nil, nil, c: nil)").unwrap();
+    assert_eq!(res, vec!["nil", "None", "nil"]);
+    let res: Vec<String> = eval("example(1, 2, c: 3)").unwrap();
+    assert_eq!(res, vec!["1", "Some(2)", "3"]);
+
+    let hash: RHash = eval("{a: 1, b: nil}").unwrap();
+    let sym = |s: &str| magnus::Symbol::new(s);
+    assert_eq!(
+        hash.lookup_maybe::<_, i64>(sym("a")).unwrap(),
+        Maybe::Present(1)
+    );
+    assert_eq!(hash.lookup_maybe::<_, i64>(sym("b")).unwrap(), Maybe::Nil);
+    assert_eq!(
+        hash.lookup_maybe::<_, i64>(sym("c")).unwrap(),
+        Maybe::Absent
+    );
+    assert!(hash
+        .lookup_maybe::<_, i64>(sym("c"))
+        .unwrap()
+        .into_option()
+        .is_none());
+}
@@ -0,0 +1,23 @@
+use magnus::{define_class, eval, object_space, RString, Value};
+
+#[test]
+fn it_inspects_object_space() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let class = define_class("Widget", Default::default()).unwrap();
+    let _: Value = eval("$widgets = 3.times.map { Widget.new }").unwrap();
+    let widgets = object_space::each_object(class)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(widgets.len(), 3);
+
+    let s = RString::new(&"x".repeat(4096));
+    assert!(object_space::memsize_of(s).unwrap() >= 4096);
+
+    let id = s.object_id();
+    let res: bool = eval!("s.object_id == id", s, id).unwrap();
+    assert!(res);
+    let found: RString = object_space::id2ref(id).unwrap().try_convert().unwrap();
+    assert_eq!(found.to_string().unwrap(), "x".repeat(4096));
+}
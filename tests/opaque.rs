@@ -0,0 +1,41 @@
+use magnus::{
+    block::Proc, error::RubyUnavailableError, eval, gc, value::Opaque, without_gvl, RString,
+    Thread, Value,
+};
+
+#[test]
+fn it_moves_values_between_threads() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let callback = Opaque::new(eval::<Proc>("proc { |s| s.upcase }").unwrap());
+    let string = Opaque::new(RString::new("foo"));
+
+    // values can't be resolved on a non-Ruby thread, but can be moved and
+    // dropped there
+    let (callback, res) = std::thread::spawn(move || {
+        let res = callback.get().is_err() && string.get().is_err();
+        drop(string);
+        (callback, res)
+    })
+    .join()
+    .unwrap();
+    assert!(res);
+
+    // or while the GVL is released
+    let res = unsafe { without_gvl(|| callback.get().err(), None::<fn()>) }.unwrap();
+    assert!(matches!(res, Some(RubyUnavailableError::GvlUnlocked)));
+
+    // make some garbage
+    eval::<Value>(r#"1024.times.map {|i| "test#{i}"}"#).unwrap();
+    gc::start();
+
+    let worker = Thread::create(move || {
+        let arg = Opaque::new(RString::new("bar"));
+        callback
+            .get()
+            .unwrap()
+            .call::<_, Value>((arg.get().unwrap(),))
+    })
+    .unwrap();
+    assert_eq!(worker.value::<String>().unwrap(), "BAR");
+}
@@ -0,0 +1,39 @@
+use std::{
+    convert::TryFrom,
+    ffi::{CStr, CString, OsStr, OsString},
+};
+
+use magnus::{eval, RString, Value};
+
+#[test]
+fn it_converts_os_and_c_strings() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let val = RString::try_from(OsStr::new("example")).unwrap();
+    let res: bool = eval!(r#"val == "example""#, val).unwrap();
+    assert!(res);
+    let res: OsString = eval(r#""example""#).unwrap();
+    assert_eq!(res, OsString::from("example"));
+
+    let val = Value::from(CStr::from_bytes_with_nul(b"hello\0").unwrap());
+    let res: bool = eval!(r#"val.b == "hello".b && val.bytesize == 5"#, val).unwrap();
+    assert!(res);
+    let res: CString = eval(r#""hello""#).unwrap();
+    assert_eq!(res, CString::new("hello").unwrap());
+
+    let err = eval::<CString>(r#""hel\0lo""#).unwrap_err();
+    assert!(err.is_arg_error());
+    assert!(eval::<CString>("1").unwrap_err().is_type_error());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let bytes = b"caf\xe9";
+        let val = RString::try_from(OsStr::from_bytes(bytes)).unwrap();
+        let res: bool = eval!(r#"val.b == "caf\xE9".b"#, val).unwrap();
+        assert!(res);
+        let res: OsString = eval(r#""caf\xE9".b"#).unwrap();
+        assert_eq!(res.as_bytes(), bytes);
+    }
+}
@@ -0,0 +1,67 @@
+use magnus::{error::OwnedError, eval, exception, Error, Value};
+
+fn assert_send_sync<T: Send + Sync + 'static>(_: &T) {}
+
+#[test]
+fn it_converts_to_and_from_owned_error() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let err = eval::<Value>(
+        r#"
+        def fail_here
+          raise KeyError, "missing"
+        end
+        fail_here
+        "#,
+    )
+    .unwrap_err();
+    let owned = OwnedError::from(err);
+    assert_send_sync(&owned);
+    assert_eq!(owned.class_name(), "KeyError");
+    assert_eq!(owned.message(), "missing");
+    assert!(owned.backtrace().unwrap()[0].contains("fail_here"));
+
+    let handle = std::thread::spawn(move || {
+        let boxed: Box<dyn std::error::Error + Send + Sync> = Box::new(owned);
+        boxed
+    });
+    let boxed = handle.join().unwrap();
+    assert_eq!(boxed.to_string(), "KeyError: missing");
+    let owned = boxed.downcast::<OwnedError>().unwrap();
+
+    let err = Error::from(*owned);
+    assert!(err.is_key_error());
+    let backtrace = err.value().unwrap().backtrace().unwrap().unwrap();
+    let first: String = backtrace.entry(0).unwrap();
+    assert!(first.contains("fail_here"));
+
+    let owned = OwnedError::from(Error::new(exception::type_error(), "wrong"));
+    assert_eq!(owned.to_string(), "TypeError: wrong");
+    assert_eq!(owned.backtrace(), None);
+
+    let owned = OwnedError::from(Error::new(exception::arg_error(), "x"));
+    let err: Error = owned.into();
+    assert!(err.is_arg_error());
+
+    let err = eval::<Value>(
+        r#"
+        module Example
+          class CustomError < StandardError; end
+        end
+        raise Example::CustomError, "namespaced"
+        "#,
+    )
+    .unwrap_err();
+    let owned = OwnedError::from(err);
+    assert_eq!(owned.class_name(), "Example::CustomError");
+    let err = Error::from(owned);
+    let res: bool = eval!(
+        "err.instance_of?(Example::CustomError)",
+        err = err.value().unwrap()
+    )
+    .unwrap();
+    assert!(res);
+
+    let err: Box<dyn std::error::Error> = Box::new(Error::new(exception::type_error(), "t"));
+    assert_eq!(err.to_string(), "TypeError: t");
+}
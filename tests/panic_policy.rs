@@ -0,0 +1,43 @@
+use magnus::{
+    define_global_function,
+    error::{set_panic_policy, PanicPolicy},
+    eval, function,
+};
+
+fn explode(msg: String) -> i64 {
+    panic!("{}", msg)
+}
+
+#[test]
+fn it_converts_panics_to_runtime_error() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    set_panic_policy(PanicPolicy::RuntimeError);
+    define_global_function("explode", function!(explode, 1));
+
+    let res: String = eval(
+        r#"
+        begin
+          explode("kaboom")
+        rescue RuntimeError => e
+          e.message
+        end
+        "#,
+    )
+    .unwrap();
+    assert!(res.starts_with("kaboom at "));
+    assert!(res.contains("panic_policy.rs"));
+
+    let res: bool = eval(
+        r#"
+        begin
+          [1, 2].map { |i| i == 2 ? explode("in block") : i }
+          false
+        rescue RuntimeError => e
+          e.message.start_with?("in block")
+        end
+        "#,
+    )
+    .unwrap();
+    assert!(res);
+}
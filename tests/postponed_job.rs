@@ -0,0 +1,58 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use magnus::{enqueue_on_ruby_thread, eval, without_gvl, Thread, Value};
+
+#[test]
+fn it_runs_enqueued_functions() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let _: Value = eval("$results = []").unwrap();
+    let count = Arc::new(AtomicUsize::new(0));
+
+    // from a Ruby thread that has released the GVL
+    let counter = count.clone();
+    let worker = Thread::create(move || unsafe {
+        without_gvl(
+            move || {
+                for i in 0..3 {
+                    let counter = counter.clone();
+                    enqueue_on_ruby_thread(move || {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        let _: Value = eval(&format!("$results << {}", i)).unwrap();
+                    })
+                    .unwrap();
+                }
+            },
+            None::<fn()>,
+        )
+    })
+    .unwrap();
+    worker.join(None).unwrap();
+
+    let res: Vec<i64> = eval("Thread.pass; $results").unwrap();
+    assert_eq!(res, vec![0, 1, 2]);
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+
+    // from a non-Ruby thread
+    let res = std::thread::spawn(|| {
+        enqueue_on_ruby_thread(|| {
+            let _: Value = eval("$results << 3").unwrap();
+        })
+        .is_ok()
+    })
+    .join()
+    .unwrap();
+    #[cfg(ruby_gte_3_3)]
+    {
+        assert!(res);
+        let res: Vec<i64> = eval("Thread.pass; $results").unwrap();
+        assert_eq!(res, vec![0, 1, 2, 3]);
+    }
+    #[cfg(ruby_lt_3_3)]
+    {
+        assert!(!res);
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(ruby_gte_3_0)]
+#[test]
+fn it_makes_values_shareable() {
+    use magnus::{eval, RArray, RString, Ractor, Value};
+
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    #[magnus::wrap(class = "Point", free_immediately, frozen_shareable)]
+    struct Point {
+        x: isize,
+        y: isize,
+    }
+
+    let _: Value = eval("class Point; end").unwrap();
+
+    assert!(Ractor::is_shareable(1));
+    assert!(!Ractor::is_shareable(RString::new("foo")));
+
+    let ary: RArray = eval(r#"[["a"], "b"]"#).unwrap();
+    let ary = Ractor::make_shareable(ary).unwrap();
+    assert!(Ractor::is_shareable(ary));
+    assert!(ary.entry::<RArray>(0).unwrap().is_frozen());
+
+    let point = Value::from(Point { x: 1, y: 2 });
+    assert!(!Ractor::is_shareable(point));
+    let point = Ractor::make_shareable(point).unwrap();
+    assert!(Ractor::is_shareable(point));
+    let point: &Point = point.try_convert().unwrap();
+    assert_eq!((point.x, point.y), (1, 2));
+
+    // procs referencing unshareable objects can't be made shareable
+    let res = Ractor::make_shareable(eval::<Value>("s = String.new; proc { s }").unwrap());
+    assert!(res.is_err());
+
+    assert!(Ractor::from_value(*Ractor::current()).is_some());
+}
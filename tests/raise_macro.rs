@@ -0,0 +1,34 @@
+use magnus::{define_global_function, eval, exception, function, raise, Error};
+
+fn divide(a: i64, b: i64) -> Result<i64, Error> {
+    if b == 0 {
+        raise!(exception::zero_div_error(), "can't divide {} by {}", a, b);
+    }
+    if a < 0 {
+        raise!(exception::arg_error(), "negative numbers unsupported");
+    }
+    Ok(a / b)
+}
+
+#[test]
+fn it_raises_formatted_errors() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    assert_eq!(divide(6, 3).unwrap(), 2);
+
+    let err = divide(1, 0).unwrap_err();
+    assert!(err.is_zero_div_error());
+    assert_eq!(
+        err.full_message(),
+        "can't divide 1 by 0 (ZeroDivisionError)"
+    );
+
+    let err = divide(-1, 1).unwrap_err();
+    assert!(err.is_arg_error());
+
+    define_global_function("divide", function!(divide, 2));
+    let err = eval::<i64>("divide(4, 0)").unwrap_err();
+    let msg = err.full_message();
+    assert!(msg.contains("can't divide 4 by 0 (ZeroDivisionError)"));
+    assert!(!msg.contains('\u{1b}'));
+}
@@ -0,0 +1,60 @@
+use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use magnus::eval;
+
+#[test]
+fn it_converts_ranges_to_rust() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    assert_eq!(eval::<Range<i64>>("2...7").unwrap(), 2..7);
+    assert_eq!(eval::<RangeInclusive<i64>>("2..7").unwrap(), 2..=7);
+    assert_eq!(eval::<RangeFrom<i64>>("2..").unwrap(), 2..);
+    assert_eq!(eval::<RangeFrom<i64>>("2...").unwrap(), 2..);
+    assert_eq!(eval::<RangeFull>("Range.new(nil, nil)").unwrap(), ..);
+    #[cfg(ruby_gte_2_7)]
+    {
+        assert_eq!(eval::<RangeTo<i64>>("...7").unwrap(), ..7);
+        assert_eq!(eval::<RangeToInclusive<i64>>("..7").unwrap(), ..=7);
+    }
+    assert_eq!(
+        eval::<RangeInclusive<String>>(r#""a".."z""#).unwrap(),
+        "a".to_owned()..="z".to_owned()
+    );
+
+    // inclusive/exclusive and beginless/endless must match
+    assert!(eval::<Range<i64>>("2..7").unwrap_err().is_type_error());
+    assert!(eval::<RangeInclusive<i64>>("2...7")
+        .unwrap_err()
+        .is_type_error());
+    assert!(eval::<Range<i64>>("2..").unwrap_err().is_type_error());
+    assert!(eval::<RangeTo<i64>>("2...7").unwrap_err().is_type_error());
+    assert!(eval::<Range<i64>>("[2, 7]").unwrap_err().is_type_error());
+
+    let range = eval::<magnus::Range>("1...10").unwrap();
+    assert_eq!(range.size::<usize>().unwrap(), 9);
+    let res = range
+        .to_seq_iter::<i64, _>(4)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(res, vec![1, 5, 9]);
+    let range = eval::<magnus::Range>(r#""a".."z""#).unwrap();
+    assert_eq!(range.size::<Option<usize>>().unwrap(), None);
+
+    // Ruby 3.3 raises for ranges that can't be iterated
+    let range = eval::<magnus::Range>("0.5..2").unwrap();
+    #[cfg(ruby_gte_3_3)]
+    assert!(range.size::<Option<usize>>().unwrap_err().is_type_error());
+    #[cfg(ruby_lt_3_3)]
+    assert_eq!(range.size::<Option<usize>>().unwrap(), Some(2));
+
+    // beginless ranges were added in Ruby 2.7
+    #[cfg(ruby_gte_2_7)]
+    {
+        let range = eval::<magnus::Range>("..2").unwrap();
+        #[cfg(ruby_gte_3_3)]
+        assert!(range.size::<f64>().unwrap_err().is_type_error());
+        #[cfg(ruby_lt_3_3)]
+        assert_eq!(range.size::<f64>().unwrap(), f64::INFINITY);
+    }
+}
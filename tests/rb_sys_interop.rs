@@ -0,0 +1,34 @@
+use magnus::{
+    eval,
+    rb_sys::{AsRawDataType, AsRawValue, FromRawValue},
+    RArray, RString, TypedData, Value,
+};
+
+#[magnus::wrap(class = "Point")]
+struct Point;
+
+#[test]
+fn it_converts_wrapper_types_to_and_from_raw() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let s = RString::new("foo");
+    let raw = s.as_raw();
+    assert_eq!(raw, (*s).as_raw());
+    unsafe { rb_sys::rb_str_cat(raw, "bar".as_ptr() as *const _, 3) };
+    let s = unsafe { RString::from_raw(raw) };
+    assert_eq!(s.to_string().unwrap(), "foobar");
+
+    let ary: RArray = eval("[1, 2, 3]").unwrap();
+    let ary = unsafe { RArray::from_raw(ary.as_raw()) };
+    assert_eq!(ary.to_vec::<i64>().unwrap(), vec![1, 2, 3]);
+
+    let val: Value = eval("nil").unwrap();
+    assert!(unsafe { Value::from_raw(val.as_raw()) }.is_nil());
+
+    let _: Value = eval("class Point; end").unwrap();
+    let data_type = <Point as TypedData>::data_type();
+    assert!(!data_type.as_raw().is_null());
+    let obj: Value = Point.into();
+    let raw = unsafe { rb_sys::rb_check_typeddata(obj.as_raw(), data_type.as_raw()) };
+    assert!(!raw.is_null());
+}
@@ -0,0 +1,23 @@
+use magnus::{class, define_module, eval, method, Module, RModule};
+
+fn shout(rb_self: String) -> String {
+    rb_self.to_uppercase()
+}
+
+#[test]
+fn it_defines_a_refinement() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let module = define_module("Shouting").unwrap();
+    let refinement = module.define_refinement(class::string()).unwrap();
+    refinement
+        .define_method("shout", method!(shout, 0))
+        .unwrap();
+
+    let again: RModule = module.define_refinement(class::string()).unwrap();
+    assert!(refinement.equal(again).unwrap());
+
+    assert!(!eval::<bool>(r#""hello".respond_to?(:shout)"#).unwrap());
+    let res: String = eval(r#"using Shouting; "hello".shout"#).unwrap();
+    assert_eq!(res, "HELLO");
+}
@@ -0,0 +1,39 @@
+use magnus::{define_error, error::rescue, eval, exception, Error, Value};
+
+#[test]
+fn it_rescues_only_listed_exceptions() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let my_error = define_error("MyError", exception::standard_error()).unwrap();
+
+    let res: Result<String, Error> = rescue(
+        || eval("raise MyError, 'oops'"),
+        &[exception::arg_error(), my_error],
+        |e| Ok(format!("rescued {}", e.value().unwrap().message().unwrap())),
+    );
+    assert_eq!(res.unwrap(), "rescued oops");
+
+    let res: Result<String, Error> = rescue(
+        || Err(Error::new(exception::arg_error(), "bad")),
+        &[exception::arg_error(), my_error],
+        |_| Ok(String::from("rescued")),
+    );
+    assert_eq!(res.unwrap(), "rescued");
+
+    let res: Result<Value, Error> = rescue(
+        || eval("raise TypeError"),
+        &[exception::arg_error(), my_error],
+        |_| panic!("should not be rescued"),
+    );
+    assert!(res.unwrap_err().is_type_error());
+
+    let res: Result<Value, Error> = rescue(
+        || eval("exit 1"),
+        &[exception::standard_error()],
+        |_| panic!("should not be rescued"),
+    );
+    assert!(res.unwrap_err().is_kind_of(exception::system_exit()));
+
+    let res: Result<i64, Error> = rescue(|| Ok(1), &[exception::exception()], |_| Ok(2));
+    assert_eq!(res.unwrap(), 1);
+}
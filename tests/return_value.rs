@@ -0,0 +1,40 @@
+use magnus::{define_global_function, eval, function, method::Required};
+
+fn read(path: String) -> Result<String, std::io::Error> {
+    std::fs::read_to_string(path)
+}
+
+fn parse(s: String) -> Result<Option<i64>, std::num::ParseIntError> {
+    if s.is_empty() {
+        return Ok(None);
+    }
+    s.parse().map(Some)
+}
+
+fn find(s: String) -> Required<usize> {
+    Required(s.find('x'))
+}
+
+#[test]
+fn it_converts_return_values() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    define_global_function("read", function!(read, 1));
+    define_global_function("parse", function!(parse, 1));
+    define_global_function("find", function!(find, 1));
+
+    let res: bool = eval("(read('/path/does/not/exist') rescue $!).is_a?(Errno::ENOENT)").unwrap();
+    assert!(res);
+
+    let res: Option<i64> = eval("parse('42')").unwrap();
+    assert_eq!(res, Some(42));
+    let res: bool = eval("parse('').nil?").unwrap();
+    assert!(res);
+    let res: bool = eval("(parse('forty two') rescue $!).is_a?(ArgumentError)").unwrap();
+    assert!(res);
+
+    let res: usize = eval("find('abxd')").unwrap();
+    assert_eq!(res, 2);
+    let res: bool = eval("(find('abcd') rescue $!).is_a?(RuntimeError)").unwrap();
+    assert!(res);
+}
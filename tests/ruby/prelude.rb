@@ -0,0 +1,7 @@
+def prelude_location
+  [__FILE__, __LINE__, __dir__]
+end
+
+def prelude_fail
+  raise "failed in prelude"
+end
@@ -0,0 +1,60 @@
+use magnus::{eval, ruby_class, Error, Object, RClass, Value};
+
+ruby_class!(Animal, "Zoo::Animal");
+ruby_class!(pub(crate) Dog, "Zoo::Dog");
+
+fn speak(animal: Animal) -> Result<String, Error> {
+    animal.funcall("speak", ())
+}
+
+#[test]
+fn it_wraps_a_ruby_class() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let _: Value = eval(
+        r#"
+        module Zoo
+          class Animal
+            def speak
+              "..."
+            end
+          end
+
+          class Dog < Animal
+            def speak
+              "woof"
+            end
+          end
+        end
+        "#,
+    )
+    .unwrap();
+
+    let class: RClass = eval("Zoo::Animal").unwrap();
+    assert!(Animal::class().equal(class).unwrap());
+
+    let dog: Dog = eval("Zoo::Dog.new").unwrap();
+    let animal: Animal = dog.try_convert().unwrap();
+    assert_eq!(speak(animal).unwrap(), "woof");
+
+    assert!(Dog::from_value(*animal).is_some());
+    assert!(Dog::from_value(eval("Zoo::Animal.new").unwrap()).is_none());
+
+    let err = eval::<Animal>("Object.new").unwrap_err();
+    assert!(err.is_kind_of(magnus::exception::type_error()));
+    assert!(err
+        .to_string()
+        .contains("no implicit conversion of Object into Zoo::Animal"));
+
+    dog.ivar_set("@name", "Rex").unwrap();
+    let name: String = dog.ivar_get("@name").unwrap();
+    assert_eq!(name, "Rex");
+
+    let val: Value = dog.into();
+    assert!(val.is_kind_of(Animal::class()));
+    let moved: Dog = magnus::gc::location(dog);
+    assert!(moved.equal(dog).unwrap());
+    let val = Value::from(vec![Some(dog), None]);
+    let res: bool = eval!(r#"val.first.speak == "woof" && val.last.nil?"#, val).unwrap();
+    assert!(res);
+}
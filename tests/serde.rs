@@ -0,0 +1,98 @@
+#![cfg(feature = "serde")]
+
+use std::collections::HashMap;
+
+use magnus::{
+    eval,
+    serde::{deserialize, serialize},
+    Value,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum Level {
+    Debug,
+    Info,
+    Custom(u8),
+    Range { min: u8, max: u8 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Config {
+    name: String,
+    port: u16,
+    ratio: f64,
+    verbose: bool,
+    parent: Option<String>,
+    tags: Vec<String>,
+    env: HashMap<String, i64>,
+    levels: Vec<Level>,
+    offset: (i32, i32),
+}
+
+#[test]
+fn it_converts_with_serde() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let config = Config {
+        name: "example".to_owned(),
+        port: 8080,
+        ratio: 0.5,
+        verbose: true,
+        parent: None,
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        env: vec![("threads".to_owned(), 4)].into_iter().collect(),
+        levels: vec![
+            Level::Debug,
+            Level::Custom(3),
+            Level::Range { min: 1, max: 5 },
+        ],
+        offset: (-1, 1),
+    };
+
+    let val = serialize(&config).unwrap();
+    let res: bool = eval!(
+        r#"
+        val == {
+          name: "example",
+          port: 8080,
+          ratio: 0.5,
+          verbose: true,
+          parent: nil,
+          tags: ["a", "b"],
+          env: {"threads" => 4},
+          levels: [:debug, {custom: 3}, {range: {min: 1, max: 5}}],
+          offset: [-1, 1],
+        }
+        "#,
+        val
+    )
+    .unwrap();
+    assert!(res);
+    assert_eq!(deserialize::<Config>(val).unwrap(), config);
+
+    // string keys and variant names are accepted too
+    let val: Value = eval(
+        r#"
+        {
+          "name" => "other",
+          "port" => 1,
+          "ratio" => 1.0,
+          "verbose" => false,
+          "parent" => "example",
+          "tags" => [],
+          "env" => {},
+          "levels" => ["info"],
+          "offset" => [0, 0],
+        }
+        "#,
+    )
+    .unwrap();
+    let other: Config = deserialize(val).unwrap();
+    assert_eq!(other.parent.as_deref(), Some("example"));
+    assert_eq!(other.levels, vec![Level::Info]);
+
+    let err = deserialize::<Config>(eval("{name: 1}").unwrap()).unwrap_err();
+    assert!(err.is_type_error());
+}
@@ -0,0 +1,36 @@
+use std::collections::{BTreeSet, HashSet};
+
+use magnus::{eval, RSet, Value};
+
+#[test]
+fn it_converts_sets() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let set: RSet = eval("Set[1, 2, 3]").unwrap();
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(1).unwrap());
+    assert!(!set.contains("1").unwrap());
+
+    set.add(4).unwrap();
+    set.add(4).unwrap();
+    assert_eq!(set.len(), 4);
+    assert!(set.delete(1).unwrap());
+    assert!(!set.delete(1).unwrap());
+
+    let res: BTreeSet<i64> = eval!("set", set).unwrap();
+    assert_eq!(res.into_iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+    let res: HashSet<String> = eval("Set['a', 'b']").unwrap();
+    assert_eq!(res.len(), 2);
+    assert!(res.contains("a"));
+
+    assert!(eval::<HashSet<i64>>("[1, 2]").unwrap_err().is_type_error());
+
+    let val = Value::from(vec!["a", "b", "a"].into_iter().collect::<HashSet<_>>());
+    let res: bool = eval!("val == Set['a', 'b']", val).unwrap();
+    assert!(res);
+
+    let set: RSet = (1..=3).collect();
+    let res: bool = eval!("set == Set[1, 2, 3]", set).unwrap();
+    assert!(res);
+}
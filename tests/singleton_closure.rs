@@ -0,0 +1,38 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use magnus::{eval, gc, Error, Object, RObject, RString};
+
+#[test]
+fn it_defines_singleton_methods_from_closures() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let obj: RObject = eval("Object.new").unwrap();
+    let prefix = String::from("conn-");
+    let counter = calls.clone();
+    obj.define_singleton_method_fn("label", move |rb_self, args| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        let id: i64 = args[0].try_convert()?;
+        assert!(rb_self.is_equal(obj));
+        Ok::<_, Error>(RString::new(&format!("{}{}", prefix, id)))
+    })
+    .unwrap();
+
+    // closure must survive a GC cycle
+    gc::start();
+
+    let res: bool = eval!(
+        r#"obj.label(1) == "conn-1" && obj.label(2) == "conn-2""#,
+        obj
+    )
+    .unwrap();
+    assert!(res);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    let other: RObject = eval("Object.new").unwrap();
+    let res: bool = eval!("other.respond_to?(:label)", other).unwrap();
+    assert!(!res);
+}
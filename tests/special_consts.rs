@@ -0,0 +1,25 @@
+use magnus::{
+    eval,
+    value::{QFALSE, QNIL, QTRUE, QUNDEF},
+    RArray, Value,
+};
+
+#[test]
+fn it_handles_special_consts() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    assert!(QNIL.is_nil());
+    assert!(QTRUE.is_true());
+    assert!(QFALSE.is_false());
+    assert!(unsafe { QUNDEF.to_value() }.is_undef());
+
+    let val: Value = eval("nil").unwrap();
+    assert!(val.is_nil() && !val.is_true() && !val.is_false() && !val.is_undef());
+
+    let ary = RArray::new();
+    ary.push(QNIL).unwrap();
+    ary.push(QTRUE).unwrap();
+    ary.push(QFALSE).unwrap();
+    let res: bool = eval!("ary == [nil, true, false]", ary).unwrap();
+    assert!(res);
+}
@@ -0,0 +1,43 @@
+use std::{convert::TryFrom, io};
+
+use magnus::{eval, Error, ExceptionClass};
+
+fn read(path: &str) -> Result<String, Error> {
+    Ok(std::fs::read_to_string(path)?)
+}
+
+fn parse(s: &str) -> Result<i64, Error> {
+    Ok(s.parse()?)
+}
+
+fn to_u8(i: i64) -> Result<u8, Error> {
+    Ok(u8::try_from(i)?)
+}
+
+fn to_str(bytes: Vec<u8>) -> Result<String, Error> {
+    Ok(String::from_utf8(bytes)?)
+}
+
+#[test]
+fn it_converts_std_errors() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let enoent: ExceptionClass = eval("Errno::ENOENT").unwrap();
+    assert!(read("/path/does/not/exist").unwrap_err().is_kind_of(enoent));
+
+    let err: Error = io::Error::new(io::ErrorKind::Other, "custom").into();
+    assert!(err.is_kind_of(magnus::exception::io_error()));
+    assert!(err.to_string().contains("custom"));
+
+    let err: Error = io::Error::from(io::ErrorKind::UnexpectedEof).into();
+    assert!(err.is_kind_of(magnus::exception::eof_error()));
+
+    assert_eq!(parse("42").unwrap(), 42);
+    assert!(parse("forty two").unwrap_err().is_arg_error());
+
+    assert!(to_u8(256).unwrap_err().is_range_error());
+
+    assert!(to_str(vec![0xff, 0xfe])
+        .unwrap_err()
+        .is_kind_of(magnus::exception::encoding_error()));
+}
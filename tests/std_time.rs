@@ -0,0 +1,37 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use magnus::{eval, time::Exact, Time, Value};
+
+#[test]
+fn it_converts_std_times_and_durations() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let now = SystemTime::now();
+    let val = Value::from(now);
+    assert!(!Time::from_value(val).unwrap().is_utc());
+    assert_eq!(val.try_convert::<SystemTime>().unwrap(), now);
+
+    let before_epoch = UNIX_EPOCH - Duration::new(1, 250_000_000);
+    let val = Value::from(before_epoch);
+    let res: bool = eval!("val == Time.at(Rational(-5, 4))", val).unwrap();
+    assert!(res);
+    assert_eq!(val.try_convert::<SystemTime>().unwrap(), before_epoch);
+
+    let val = Value::from(Duration::from_millis(1500));
+    assert_eq!(val.try_convert::<f64>().unwrap(), 1.5);
+    let val = Value::from(Exact(Duration::new(3, 1)));
+    let res: bool = eval!("val == 3 + Rational(1, 1_000_000_000)", val).unwrap();
+    assert!(res);
+    assert_eq!(
+        val.try_convert::<Exact<Duration>>().unwrap(),
+        Exact(Duration::new(3, 1))
+    );
+
+    let res: Duration = eval("2").unwrap();
+    assert_eq!(res, Duration::from_secs(2));
+    let res: Duration = eval("0.25").unwrap();
+    assert_eq!(res, Duration::from_millis(250));
+    let err = eval::<Duration>("-1").unwrap_err();
+    assert!(err.is_arg_error());
+    assert!(eval::<Duration>(r#""1""#).is_err());
+}
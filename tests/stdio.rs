@@ -0,0 +1,40 @@
+use std::io::{Read, Write};
+
+use magnus::{eval, io, kernel, RString, Value};
+
+#[test]
+fn it_respects_ruby_level_redirection() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let _: Value = eval(
+        r#"
+        require "stringio"
+        $stdout = StringIO.new
+        $stderr = StringIO.new
+        $stdin = StringIO.new("input")
+        "#,
+    )
+    .unwrap();
+
+    kernel::puts(("a", 1)).unwrap();
+    kernel::print(("b",)).unwrap();
+    kernel::p((RString::new("c"),)).unwrap();
+    kernel::pp((vec![1, 2],)).unwrap();
+    write!(io::stdout(), "d").unwrap();
+    io::stderr().write_all(b"e").unwrap();
+
+    let mut buf = String::new();
+    io::stdin().unwrap().read_to_string(&mut buf).unwrap();
+    assert_eq!(buf, "input");
+
+    let res: String = eval("$stdout.string").unwrap();
+    assert_eq!(res, "a\n1\nb\"c\"\n[1, 2]\nd");
+    let res: String = eval("$stderr.string").unwrap();
+    assert_eq!(res, "e");
+
+    let _: Value = eval("$stdin = 1").unwrap();
+    assert!(io::stdin().unwrap_err().is_type_error());
+
+    let _: Value = eval("$stdout, $stderr, $stdin = STDOUT, STDERR, STDIN").unwrap();
+    assert!(io::Io::from_value(*io::stdout()).is_some());
+}
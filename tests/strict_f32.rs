@@ -0,0 +1,27 @@
+use magnus::{eval, Strict, Value};
+
+#[test]
+fn it_converts_f32_strictly() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let val: Value = eval("0.1").unwrap();
+    assert_eq!(val.try_convert::<f32>().unwrap(), 0.1_f32);
+    assert_eq!(val.try_convert::<Strict<f32>>().unwrap().0, 0.1_f32);
+
+    let val: Value = eval("-1e40").unwrap();
+    assert_eq!(val.try_convert::<f32>().unwrap(), f32::NEG_INFINITY);
+    assert!(val.try_convert::<Strict<f32>>().is_err());
+
+    let val: Value = eval("1e-50").unwrap();
+    assert_eq!(val.try_convert::<f32>().unwrap(), 0.0);
+    assert!(val.try_convert::<Strict<f32>>().is_err());
+
+    let val: Value = eval("Float::INFINITY").unwrap();
+    assert_eq!(val.try_convert::<Strict<f32>>().unwrap().0, f32::INFINITY);
+    let val: Value = eval("Float::NAN").unwrap();
+    assert!(val.try_convert::<Strict<f32>>().unwrap().0.is_nan());
+
+    let val = Value::from(1.5_f32);
+    let res: bool = eval!("val == 1.5", val).unwrap();
+    assert!(res);
+}
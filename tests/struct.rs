@@ -39,5 +39,18 @@ fn it_defines_a_struct() {
         }
     }
 
-    assert_eq!(&["bar", "baz"], obj.members().unwrap().as_slice())
+    assert_eq!(&["bar", "baz"], obj.members().unwrap().as_slice());
+    assert_eq!(2, obj.get::<usize>(1).unwrap());
+    assert!(obj.get::<usize>(2).is_err());
+    assert_eq!(1, obj.getmember::<_, usize>("bar").unwrap());
+    assert!(obj.getmember::<_, usize>("qux").is_err());
+
+    let members = ["x", "y", "z"];
+    let struct_class = define_struct(Some("Point3"), &members[..]).unwrap();
+    rb_assert!(r#"val.name == "Struct::Point3""#, val = struct_class);
+    rb_assert!("val.members == [:x, :y, :z]", val = struct_class);
+    rb_assert!(
+        "val.members == [:a, :b]",
+        val = define_struct(None, &["a", "b"][..]).unwrap()
+    );
 }
@@ -0,0 +1,40 @@
+use magnus::{eval, SymbolEnum, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, SymbolEnum)]
+enum Mode {
+    Strict,
+    NotStrict,
+    #[magnus(rename = "none")]
+    Off,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, SymbolEnum)]
+#[magnus(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Level {
+    Debug,
+    WarnOnce,
+}
+
+#[test]
+fn it_converts_enums_to_symbols() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let val = Value::from(Mode::NotStrict);
+    let res: bool = eval!("val == :not_strict", val).unwrap();
+    assert!(res);
+    let val = Value::from(Mode::Off);
+    let res: bool = eval!("val == :none", val).unwrap();
+    assert!(res);
+    let val = Value::from(Level::WarnOnce);
+    let res: bool = eval!("val == :WARN_ONCE", val).unwrap();
+    assert!(res);
+
+    assert_eq!(eval::<Mode>(":strict").unwrap(), Mode::Strict);
+    assert_eq!(eval::<Mode>("'not_strict'").unwrap(), Mode::NotStrict);
+    assert_eq!(eval::<Mode>(":none").unwrap(), Mode::Off);
+    assert_eq!(eval::<Level>(":DEBUG").unwrap(), Level::Debug);
+
+    assert!(eval::<Mode>(":off").unwrap_err().is_arg_error());
+    assert!(eval::<Level>(":debug").unwrap_err().is_arg_error());
+    assert!(eval::<Mode>("1").unwrap_err().is_type_error());
+}
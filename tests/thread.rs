@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use magnus::{eval, exception, thread::ThreadStatus, Error, RString, Thread, Value};
+
+#[test]
+fn it_creates_threads() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let name = String::from("worker");
+    let thread = Thread::create(move || RString::new(&name)).unwrap();
+    assert_eq!(thread.value::<String>().unwrap(), "worker");
+    assert!(!thread.is_alive().unwrap());
+    assert_eq!(thread.status().unwrap(), ThreadStatus::Finished);
+
+    let thread = Thread::create(|| {
+        Thread::current().local_set("example", 42)?;
+        eval::<Value>("sleep")?;
+        Thread::current().local_get::<_, i64>("example")
+    })
+    .unwrap();
+    assert!(!thread.join(Some(Duration::from_millis(50))).unwrap());
+    assert_eq!(thread.status().unwrap(), ThreadStatus::Sleep);
+    assert_eq!(thread.local_get::<_, i64>("example").unwrap(), 42);
+    thread.wakeup().unwrap();
+    assert!(thread.join(None).unwrap());
+    assert_eq!(thread.value::<i64>().unwrap(), 42);
+
+    let thread =
+        Thread::create(|| Err::<Value, _>(Error::new(exception::arg_error(), "failed"))).unwrap();
+    let _: Value = eval!("thread.report_on_exception = false", thread).unwrap();
+    assert!(thread
+        .join(None)
+        .unwrap_err()
+        .is_kind_of(exception::arg_error()));
+
+    let thread = Thread::create(|| eval::<Value>("sleep")).unwrap();
+    thread.kill().unwrap();
+    assert!(thread.join(None).unwrap());
+    assert!(thread.wakeup().is_err());
+
+    assert!(Thread::current().is_equal(Thread::main()));
+    let res: bool = eval!("thread.is_a?(Thread)", thread).unwrap();
+    assert!(res);
+}
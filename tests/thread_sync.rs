@@ -0,0 +1,98 @@
+use magnus::{
+    eval,
+    thread::{ConditionVariable, Mutex, Queue, SizedQueue},
+    Error, Thread, Value,
+};
+
+#[test]
+fn it_coordinates_with_ruby_threads() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    // Mutex
+    let mutex: Mutex = eval("$mutex = Mutex.new").unwrap();
+    assert!(mutex.try_lock());
+    assert!(mutex.is_locked());
+    assert!(mutex.is_owned().unwrap());
+    assert!(mutex.lock().is_err());
+    mutex.unlock().unwrap();
+    assert!(mutex.unlock().is_err());
+    let res = mutex.synchronize(|| eval::<bool>("$mutex.owned?").unwrap());
+    assert!(res.unwrap());
+    assert!(!mutex.is_locked());
+    let res = std::panic::catch_unwind(|| mutex.synchronize(|| panic!("oh no")));
+    assert!(res.is_err());
+    assert!(!mutex.is_locked());
+
+    // Queue shared with a Ruby thread
+    let queue = Queue::new();
+    let results = Queue::new();
+    let worker = Thread::create(move || -> Result<(), Error> {
+        while let Some(i) = queue.pop::<Option<i64>>()? {
+            results.push(i * 2)?;
+        }
+        results.close()
+    })
+    .unwrap();
+    for i in 1..=3 {
+        queue.push(i).unwrap();
+    }
+    queue.close().unwrap();
+    assert!(queue.push(4).is_err());
+    worker.join(None).unwrap();
+    assert_eq!(results.len().unwrap(), 3);
+    let mut doubled = Vec::new();
+    while let Some(i) = results.try_pop::<i64>().unwrap() {
+        doubled.push(i);
+    }
+    assert_eq!(doubled, vec![2, 4, 6]);
+    assert!(results.is_empty().unwrap());
+
+    // SizedQueue
+    let sized = SizedQueue::new(2).unwrap();
+    assert!(SizedQueue::new(0).is_err());
+    assert!(sized.try_push(1).unwrap());
+    assert!(sized.try_push(2).unwrap());
+    assert!(!sized.try_push(3).unwrap());
+    assert_eq!(sized.max().unwrap(), 2);
+    sized.set_max(3).unwrap();
+    assert!(sized.try_push(3).unwrap());
+    assert!(Queue::from_value(*sized).is_some());
+    assert_eq!(sized.as_queue().len().unwrap(), 3);
+    #[cfg(ruby_gte_3_2)]
+    {
+        use std::time::Duration;
+        assert!(!sized.push_timeout(4, Duration::from_millis(10)).unwrap());
+        assert_eq!(
+            sized.pop_timeout::<i64>(Duration::from_millis(10)).unwrap(),
+            1
+        );
+        let empty = Queue::new();
+        assert_eq!(
+            empty
+                .pop_timeout::<Option<i64>>(Duration::from_millis(10))
+                .unwrap(),
+            None
+        );
+    }
+
+    // ConditionVariable
+    let cond = ConditionVariable::new();
+    let _: Value = eval("$ready = false").unwrap();
+    let waiter = Thread::create(move || -> Result<bool, Error> {
+        mutex.synchronize(|| {
+            while !eval::<bool>("$ready")? {
+                cond.wait(mutex, None)?;
+            }
+            Ok(true)
+        })?
+    })
+    .unwrap();
+    let _: Value = eval("sleep 0.01").unwrap();
+    mutex
+        .synchronize(|| {
+            let _: Value = eval("$ready = true").unwrap();
+            cond.broadcast().unwrap();
+        })
+        .unwrap();
+    assert!(waiter.value::<bool>().unwrap());
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "time")]
+
+use magnus::{eval, Time, Value};
+use time::{macros::datetime, Duration, OffsetDateTime};
+
+#[test]
+fn it_converts_times_with_the_time_crate() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let utc = datetime!(2009-02-13 23:31:30.123456789 UTC);
+    let val = Value::from(utc);
+    assert!(Time::from_value(val).unwrap().is_utc());
+    let res: bool = eval!("val == Time.at(1_234_567_890, 123_456_789, :nsec)", val).unwrap();
+    assert!(res);
+    assert_eq!(val.try_convert::<OffsetDateTime>().unwrap(), utc);
+
+    let fixed = datetime!(2001-02-03 04:05:06.000000007 +05:30);
+    let val = Value::from(fixed);
+    let res: bool = eval!(
+        r#"val.utc_offset == 19800 && val.strftime("%F %T.%N") == "2001-02-03 04:05:06.000000007""#,
+        val
+    )
+    .unwrap();
+    assert!(res);
+    let res: OffsetDateTime = val.try_convert().unwrap();
+    assert_eq!(res, fixed);
+    assert_eq!(res.offset(), fixed.offset());
+
+    let val = Value::from(Duration::milliseconds(1500));
+    assert_eq!(val.try_convert::<f64>().unwrap(), 1.5);
+    let res: Duration = eval("Rational(-3, 2)").unwrap();
+    assert_eq!(res, Duration::milliseconds(-1500));
+    let res: Duration = eval("2").unwrap();
+    assert_eq!(res, Duration::seconds(2));
+    assert!(eval::<Duration>(r#""1""#).is_err());
+}
@@ -0,0 +1,35 @@
+use magnus::{define_class, embed::init, eval, function, typed_data, Module, Object};
+
+#[magnus::wrap(class = "Temperature", free_immediatly)]
+#[derive(PartialEq, PartialOrd)]
+struct Temperature(f64);
+
+impl Temperature {
+    fn new(degrees: f64) -> Self {
+        Self(degrees)
+    }
+}
+
+#[test]
+fn it_includes_comparable() {
+    let _cleanup = unsafe { init() };
+
+    let class = define_class("Temperature", Default::default()).unwrap();
+    class
+        .define_singleton_method("new", function!(Temperature::new, 1))
+        .unwrap();
+    typed_data::define_comparable::<Temperature>().unwrap();
+
+    assert!(eval::<bool>("Temperature.include?(Comparable)").unwrap());
+    assert!(eval::<bool>("(Temperature.new(1.0) <=> Temperature.new(2.0)) == -1").unwrap());
+    assert!(eval::<bool>("(Temperature.new(2.0) <=> Temperature.new(2.0)) == 0").unwrap());
+    assert!(eval::<bool>("(Temperature.new(3.0) <=> Temperature.new(2.0)) == 1").unwrap());
+    assert!(eval::<bool>("(Temperature.new(1.0) <=> 1.0).nil?").unwrap());
+    assert!(eval::<bool>("(Temperature.new(Float::NAN) <=> Temperature.new(1.0)).nil?").unwrap());
+    assert!(eval::<bool>("Temperature.new(1.0) < Temperature.new(2.0)").unwrap());
+    assert!(eval::<bool>("Temperature.new(1.0) == Temperature.new(1.0)").unwrap());
+    assert!(eval::<bool>(
+        "Temperature.new(5.0).clamp(Temperature.new(0.0), Temperature.new(3.0)) == Temperature.new(3.0)"
+    )
+    .unwrap());
+}
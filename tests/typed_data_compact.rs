@@ -0,0 +1,53 @@
+#[cfg(ruby_gte_2_7)]
+mod compact {
+    use std::cell::Cell;
+
+    use magnus::{define_class, eval, gc, DataTypeFunctions, RString, TypedData, Value};
+
+    #[derive(TypedData)]
+    #[magnus(class = "Holder", mark, compact)]
+    pub struct Holder {
+        pub value: Cell<Value>,
+    }
+
+    impl DataTypeFunctions for Holder {
+        fn mark(&self) {
+            gc::mark_movable(&self.value.get());
+        }
+
+        fn compact(&self) {
+            self.value.set(gc::location(self.value.get()));
+        }
+    }
+
+    pub fn run() {
+        define_class("Holder", Default::default()).unwrap();
+
+        let holder: Value = Holder {
+            value: Cell::new(*RString::new("moveable")),
+        }
+        .into();
+
+        let _: Value = eval!(
+            r#"
+            $holder = holder
+            1024.times.map {|i| "test#{i}"}
+            GC.compact if GC.respond_to?(:compact)
+            "#,
+            holder
+        )
+        .unwrap();
+
+        let holder: &Holder = eval("$holder").unwrap();
+        let value: String = holder.value.get().funcall("to_s", ()).unwrap();
+        assert_eq!(value, "moveable");
+    }
+}
+
+#[test]
+fn it_updates_moved_values() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    #[cfg(ruby_gte_2_7)]
+    compact::run();
+}
@@ -0,0 +1,56 @@
+use magnus::{define_class, embed::init, eval, function, typed_data, Module, Object};
+
+#[magnus::wrap(class = "Words", free_immediatly)]
+struct Words(Vec<String>);
+
+impl Words {
+    fn new(s: String) -> Self {
+        Self(s.split_whitespace().map(ToOwned::to_owned).collect())
+    }
+}
+
+impl typed_data::Each for Words {
+    type Item = String;
+    type Iter = std::vec::IntoIter<String>;
+
+    fn each(&self) -> Self::Iter {
+        self.0.clone().into_iter()
+    }
+}
+
+#[test]
+fn it_includes_enumerable() {
+    let _cleanup = unsafe { init() };
+
+    let class = define_class("Words", Default::default()).unwrap();
+    class
+        .define_singleton_method("new", function!(Words::new, 1))
+        .unwrap();
+    typed_data::define_enumerable::<Words>().unwrap();
+
+    assert!(eval::<bool>("Words.include?(Enumerable)").unwrap());
+    assert!(eval::<bool>(
+        r#"
+        words = Words.new("foo bar baz")
+        res = []
+        words.each { |w| res << w }
+        res == ["foo", "bar", "baz"]
+        "#
+    )
+    .unwrap());
+    assert!(eval::<bool>(r#"Words.new("foo bar").map(&:upcase) == ["FOO", "BAR"]"#).unwrap());
+    assert!(
+        eval::<bool>(r#"Words.new("a bb ccc").select { |w| w.size > 1 } == ["bb", "ccc"]"#)
+            .unwrap()
+    );
+    assert!(eval::<bool>(r#"Words.new("x y").to_a == ["x", "y"]"#).unwrap());
+    assert!(eval::<bool>(r#"Words.new("x y").each.is_a?(Enumerator)"#).unwrap());
+    assert!(eval::<bool>(
+        r#"
+        e = Words.new("x y").each
+        e.next == "x" && e.next == "y"
+        "#
+    )
+    .unwrap());
+    assert!(eval::<bool>(r#"Words.new("x y z").find { |w| break w if w == "y" } == "y""#).unwrap());
+}
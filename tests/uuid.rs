@@ -0,0 +1,31 @@
+#![cfg(feature = "uuid")]
+
+use magnus::{eval, RString, Value};
+use uuid::Uuid;
+
+#[test]
+fn it_converts_uuids() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let uuid = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+    let val = Value::from(uuid);
+    let res: bool = eval!(r#"val == "67e55044-10b1-426f-9247-bb680e5fe0c8""#, val).unwrap();
+    assert!(res);
+    assert_eq!(val.try_convert::<Uuid>().unwrap(), uuid);
+
+    let res: Uuid = eval(r#""67E5504410B1426F9247BB680E5FE0C8""#).unwrap();
+    assert_eq!(res, uuid);
+
+    let binary = RString::from_uuid_bytes(uuid);
+    assert_eq!(binary.len(), 16);
+    assert_eq!(binary.try_convert::<Uuid>().unwrap(), uuid);
+    let binary = RString::from_slice(uuid.as_bytes());
+    assert_eq!(binary.try_convert::<Uuid>().unwrap(), uuid);
+
+    // 16 bytes of text isn't the binary form
+    let err = eval::<Uuid>(r#""0123456789abcdef""#).unwrap_err();
+    assert!(err.is_arg_error());
+
+    let err = eval::<Uuid>(r#""not-a-uuid""#).unwrap_err();
+    assert!(err.is_arg_error());
+}
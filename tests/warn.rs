@@ -0,0 +1,34 @@
+use magnus::{error, eval, Value};
+
+#[test]
+fn it_emits_warnings() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let _: Value = eval(
+        r#"
+        $warnings = []
+        module Warning
+          def self.warn(msg, category: nil)
+            $warnings << [msg, category]
+          end
+        end
+        "#,
+    )
+    .unwrap();
+
+    error::warn("careful: 100%");
+    assert!(eval::<bool>(r#"$warnings.last[0].include?("careful: 100%")"#).unwrap());
+
+    #[cfg(ruby_gte_3_0)]
+    {
+        use magnus::error::{category_warn, WarningCategory};
+
+        let _: Value = eval("Warning[:deprecated] = true").unwrap();
+        category_warn(WarningCategory::Deprecated, "old").unwrap();
+        assert!(eval::<bool>(r#"$warnings.last == ["old\n", :deprecated]"#).unwrap());
+
+        let _: Value = eval("$warnings.clear; Warning[:deprecated] = false").unwrap();
+        category_warn(WarningCategory::Deprecated, "old").unwrap();
+        assert!(eval::<bool>("$warnings.empty?").unwrap());
+    }
+}
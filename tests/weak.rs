@@ -0,0 +1,39 @@
+use magnus::{
+    eval,
+    object_space::{WeakMap, WeakRef},
+    RString, Value,
+};
+
+#[test]
+fn it_holds_weak_references() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let s = RString::new("example");
+    let weak = WeakRef::new(s).unwrap();
+    assert!(weak.is_alive().unwrap());
+    assert!(weak.get().unwrap().unwrap().equal(s).unwrap());
+
+    let weak: WeakRef = eval!("weak", weak).unwrap();
+    assert!(weak.is_alive().unwrap());
+    assert!(eval::<Value>(":foo")
+        .unwrap()
+        .try_convert::<WeakRef>()
+        .is_err());
+
+    let map = WeakMap::new().unwrap();
+    assert!(map.is_empty().unwrap());
+    let key = RString::new("key");
+    let val = RString::new("value");
+    map.aset(key, val).unwrap();
+    assert_eq!(map.len().unwrap(), 1);
+    assert!(map.contains_key(key).unwrap());
+    assert!(map.get(key).unwrap().unwrap().equal(val).unwrap());
+    assert!(map.get(RString::new("key")).unwrap().is_none());
+
+    let map: WeakMap = eval!("map", map).unwrap();
+    assert_eq!(map.len().unwrap(), 1);
+    assert!(eval::<Value>("{}")
+        .unwrap()
+        .try_convert::<WeakMap>()
+        .is_err());
+}
@@ -0,0 +1,59 @@
+use magnus::{eval, Value};
+
+type I13 = (
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+);
+type U16 = (
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    u8,
+    String,
+);
+
+#[test]
+fn it_converts_tuples_of_up_to_16_elements() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let val = Value::from((1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16));
+    let res: bool = eval!("val == (1..16).to_a", val).unwrap();
+    assert!(res);
+
+    let res: I13 = eval("(1..13).to_a").unwrap();
+    assert_eq!(res.12, 13);
+    let res: U16 = eval(r#"(1..15).to_a << "sixteen""#).unwrap();
+    assert_eq!((res.14, res.15.as_str()), (15, "sixteen"));
+    assert!(eval::<I13>("(1..14).to_a").unwrap_err().is_type_error());
+
+    let res: i64 = eval::<Value>("proc { |*args| args.sum }")
+        .unwrap()
+        .funcall(
+            "call",
+            (1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16),
+        )
+        .unwrap();
+    assert_eq!(res, 136);
+}
@@ -0,0 +1,34 @@
+use magnus::{error::RubyUnavailableError, eval, thread::with_gvl, without_gvl, RArray};
+
+#[test]
+fn it_reacquires_gvl() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    // already holding the GVL
+    assert_eq!(with_gvl(|| 1).unwrap(), 1);
+
+    let ary = RArray::new();
+    let res = unsafe {
+        without_gvl(
+            || {
+                let mut total = 0;
+                for i in 1..=3 {
+                    total += i;
+                    with_gvl(|| ary.push(total)).unwrap().unwrap();
+                }
+                total
+            },
+            None::<fn()>,
+        )
+    }
+    .unwrap();
+    assert_eq!(res, 6);
+    let res: bool = eval!("ary == [1, 3, 6]", ary).unwrap();
+    assert!(res);
+
+    let res =
+        std::thread::spawn(|| matches!(with_gvl(|| ()), Err(RubyUnavailableError::NonRubyThread)))
+            .join()
+            .unwrap();
+    assert!(res);
+}
@@ -0,0 +1,58 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use magnus::{eval, without_gvl, Thread, Value};
+
+#[test]
+fn it_runs_without_gvl() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    // another Ruby thread can make progress while we're blocked without the
+    // GVL
+    let _: Value = eval("$counter = 0").unwrap();
+    let thread = Thread::create(|| eval::<Value>("loop { $counter += 1; sleep 0.001 }")).unwrap();
+    let res = unsafe {
+        without_gvl(
+            || {
+                std::thread::sleep(Duration::from_millis(100));
+                42
+            },
+            None::<fn()>,
+        )
+    }
+    .unwrap();
+    assert_eq!(res, 42);
+    let count: i64 = eval("$counter").unwrap();
+    assert!(count > 0);
+    thread.kill().unwrap();
+    thread.join(None).unwrap();
+
+    // unblock function is called to interrupt
+    let stop = Arc::new(AtomicBool::new(false));
+    let flag = stop.clone();
+    let unblocked = Arc::new(AtomicBool::new(false));
+    let was_unblocked = unblocked.clone();
+    let worker = Thread::create(move || unsafe {
+        without_gvl(
+            || {
+                while !stop.load(Ordering::SeqCst) {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+            },
+            Some(move || {
+                was_unblocked.store(true, Ordering::SeqCst);
+                flag.store(true, Ordering::SeqCst);
+            }),
+        )
+    })
+    .unwrap();
+    assert!(!worker.join(Some(Duration::from_millis(20))).unwrap());
+    worker.kill().unwrap();
+    worker.join(None).unwrap();
+    assert!(unblocked.load(Ordering::SeqCst));
+}
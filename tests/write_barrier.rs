@@ -0,0 +1,51 @@
+use magnus::{
+    define_class, eval, gc, method, typed_data::Obj, DataTypeFunctions, Module, RString, TypedData,
+    Value, QNIL,
+};
+
+#[derive(TypedData)]
+#[magnus(class = "Container", mark, wb_protected)]
+struct Container {
+    value: gc::WriteBarrier<Value>,
+}
+
+impl DataTypeFunctions for Container {
+    fn mark(&self) {
+        gc::mark(&self.value.get());
+    }
+}
+
+fn set(rb_self: Obj<Container>, value: Value) {
+    rb_self.get().value.set(rb_self, value);
+}
+
+#[test]
+fn it_runs_write_barriers() {
+    let _cleanup = unsafe { magnus::embed::init() };
+
+    let class = define_class("Container", Default::default()).unwrap();
+    class.define_method("value=", method!(set, 1)).unwrap();
+
+    let container = Obj::wrap(Container {
+        value: gc::WriteBarrier::new(*QNIL),
+    });
+    let _: Value = eval!("$container = container", container).unwrap();
+
+    // promote the container to the old generation
+    for _ in 0..4 {
+        gc::start();
+    }
+
+    let _: Value = eval(r#"$container.value = "value #{1 + 1}""#).unwrap();
+    for _ in 0..4 {
+        gc::start_with(false, true).unwrap();
+        let _: Value = eval(r#"1024.times.map {|i| "test#{i}"}"#).unwrap();
+    }
+
+    let value: RString = container.get().value.get().try_convert().unwrap();
+    assert_eq!(value.to_string().unwrap(), "value 2");
+
+    let other = RString::new("other");
+    gc::writebarrier(container, other);
+    gc::writebarrier_unprotect(container);
+}